@@ -47,6 +47,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     let metadata_config = MetadataConfig {
         track_performance: true,
         track_optimization: true,
+        track_parametric_paths: false,
     };
     
     let start_time = std::time::Instant::now();
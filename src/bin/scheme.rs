@@ -0,0 +1,434 @@
+//! scheme.rs - Command-Line Tool
+//!
+//! Generates, renders, validates and exports channel systems without writing
+//! Rust code. Inputs are design files (`.toml` or `.json`), channel system
+//! JSON as written by `ChannelSystem::to_json`, or SVG and DXF drawings
+//! imported with the default `ImportOptions`.
+//!
+//! # Exit Codes
+//!
+//! - `0`: success
+//! - `1`: input file could not be read or is not a design or channel system
+//! - `2`: invalid command-line usage
+//! - `3`-`9`: [`SchemeError::exit_code`] for geometry, configuration,
+//!   visualization, strategy, simulation, design and import errors
+
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+
+use clap::{Parser, Subcommand};
+use scheme::{
+    design::{DesignFormat, DesignSpec},
+    error::{DesignError, GeometryError, ImportError, SchemeError, VisualizationError},
+    geometry::{
+        composition::PortKind,
+        drc::{check_design_rules, RuleDeck, Severity},
+        optimization::calculate_path_length,
+        Channel, ChannelSystem, ChannelType, Point2D,
+    },
+    import::{dxf, svg, ImportOptions},
+    visualizations::{schematic::plot_geometry_with_config, RenderConfig},
+};
+
+/// Generate, render, validate and export microfluidic channel schematics
+#[derive(Parser, Debug)]
+#[command(name = "scheme", version, about)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Generate a channel system from a design file and write it as JSON
+    Generate {
+        /// Design file (.toml or .json)
+        design: PathBuf,
+        /// Output file; the system is printed to stdout if omitted
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Render a design or channel system to PNG, JPG or SVG
+    Render {
+        /// Design file, channel system JSON or SVG/DXF drawing
+        input: PathBuf,
+        /// Output image; the format is taken from the extension
+        #[arg(short, long)]
+        output: PathBuf,
+        /// Image width in pixels
+        #[arg(long, default_value_t = 1024)]
+        width: u32,
+        /// Image height in pixels
+        #[arg(long, default_value_t = 768)]
+        height: u32,
+        /// Title drawn above the schematic
+        #[arg(long)]
+        title: Option<String>,
+    },
+    /// Check a design or channel system for structural errors
+    Validate {
+        /// Design file, channel system JSON or SVG/DXF drawing
+        input: PathBuf,
+        /// Also check the default design rules
+        #[arg(long)]
+        drc: bool,
+    },
+    /// Print node, channel, length and size statistics
+    Stats {
+        /// Design file, channel system JSON or SVG/DXF drawing
+        input: PathBuf,
+        /// Print statistics as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Convert a design or channel system to the format of the output file
+    ///
+    /// Supported extensions are .json (channel system), .toml (design, for
+    /// design inputs only) and the image formats accepted by render.
+    Export {
+        /// Design file, channel system JSON or SVG/DXF drawing
+        input: PathBuf,
+        /// Output file
+        #[arg(short, long)]
+        output: PathBuf,
+    },
+}
+
+/// Reason a command failed
+#[derive(Debug)]
+enum Failure {
+    /// Input could not be read or recognized
+    Input(String),
+    /// Error reported by the library
+    Scheme(SchemeError),
+}
+
+impl Failure {
+    const fn exit_code(&self) -> u8 {
+        match self {
+            Self::Input(_) => 1,
+            Self::Scheme(error) => error.exit_code(),
+        }
+    }
+}
+
+impl std::fmt::Display for Failure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Input(message) => write!(f, "{message}"),
+            Self::Scheme(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+impl From<SchemeError> for Failure {
+    fn from(error: SchemeError) -> Self {
+        Self::Scheme(error)
+    }
+}
+
+impl From<DesignError> for Failure {
+    fn from(error: DesignError) -> Self {
+        Self::Scheme(error.into())
+    }
+}
+
+impl From<GeometryError> for Failure {
+    fn from(error: GeometryError) -> Self {
+        Self::Scheme(error.into())
+    }
+}
+
+impl From<ImportError> for Failure {
+    fn from(error: ImportError) -> Self {
+        Self::Scheme(error.into())
+    }
+}
+
+impl From<VisualizationError> for Failure {
+    fn from(error: VisualizationError) -> Self {
+        Self::Scheme(error.into())
+    }
+}
+
+/// A loaded input file
+enum Input {
+    Design(Box<DesignSpec>),
+    System(ChannelSystem),
+}
+
+impl Input {
+    /// Load a design or channel system
+    ///
+    /// TOML files are designs, and SVG and DXF files are imported drawings.
+    /// JSON files with a `nodes` field are channel systems; any other JSON
+    /// file is read as a design.
+    fn load(path: &Path) -> Result<Self, Failure> {
+        let text = fs::read_to_string(path)
+            .map_err(|error| Failure::Input(format!("cannot read {}: {error}", path.display())))?;
+        if DesignFormat::from_path(path).ok() == Some(DesignFormat::Toml) {
+            return Ok(Self::Design(Box::new(DesignSpec::parse(&text, DesignFormat::Toml)?)));
+        }
+        let extension = path.extension().and_then(|extension| extension.to_str()).map(str::to_ascii_lowercase);
+        match extension.as_deref() {
+            Some("svg") => return Ok(Self::System(svg::import(&text, &ImportOptions::default())?.system)),
+            Some("dxf") => return Ok(Self::System(dxf::import(&text, &ImportOptions::default())?.system)),
+            _ => {}
+        }
+
+        let value: serde_json::Value = serde_json::from_str(&text)
+            .map_err(|error| Failure::Input(format!("{} is not valid JSON: {error}", path.display())))?;
+        if value.get("nodes").is_some() {
+            Ok(Self::System(ChannelSystem::from_json(&text)?))
+        } else {
+            Ok(Self::Design(Box::new(DesignSpec::parse(&text, DesignFormat::Json)?)))
+        }
+    }
+
+    /// The channel system, generating it from the design if needed
+    ///
+    /// Loaded systems are validated so that broken files are reported
+    /// instead of failing while drawing or measuring them.
+    fn into_system(self) -> Result<ChannelSystem, Failure> {
+        match self {
+            Self::Design(spec) => Ok(spec.generate()?),
+            Self::System(system) => {
+                system.validate()?;
+                Ok(system)
+            }
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    match run(cli.command) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(failure) => {
+            eprintln!("error: {failure}");
+            ExitCode::from(failure.exit_code())
+        }
+    }
+}
+
+fn run(command: Command) -> Result<(), Failure> {
+    match command {
+        Command::Generate { design, output } => {
+            let system = match Input::load(&design)? {
+                Input::Design(spec) => spec.generate()?,
+                Input::System(_) => {
+                    return Err(Failure::Input(format!("{} is a channel system, not a design", design.display())))
+                }
+            };
+            let json = system_json(&system)?;
+            output.map_or_else(
+                || {
+                    println!("{json}");
+                    Ok(())
+                },
+                |path| write_file(&path, &json),
+            )
+        }
+        Command::Render { input, output, width, height, title } => {
+            let system = Input::load(&input)?.into_system()?;
+            let mut config = RenderConfig { width, height, ..RenderConfig::default() };
+            if let Some(title) = title {
+                config.title = title;
+            }
+            render(&system, &output, &config)
+        }
+        Command::Validate { input, drc } => {
+            let system = match Input::load(&input)? {
+                Input::Design(spec) => spec.generate()?,
+                Input::System(system) => system,
+            };
+            validate_structure(&system)?;
+            if drc {
+                check_rules(&system)?;
+            }
+            println!("valid: {} nodes, {} channels", system.nodes.len(), system.channels.len());
+            Ok(())
+        }
+        Command::Stats { input, json } => {
+            let system = Input::load(&input)?.into_system()?;
+            let stats = Stats::collect(&system);
+            if json {
+                println!("{}", stats.to_json());
+            } else {
+                stats.print();
+            }
+            Ok(())
+        }
+        Command::Export { input, output } => export(Input::load(&input)?, &output),
+    }
+}
+
+/// Write the input in the format given by the output extension
+fn export(input: Input, output: &Path) -> Result<(), Failure> {
+    let extension = output
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .map(str::to_ascii_lowercase)
+        .unwrap_or_default();
+    match (extension.as_str(), input) {
+        ("toml", Input::Design(spec)) => write_file(output, &spec.to_toml()?),
+        ("toml", Input::System(_)) => Err(VisualizationError::unsupported_format(
+            "toml",
+            "channel systems can only be exported as JSON or images; TOML holds designs",
+        )
+        .into()),
+        ("json", input) => write_file(output, &system_json(&input.into_system()?)?),
+        (_, input) => render(&input.into_system()?, output, &RenderConfig::default()),
+    }
+}
+
+fn render(system: &ChannelSystem, output: &Path, config: &RenderConfig) -> Result<(), Failure> {
+    plot_geometry_with_config(system, &output.to_string_lossy(), config)?;
+    Ok(())
+}
+
+fn system_json(system: &ChannelSystem) -> Result<String, Failure> {
+    system
+        .to_json()
+        .map_err(|error| DesignError::serialize_error("JSON", &error.to_string()).into())
+}
+
+fn write_file(path: &Path, contents: &str) -> Result<(), Failure> {
+    fs::write(path, contents)
+        .map_err(|error| VisualizationError::file_error(&format!("cannot write {}: {error}", path.display())).into())
+}
+
+/// Check the structure of a system, printing every problem
+fn validate_structure(system: &ChannelSystem) -> Result<(), Failure> {
+    let mut errors = system.validation_errors();
+    for error in &errors {
+        println!("invalid: {error}");
+    }
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors.swap_remove(0).into())
+    }
+}
+
+/// Run the default design rules, printing every violation
+fn check_rules(system: &ChannelSystem) -> Result<(), Failure> {
+    let report = check_design_rules(system, &RuleDeck::default())?;
+    for violation in &report.violations {
+        println!("{violation}");
+    }
+    if report.passes() {
+        Ok(())
+    } else {
+        Err(GeometryError::DesignRuleViolations { count: report.count(Severity::Error) }.into())
+    }
+}
+
+/// Explicit path of a channel, if it has one
+fn channel_path(channel_type: &ChannelType) -> Option<&[Point2D]> {
+    match channel_type {
+        ChannelType::Straight => None,
+        ChannelType::SmoothStraight { path }
+        | ChannelType::Serpentine { path }
+        | ChannelType::Arc { path }
+        | ChannelType::Frustum { path, .. } => Some(path),
+    }
+}
+
+const fn type_name(channel_type: &ChannelType) -> &'static str {
+    match channel_type {
+        ChannelType::Straight => "Straight",
+        ChannelType::SmoothStraight { .. } => "SmoothStraight",
+        ChannelType::Serpentine { .. } => "Serpentine",
+        ChannelType::Arc { .. } => "Arc",
+        ChannelType::Frustum { .. } => "Frustum",
+    }
+}
+
+/// Centerline length of a channel in mm
+fn channel_length(system: &ChannelSystem, channel: &Channel) -> f64 {
+    channel_path(&channel.channel_type).map_or_else(
+        || {
+            let from = system.nodes[channel.from_node].point;
+            let to = system.nodes[channel.to_node].point;
+            (to.0 - from.0).hypot(to.1 - from.1)
+        },
+        calculate_path_length,
+    )
+}
+
+/// Summary statistics of a channel system
+struct Stats {
+    nodes: usize,
+    channels: usize,
+    inlets: usize,
+    outlets: usize,
+    channel_types: BTreeMap<&'static str, usize>,
+    total_length: f64,
+    width_range: Option<(f64, f64)>,
+    box_dims: (f64, f64),
+    bounds: (Point2D, Point2D),
+}
+
+impl Stats {
+    fn collect(system: &ChannelSystem) -> Self {
+        let ports = system.ports();
+        let mut channel_types = BTreeMap::new();
+        let mut width_range: Option<(f64, f64)> = None;
+        for channel in &system.channels {
+            *channel_types.entry(type_name(&channel.channel_type)).or_insert(0) += 1;
+            let widths = match &channel.channel_type {
+                ChannelType::Frustum { widths, .. } => widths.clone(),
+                _ => vec![channel.width],
+            };
+            for width in widths {
+                width_range = Some(width_range.map_or((width, width), |(low, high)| (low.min(width), high.max(width))));
+            }
+        }
+        Self {
+            nodes: system.nodes.len(),
+            channels: system.channels.len(),
+            inlets: ports.iter().filter(|port| port.kind == PortKind::Inlet).count(),
+            outlets: ports.iter().filter(|port| port.kind == PortKind::Outlet).count(),
+            channel_types,
+            total_length: system.channels.iter().map(|channel| channel_length(system, channel)).sum(),
+            width_range,
+            box_dims: system.box_dims,
+            bounds: system.bounds(),
+        }
+    }
+
+    fn print(&self) {
+        println!("nodes:         {}", self.nodes);
+        println!("channels:      {}", self.channels);
+        for (name, count) in &self.channel_types {
+            println!("  {name:<12} {count}");
+        }
+        println!("ports:         {} inlet(s), {} outlet(s)", self.inlets, self.outlets);
+        println!("total length:  {:.3} mm", self.total_length);
+        if let Some((low, high)) = self.width_range {
+            println!("channel width: {low:.3} - {high:.3} mm");
+        }
+        println!("box:           {:.3} x {:.3} mm", self.box_dims.0, self.box_dims.1);
+        let ((min_x, min_y), (max_x, max_y)) = self.bounds;
+        println!("bounds:        ({min_x:.3}, {min_y:.3}) - ({max_x:.3}, {max_y:.3})");
+    }
+
+    fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "nodes": self.nodes,
+            "channels": self.channels,
+            "channel_types": self.channel_types,
+            "inlets": self.inlets,
+            "outlets": self.outlets,
+            "total_length": self.total_length,
+            "width_range": self.width_range,
+            "box_dims": self.box_dims,
+            "bounds": self.bounds,
+        })
+    }
+}
@@ -14,6 +14,8 @@
 //! - **Discoverability**: Presets and builders make common configurations easy
 
 use crate::geometry::ChannelType;
+use crate::geometry::collision_detection::SystemAvoidanceConfig;
+use crate::geometry::objectives::ObjectiveSpec;
 use crate::geometry::strategies::SmoothTransitionConfig;
 use crate::error::{ConfigurationError, ConfigurationResult};
 use serde::{Serialize, Deserialize};
@@ -59,6 +61,20 @@ pub mod constants {
     /// Maximum wave multiplier for smooth transitions
     pub const MAX_TRANSITION_WAVE_MULTIPLIER: f64 = 10.0;
 
+    /// Default maximum chord error for adaptive path sampling (mm)
+    pub const DEFAULT_MAX_CHORD_ERROR: f64 = 0.01;
+    /// Minimum chord error for adaptive path sampling (mm)
+    pub const MIN_MAX_CHORD_ERROR: f64 = 1e-5;
+    /// Maximum chord error for adaptive path sampling (mm)
+    pub const MAX_MAX_CHORD_ERROR: f64 = 1.0;
+
+    /// Default maximum turn angle between adaptively sampled segments (radians, 5°)
+    pub const DEFAULT_MAX_TURN_ANGLE: f64 = 5.0 * std::f64::consts::PI / 180.0;
+    /// Minimum turn angle for adaptive path sampling (radians, 0.1°)
+    pub const MIN_MAX_TURN_ANGLE: f64 = 0.1 * std::f64::consts::PI / 180.0;
+    /// Maximum turn angle for adaptive path sampling (radians, 45°)
+    pub const MAX_MAX_TURN_ANGLE: f64 = std::f64::consts::FRAC_PI_4;
+
     // Adaptive serpentine control constants
     /// Default distance normalization factor for node proximity effects
     pub const DEFAULT_NODE_DISTANCE_NORMALIZATION: f64 = 10.0;
@@ -137,6 +153,11 @@ pub mod constants {
     /// Default wave density factor for serpentine channels
     pub const DEFAULT_WAVE_DENSITY_FACTOR: f64 = 1.5;
 
+    /// Factor applied to wave density or fill factor per self-intersection correction step
+    pub const SELF_INTERSECTION_RELAXATION: f64 = 0.8;
+    /// Maximum number of self-intersection correction steps
+    pub const SELF_INTERSECTION_CORRECTION_STEPS: usize = 20;
+
     /// Minimum curvature factor for arc channels
     pub const MIN_CURVATURE_FACTOR: f64 = 0.0;
     /// Maximum curvature factor for arc channels
@@ -165,6 +186,22 @@ pub mod constants {
     /// Default maximum curvature reduction factor
     pub const DEFAULT_MAX_CURVATURE_REDUCTION: f64 = 0.5;
 
+    /// Minimum relative tolerance for targeted optimization
+    pub const MIN_TARGET_TOLERANCE: f64 = 1e-4;
+    /// Maximum relative tolerance for targeted optimization
+    pub const MAX_TARGET_TOLERANCE: f64 = 0.5;
+    /// Default relative tolerance for targeted optimization (1%)
+    pub const DEFAULT_TARGET_TOLERANCE: f64 = 0.01;
+
+    /// Dynamic viscosity of water at 20 °C (Pa·s)
+    pub const WATER_VISCOSITY: f64 = 1.0e-3;
+    /// Density of water at 20 °C (kg/m³)
+    pub const WATER_DENSITY: f64 = 998.2;
+    /// Conversion factor from millimetres to metres
+    pub const MM_TO_M: f64 = 1.0e-3;
+    /// Conversion factor from µL/min to m³/s
+    pub const UL_PER_MIN_TO_M3_PER_S: f64 = 1.0e-9 / 60.0;
+
     /// Strategy thresholds for smart channel type selection
     pub mod strategy_thresholds {
         /// Threshold for long horizontal channels (fraction of box width)
@@ -205,7 +242,7 @@ pub mod constants {
 ///     enable_neighbor_avoidance: true,
 /// };
 /// ```
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
 pub struct AdaptiveSerpentineConfig {
     /// Distance normalization factor for node proximity effects (1.0-50.0)
     pub node_distance_normalization: f64,
@@ -355,6 +392,82 @@ impl AdaptiveSerpentineConfig {
     }
 }
 
+/// Tolerances for adaptive, curvature-based path sampling
+///
+/// When set on [`GeometryGenerationConfig::adaptive_sampling`], path-producing
+/// strategies place points where the curve bends instead of at a fixed count:
+/// a segment is split until the curve deviates from its chord by at most
+/// `max_chord_error` and turns by at most `max_turn_angle` across it. Path
+/// endpoints are always hit exactly.
+///
+/// # Examples
+///
+/// ```rust
+/// use scheme::config::{AdaptiveSamplingConfig, GeometryGenerationConfig};
+///
+/// let sampling = AdaptiveSamplingConfig::new(0.005, 0.05).unwrap();
+/// let generation = GeometryGenerationConfig::default().with_adaptive_sampling(sampling);
+/// assert!(generation.validate().is_ok());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AdaptiveSamplingConfig {
+    /// Maximum distance between the curve and a sampled segment (mm)
+    pub max_chord_error: f64,
+    /// Maximum change of direction across a sampled segment (radians)
+    pub max_turn_angle: f64,
+}
+
+impl Default for AdaptiveSamplingConfig {
+    fn default() -> Self {
+        Self {
+            max_chord_error: constants::DEFAULT_MAX_CHORD_ERROR,
+            max_turn_angle: constants::DEFAULT_MAX_TURN_ANGLE,
+        }
+    }
+}
+
+impl AdaptiveSamplingConfig {
+    /// Create a new adaptive sampling configuration with validation
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if either tolerance is outside its allowed range.
+    pub fn new(max_chord_error: f64, max_turn_angle: f64) -> ConfigurationResult<Self> {
+        let config = Self {
+            max_chord_error,
+            max_turn_angle,
+        };
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Validate the configuration parameters
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if either tolerance is outside its allowed range.
+    pub fn validate(&self) -> ConfigurationResult<()> {
+        if !(constants::MIN_MAX_CHORD_ERROR..=constants::MAX_MAX_CHORD_ERROR).contains(&self.max_chord_error) {
+            return Err(ConfigurationError::invalid_generation_config(
+                "adaptive_sampling.max_chord_error",
+                &format!("Must be between {} and {}",
+                    constants::MIN_MAX_CHORD_ERROR, constants::MAX_MAX_CHORD_ERROR)
+            ));
+        }
+
+        if !(constants::MIN_MAX_TURN_ANGLE..=constants::MAX_MAX_TURN_ANGLE).contains(&self.max_turn_angle) {
+            return Err(ConfigurationError::invalid_generation_config(
+                "adaptive_sampling.max_turn_angle",
+                &format!("Must be between {} and {}",
+                    constants::MIN_MAX_TURN_ANGLE, constants::MAX_MAX_TURN_ANGLE)
+            ));
+        }
+
+        Ok(())
+    }
+}
+
 /// Configuration for geometry generation parameters
 ///
 /// This struct controls the quality and precision of geometry generation,
@@ -374,9 +487,12 @@ impl AdaptiveSerpentineConfig {
 ///     optimization_points: 100,
 ///     smooth_straight_middle_points: 20,
 ///     transition_wave_multiplier: 2.0,
+///     adaptive_sampling: None,
+///     collision_avoidance: None,
 /// };
 /// ```
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct GeometryGenerationConfig {
     /// Number of points to generate for serpentine paths (10-1000)
     pub serpentine_points: usize,
@@ -386,6 +502,13 @@ pub struct GeometryGenerationConfig {
     pub smooth_straight_middle_points: usize,
     /// Wave multiplier for smooth transitions (0.5-10.0, where 2.0 = one complete wave)
     pub transition_wave_multiplier: f64,
+    /// Adaptive sampling tolerances; when set, paths are sampled by curvature
+    /// instead of the fixed point counts above (disabled by default)
+    pub adaptive_sampling: Option<AdaptiveSamplingConfig>,
+    /// Collision avoidance settings; when set, `create_geometry` resolves
+    /// collisions between the finished channel paths and records the outcome
+    /// as `CollisionAvoidanceMetadata` (disabled by default)
+    pub collision_avoidance: Option<SystemAvoidanceConfig>,
 }
 
 impl Default for GeometryGenerationConfig {
@@ -395,6 +518,8 @@ impl Default for GeometryGenerationConfig {
             optimization_points: constants::DEFAULT_OPTIMIZATION_POINTS,
             smooth_straight_middle_points: constants::DEFAULT_SMOOTH_STRAIGHT_MIDDLE_POINTS,
             transition_wave_multiplier: constants::DEFAULT_TRANSITION_WAVE_MULTIPLIER,
+            adaptive_sampling: None,
+            collision_avoidance: None,
         }
     }
 }
@@ -412,6 +537,8 @@ impl GeometryGenerationConfig {
             optimization_points,
             smooth_straight_middle_points,
             transition_wave_multiplier,
+            adaptive_sampling: None,
+            collision_avoidance: None,
         };
         config.validate()?;
         Ok(config)
@@ -455,6 +582,14 @@ impl GeometryGenerationConfig {
             ));
         }
 
+        if let Some(sampling) = &self.adaptive_sampling {
+            sampling.validate()?;
+        }
+
+        if let Some(avoidance) = &self.collision_avoidance {
+            avoidance.validate()?;
+        }
+
         Ok(())
     }
 
@@ -465,6 +600,8 @@ impl GeometryGenerationConfig {
             optimization_points: 100,
             smooth_straight_middle_points: 20,
             transition_wave_multiplier: 2.0,
+            adaptive_sampling: None,
+            collision_avoidance: None,
         }
     }
 
@@ -475,8 +612,24 @@ impl GeometryGenerationConfig {
             optimization_points: 25,
             smooth_straight_middle_points: 5,
             transition_wave_multiplier: 2.0,
+            adaptive_sampling: None,
+            collision_avoidance: None,
         }
     }
+
+    /// Sample paths adaptively with the given tolerances
+    #[must_use]
+    pub const fn with_adaptive_sampling(mut self, sampling: AdaptiveSamplingConfig) -> Self {
+        self.adaptive_sampling = Some(sampling);
+        self
+    }
+
+    /// Resolve collisions between generated channels with the given settings
+    #[must_use]
+    pub const fn with_collision_avoidance(mut self, avoidance: SystemAvoidanceConfig) -> Self {
+        self.collision_avoidance = Some(avoidance);
+        self
+    }
 }
 
 /// Configuration for basic geometry parameters
@@ -495,7 +648,8 @@ impl GeometryGenerationConfig {
 /// // Create with custom values
 /// let custom_config = GeometryConfig::new(0.5, 1.0, 0.5).unwrap();
 /// ```
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct GeometryConfig {
     /// Minimum distance between channels and walls (mm)
     pub wall_clearance: f64,
@@ -539,7 +693,7 @@ impl GeometryConfig {
 
     /// Validate the geometry configuration
     pub fn validate(&self) -> ConfigurationResult<()> {
-        if self.wall_clearance < constants::MIN_WALL_CLEARANCE || self.wall_clearance > constants::MAX_WALL_CLEARANCE {
+        if !self.wall_clearance.is_finite() || self.wall_clearance < constants::MIN_WALL_CLEARANCE || self.wall_clearance > constants::MAX_WALL_CLEARANCE {
             return Err(ConfigurationError::invalid_geometry_config(
                 "wall_clearance",
                 self.wall_clearance,
@@ -547,7 +701,7 @@ impl GeometryConfig {
             ));
         }
 
-        if self.channel_width < constants::MIN_CHANNEL_WIDTH || self.channel_width > constants::MAX_CHANNEL_WIDTH {
+        if !self.channel_width.is_finite() || self.channel_width < constants::MIN_CHANNEL_WIDTH || self.channel_width > constants::MAX_CHANNEL_WIDTH {
             return Err(ConfigurationError::invalid_geometry_config(
                 "channel_width",
                 self.channel_width,
@@ -555,7 +709,7 @@ impl GeometryConfig {
             ));
         }
 
-        if self.channel_height < constants::MIN_CHANNEL_HEIGHT || self.channel_height > constants::MAX_CHANNEL_HEIGHT {
+        if !self.channel_height.is_finite() || self.channel_height < constants::MIN_CHANNEL_HEIGHT || self.channel_height > constants::MAX_CHANNEL_HEIGHT {
             return Err(ConfigurationError::invalid_geometry_config(
                 "channel_height",
                 self.channel_height,
@@ -582,7 +736,7 @@ impl Default for GeometryConfig {
 }
 
 /// Optimization profile for serpentine channel optimization
-#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Serialize, Deserialize)]
 pub enum OptimizationProfile {
     /// Fast optimization with limited parameter exploration (5-10x slower)
     Fast,
@@ -590,10 +744,238 @@ pub enum OptimizationProfile {
     Balanced,
     /// Thorough optimization with extensive exploration (100-500x slower)
     Thorough,
+    /// Global search using simulated annealing
+    SimulatedAnnealing,
+    /// Global search using particle swarm optimization
+    ParticleSwarm,
+}
+
+/// Target for inverse-design serpentine optimization
+///
+/// Instead of maximizing channel length, a targeted optimization tunes the
+/// serpentine parameters until the channel reaches a specific length or
+/// hydraulic resistance within a relative tolerance.
+///
+/// # Examples
+///
+/// ```rust
+/// use scheme::config::{OptimizationTarget, SerpentineConfig};
+///
+/// // Aim for a 150 mm long channel, accepting 1% deviation
+/// let target = OptimizationTarget::length(150.0, 0.01);
+/// assert!(target.validate().is_ok());
+///
+/// let config = SerpentineConfig::default().with_optimization_target(target);
+/// assert!(config.optimization_enabled);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum OptimizationTarget {
+    /// Reach a specific centerline length
+    Length {
+        /// Desired channel length (mm)
+        length: f64,
+        /// Acceptable relative deviation from the target (0.0001 to 0.5)
+        tolerance: f64,
+    },
+    /// Reach a specific hydraulic resistance
+    HydraulicResistance {
+        /// Desired hydraulic resistance (Pa·s/m³)
+        resistance: f64,
+        /// Dynamic viscosity of the working fluid (Pa·s)
+        viscosity: f64,
+        /// Acceptable relative deviation from the target (0.0001 to 0.5)
+        tolerance: f64,
+    },
+}
+
+impl OptimizationTarget {
+    /// Create a length target
+    #[must_use]
+    pub const fn length(length: f64, tolerance: f64) -> Self {
+        Self::Length { length, tolerance }
+    }
+
+    /// Create a hydraulic resistance target
+    #[must_use]
+    pub const fn hydraulic_resistance(resistance: f64, viscosity: f64, tolerance: f64) -> Self {
+        Self::HydraulicResistance { resistance, viscosity, tolerance }
+    }
+
+    /// Create a hydraulic resistance target from a desired pressure drop at a given flow rate
+    ///
+    /// # Arguments
+    ///
+    /// * `pressure_drop` - Desired pressure drop across the channel (Pa)
+    /// * `flow_rate` - Volumetric flow rate through the channel (µL/min)
+    /// * `viscosity` - Dynamic viscosity of the working fluid (Pa·s)
+    /// * `tolerance` - Acceptable relative deviation from the target
+    #[must_use]
+    pub fn pressure_drop(pressure_drop: f64, flow_rate: f64, viscosity: f64, tolerance: f64) -> Self {
+        let flow_rate_si = flow_rate * constants::UL_PER_MIN_TO_M3_PER_S;
+        Self::hydraulic_resistance(pressure_drop / flow_rate_si, viscosity, tolerance)
+    }
+
+    /// Relative tolerance of this target
+    #[must_use]
+    pub const fn tolerance(&self) -> f64 {
+        match *self {
+            Self::Length { tolerance, .. } | Self::HydraulicResistance { tolerance, .. } => tolerance,
+        }
+    }
+
+    /// Target value in its native unit (mm for length, Pa·s/m³ for resistance)
+    #[must_use]
+    pub const fn value(&self) -> f64 {
+        match *self {
+            Self::Length { length, .. } => length,
+            Self::HydraulicResistance { resistance, .. } => resistance,
+        }
+    }
+
+    /// Channel length (mm) required to meet this target for the given cross-section
+    #[must_use]
+    pub fn required_length(&self, geometry_config: &GeometryConfig) -> f64 {
+        match *self {
+            Self::Length { length, .. } => length,
+            Self::HydraulicResistance { resistance, viscosity, .. } => {
+                let resistance_per_mm = crate::geometry::optimization::calculate_hydraulic_resistance(
+                    1.0,
+                    geometry_config.channel_width,
+                    geometry_config.channel_height,
+                    viscosity,
+                );
+                resistance / resistance_per_mm
+            }
+        }
+    }
+
+    /// Value of the targeted quantity for a channel of the given length (mm)
+    #[must_use]
+    pub fn value_for_length(&self, length: f64, geometry_config: &GeometryConfig) -> f64 {
+        match *self {
+            Self::Length { .. } => length,
+            Self::HydraulicResistance { viscosity, .. } => {
+                crate::geometry::optimization::calculate_hydraulic_resistance(
+                    length,
+                    geometry_config.channel_width,
+                    geometry_config.channel_height,
+                    viscosity,
+                )
+            }
+        }
+    }
+
+    /// Validate the optimization target
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the target value or viscosity is not positive and
+    /// finite, or if the tolerance is outside the allowed range.
+    pub fn validate(&self) -> ConfigurationResult<()> {
+        let (field, value) = match *self {
+            Self::Length { length, .. } => ("optimization_target.length", length),
+            Self::HydraulicResistance { resistance, .. } => ("optimization_target.resistance", resistance),
+        };
+        if !value.is_finite() || value <= 0.0 {
+            return Err(ConfigurationError::invalid_serpentine_config(
+                field,
+                value,
+                "Must be a positive, finite value"
+            ));
+        }
+
+        if let Self::HydraulicResistance { viscosity, .. } = *self {
+            if !viscosity.is_finite() || viscosity <= 0.0 {
+                return Err(ConfigurationError::invalid_serpentine_config(
+                    "optimization_target.viscosity",
+                    viscosity,
+                    "Must be a positive, finite value"
+                ));
+            }
+        }
+
+        let tolerance = self.tolerance();
+        if !(constants::MIN_TARGET_TOLERANCE..=constants::MAX_TARGET_TOLERANCE).contains(&tolerance) {
+            return Err(ConfigurationError::invalid_serpentine_config(
+                "optimization_target.tolerance",
+                tolerance,
+                &format!("Must be between {} and {}", constants::MIN_TARGET_TOLERANCE, constants::MAX_TARGET_TOLERANCE)
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// Physical properties of the working fluid
+///
+/// Used by flow analyses such as the Dean number computation in
+/// [`crate::geometry::dean_flow`].
+///
+/// # Examples
+///
+/// ```rust
+/// use scheme::config::FluidProperties;
+///
+/// let water = FluidProperties::water();
+/// assert!(water.validate().is_ok());
+///
+/// let glycerol_mix = FluidProperties::new(1150.0, 0.01);
+/// assert!(glycerol_mix.kinematic_viscosity() > water.kinematic_viscosity());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FluidProperties {
+    /// Density (kg/m³)
+    pub density: f64,
+    /// Dynamic viscosity (Pa·s)
+    pub viscosity: f64,
+}
+
+impl FluidProperties {
+    /// Create fluid properties from density and dynamic viscosity
+    #[must_use]
+    pub const fn new(density: f64, viscosity: f64) -> Self {
+        Self { density, viscosity }
+    }
+
+    /// Water at 20 °C
+    #[must_use]
+    pub const fn water() -> Self {
+        Self::new(constants::WATER_DENSITY, constants::WATER_VISCOSITY)
+    }
+
+    /// Kinematic viscosity (m²/s)
+    #[must_use]
+    pub fn kinematic_viscosity(&self) -> f64 {
+        self.viscosity / self.density
+    }
+
+    /// Validate the fluid properties
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the density or viscosity is not positive and finite.
+    pub fn validate(&self) -> ConfigurationResult<()> {
+        for (field, value) in [("fluid.density", self.density), ("fluid.viscosity", self.viscosity)] {
+            if !value.is_finite() || value <= 0.0 {
+                return Err(ConfigurationError::invalid_generation_config(
+                    field,
+                    "Must be a positive, finite value",
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Default for FluidProperties {
+    fn default() -> Self {
+        Self::water()
+    }
 }
 
 /// Wave shape types for serpentine channels
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum WaveShape {
     /// Smooth sine wave (default) - provides natural, flowing curves
     Sine,
@@ -625,7 +1007,8 @@ impl Default for TaperProfile {
 }
 
 /// Configuration for serpentine (S-shaped) channels
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct SerpentineConfig {
     /// Fraction of available vertical space to fill (0.1 to 0.95)
     pub fill_factor: f64,
@@ -647,6 +1030,12 @@ pub struct SerpentineConfig {
     pub optimization_profile: OptimizationProfile,
     /// Adaptive behavior configuration for dynamic channel properties
     pub adaptive_config: AdaptiveSerpentineConfig,
+    /// Optional length or resistance target; when set, optimization tunes toward it instead of maximizing length
+    pub optimization_target: Option<OptimizationTarget>,
+    /// Optional custom objective; when set, optimization maximizes it instead of the default length objective
+    pub objective: Option<ObjectiveSpec>,
+    /// Keep adjacent half-waves at least the wall clearance apart, as an optimization constraint and by correcting generated paths (default: false)
+    pub avoid_self_intersections: bool,
 }
 
 impl SerpentineConfig {
@@ -668,6 +1057,9 @@ impl SerpentineConfig {
             target_fill_ratio: 0.9, // Default target for optimization
             optimization_profile: OptimizationProfile::Balanced, // Default profile
             adaptive_config: AdaptiveSerpentineConfig::default(), // Default adaptive behavior
+            optimization_target: None,
+            objective: None,
+            avoid_self_intersections: false,
         };
         config.validate()?;
         Ok(config)
@@ -692,6 +1084,9 @@ impl SerpentineConfig {
             target_fill_ratio,
             optimization_profile: OptimizationProfile::Balanced, // Default profile
             adaptive_config: AdaptiveSerpentineConfig::default(), // Default adaptive behavior
+            optimization_target: None,
+            objective: None,
+            avoid_self_intersections: false,
         };
         config.validate()?;
         Ok(config)
@@ -717,6 +1112,9 @@ impl SerpentineConfig {
             target_fill_ratio,
             optimization_profile,
             adaptive_config: AdaptiveSerpentineConfig::default(), // Default adaptive behavior
+            optimization_target: None,
+            objective: None,
+            avoid_self_intersections: false,
         };
         config.validate()?;
         Ok(config)
@@ -741,6 +1139,9 @@ impl SerpentineConfig {
             target_fill_ratio: 0.9, // Default target for optimization
             optimization_profile: OptimizationProfile::Balanced, // Default profile
             adaptive_config: AdaptiveSerpentineConfig::default(), // Default adaptive behavior
+            optimization_target: None,
+            objective: None,
+            avoid_self_intersections: false,
         };
         config.validate()?;
         Ok(config)
@@ -796,6 +1197,14 @@ impl SerpentineConfig {
             ));
         }
 
+        if let Some(target) = &self.optimization_target {
+            target.validate()?;
+        }
+
+        if let Some(objective) = &self.objective {
+            objective.validate()?;
+        }
+
         Ok(())
     }
 
@@ -816,10 +1225,45 @@ impl SerpentineConfig {
         self.wave_shape = wave_shape;
         self
     }
+
+    /// Tune this configuration toward a length or hydraulic resistance target
+    ///
+    /// Enables optimization; the optimizer adjusts wavelength, wave density and
+    /// fill factor until the channel meets the target within its tolerance.
+    #[must_use]
+    pub const fn with_optimization_target(mut self, target: OptimizationTarget) -> Self {
+        self.optimization_enabled = true;
+        self.optimization_target = Some(target);
+        self
+    }
+
+    /// Optimize this configuration against a custom objective
+    ///
+    /// Enables optimization; the optimizer maximizes the weighted objective
+    /// instead of the default length-minus-clearance-penalty objective.
+    #[must_use]
+    pub const fn with_objective(mut self, objective: ObjectiveSpec) -> Self {
+        self.optimization_enabled = true;
+        self.objective = Some(objective);
+        self
+    }
+
+    /// Keep the half-waves of each serpentine from folding onto each other
+    ///
+    /// Optimization treats the spacing of a path to itself like wall and
+    /// neighbor clearance. When a generated path still comes closer to itself
+    /// than the channel width plus wall clearance, its wave density and then
+    /// its fill factor are reduced until it no longer does.
+    #[must_use]
+    pub const fn with_self_intersection_avoidance(mut self) -> Self {
+        self.avoid_self_intersections = true;
+        self
+    }
 }
 
 /// Configuration for arc (curved) channels
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct ArcConfig {
     /// Controls how curved the arc is - 0.0 = straight, 1.0 = semicircle (0.0 to 2.0)
     pub curvature_factor: f64,
@@ -950,6 +1394,9 @@ impl Default for SerpentineConfig {
             target_fill_ratio: 0.9, // Default target for optimization
             optimization_profile: OptimizationProfile::Balanced, // Default profile
             adaptive_config: AdaptiveSerpentineConfig::default(), // Default adaptive behavior
+            optimization_target: None,
+            objective: None,
+            avoid_self_intersections: false,
         }
     }
 }
@@ -969,7 +1416,7 @@ impl Default for ArcConfig {
 }
 
 /// Configuration for frustum (tapered) channels with venturi throat functionality
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct FrustumConfig {
     /// Inlet width (starting width) - must be positive (0.1 to 50.0)
     pub inlet_width: f64,
@@ -1183,7 +1630,7 @@ impl Default for FrustumConfig {
 ///     frustum_config: FrustumConfig::default(),
 /// };
 /// ```
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub enum ChannelTypeConfig {
     /// All channels will be straight lines
     AllStraight,
@@ -1221,6 +1668,10 @@ pub enum ChannelTypeConfig {
         smooth_straight_config: SmoothTransitionConfig,
     },
     /// Custom function for determining channel type based on endpoints and box dimensions
+    ///
+    /// Functions cannot be saved, so configurations using this variant fail
+    /// to serialize.
+    #[serde(skip)]
     Custom(fn(from: (f64, f64), to: (f64, f64), box_dims: (f64, f64)) -> ChannelType),
 }
 
@@ -1234,6 +1685,50 @@ impl Default for ChannelTypeConfig {
     }
 }
 
+impl ChannelTypeConfig {
+    /// Validate every configuration used by this channel type selection
+    ///
+    /// Custom functions cannot be checked and are always accepted.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first invalid value found.
+    pub fn validate(&self) -> ConfigurationResult<()> {
+        let smooth = |config: &SmoothTransitionConfig| {
+            config
+                .validate()
+                .map_err(|message| ConfigurationError::ConflictingValues { conflict: message })
+        };
+        match self {
+            Self::AllStraight | Self::Custom(_) => Ok(()),
+            Self::AllSmoothStraight(config) => smooth(config),
+            Self::AllSerpentine(config) => config.validate(),
+            Self::AllArcs(config) => config.validate(),
+            Self::AllFrustum(config) => config.validate(),
+            Self::MixedByPosition { middle_zone_fraction, serpentine_config, arc_config } => {
+                if !(0.0..=1.0).contains(middle_zone_fraction) {
+                    return Err(ConfigurationError::invalid_geometry_config(
+                        "middle_zone_fraction",
+                        *middle_zone_fraction,
+                        "Must be between 0 and 1",
+                    ));
+                }
+                serpentine_config.validate()?;
+                arc_config.validate()
+            }
+            Self::Adaptive { serpentine_config, arc_config, frustum_config } => {
+                serpentine_config.validate()?;
+                arc_config.validate()?;
+                frustum_config.validate()
+            }
+            Self::SmoothSerpentineWithTransitions { serpentine_config, smooth_straight_config } => {
+                serpentine_config.validate()?;
+                smooth(smooth_straight_config)
+            }
+        }
+    }
+}
+
 /// Configuration presets for common use cases
 pub mod presets {
     use super::*;
@@ -1276,6 +1771,9 @@ pub mod presets {
             target_fill_ratio: 0.9,
             optimization_profile: OptimizationProfile::Balanced,
             adaptive_config: AdaptiveSerpentineConfig::aggressive(), // High-density needs aggressive adaptation
+            optimization_target: None,
+            objective: None,
+            avoid_self_intersections: false,
         }
     }
 
@@ -1292,6 +1790,9 @@ pub mod presets {
             target_fill_ratio: 0.9,
             optimization_profile: OptimizationProfile::Balanced,
             adaptive_config: AdaptiveSerpentineConfig::conservative(), // Smooth channels need conservative adaptation
+            optimization_target: None,
+            objective: None,
+            avoid_self_intersections: false,
         }
     }
 
@@ -1308,6 +1809,9 @@ pub mod presets {
             target_fill_ratio: 0.9,
             optimization_profile: OptimizationProfile::Balanced,
             adaptive_config: AdaptiveSerpentineConfig::default(), // Default adaptive behavior
+            optimization_target: None,
+            objective: None,
+            avoid_self_intersections: false,
         }
     }
 
@@ -1324,6 +1828,9 @@ pub mod presets {
             target_fill_ratio: 0.9,
             optimization_profile: OptimizationProfile::Balanced,
             adaptive_config: AdaptiveSerpentineConfig::default(), // Default adaptive behavior
+            optimization_target: None,
+            objective: None,
+            avoid_self_intersections: false,
         }
     }
 
@@ -1340,6 +1847,9 @@ pub mod presets {
             target_fill_ratio: 0.95, // Aggressive optimization target
             optimization_profile: OptimizationProfile::Balanced,
             adaptive_config: AdaptiveSerpentineConfig::default(), // Default adaptive behavior
+            optimization_target: None,
+            objective: None,
+            avoid_self_intersections: false,
         }
     }
 
@@ -1356,6 +1866,9 @@ pub mod presets {
             target_fill_ratio: 0.9, // Moderate optimization target
             optimization_profile: OptimizationProfile::Fast,
             adaptive_config: AdaptiveSerpentineConfig::default(), // Default adaptive behavior
+            optimization_target: None,
+            objective: None,
+            avoid_self_intersections: false,
         }
     }
 
@@ -1372,6 +1885,9 @@ pub mod presets {
             target_fill_ratio: 0.98, // Very aggressive optimization target
             optimization_profile: OptimizationProfile::Thorough,
             adaptive_config: AdaptiveSerpentineConfig::default(), // Default adaptive behavior
+            optimization_target: None,
+            objective: None,
+            avoid_self_intersections: false,
         }
     }
 
@@ -1388,6 +1904,9 @@ pub mod presets {
             target_fill_ratio: 0.9,
             optimization_profile: OptimizationProfile::Balanced,
             adaptive_config: AdaptiveSerpentineConfig::default(),
+            optimization_target: None,
+            objective: None,
+            avoid_self_intersections: false,
         }
     }
 
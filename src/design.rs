@@ -0,0 +1,411 @@
+//! design.rs - Declarative Design Files
+//!
+//! [`ChannelSystem::to_json`](crate::geometry::ChannelSystem::to_json) saves
+//! the generated geometry, but not the inputs it was generated from. A
+//! [`DesignSpec`] captures those inputs — box dimensions, split pattern,
+//! geometry configuration and channel type configuration, including
+//! serpentine optimization settings — in a versioned TOML or JSON file that
+//! can be kept under version control and regenerated at any time.
+//!
+//! Omitted configuration fields take their default values and unknown fields
+//! are rejected. [`FrustumConfig`](crate::config::FrustumConfig) and
+//! [`AdaptiveSerpentineConfig`](crate::config::AdaptiveSerpentineConfig) were
+//! serializable before design files existed and keep their own rules: when
+//! given, they must list every field, and unknown fields in them are ignored.
+//! [`DesignSpec::validate`] reports constraint violations with
+//! the dotted path of the offending field, such as
+//! `channel_type.AllSerpentine.fill_factor`.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use scheme::design::DesignSpec;
+//!
+//! let spec = DesignSpec::from_toml(r#"
+//!     version = 1
+//!     name = "two-level splitter"
+//!     box_dims = [200.0, 100.0]
+//!     splits = ["Bifurcation", "Trifurcation"]
+//!
+//!     [geometry]
+//!     channel_width = 1.5
+//!
+//!     [channel_type.AllSerpentine]
+//!     fill_factor = 0.7
+//!     wave_shape = "Square"
+//! "#)?;
+//!
+//! let system = spec.generate()?;
+//! assert_eq!(system.box_dims, (200.0, 100.0));
+//!
+//! // Saved designs load back unchanged
+//! let reloaded = DesignSpec::from_toml(&spec.to_toml()?)?;
+//! assert_eq!(reloaded.generate()?.channels.len(), system.channels.len());
+//! # Ok::<(), scheme::error::SchemeError>(())
+//! ```
+
+use crate::config::{ChannelTypeConfig, GeometryConfig, SerpentineConfig};
+use crate::error::{ConfigurationError, DesignError, DesignResult, FieldError, SchemeResult};
+use crate::geometry::{generator::try_create_geometry, ChannelSystem, SplitType};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Version of the design file format written by this library
+pub const DESIGN_FORMAT_VERSION: u32 = 1;
+
+/// Text format of a design file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DesignFormat {
+    /// TOML, the recommended format for hand-written designs
+    Toml,
+    /// JSON
+    Json,
+}
+
+impl DesignFormat {
+    /// Format implied by the extension of `path` (`.toml` or `.json`)
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DesignError::FileError`] if the extension is missing or not
+    /// recognized.
+    pub fn from_path(path: &Path) -> DesignResult<Self> {
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some(extension) if extension.eq_ignore_ascii_case("toml") => Ok(Self::Toml),
+            Some(extension) if extension.eq_ignore_ascii_case("json") => Ok(Self::Json),
+            _ => Err(DesignError::file_error(&format!(
+                "cannot tell the format of '{}': expected a .toml or .json extension",
+                path.display()
+            ))),
+        }
+    }
+
+    /// Name of the format for messages
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Toml => "TOML",
+            Self::Json => "JSON",
+        }
+    }
+}
+
+/// Complete, reproducible description of a channel system design
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DesignSpec {
+    /// Version of the design file format
+    pub version: u32,
+    /// Optional name of the design
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Box dimensions (length, width) in mm
+    pub box_dims: (f64, f64),
+    /// Split pattern from inlet to center
+    #[serde(default)]
+    pub splits: Vec<SplitType>,
+    /// Wall clearance, channel dimensions and generation quality
+    #[serde(default)]
+    pub geometry: GeometryConfig,
+    /// Channel type configuration, including optimization settings
+    #[serde(default)]
+    pub channel_type: ChannelTypeConfig,
+}
+
+/// Fields read before the rest of a design to check its format version
+#[derive(Deserialize)]
+struct VersionProbe {
+    version: Option<u32>,
+}
+
+impl DesignSpec {
+    /// Create a design in the current format version
+    #[must_use]
+    pub fn new(
+        box_dims: (f64, f64),
+        splits: &[SplitType],
+        geometry: GeometryConfig,
+        channel_type: ChannelTypeConfig,
+    ) -> Self {
+        Self {
+            version: DESIGN_FORMAT_VERSION,
+            name: None,
+            box_dims,
+            splits: splits.to_vec(),
+            geometry,
+            channel_type,
+        }
+    }
+
+    /// Set the name of the design
+    #[must_use]
+    pub fn with_name(mut self, name: &str) -> Self {
+        self.name = Some(name.to_string());
+        self
+    }
+
+    /// Parse a design from TOML
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the text is not a design in a supported format
+    /// version. Field constraints are checked by [`Self::validate`].
+    pub fn from_toml(text: &str) -> DesignResult<Self> {
+        Self::parse(text, DesignFormat::Toml)
+    }
+
+    /// Parse a design from JSON
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the text is not a design in a supported format
+    /// version. Field constraints are checked by [`Self::validate`].
+    pub fn from_json(text: &str) -> DesignResult<Self> {
+        Self::parse(text, DesignFormat::Json)
+    }
+
+    /// Parse a design from text in `format`
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DesignError::ParseError`] if the text is malformed, misses a
+    /// required field or has unknown fields, and
+    /// [`DesignError::UnsupportedVersion`] if it was written in a format
+    /// version this library cannot read.
+    pub fn parse(text: &str, format: DesignFormat) -> DesignResult<Self> {
+        let probe: VersionProbe = deserialize(text, format)?;
+        match probe.version {
+            None => Err(DesignError::parse_error(format.name(), "missing field `version`")),
+            Some(DESIGN_FORMAT_VERSION) => deserialize(text, format),
+            Some(found) => Err(DesignError::UnsupportedVersion {
+                found,
+                supported: DESIGN_FORMAT_VERSION,
+            }),
+        }
+    }
+
+    /// Write the design as TOML
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DesignError::SerializeError`] if the design uses custom
+    /// functions, which cannot be saved.
+    pub fn to_toml(&self) -> DesignResult<String> {
+        self.to_text(DesignFormat::Toml)
+    }
+
+    /// Write the design as JSON
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DesignError::SerializeError`] if the design uses custom
+    /// functions, which cannot be saved.
+    pub fn to_json(&self) -> DesignResult<String> {
+        self.to_text(DesignFormat::Json)
+    }
+
+    /// Write the design as text in `format`
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DesignError::SerializeError`] if the design uses custom
+    /// functions, which cannot be saved.
+    pub fn to_text(&self, format: DesignFormat) -> DesignResult<String> {
+        let result = match format {
+            DesignFormat::Toml => toml::to_string_pretty(self).map_err(|error| error.to_string()),
+            DesignFormat::Json => serde_json::to_string_pretty(self).map_err(|error| error.to_string()),
+        };
+        result.map_err(|message| DesignError::serialize_error(format.name(), &message))
+    }
+
+    /// Load a design file, choosing the format by its extension
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DesignError::FileError`] if the file cannot be read or has
+    /// an unknown extension, and the errors of [`Self::parse`] otherwise.
+    pub fn load(path: impl AsRef<Path>) -> DesignResult<Self> {
+        let path = path.as_ref();
+        let format = DesignFormat::from_path(path)?;
+        let text = std::fs::read_to_string(path)
+            .map_err(|error| DesignError::file_error(&format!("cannot read '{}': {error}", path.display())))?;
+        Self::parse(&text, format)
+    }
+
+    /// Save the design to a file, choosing the format by its extension
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DesignError::FileError`] if the file cannot be written or
+    /// has an unknown extension, and the errors of [`Self::to_text`]
+    /// otherwise.
+    pub fn save(&self, path: impl AsRef<Path>) -> DesignResult<()> {
+        let path = path.as_ref();
+        let text = self.to_text(DesignFormat::from_path(path)?)?;
+        std::fs::write(path, text)
+            .map_err(|error| DesignError::file_error(&format!("cannot write '{}': {error}", path.display())))
+    }
+
+    /// Check every field against its constraints
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DesignError::UnsupportedVersion`] for a design in another
+    /// format version, and [`DesignError::InvalidFields`] listing the first
+    /// violation of each configuration section otherwise.
+    pub fn validate(&self) -> DesignResult<()> {
+        if self.version != DESIGN_FORMAT_VERSION {
+            return Err(DesignError::UnsupportedVersion {
+                found: self.version,
+                supported: DESIGN_FORMAT_VERSION,
+            });
+        }
+
+        let mut errors = Vec::new();
+        let (length, width) = self.box_dims;
+        if !(length.is_finite() && width.is_finite() && length > 0.0 && width > 0.0) {
+            errors.push(FieldError::new(
+                "box_dims",
+                &format!("Both dimensions must be positive, got ({length}, {width})"),
+            ));
+        }
+        if let Err(error) = self.geometry.validate() {
+            // Generation errors come from the nested `generation` section
+            let prefix = if matches!(error, ConfigurationError::InvalidGenerationConfig { .. }) {
+                "geometry.generation"
+            } else {
+                "geometry"
+            };
+            errors.push(field_error(prefix, &error));
+        }
+        validate_channel_type(&self.channel_type, &mut errors);
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(DesignError::InvalidFields { errors })
+        }
+    }
+
+    /// Validate the design and generate its channel system
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`Self::validate`] as [`SchemeError::Design`],
+    /// then the feasibility errors of [`try_create_geometry`], such as a
+    /// split pattern too dense for the box.
+    ///
+    /// [`SchemeError::Design`]: crate::error::SchemeError::Design
+    pub fn generate(&self) -> SchemeResult<ChannelSystem> {
+        self.validate()?;
+        try_create_geometry(self.box_dims, &self.splits, &self.geometry, &self.channel_type)
+    }
+}
+
+/// Deserialize `text` in `format` into `T`
+fn deserialize<T: for<'de> Deserialize<'de>>(text: &str, format: DesignFormat) -> DesignResult<T> {
+    let result = match format {
+        DesignFormat::Toml => toml::from_str(text).map_err(|error| error.to_string()),
+        DesignFormat::Json => serde_json::from_str(text).map_err(|error| error.to_string()),
+    };
+    result.map_err(|message| DesignError::parse_error(format.name(), message.trim_end()))
+}
+
+/// Collect the first violation of each configuration within `channel_type`
+fn validate_channel_type(channel_type: &ChannelTypeConfig, errors: &mut Vec<FieldError>) {
+    match channel_type {
+        ChannelTypeConfig::AllStraight | ChannelTypeConfig::Custom(_) => {}
+        ChannelTypeConfig::AllSmoothStraight(config) => {
+            if let Err(message) = config.validate() {
+                errors.push(FieldError::new("channel_type.AllSmoothStraight", &message));
+            }
+        }
+        ChannelTypeConfig::AllSerpentine(config) => {
+            validate_serpentine("channel_type.AllSerpentine", config, errors);
+        }
+        ChannelTypeConfig::AllArcs(config) => {
+            if let Err(error) = config.validate() {
+                errors.push(field_error("channel_type.AllArcs", &error));
+            }
+        }
+        ChannelTypeConfig::AllFrustum(config) => {
+            if let Err(error) = config.validate() {
+                errors.push(field_error("channel_type.AllFrustum", &error));
+            }
+        }
+        ChannelTypeConfig::MixedByPosition {
+            middle_zone_fraction,
+            serpentine_config,
+            arc_config,
+        } => {
+            if !(0.0..=1.0).contains(middle_zone_fraction) {
+                errors.push(FieldError::new(
+                    "channel_type.MixedByPosition.middle_zone_fraction",
+                    &format!("Must be between 0 and 1, got {middle_zone_fraction}"),
+                ));
+            }
+            validate_serpentine("channel_type.MixedByPosition.serpentine_config", serpentine_config, errors);
+            if let Err(error) = arc_config.validate() {
+                errors.push(field_error("channel_type.MixedByPosition.arc_config", &error));
+            }
+        }
+        ChannelTypeConfig::Adaptive {
+            serpentine_config,
+            arc_config,
+            frustum_config,
+        } => {
+            validate_serpentine("channel_type.Adaptive.serpentine_config", serpentine_config, errors);
+            if let Err(error) = arc_config.validate() {
+                errors.push(field_error("channel_type.Adaptive.arc_config", &error));
+            }
+            if let Err(error) = frustum_config.validate() {
+                errors.push(field_error("channel_type.Adaptive.frustum_config", &error));
+            }
+        }
+        ChannelTypeConfig::SmoothSerpentineWithTransitions {
+            serpentine_config,
+            smooth_straight_config,
+        } => {
+            validate_serpentine(
+                "channel_type.SmoothSerpentineWithTransitions.serpentine_config",
+                serpentine_config,
+                errors,
+            );
+            if let Err(message) = smooth_straight_config.validate() {
+                errors.push(FieldError::new(
+                    "channel_type.SmoothSerpentineWithTransitions.smooth_straight_config",
+                    &message,
+                ));
+            }
+        }
+    }
+}
+
+/// Collect the first violation of a serpentine configuration
+fn validate_serpentine(prefix: &str, config: &SerpentineConfig, errors: &mut Vec<FieldError>) {
+    if let Err(error) = config.validate() {
+        errors.push(field_error(prefix, &error));
+    }
+}
+
+/// Field error for a configuration error raised by the section at `prefix`
+///
+/// `prefix` is the path of the section that raised the error, so nested
+/// sections such as `geometry.generation` must be named by the caller.
+fn field_error(prefix: &str, error: &ConfigurationError) -> FieldError {
+    match error {
+        ConfigurationError::InvalidGeometryConfig { field, value, constraint }
+        | ConfigurationError::InvalidSerpentineConfig { field, value, constraint }
+        | ConfigurationError::InvalidArcConfig { field, value, constraint }
+        | ConfigurationError::InvalidFrustumConfig { field, value, constraint } => {
+            FieldError::new(&format!("{prefix}.{field}"), &format!("{constraint}, got {value}"))
+        }
+        ConfigurationError::InvalidGenerationConfig { field, constraint } => {
+            FieldError::new(&format!("{prefix}.{field}"), constraint)
+        }
+        ConfigurationError::MissingConfiguration { field } => {
+            FieldError::new(&format!("{prefix}.{field}"), "Required value is missing")
+        }
+        ConfigurationError::ConflictingValues { conflict } => FieldError::new(prefix, conflict),
+    }
+}
@@ -32,6 +32,14 @@ pub enum SchemeError {
     /// Legacy simulation errors (kept for backward compatibility)
     #[error("Simulation error: {0}")]
     Simulation(#[from] SimulationError),
+
+    /// Errors related to design files
+    #[error("Design error: {0}")]
+    Design(#[from] DesignError),
+
+    /// Errors related to importing drawings from other tools
+    #[error("Import error: {0}")]
+    Import(#[from] ImportError),
 }
 
 /// Errors related to geometry generation and validation
@@ -73,6 +81,50 @@ pub enum GeometryError {
     /// Overlapping channels detected
     #[error("Overlapping channels detected between points ({x1}, {y1}) and ({x2}, {y2})")]
     OverlappingChannels { x1: f64, y1: f64, x2: f64, y2: f64 },
+
+    /// Invalid composition of channel systems
+    #[error("Invalid composition: {reason}")]
+    InvalidComposition { reason: String },
+
+    /// Invalid cell hierarchy
+    #[error("Invalid cell hierarchy: {reason}")]
+    InvalidHierarchy { reason: String },
+
+    /// Invalid step-and-repeat array
+    #[error("Invalid array layout: {reason}")]
+    InvalidArray { reason: String },
+
+    /// Design rule check found error-severity violations
+    #[error("Design rule check failed with {count} error(s)")]
+    DesignRuleViolations { count: usize },
+
+    /// Saved channel system uses a newer format than supported
+    #[error("Unsupported channel system format version {found}; versions 1 to {supported} can be read")]
+    UnsupportedFormatVersion { found: u32, supported: u32 },
+
+    /// Saved channel system could not be read
+    #[error("Invalid channel system document: {reason}")]
+    InvalidDocument { reason: String },
+
+    /// Channel refers to a node that does not exist
+    #[error("Channel {channel_id} refers to node {node_id}, but the system has {node_count} nodes")]
+    DanglingNodeReference { channel_id: usize, node_id: usize, node_count: usize },
+
+    /// Two nodes or two channels share an ID
+    #[error("Duplicate {element} ID {id}")]
+    DuplicateId { element: String, id: usize },
+
+    /// Node ID differs from the node's position in the node list
+    #[error("Node at index {index} has ID {id}; node IDs must match their index")]
+    NodeIdMismatch { index: usize, id: usize },
+
+    /// Channel path does not start or end at its node
+    #[error("Channel {channel_id} path ends {distance} away from its node {node_id}")]
+    PathEndpointMismatch { channel_id: usize, node_id: usize, distance: f64 },
+
+    /// Point lies outside the bounds of the system outline
+    #[error("Point ({x}, {y}) lies outside the system outline")]
+    OutsideOutline { x: f64, y: f64 },
 }
 
 /// Errors related to configuration validation
@@ -159,6 +211,69 @@ pub enum StrategyError {
     UnsupportedChannelType { channel_type: String },
 }
 
+/// Errors related to loading, saving and validating design files
+#[derive(Error, Debug)]
+pub enum DesignError {
+    /// Design file could not be read or written
+    #[error("Design file error: {message}")]
+    FileError { message: String },
+
+    /// Design text is not valid or does not match the design format
+    #[error("Failed to parse {format} design: {message}")]
+    ParseError { format: String, message: String },
+
+    /// Design could not be written in the requested format
+    #[error("Failed to serialize {format} design: {message}")]
+    SerializeError { format: String, message: String },
+
+    /// Design was written in a format version this library cannot read
+    #[error("Unsupported design format version {found}. Supported version: {supported}")]
+    UnsupportedVersion { found: u32, supported: u32 },
+
+    /// One or more fields of the design violate their constraints
+    #[error("Invalid design: {}", .errors.iter().map(ToString::to_string).collect::<Vec<_>>().join("; "))]
+    InvalidFields { errors: Vec<FieldError> },
+}
+
+/// Constraint violation of a single design field
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldError {
+    /// Dotted path of the field within the design, e.g. `geometry.channel_width`
+    pub field: String,
+    /// Description of the violated constraint
+    pub message: String,
+}
+
+impl std::fmt::Display for FieldError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.field, self.message)
+    }
+}
+
+/// Errors related to importing channel layouts from SVG and DXF drawings
+#[derive(Error, Debug)]
+pub enum ImportError {
+    /// Drawing file could not be read
+    #[error("Import file error: {message}")]
+    FileError { message: String },
+
+    /// Drawing is not valid in its format
+    #[error("Failed to parse {format} drawing: {message}")]
+    ParseError { format: String, message: String },
+
+    /// No shapes were found on the outline layer
+    #[error("No chip outline found on layer '{layer}'")]
+    MissingOutline { layer: String },
+
+    /// No channels were found on the channel layers
+    #[error("No channels found in the drawing")]
+    NoChannels,
+
+    /// The imported channels do not form a valid channel system
+    #[error("Imported channel system is invalid: {0}")]
+    InvalidSystem(#[from] GeometryError),
+}
+
 /// Legacy simulation errors (kept for backward compatibility)
 #[derive(Error, Debug)]
 pub enum SimulationError {
@@ -170,6 +285,24 @@ pub enum SimulationError {
 /// Convenient result type for scheme operations
 pub type SchemeResult<T> = Result<T, SchemeError>;
 
+impl SchemeError {
+    /// Process exit code for this error category
+    ///
+    /// Codes 0-2 are reserved for success, unreadable input and usage errors.
+    #[must_use]
+    pub const fn exit_code(&self) -> u8 {
+        match self {
+            Self::Geometry(_) => 3,
+            Self::Configuration(_) => 4,
+            Self::Visualization(_) => 5,
+            Self::Strategy(_) => 6,
+            Self::Simulation(_) => 7,
+            Self::Design(_) => 8,
+            Self::Import(_) => 9,
+        }
+    }
+}
+
 /// Convenient result type for geometry operations
 pub type GeometryResult<T> = Result<T, GeometryError>;
 
@@ -182,6 +315,12 @@ pub type VisualizationResult<T> = Result<T, VisualizationError>;
 /// Convenient result type for strategy operations
 pub type StrategyResult<T> = Result<T, StrategyError>;
 
+/// Convenient result type for design file operations
+pub type DesignResult<T> = Result<T, DesignError>;
+
+/// Convenient result type for drawing imports
+pub type ImportResult<T> = Result<T, ImportError>;
+
 impl GeometryError {
     /// Create an invalid point error
     pub fn invalid_point(point: Point2D) -> Self {
@@ -197,6 +336,30 @@ impl GeometryError {
     pub fn insufficient_space(required: f64, available: f64) -> Self {
         Self::InsufficientSpace { required, available }
     }
+
+    /// Create an invalid composition error
+    #[must_use]
+    pub fn invalid_composition(reason: &str) -> Self {
+        Self::InvalidComposition { reason: reason.to_string() }
+    }
+
+    /// Create an invalid hierarchy error
+    #[must_use]
+    pub fn invalid_hierarchy(reason: &str) -> Self {
+        Self::InvalidHierarchy { reason: reason.to_string() }
+    }
+
+    /// Create an invalid array layout error
+    #[must_use]
+    pub fn invalid_array(reason: &str) -> Self {
+        Self::InvalidArray { reason: reason.to_string() }
+    }
+
+    /// Create an invalid document error
+    #[must_use]
+    pub fn invalid_document(reason: &str) -> Self {
+        Self::InvalidDocument { reason: reason.to_string() }
+    }
 }
 
 impl ConfigurationError {
@@ -273,6 +436,60 @@ impl VisualizationError {
     }
 }
 
+impl DesignError {
+    /// Create a design file error
+    #[must_use]
+    pub fn file_error(message: &str) -> Self {
+        Self::FileError { message: message.to_string() }
+    }
+
+    /// Create a parse error for a design in `format`
+    #[must_use]
+    pub fn parse_error(format: &str, message: &str) -> Self {
+        Self::ParseError {
+            format: format.to_string(),
+            message: message.to_string(),
+        }
+    }
+
+    /// Create a serialization error for a design in `format`
+    #[must_use]
+    pub fn serialize_error(format: &str, message: &str) -> Self {
+        Self::SerializeError {
+            format: format.to_string(),
+            message: message.to_string(),
+        }
+    }
+}
+
+impl ImportError {
+    /// Create an import file error
+    #[must_use]
+    pub fn file_error(message: &str) -> Self {
+        Self::FileError { message: message.to_string() }
+    }
+
+    /// Create a parse error for a drawing in `format`
+    #[must_use]
+    pub fn parse_error(format: &str, message: &str) -> Self {
+        Self::ParseError {
+            format: format.to_string(),
+            message: message.to_string(),
+        }
+    }
+}
+
+impl FieldError {
+    /// Create a field error
+    #[must_use]
+    pub fn new(field: &str, message: &str) -> Self {
+        Self {
+            field: field.to_string(),
+            message: message.to_string(),
+        }
+    }
+}
+
 impl StrategyError {
     /// Create a strategy creation failed error
     pub fn strategy_creation_failed(channel_type: &str, reason: &str) -> Self {
@@ -0,0 +1,439 @@
+//! `geometry/array.rs` - Step-and-Repeat Arrays
+//!
+//! Masks usually carry many devices: identical copies for yield, or variants
+//! of one design for a parameter study. An [`ArrayGenerator`] lays out a grid
+//! of dies on a round wafer or a rectangular plate ([`WaferOutline`]). Dies
+//! are spaced by a pitch, separated by dicing lanes and kept a margin away
+//! from the outline; sites that do not fit are left empty.
+//!
+//! Every die is generated by a closure from its [`DieSite`], which carries the
+//! die's row, column, label and, when a [`ParameterSweep`] is configured, the
+//! swept parameter value for that row or column. A single mask can thereby
+//! cover a whole study, such as a range of serpentine fill factors across the
+//! columns.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use scheme::config::{ChannelTypeConfig, GeometryConfig, SerpentineConfig};
+//! use scheme::geometry::array::{ArrayGenerator, ParameterSweep, SweepAxis, WaferOutline};
+//! use scheme::geometry::generator::create_geometry;
+//! use scheme::geometry::SplitType;
+//!
+//! let generator = ArrayGenerator::new(WaferOutline::Wafer { diameter: 100.0 }, (25.0, 20.0))
+//!     .with_dicing_lane(2.0)
+//!     .with_keep_out(3.0)
+//!     .with_sweep(ParameterSweep::linear(SweepAxis::Columns, 0.3, 0.9, 3));
+//!
+//! let layout = generator.build(|site| {
+//!     let serpentine = SerpentineConfig {
+//!         fill_factor: site.parameter.unwrap_or(0.8),
+//!         ..SerpentineConfig::default()
+//!     };
+//!     create_geometry(
+//!         (22.0, 16.0),
+//!         &[SplitType::Bifurcation],
+//!         &GeometryConfig::default(),
+//!         &ChannelTypeConfig::AllSerpentine(serpentine),
+//!     )
+//! })?;
+//!
+//! assert!(layout.dies.len() >= 6);
+//! assert_eq!(layout.dies[0].site.label, "A2");
+//! # Ok::<(), scheme::error::GeometryError>(())
+//! ```
+
+use crate::error::{GeometryError, GeometryResult};
+use crate::geometry::{ChannelSystem, Point2D};
+use std::f64::consts::TAU;
+
+/// Constants for array layout
+mod constants {
+    /// Number of straight segments approximating a round wafer outline
+    pub const WAFER_OUTLINE_SEGMENTS: usize = 256;
+
+    /// Distance of a die label from the upper left corner of its die (mm)
+    pub const LABEL_INSET: f64 = 1.0;
+
+    /// Tolerance when checking that a design fits its die (mm)
+    pub const FIT_TOLERANCE: f64 = 1e-9;
+}
+
+/// Substrate the array is laid out on, centered on the origin
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WaferOutline {
+    /// Round wafer
+    Wafer {
+        /// Wafer diameter (mm)
+        diameter: f64,
+    },
+    /// Rectangular plate or slide
+    Plate {
+        /// Extent along x (mm)
+        width: f64,
+        /// Extent along y (mm)
+        height: f64,
+    },
+}
+
+impl WaferOutline {
+    /// Width and height of the outline
+    #[must_use]
+    pub const fn extent(&self) -> (f64, f64) {
+        match *self {
+            Self::Wafer { diameter } => (diameter, diameter),
+            Self::Plate { width, height } => (width, height),
+        }
+    }
+
+    /// Whether the rectangle from `min` to `max` lies at least `margin` inside the outline
+    #[must_use]
+    pub fn contains(&self, min: Point2D, max: Point2D, margin: f64) -> bool {
+        match *self {
+            Self::Wafer { diameter } => {
+                let radius = diameter / 2.0 - margin;
+                radius >= 0.0
+                    && [min, max, (min.0, max.1), (max.0, min.1)]
+                        .iter()
+                        .all(|&(x, y)| x.hypot(y) <= radius + constants::FIT_TOLERANCE)
+            }
+            Self::Plate { width, height } => {
+                let (half_width, half_height) = (width / 2.0 - margin, height / 2.0 - margin);
+                min.0 >= -half_width - constants::FIT_TOLERANCE
+                    && min.1 >= -half_height - constants::FIT_TOLERANCE
+                    && max.0 <= half_width + constants::FIT_TOLERANCE
+                    && max.1 <= half_height + constants::FIT_TOLERANCE
+            }
+        }
+    }
+
+    /// Line segments drawing the outline
+    #[allow(clippy::cast_precision_loss)]
+    fn segments(&self) -> Vec<(Point2D, Point2D)> {
+        match *self {
+            Self::Wafer { diameter } => {
+                let radius = diameter / 2.0;
+                let point = |index: usize| {
+                    let (sin, cos) = (TAU * index as f64 / constants::WAFER_OUTLINE_SEGMENTS as f64).sin_cos();
+                    (radius * cos, radius * sin)
+                };
+                (0..constants::WAFER_OUTLINE_SEGMENTS).map(|index| (point(index), point(index + 1))).collect()
+            }
+            Self::Plate { width, height } => rectangle((-width / 2.0, -height / 2.0), (width / 2.0, height / 2.0)),
+        }
+    }
+}
+
+/// Direction along which a [`ParameterSweep`] varies
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SweepAxis {
+    /// One value per row, counted from the top
+    Rows,
+    /// One value per column, counted from the left
+    Columns,
+}
+
+/// Parameter values assigned to the rows or columns of an array
+///
+/// If the grid has more rows or columns than there are values, the values
+/// repeat, so every value of the study appears on the mask more than once.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParameterSweep {
+    /// Whether the values vary across rows or columns
+    pub axis: SweepAxis,
+    /// Value for each row or column
+    pub values: Vec<f64>,
+}
+
+impl ParameterSweep {
+    /// Sweep through the given values
+    #[must_use]
+    pub const fn new(axis: SweepAxis, values: Vec<f64>) -> Self {
+        Self { axis, values }
+    }
+
+    /// Sweep through `steps` evenly spaced values from `start` to `end`
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn linear(axis: SweepAxis, start: f64, end: f64, steps: usize) -> Self {
+        let values = match steps {
+            0 => Vec::new(),
+            1 => vec![start],
+            _ => (0..steps)
+                .map(|step| (end - start).mul_add(step as f64 / (steps - 1) as f64, start))
+                .collect(),
+        };
+        Self::new(axis, values)
+    }
+
+    /// Value for the die in `row` and `column`
+    #[must_use]
+    pub fn value(&self, row: usize, column: usize) -> Option<f64> {
+        let index = match self.axis {
+            SweepAxis::Rows => row,
+            SweepAxis::Columns => column,
+        };
+        (!self.values.is_empty()).then(|| self.values[index % self.values.len()])
+    }
+}
+
+/// Position of a die in the array, passed to the design closure
+#[derive(Debug, Clone, PartialEq)]
+pub struct DieSite {
+    /// Row of the die, counted from the top
+    pub row: usize,
+    /// Column of the die, counted from the left
+    pub column: usize,
+    /// Label of the die: row letters followed by the column number, e.g. `B3`
+    pub label: String,
+    /// Swept parameter value for this die, if a sweep is configured
+    pub parameter: Option<f64>,
+}
+
+/// Where a die ended up in a wafer layout
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiePlacement {
+    /// Grid position, label and parameter of the die
+    pub site: DieSite,
+    /// Die area between the dicing lanes
+    pub bounds: (Point2D, Point2D),
+    /// Anchor of the die label, inside the upper left corner of the die
+    pub label_position: Point2D,
+    /// Index of the die's first node in the layout
+    pub node_offset: usize,
+    /// Number of nodes of the die
+    pub node_count: usize,
+    /// Index of the die's first channel in the layout
+    pub channel_offset: usize,
+    /// Number of channels of the die
+    pub channel_count: usize,
+}
+
+/// Result of [`ArrayGenerator::build`]
+#[derive(Debug, Clone)]
+pub struct WaferLayout {
+    /// All dies as one system; its outline is the substrate outline plus
+    /// one rectangle per die marking the dicing lanes
+    pub system: ChannelSystem,
+    /// Every placed die, row by row from the top left
+    pub dies: Vec<DiePlacement>,
+}
+
+/// Builder for step-and-repeat die arrays on a wafer or plate
+///
+/// The grid is centered on the outline. Each die occupies its pitch minus the
+/// dicing lane, and the design generated for it is centered in that area.
+/// Unless a grid size is set, as many rows and columns are used as fit across
+/// the outline; sites that do not lie entirely within the keep-out margin are
+/// skipped.
+#[derive(Debug, Clone)]
+pub struct ArrayGenerator {
+    outline: WaferOutline,
+    pitch: (f64, f64),
+    dicing_lane: f64,
+    keep_out: f64,
+    grid: Option<(usize, usize)>,
+    sweep: Option<ParameterSweep>,
+    label_prefix: String,
+}
+
+impl ArrayGenerator {
+    /// Create a generator for dies repeated every `pitch` (mm) on `outline`
+    #[must_use]
+    pub const fn new(outline: WaferOutline, pitch: (f64, f64)) -> Self {
+        Self {
+            outline,
+            pitch,
+            dicing_lane: 0.0,
+            keep_out: 0.0,
+            grid: None,
+            sweep: None,
+            label_prefix: String::new(),
+        }
+    }
+
+    /// Set the width of the dicing lanes between dies (mm)
+    #[must_use]
+    pub const fn with_dicing_lane(mut self, dicing_lane: f64) -> Self {
+        self.dicing_lane = dicing_lane;
+        self
+    }
+
+    /// Set the margin dies keep from the outline (mm)
+    #[must_use]
+    pub const fn with_keep_out(mut self, keep_out: f64) -> Self {
+        self.keep_out = keep_out;
+        self
+    }
+
+    /// Use a fixed grid of `rows` by `columns` sites
+    #[must_use]
+    pub const fn with_grid(mut self, rows: usize, columns: usize) -> Self {
+        self.grid = Some((rows, columns));
+        self
+    }
+
+    /// Vary a design parameter across the rows or columns
+    #[must_use]
+    pub fn with_sweep(mut self, sweep: ParameterSweep) -> Self {
+        self.sweep = Some(sweep);
+        self
+    }
+
+    /// Prepend `prefix` to every die label
+    #[must_use]
+    pub fn with_label_prefix(mut self, prefix: &str) -> Self {
+        self.label_prefix = prefix.to_string();
+        self
+    }
+
+    /// Size of the die area inside the dicing lanes
+    #[must_use]
+    pub fn die_size(&self) -> (f64, f64) {
+        (self.pitch.0 - self.dicing_lane, self.pitch.1 - self.dicing_lane)
+    }
+
+    /// Sites that fit on the outline, with the die area of each
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GeometryError::InvalidArray`] if the pitch, dicing lane or
+    /// keep-out margin are invalid.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn sites(&self) -> GeometryResult<Vec<(DieSite, (Point2D, Point2D))>> {
+        self.validate()?;
+        let (rows, columns) = self.grid.unwrap_or_else(|| self.auto_grid());
+        let (die_width, die_height) = self.die_size();
+        let mut sites = Vec::new();
+        for row in 0..rows {
+            for column in 0..columns {
+                let center = (
+                    (column as f64 - (columns - 1) as f64 / 2.0) * self.pitch.0,
+                    ((rows - 1) as f64 / 2.0 - row as f64) * self.pitch.1,
+                );
+                let min = (center.0 - die_width / 2.0, center.1 - die_height / 2.0);
+                let max = (center.0 + die_width / 2.0, center.1 + die_height / 2.0);
+                if !self.outline.contains(min, max, self.keep_out) {
+                    continue;
+                }
+                let site = DieSite {
+                    row,
+                    column,
+                    label: format!("{}{}{}", self.label_prefix, row_letters(row), column + 1),
+                    parameter: self.sweep.as_ref().and_then(|sweep| sweep.value(row, column)),
+                };
+                sites.push((site, (min, max)));
+            }
+        }
+        Ok(sites)
+    }
+
+    /// Generate a design for every site and place it in its die
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GeometryError::InvalidArray`] if the layout parameters are
+    /// invalid, no die fits on the outline, or a generated design is larger
+    /// than its die.
+    pub fn build<F>(&self, mut design: F) -> GeometryResult<WaferLayout>
+    where
+        F: FnMut(&DieSite) -> ChannelSystem,
+    {
+        let sites = self.sites()?;
+        if sites.is_empty() {
+            return Err(GeometryError::invalid_array("no die fits inside the outline and keep-out margin"));
+        }
+
+        let (width, height) = self.outline.extent();
+        let mut system = ChannelSystem {
+            box_dims: (width, height),
+            nodes: Vec::new(),
+            channels: Vec::new(),
+            box_outline: self.outline.segments(),
+        };
+        let (die_width, die_height) = self.die_size();
+        let mut dies = Vec::with_capacity(sites.len());
+        for (site, (min, max)) in sites {
+            let mut die = design(&site);
+            let (die_min, die_max) = die.bounds();
+            if die_max.0 - die_min.0 > die_width + constants::FIT_TOLERANCE
+                || die_max.1 - die_min.1 > die_height + constants::FIT_TOLERANCE
+            {
+                return Err(GeometryError::invalid_array(&format!(
+                    "design for die {} is larger than the {die_width} x {die_height} mm die area",
+                    site.label
+                )));
+            }
+            die.translate(
+                (min.0 + max.0 - die_min.0 - die_max.0) / 2.0,
+                (min.1 + max.1 - die_min.1 - die_max.1) / 2.0,
+            );
+
+            let (node_offset, channel_offset) = system.append(&die);
+            system.box_outline.extend(rectangle(min, max));
+            dies.push(DiePlacement {
+                site,
+                bounds: (min, max),
+                label_position: (min.0 + constants::LABEL_INSET, max.1 - constants::LABEL_INSET),
+                node_offset,
+                node_count: die.nodes.len(),
+                channel_offset,
+                channel_count: die.channels.len(),
+            });
+        }
+
+        Ok(WaferLayout { system, dies })
+    }
+
+    /// Check the pitch, dicing lane and keep-out margin
+    fn validate(&self) -> GeometryResult<()> {
+        if self.dicing_lane < 0.0 || self.keep_out < 0.0 {
+            return Err(GeometryError::invalid_array("dicing lane and keep-out margin must not be negative"));
+        }
+        if self.pitch.0 <= self.dicing_lane || self.pitch.1 <= self.dicing_lane {
+            return Err(GeometryError::invalid_array(&format!(
+                "pitch {:?} must exceed the dicing lane width {}",
+                self.pitch, self.dicing_lane
+            )));
+        }
+        Ok(())
+    }
+
+    /// Largest grid whose dies span the outline within the keep-out margin
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn auto_grid(&self) -> (usize, usize) {
+        let (width, height) = self.outline.extent();
+        let count = |extent: f64, pitch: f64| {
+            let usable = 2.0f64.mul_add(-self.keep_out, extent) + self.dicing_lane;
+            if usable <= 0.0 {
+                0
+            } else {
+                (usable / pitch).floor() as usize
+            }
+        };
+        (count(height, self.pitch.1), count(width, self.pitch.0))
+    }
+}
+
+/// Closed rectangle from `min` to `max` as four line segments
+fn rectangle(min: Point2D, max: Point2D) -> Vec<(Point2D, Point2D)> {
+    vec![
+        (min, (max.0, min.1)),
+        ((max.0, min.1), max),
+        (max, (min.0, max.1)),
+        ((min.0, max.1), min),
+    ]
+}
+
+/// Letters naming `row`: `A` to `Z`, then `AA`, `AB` and so on
+#[allow(clippy::cast_possible_truncation)]
+fn row_letters(row: usize) -> String {
+    let mut letters = Vec::new();
+    let mut remaining = row + 1;
+    while remaining > 0 {
+        remaining -= 1;
+        letters.push(char::from(b'A' + (remaining % 26) as u8));
+        remaining /= 26;
+    }
+    letters.iter().rev().collect()
+}
@@ -5,14 +5,19 @@
 //! both channel-to-channel and channel-to-wall boundary constraints.
 
 use crate::{
-    geometry::Point2D,
-    state_management::{
-        ParameterRegistry,
-        adaptive::ChannelGenerationContext,
+    config::GeometryConfig,
+    geometry::{
+        builders::ChannelExt,
+        metadata::ParametricPathMetadata,
+        optimization::{calculate_min_wall_distance_within, calculate_path_length},
+        spatial_index::{channel_paths, ChannelIntersection, ChannelProximity, ChannelSpatialIndex},
+        ChannelSystem, ChannelType, Point2D,
     },
+    state_management::adaptive::ChannelGenerationContext,
     config_constants::ConstantsRegistry,
-    error::{SchemeResult, SchemeError, ConfigurationError},
+    error::{ConfigurationResult, SchemeResult, ConfigurationError},
 };
+use serde::{Deserialize, Serialize};
 
 /// Enhanced collision detection context that integrates with adaptive parameter system
 #[derive(Debug, Clone)]
@@ -104,12 +109,7 @@ pub struct ChannelInfo {
 
 /// Collision detection and avoidance system
 pub struct CollisionDetectionSystem {
-    /// Parameter registry for collision parameters
-    #[allow(dead_code)] // Part of comprehensive collision detection framework
-    registry: ParameterRegistry,
-
-    /// Cached collision parameters
-    #[allow(dead_code)] // Part of comprehensive collision detection framework
+    /// Cached system-level collision parameters
     cached_params: Option<CollisionParameters>,
 }
 
@@ -197,29 +197,9 @@ impl CollisionParameters {
 
 impl CollisionDetectionSystem {
     /// Create a new collision detection system
-    ///
-    /// # Errors
-    ///
-    /// Returns an error if the parameter registry cannot be initialized with default values.
-    pub fn new() -> SchemeResult<Self> {
-        let registry = ParameterRegistry::with_defaults()
-            .map_err(|e| SchemeError::Configuration(
-                ConfigurationError::MissingConfiguration { field: e.to_string() }
-            ))?;
-        
-        Ok(Self {
-            registry,
-            cached_params: None,
-        })
-    }
-    
-    /// Create with existing parameter registry
     #[must_use]
-    pub const fn with_registry(registry: ParameterRegistry) -> Self {
-        Self {
-            registry,
-            cached_params: None,
-        }
+    pub const fn new() -> Self {
+        Self { cached_params: None }
     }
     
     /// Get collision parameters with adaptive behavior
@@ -268,6 +248,139 @@ impl CollisionDetectionSystem {
         Ok(result)
     }
     
+    /// Detect collisions between the actual paths of every channel in a system
+    ///
+    /// Unlike [`detect_collisions`](Self::detect_collisions), which compares a
+    /// path against neighbor y-coordinates, this measures the true distance
+    /// between channel polylines, so angled and curved channels are handled
+    /// correctly. Channels whose edges come closer than the minimum channel
+    /// distance (scaled by the safety margin) are reported, as are crossing
+    /// centerlines.
+    #[must_use]
+    pub fn detect_system_collisions(&self, system: &ChannelSystem) -> SystemCollisionResult {
+        let params = Self::get_collision_parameters(None);
+        let required_spacing = params.min_channel_distance * params.safety_margin_factor;
+
+        let index = ChannelSpatialIndex::new(system).with_junction_clearance(required_spacing);
+        let intersections = index.intersections();
+        let proximities = index.proximities_within(required_spacing);
+
+        let proximity_severity: f64 = proximities.iter()
+            .map(|proximity| ((required_spacing - proximity.spacing) / required_spacing).min(1.0))
+            .sum();
+        #[allow(clippy::cast_precision_loss)]
+        let severity_score = proximity_severity + intersections.len() as f64;
+
+        SystemCollisionResult {
+            has_collisions: !proximities.is_empty() || !intersections.is_empty(),
+            intersections,
+            proximities,
+            required_spacing,
+            severity_score,
+        }
+    }
+
+    /// Shrink curved channels of a finished system until no collisions remain
+    ///
+    /// This is the post-generation counterpart of
+    /// [`apply_collision_avoidance`](Self::apply_collision_avoidance): instead
+    /// of comparing one path against neighbor y-coordinates, every round
+    /// measures the real distances between all channel paths (and, optionally,
+    /// to the box walls). Each serpentine or arc channel involved in a
+    /// collision is pulled toward its straight chord, which scales its
+    /// amplitude or curvature down while keeping its endpoints fixed. The
+    /// reduction per round grows with the collision severity and is capped by
+    /// the maximum reduction factor of the collision parameters.
+    ///
+    /// Straight and frustum channels are never modified, so collisions between
+    /// them are reported as remaining rather than resolved.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the avoidance configuration is invalid.
+    pub fn resolve_system_collisions(
+        &mut self,
+        system: &mut ChannelSystem,
+        geometry_config: &GeometryConfig,
+        config: &SystemAvoidanceConfig,
+    ) -> ConfigurationResult<SystemAvoidanceReport> {
+        config.validate()?;
+        let max_reduction = self.system_parameters().max_reduction_factor;
+        let required_spacing = config.min_channel_spacing.unwrap_or(geometry_config.wall_clearance);
+        let wall_clearance = config.include_walls.then_some(geometry_config.wall_clearance);
+
+        let initial_lengths: Vec<f64> = channel_paths(system).iter().map(|path| calculate_path_length(path)).collect();
+        let mut amplitude_scales = vec![1.0; system.channels.len()];
+        let mut rounds = vec![0; system.channels.len()];
+
+        let initial = survey_collisions(system, required_spacing, wall_clearance);
+        let initial_collisions = initial.collisions;
+        let mut survey = initial;
+        let mut iterations = 0;
+
+        while survey.collisions > 0 && iterations < config.max_iterations {
+            let mut adjusted = false;
+            for (index, &severity) in survey.severities.iter().enumerate() {
+                if severity <= 0.0 {
+                    continue;
+                }
+                let Some(path) = curved_path_mut(&mut system.channels[index].channel_type) else {
+                    continue;
+                };
+                let (Some(&start), Some(&end)) = (path.first(), path.last()) else {
+                    continue;
+                };
+
+                let reduction_factor = (severity * max_reduction).max(config.min_reduction_step).min(max_reduction);
+                Self::apply_standard_reduction(path, start, end, reduction_factor);
+                // The reduced path no longer follows its analytic curve
+                system.channels[index].remove_metadata::<ParametricPathMetadata>();
+                amplitude_scales[index] *= 1.0 - reduction_factor;
+                rounds[index] += 1;
+                adjusted = true;
+            }
+
+            if !adjusted {
+                break;
+            }
+            iterations += 1;
+            survey = survey_collisions(system, required_spacing, wall_clearance);
+        }
+
+        let final_lengths = channel_paths(system);
+        let adjustments = (0..system.channels.len())
+            .filter(|&index| rounds[index] > 0)
+            .map(|index| ChannelAdjustment {
+                channel_id: system.channels[index].id,
+                amplitude_scale: amplitude_scales[index],
+                rounds: rounds[index],
+                initial_length: initial_lengths[index],
+                final_length: calculate_path_length(&final_lengths[index]),
+            })
+            .collect();
+        let unresolved_channels = survey
+            .severities
+            .iter()
+            .zip(&system.channels)
+            .filter(|(&severity, _)| severity > 0.0)
+            .map(|(_, channel)| channel.id)
+            .collect();
+
+        Ok(SystemAvoidanceReport {
+            iterations,
+            initial_collisions,
+            remaining_collisions: survey.collisions,
+            required_spacing,
+            adjustments,
+            unresolved_channels,
+        })
+    }
+
+    /// System-level collision parameters, computed once and cached
+    fn system_parameters(&mut self) -> &CollisionParameters {
+        self.cached_params.get_or_insert_with(|| Self::get_collision_parameters(None))
+    }
+
     /// Detect collisions with neighboring channels
     fn detect_neighbor_collisions(
         path: &[Point2D],
@@ -593,6 +706,199 @@ pub struct CollisionDetectionResult {
     pub severity_score: f64,
 }
 
+/// Result of checking every channel of a system against every other
+#[derive(Debug)]
+pub struct SystemCollisionResult {
+    /// Whether any channels are too close or cross
+    pub has_collisions: bool,
+
+    /// Points where two channel centerlines cross
+    pub intersections: Vec<ChannelIntersection>,
+
+    /// Channel pairs closer than the required spacing, at their closest approach
+    pub proximities: Vec<ChannelProximity>,
+
+    /// Edge-to-edge spacing that was required (mm)
+    pub required_spacing: f64,
+
+    /// Overall severity score (each crossing counts 1.0)
+    pub severity_score: f64,
+}
+
+/// Settings for the post-generation collision avoidance pass
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SystemAvoidanceConfig {
+    /// Edge-to-edge spacing required between channels (mm); `None` uses the
+    /// wall clearance of the geometry configuration
+    pub min_channel_spacing: Option<f64>,
+
+    /// Whether channels closer to the box walls than the wall clearance count
+    /// as collisions (walls that a channel's own ports sit on are ignored)
+    pub include_walls: bool,
+
+    /// Maximum number of detect-and-shrink rounds
+    pub max_iterations: usize,
+
+    /// Smallest fraction of a channel's remaining amplitude removed per round
+    pub min_reduction_step: f64,
+}
+
+impl SystemAvoidanceConfig {
+    /// Validate the configuration
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the spacing is negative or not finite, no rounds
+    /// are allowed, or the reduction step is outside (0, 1].
+    pub fn validate(&self) -> ConfigurationResult<()> {
+        if self.min_channel_spacing.is_some_and(|spacing| !spacing.is_finite() || spacing < 0.0) {
+            return Err(ConfigurationError::invalid_generation_config(
+                "collision_avoidance.min_channel_spacing",
+                "Must be a non-negative, finite value",
+            ));
+        }
+        if self.max_iterations == 0 {
+            return Err(ConfigurationError::invalid_generation_config(
+                "collision_avoidance.max_iterations",
+                "Must be at least 1",
+            ));
+        }
+        if !(self.min_reduction_step > 0.0 && self.min_reduction_step <= 1.0) {
+            return Err(ConfigurationError::invalid_generation_config(
+                "collision_avoidance.min_reduction_step",
+                "Must be greater than 0.0 and at most 1.0",
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl Default for SystemAvoidanceConfig {
+    fn default() -> Self {
+        Self {
+            min_channel_spacing: None,
+            include_walls: true,
+            max_iterations: 20,
+            min_reduction_step: 0.1,
+        }
+    }
+}
+
+/// How one channel was changed by the collision avoidance pass
+#[derive(Debug, Clone)]
+pub struct ChannelAdjustment {
+    /// Channel ID
+    pub channel_id: usize,
+
+    /// Fraction of the original amplitude (or curvature) that remains
+    pub amplitude_scale: f64,
+
+    /// Number of rounds in which the channel was shrunk
+    pub rounds: usize,
+
+    /// Path length before the pass (mm)
+    pub initial_length: f64,
+
+    /// Path length after the pass (mm)
+    pub final_length: f64,
+}
+
+/// Report of what the collision avoidance pass changed
+#[derive(Debug, Clone)]
+pub struct SystemAvoidanceReport {
+    /// Number of detect-and-shrink rounds performed
+    pub iterations: usize,
+
+    /// Colliding channel pairs and wall violations before the pass
+    pub initial_collisions: usize,
+
+    /// Colliding channel pairs and wall violations after the pass
+    pub remaining_collisions: usize,
+
+    /// Edge-to-edge spacing that was enforced (mm)
+    pub required_spacing: f64,
+
+    /// Channels that were modified, in channel order
+    pub adjustments: Vec<ChannelAdjustment>,
+
+    /// IDs of channels still involved in a collision after the pass
+    pub unresolved_channels: Vec<usize>,
+}
+
+impl SystemAvoidanceReport {
+    /// Whether the system is free of collisions after the pass
+    #[must_use]
+    pub const fn is_clean(&self) -> bool {
+        self.remaining_collisions == 0
+    }
+
+    /// Whether the pass modified any channel
+    #[must_use]
+    pub const fn changed(&self) -> bool {
+        !self.adjustments.is_empty()
+    }
+}
+
+/// Collisions found in one round of the avoidance pass
+struct CollisionSurvey {
+    /// Colliding channel pairs plus channels too close to a wall
+    collisions: usize,
+
+    /// Worst collision severity (0.0 to 1.0) of each channel, by index
+    severities: Vec<f64>,
+}
+
+/// Measure every channel pair, and optionally every channel against the walls
+fn survey_collisions(system: &ChannelSystem, required_spacing: f64, wall_clearance: Option<f64>) -> CollisionSurvey {
+    let paths = channel_paths(system);
+    let mut severities = vec![0.0_f64; system.channels.len()];
+
+    let proximities = ChannelSpatialIndex::with_paths(system, &paths)
+        .with_junction_clearance(required_spacing)
+        .proximities_within(required_spacing);
+    for proximity in &proximities {
+        let severity = shortfall(required_spacing, proximity.spacing);
+        for index in [proximity.first_channel, proximity.second_channel] {
+            severities[index] = severities[index].max(severity);
+        }
+    }
+
+    let mut wall_violations = 0;
+    if let Some(clearance) = wall_clearance {
+        let bounds = system.bounds();
+        for (index, (channel, path)) in system.channels.iter().zip(&paths).enumerate() {
+            let distance = calculate_min_wall_distance_within(path, bounds, channel.width, clearance);
+            if distance < clearance {
+                wall_violations += 1;
+                severities[index] = severities[index].max(shortfall(clearance, distance));
+            }
+        }
+    }
+
+    CollisionSurvey {
+        collisions: proximities.len() + wall_violations,
+        severities,
+    }
+}
+
+/// Relative shortfall of a measured distance, from 0.0 (met) to 1.0 (touching or worse)
+fn shortfall(required: f64, measured: f64) -> f64 {
+    if required > 0.0 {
+        ((required - measured) / required).clamp(0.0, 1.0)
+    } else {
+        1.0
+    }
+}
+
+/// Path of a channel whose amplitude or curvature can be reduced
+const fn curved_path_mut(channel_type: &mut ChannelType) -> Option<&mut Vec<Point2D>> {
+    match channel_type {
+        ChannelType::Serpentine { path } | ChannelType::Arc { path } => Some(path),
+        _ => None,
+    }
+}
+
 /// Collision with a neighboring channel
 #[derive(Debug)]
 pub struct NeighborCollision {
@@ -666,6 +972,6 @@ impl CollisionSeverity for WallCollision {
 
 impl Default for CollisionDetectionSystem {
     fn default() -> Self {
-        Self::new().expect("Failed to create default CollisionDetectionSystem")
+        Self::new()
     }
 }
@@ -0,0 +1,340 @@
+//! `geometry/composition.rs` - Composition of Channel Systems into Chips
+//!
+//! Chips are often built from several independently generated modules, such
+//! as a mixer, a splitter tree and a serpentine delay line. A [`ChipComposer`]
+//! places such modules at given positions, renumbers their node and channel
+//! IDs into one system, stitches a chosen outlet of one module to an inlet of
+//! another with a generated connector channel, and replaces the module boxes
+//! with a single chip outline.
+//!
+//! Modules are connected through their [`Port`]s: nodes that end exactly one
+//! channel. A port is an inlet if its channel starts there and an outlet if
+//! its channel ends there; generated systems have one inlet on the left wall
+//! and one outlet on the right wall.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use scheme::config::{ChannelTypeConfig, GeometryConfig, SerpentineConfig};
+//! use scheme::geometry::composition::ChipComposer;
+//! use scheme::geometry::generator::create_geometry;
+//! use scheme::geometry::SplitType;
+//!
+//! let config = GeometryConfig::default();
+//! let splitter = create_geometry((100.0, 50.0), &[SplitType::Bifurcation], &config, &ChannelTypeConfig::AllStraight);
+//! let delay = create_geometry(
+//!     (150.0, 50.0),
+//!     &[],
+//!     &config,
+//!     &ChannelTypeConfig::AllSerpentine(SerpentineConfig::default()),
+//! );
+//!
+//! let mut composer = ChipComposer::new().with_margin(5.0);
+//! let splitter = composer.add_module(splitter, (0.0, 0.0));
+//! let delay = composer.add_module(delay, (130.0, 0.0));
+//! composer.connect(splitter.outlet(0), delay.inlet(0))?;
+//!
+//! let chip = composer.build();
+//! assert_eq!(chip.connectors.len(), 1);
+//! assert_eq!(chip.system.ports().len(), 2); // Chip inlet and outlet
+//! assert_eq!(chip.system.bounds(), ((-5.0, -5.0), (285.0, 55.0)));
+//! # Ok::<(), scheme::error::GeometryError>(())
+//! ```
+
+use crate::config::{ChannelTypeConfig, GeometryConfig};
+use crate::error::{GeometryError, GeometryResult};
+use crate::geometry::{
+    strategies::ChannelTypeFactory,
+    transform::Transform,
+    Channel, ChannelSystem, ChannelType, Point2D,
+};
+
+/// Direction in which fluid passes through a port
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PortKind {
+    /// Fluid enters the system here; the port's channel starts at it
+    Inlet,
+    /// Fluid leaves the system here; the port's channel ends at it
+    Outlet,
+}
+
+/// Open end of a channel system where it can be connected to another
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Port {
+    /// Index of the port node in the system
+    pub node: usize,
+    /// Position of the port
+    pub point: Point2D,
+    /// Whether the port is an inlet or an outlet
+    pub kind: PortKind,
+    /// Unit vector pointing out of the system along the port's channel
+    pub direction: (f64, f64),
+    /// Width of the port's channel (mm)
+    pub width: f64,
+}
+
+/// Handle of a module added to a [`ChipComposer`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ModuleId(usize);
+
+impl ModuleId {
+    /// Position of the module in [`ComposedChip::modules`]
+    #[must_use]
+    pub const fn index(self) -> usize {
+        self.0
+    }
+
+    /// Reference to the `index`-th inlet of the module
+    #[must_use]
+    pub const fn inlet(self, index: usize) -> PortRef {
+        PortRef { module: self, kind: PortKind::Inlet, index }
+    }
+
+    /// Reference to the `index`-th outlet of the module
+    #[must_use]
+    pub const fn outlet(self, index: usize) -> PortRef {
+        PortRef { module: self, kind: PortKind::Outlet, index }
+    }
+}
+
+/// Reference to a port of a module, counting inlets and outlets separately
+/// in the order of [`ChannelSystem::ports`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PortRef {
+    /// Module the port belongs to
+    pub module: ModuleId,
+    /// Whether the port is an inlet or an outlet
+    pub kind: PortKind,
+    /// Index among the module's ports of the same kind
+    pub index: usize,
+}
+
+/// Where a module ended up in a composed chip
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModuleLayout {
+    /// Index of the module's first node in the chip
+    pub node_offset: usize,
+    /// Number of nodes of the module
+    pub node_count: usize,
+    /// Index of the module's first channel in the chip
+    pub channel_offset: usize,
+    /// Number of channels of the module
+    pub channel_count: usize,
+    /// Bounds of the placed module's box
+    pub bounds: (Point2D, Point2D),
+}
+
+/// Result of [`ChipComposer::build`]
+#[derive(Debug, Clone)]
+pub struct ComposedChip {
+    /// All modules and connectors as one system with a single chip outline
+    pub system: ChannelSystem,
+    /// Placement of each module, indexed by [`ModuleId::index`]
+    pub modules: Vec<ModuleLayout>,
+    /// Indices of the connector channels, in the order they were connected
+    pub connectors: Vec<usize>,
+}
+
+/// Builder placing channel systems on one chip and stitching them together
+///
+/// Connectors are generated with the configured channel type strategy and
+/// take their width and height from the configured geometry. Unconnected
+/// ports remain ports of the composed chip.
+#[derive(Debug, Clone)]
+pub struct ChipComposer {
+    modules: Vec<ChannelSystem>,
+    connections: Vec<(PortRef, PortRef)>,
+    geometry_config: GeometryConfig,
+    connector_type: ChannelTypeConfig,
+    margin: f64,
+}
+
+impl Default for ChipComposer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ChipComposer {
+    /// Create an empty composer with straight connectors and no margin
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            modules: Vec::new(),
+            connections: Vec::new(),
+            geometry_config: GeometryConfig::default(),
+            connector_type: ChannelTypeConfig::AllStraight,
+            margin: 0.0,
+        }
+    }
+
+    /// Set the geometry used to generate connector channels
+    #[must_use]
+    pub const fn with_geometry_config(mut self, geometry_config: GeometryConfig) -> Self {
+        self.geometry_config = geometry_config;
+        self
+    }
+
+    /// Set the channel type of connector channels
+    #[must_use]
+    pub const fn with_connector_type(mut self, connector_type: ChannelTypeConfig) -> Self {
+        self.connector_type = connector_type;
+        self
+    }
+
+    /// Set the distance between the chip outline and its contents (mm)
+    #[must_use]
+    pub const fn with_margin(mut self, margin: f64) -> Self {
+        self.margin = margin;
+        self
+    }
+
+    /// Add a module whose box's lower left corner is placed at `position`
+    pub fn add_module(&mut self, mut system: ChannelSystem, position: Point2D) -> ModuleId {
+        let (min, _) = system.bounds();
+        system.translate(position.0 - min.0, position.1 - min.1);
+        self.modules.push(system);
+        ModuleId(self.modules.len() - 1)
+    }
+
+    /// Stitch `outlet` of one module to `inlet` of another with a connector
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GeometryError::InvalidComposition`] if either reference does
+    /// not name a port of the expected kind, if both are on the same module,
+    /// or if either port is already connected.
+    pub fn connect(&mut self, outlet: PortRef, inlet: PortRef) -> GeometryResult<()> {
+        if outlet.kind != PortKind::Outlet || inlet.kind != PortKind::Inlet {
+            return Err(GeometryError::invalid_composition("connections must run from an outlet to an inlet"));
+        }
+        if outlet.module == inlet.module {
+            return Err(GeometryError::invalid_composition("a module cannot be connected to itself"));
+        }
+        self.resolve(outlet)?;
+        self.resolve(inlet)?;
+        if self.connections.iter().any(|&(from, to)| from == outlet || to == inlet) {
+            return Err(GeometryError::invalid_composition("port is already connected"));
+        }
+        self.connections.push((outlet, inlet));
+        Ok(())
+    }
+
+    /// Combine all modules and connectors into one chip
+    #[must_use]
+    pub fn build(&self) -> ComposedChip {
+        let mut system = ChannelSystem {
+            box_dims: (0.0, 0.0),
+            nodes: Vec::new(),
+            channels: Vec::new(),
+            box_outline: Vec::new(),
+        };
+        let mut modules = Vec::with_capacity(self.modules.len());
+        for module in &self.modules {
+            let (node_offset, channel_offset) = system.append(module);
+            modules.push(ModuleLayout {
+                node_offset,
+                node_count: module.nodes.len(),
+                channel_offset,
+                channel_count: module.channels.len(),
+                bounds: module.bounds(),
+            });
+        }
+
+        let (min, max) = union_bounds(modules.iter().map(|layout| layout.bounds)).unwrap_or_default();
+        let connectors = self
+            .connections
+            .iter()
+            .map(|&(outlet, inlet)| {
+                let from = modules[outlet.module.0].node_offset + self.port(outlet).node;
+                let to = modules[inlet.module.0].node_offset + self.port(inlet).node;
+                let channel = self.connector(system.channels.len(), from, to, &system, (min, max));
+                system.channels.push(channel);
+                system.channels.len() - 1
+            })
+            .collect();
+
+        let (min, max) = union_bounds(
+            std::iter::once((min, max)).chain(system.get_path_segments().iter().flatten().map(|&point| (point, point))),
+        )
+        .unwrap_or_default();
+        let (min, max) = ((min.0 - self.margin, min.1 - self.margin), (max.0 + self.margin, max.1 + self.margin));
+        system.box_dims = (max.0 - min.0, max.1 - min.1);
+        system.box_outline = vec![
+            (min, (max.0, min.1)),
+            ((max.0, min.1), max),
+            (max, (min.0, max.1)),
+            ((min.0, max.1), min),
+        ];
+
+        ComposedChip { system, modules, connectors }
+    }
+
+    /// Port named by `port_ref`, checked for existence
+    fn resolve(&self, port_ref: PortRef) -> GeometryResult<Port> {
+        let module = self
+            .modules
+            .get(port_ref.module.0)
+            .ok_or_else(|| GeometryError::invalid_composition(&format!("unknown module {}", port_ref.module.0)))?;
+        module
+            .ports()
+            .into_iter()
+            .filter(|port| port.kind == port_ref.kind)
+            .nth(port_ref.index)
+            .ok_or_else(|| {
+                GeometryError::invalid_composition(&format!(
+                    "module {} has no {:?} port {}",
+                    port_ref.module.0, port_ref.kind, port_ref.index
+                ))
+            })
+    }
+
+    /// Port named by an already checked `port_ref`
+    fn port(&self, port_ref: PortRef) -> Port {
+        self.resolve(port_ref).expect("connections are checked when they are made")
+    }
+
+    /// Connector channel `id` from node `from` to node `to` of `system`
+    ///
+    /// The channel type strategy sees the connector in the frame of the
+    /// modules' bounds, so strategies sizing themselves to the box see the
+    /// chip rather than the origin.
+    fn connector(&self, id: usize, from: usize, to: usize, system: &ChannelSystem, bounds: (Point2D, Point2D)) -> Channel {
+        let ((min_x, min_y), (max_x, max_y)) = bounds;
+        let (to_local, to_chip) = (Transform::translation(-min_x, -min_y), Transform::translation(min_x, min_y));
+        let p1 = to_local.apply(system.nodes[from].point);
+        let p2 = to_local.apply(system.nodes[to].point);
+        let box_dims = (max_x - min_x, max_y - min_y);
+
+        let mut channel_type = ChannelTypeFactory::create_strategy(&self.connector_type, p1, p2, box_dims)
+            .create_channel(p1, p2, &self.geometry_config, box_dims, 1, None);
+        match &mut channel_type {
+            ChannelType::Straight => {}
+            ChannelType::SmoothStraight { path }
+            | ChannelType::Serpentine { path }
+            | ChannelType::Arc { path }
+            | ChannelType::Frustum { path, .. } => {
+                for point in path.iter_mut() {
+                    *point = to_chip.apply(*point);
+                }
+            }
+        }
+
+        Channel {
+            id,
+            from_node: from,
+            to_node: to,
+            width: self.geometry_config.channel_width,
+            height: self.geometry_config.channel_height,
+            channel_type,
+            metadata: None,
+        }
+    }
+}
+
+/// Smallest bounds containing all of `bounds`, if there are any
+fn union_bounds(bounds: impl IntoIterator<Item = (Point2D, Point2D)>) -> Option<(Point2D, Point2D)> {
+    bounds.into_iter().reduce(|(min, max), (low, high)| {
+        ((min.0.min(low.0), min.1.min(low.1)), (max.0.max(high.0), max.1.max(high.1)))
+    })
+}
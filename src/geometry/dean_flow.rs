@@ -0,0 +1,269 @@
+//! `geometry/dean_flow.rs` - Dean Number and Secondary-Flow Metrics
+//!
+//! Flow around a bend develops counter-rotating Dean vortices whose strength
+//! is characterized by the Dean number `De = Re·√(Dh / 2R)`, where `Re` is
+//! the Reynolds number, `Dh` the hydraulic diameter and `R` the local bend
+//! radius. Inertial focusing and mixing designs depend on it, so this module
+//! combines the local curvature from [`path_analysis`](super::path_analysis)
+//! with a channel's cross-section, its flow rate and the fluid properties to
+//! give the Dean number at every point of a channel path.
+//!
+//! Flow rates are either passed explicitly or read from the
+//! [`FlowMetadata`] of each channel.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use scheme::config::{ChannelTypeConfig, FluidProperties, GeometryConfig, SerpentineConfig};
+//! use scheme::geometry::{generator::create_geometry, SplitType};
+//! use scheme::geometry::dean_flow::analyze_system_dean_numbers;
+//!
+//! let system = create_geometry(
+//!     (200.0, 100.0),
+//!     &[SplitType::Bifurcation],
+//!     &GeometryConfig::default(),
+//!     &ChannelTypeConfig::AllSerpentine(SerpentineConfig::default()),
+//! );
+//!
+//! // 100 µL/min through every channel
+//! let flow_rates = vec![100.0; system.channels.len()];
+//! let profiles = analyze_system_dean_numbers(&system, &flow_rates, &FluidProperties::water())?;
+//! for profile in &profiles {
+//!     println!("channel {}: De max {:.2}", profile.channel_id, profile.max_dean_number);
+//! }
+//! # Ok::<(), scheme::error::ConfigurationError>(())
+//! ```
+
+use crate::{
+    config::{constants::{MM_TO_M, UL_PER_MIN_TO_M3_PER_S}, FluidProperties},
+    error::{ConfigurationError, ConfigurationResult},
+    geometry::{
+        builders::ChannelExt,
+        metadata::{DeanMetadata, FlowMetadata},
+        path_analysis::analyze_path_curvature,
+        spatial_index::channel_paths,
+        types::{Channel, ChannelSystem, ChannelType, Point2D},
+    },
+};
+
+/// Dean number at one point of a channel path
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DeanSample {
+    /// Distance along the path from its start (mm)
+    pub arc_length: f64,
+
+    /// Path point
+    pub point: Point2D,
+
+    /// Reynolds number with the local cross-section
+    pub reynolds_number: f64,
+
+    /// Curvature ratio `Dh / 2R`
+    pub curvature_ratio: f64,
+
+    /// Dean number
+    pub dean_number: f64,
+}
+
+/// Dean number along one channel
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeanProfile {
+    /// Channel ID
+    pub channel_id: usize,
+
+    /// Volumetric flow rate used (µL/min)
+    pub flow_rate: f64,
+
+    /// Reynolds number with the nominal channel cross-section
+    pub reynolds_number: f64,
+
+    /// Dean number at every path point, in path order
+    pub samples: Vec<DeanSample>,
+
+    /// Largest Dean number along the channel
+    pub max_dean_number: f64,
+
+    /// Point with the largest Dean number, if the channel bends at all
+    pub max_location: Option<Point2D>,
+
+    /// Length-weighted mean Dean number
+    pub mean_dean_number: f64,
+}
+
+impl DeanProfile {
+    /// Summary of this profile for storage in channel metadata
+    #[must_use]
+    pub fn to_metadata(&self) -> DeanMetadata {
+        DeanMetadata {
+            flow_rate: self.flow_rate,
+            reynolds_number: self.reynolds_number,
+            max_dean_number: self.max_dean_number,
+            mean_dean_number: self.mean_dean_number,
+            profile: self.samples.iter().map(|sample| (sample.arc_length, sample.dean_number)).collect(),
+        }
+    }
+}
+
+/// Hydraulic diameter `2wh / (w + h)` of a rectangular cross-section
+///
+/// # Arguments
+/// * `width` - Channel width (mm)
+/// * `height` - Channel height (mm)
+///
+/// # Returns
+/// Hydraulic diameter in mm
+#[must_use]
+pub fn hydraulic_diameter(width: f64, height: f64) -> f64 {
+    2.0 * width * height / (width + height)
+}
+
+/// Reynolds number of flow through a rectangular cross-section
+///
+/// # Arguments
+/// * `flow_rate` - Volumetric flow rate (µL/min)
+/// * `width` - Channel width (mm)
+/// * `height` - Channel height (mm)
+/// * `fluid` - Fluid density and viscosity
+///
+/// # Returns
+/// Reynolds number based on the mean velocity and the hydraulic diameter
+#[must_use]
+pub fn reynolds_number(flow_rate: f64, width: f64, height: f64, fluid: &FluidProperties) -> f64 {
+    let area_m2 = width * height * MM_TO_M * MM_TO_M;
+    let velocity = flow_rate.abs() * UL_PER_MIN_TO_M3_PER_S / area_m2;
+    velocity * hydraulic_diameter(width, height) * MM_TO_M / fluid.kinematic_viscosity()
+}
+
+/// Dean number `Re·√(Dh / 2R)`
+///
+/// # Arguments
+/// * `reynolds_number` - Reynolds number of the flow
+/// * `hydraulic_diameter` - Hydraulic diameter (mm)
+/// * `radius` - Bend radius (mm); infinite for straight stretches
+#[must_use]
+pub fn dean_number(reynolds_number: f64, hydraulic_diameter: f64, radius: f64) -> f64 {
+    reynolds_number * (hydraulic_diameter / (2.0 * radius)).sqrt()
+}
+
+/// Compute the Dean number along one channel path
+///
+/// Frustum channels use their local width at every path point; other
+/// channels use their nominal width.
+///
+/// # Arguments
+/// * `channel` - Channel providing the cross-section
+/// * `path` - Centerline of the channel
+/// * `flow_rate` - Volumetric flow rate through the channel (µL/min)
+/// * `fluid` - Fluid density and viscosity
+#[must_use]
+pub fn calculate_dean_profile(channel: &Channel, path: &[Point2D], flow_rate: f64, fluid: &FluidProperties) -> DeanProfile {
+    let curvature = analyze_path_curvature(path);
+    let local_widths = match &channel.channel_type {
+        ChannelType::Frustum { widths, .. } if widths.len() == path.len() => Some(widths.as_slice()),
+        _ => None,
+    };
+
+    let samples: Vec<DeanSample> = curvature.samples.iter()
+        .enumerate()
+        .map(|(index, sample)| {
+            let width = local_widths.map_or(channel.width, |widths| widths[index]);
+            let diameter = hydraulic_diameter(width, channel.height);
+            let reynolds_number = reynolds_number(flow_rate, width, channel.height, fluid);
+            DeanSample {
+                arc_length: sample.arc_length,
+                point: sample.point,
+                reynolds_number,
+                curvature_ratio: diameter / (2.0 * sample.radius()),
+                dean_number: dean_number(reynolds_number, diameter, sample.radius()),
+            }
+        })
+        .collect();
+
+    let peak = samples.iter()
+        .filter(|sample| sample.dean_number > 0.0)
+        .max_by(|a, b| a.dean_number.total_cmp(&b.dean_number));
+    let mean_dean_number = if curvature.length > 0.0 {
+        samples.iter()
+            .enumerate()
+            .map(|(index, sample)| sample.dean_number * curvature.sample_weight(index))
+            .sum::<f64>()
+            / curvature.length
+    } else {
+        0.0
+    };
+
+    DeanProfile {
+        channel_id: channel.id,
+        flow_rate,
+        reynolds_number: reynolds_number(flow_rate, channel.width, channel.height, fluid),
+        max_dean_number: peak.map_or(0.0, |sample| sample.dean_number),
+        max_location: peak.map(|sample| sample.point),
+        mean_dean_number,
+        samples,
+    }
+}
+
+/// Compute the Dean number along every channel of a system
+///
+/// # Arguments
+/// * `system` - Channel system to analyze
+/// * `flow_rates` - Flow rate through each channel in channel order (µL/min)
+/// * `fluid` - Fluid density and viscosity
+///
+/// # Errors
+///
+/// Returns an error if the number of flow rates doesn't match the number of
+/// channels, a flow rate is not finite, or the fluid properties are invalid.
+pub fn analyze_system_dean_numbers(
+    system: &ChannelSystem,
+    flow_rates: &[f64],
+    fluid: &FluidProperties,
+) -> ConfigurationResult<Vec<DeanProfile>> {
+    fluid.validate()?;
+    if flow_rates.len() != system.channels.len() {
+        return Err(ConfigurationError::invalid_generation_config(
+            "flow_rates",
+            &format!("Must have one entry per channel ({} channels, {} flow rates)", system.channels.len(), flow_rates.len()),
+        ));
+    }
+    if flow_rates.iter().any(|rate| !rate.is_finite()) {
+        return Err(ConfigurationError::invalid_generation_config(
+            "flow_rates",
+            "Must be finite values",
+        ));
+    }
+
+    Ok(system.channels.iter()
+        .zip(channel_paths(system))
+        .zip(flow_rates)
+        .map(|((channel, path), &flow_rate)| calculate_dean_profile(channel, &path, flow_rate, fluid))
+        .collect())
+}
+
+/// Compute the Dean number along every channel that carries [`FlowMetadata`]
+/// and store the results as [`DeanMetadata`] on those channels
+///
+/// Channels without flow metadata are left unchanged.
+///
+/// # Returns
+/// Number of channels that were annotated
+///
+/// # Errors
+///
+/// Returns an error if the fluid properties are invalid.
+pub fn attach_dean_metadata(system: &mut ChannelSystem, fluid: &FluidProperties) -> ConfigurationResult<usize> {
+    fluid.validate()?;
+    let paths = channel_paths(system);
+    let mut annotated = 0;
+
+    for (channel, path) in system.channels.iter_mut().zip(&paths) {
+        let Some(flow_rate) = channel.get_metadata::<FlowMetadata>().map(|flow| flow.flow_rate) else {
+            continue;
+        };
+        let profile = calculate_dean_profile(channel, path, flow_rate, fluid);
+        channel.add_metadata(profile.to_metadata());
+        annotated += 1;
+    }
+
+    Ok(annotated)
+}
@@ -0,0 +1,619 @@
+//! geometry/drc.rs - Design Rule Checking
+//!
+//! This module checks a finished [`ChannelSystem`] against a deck of
+//! fabrication rules: minimum feature width, minimum channel-to-channel
+//! spacing, minimum wall distance, minimum bend radius and minimum junction
+//! angle. Every broken rule is reported as a [`DrcViolation`] carrying the
+//! offending channel IDs, the location of the worst offense and a severity,
+//! so results can be listed, filtered or drawn on top of a rendered schematic.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use scheme::config::{ChannelTypeConfig, GeometryConfig};
+//! use scheme::geometry::{generator::create_geometry, SplitType};
+//! use scheme::geometry::drc::{check_design_rules, DesignRule, RuleDeck, Severity};
+//!
+//! let system = create_geometry(
+//!     (200.0, 100.0),
+//!     &[SplitType::Bifurcation],
+//!     &GeometryConfig::default(),
+//!     &ChannelTypeConfig::AllStraight,
+//! );
+//!
+//! let deck = RuleDeck::new()
+//!     .with_rule(DesignRule::MinFeatureWidth { min_width: 2.0 }, Severity::Error)
+//!     .with_rule(DesignRule::MinWallDistance { min_distance: 0.5 }, Severity::Warning);
+//! let report = check_design_rules(&system, &deck).unwrap();
+//!
+//! // Every 1 mm wide channel is narrower than the 2 mm rule
+//! assert_eq!(report.count(Severity::Error), system.channels.len());
+//! ```
+
+use crate::config::GeometryConfig;
+use crate::error::{ConfigurationError, ConfigurationResult, GeometryError, SchemeResult};
+use crate::geometry::spatial_index::{channel_paths, ChannelSpatialIndex};
+use crate::geometry::types::{Channel, ChannelSystem, ChannelType, Point2D};
+use std::fmt;
+
+/// Design rule checking constants
+mod constants {
+    /// Smallest feature width resolvable by typical soft lithography (mm)
+    pub const DEFAULT_MIN_FEATURE_WIDTH: f64 = 0.05;
+
+    /// Smallest angle between two channels meeting at a junction (degrees)
+    pub const DEFAULT_MIN_JUNCTION_ANGLE_DEGREES: f64 = 30.0;
+
+    /// Radius around a port, in multiples of channel width plus wall distance,
+    /// within which a channel is allowed to approach the wall it opens into
+    pub const PORT_EXCLUSION_FACTOR: f64 = 4.0;
+
+    /// Distance from a node, in channel widths, at which a channel's direction is sampled
+    pub const DIRECTION_SAMPLE_WIDTHS: f64 = 1.0;
+
+    /// Tolerance for detecting nodes on the box boundary (mm)
+    pub const BOUNDARY_TOLERANCE: f64 = 1e-6;
+
+    /// Segments shorter than this are ignored when measuring curvature (mm)
+    pub const MIN_SEGMENT_LENGTH: f64 = 1e-9;
+
+    /// Largest angle a junction can have (degrees)
+    pub const MAX_ANGLE_DEGREES: f64 = 180.0;
+}
+
+/// Severity of a design rule violation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    /// Worth knowing, but unlikely to affect fabrication
+    Info,
+    /// Likely to reduce yield or performance
+    Warning,
+    /// The design cannot be fabricated reliably
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Info => "info",
+            Self::Warning => "warning",
+            Self::Error => "error",
+        };
+        f.write_str(name)
+    }
+}
+
+/// A single fabrication rule and its limit
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DesignRule {
+    /// Channels must be at least this wide (mm)
+    MinFeatureWidth {
+        /// Smallest allowed channel width (mm)
+        min_width: f64,
+    },
+    /// Channel edges must be at least this far apart (mm)
+    MinChannelSpacing {
+        /// Smallest allowed edge-to-edge distance (mm)
+        min_spacing: f64,
+    },
+    /// Channel edges must be at least this far from the box walls, except at ports (mm)
+    MinWallDistance {
+        /// Smallest allowed edge-to-wall distance (mm)
+        min_distance: f64,
+    },
+    /// Channel centerlines must not bend tighter than this radius (mm)
+    MinBendRadius {
+        /// Smallest allowed radius of curvature (mm)
+        min_radius: f64,
+    },
+    /// Channels meeting at a junction must be at least this far apart in angle
+    MinJunctionAngle {
+        /// Smallest allowed angle between two channels (degrees)
+        min_angle_degrees: f64,
+    },
+}
+
+impl DesignRule {
+    /// Short name of the rule
+    #[must_use]
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::MinFeatureWidth { .. } => "min_feature_width",
+            Self::MinChannelSpacing { .. } => "min_channel_spacing",
+            Self::MinWallDistance { .. } => "min_wall_distance",
+            Self::MinBendRadius { .. } => "min_bend_radius",
+            Self::MinJunctionAngle { .. } => "min_junction_angle",
+        }
+    }
+
+    /// The limit a design must meet
+    #[must_use]
+    pub const fn limit(&self) -> f64 {
+        match *self {
+            Self::MinFeatureWidth { min_width: limit }
+            | Self::MinChannelSpacing { min_spacing: limit }
+            | Self::MinWallDistance { min_distance: limit }
+            | Self::MinBendRadius { min_radius: limit }
+            | Self::MinJunctionAngle { min_angle_degrees: limit } => limit,
+        }
+    }
+
+    /// Validate the rule limit
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the limit is not finite, is negative, or (for
+    /// widths, radii and angles) is not positive, or if an angle exceeds 180°.
+    pub fn validate(&self) -> ConfigurationResult<()> {
+        let limit = self.limit();
+        let invalid = |constraint: &str| {
+            Err(ConfigurationError::invalid_generation_config(
+                self.name(),
+                &format!("{constraint}, got {limit}"),
+            ))
+        };
+
+        if !limit.is_finite() {
+            return invalid("Must be finite");
+        }
+        match self {
+            Self::MinChannelSpacing { .. } | Self::MinWallDistance { .. } if limit < 0.0 => {
+                invalid("Must be non-negative")
+            }
+            Self::MinFeatureWidth { .. } | Self::MinBendRadius { .. } | Self::MinJunctionAngle { .. } if limit <= 0.0 => {
+                invalid("Must be positive")
+            }
+            Self::MinJunctionAngle { .. } if limit > constants::MAX_ANGLE_DEGREES => {
+                invalid("Must not exceed 180 degrees")
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// A rule together with the severity of breaking it
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DrcRule {
+    /// The rule to check
+    pub rule: DesignRule,
+    /// Severity reported when the rule is broken
+    pub severity: Severity,
+}
+
+/// A set of design rules to check a system against
+///
+/// The same kind of rule may appear more than once, e.g. a tight limit
+/// reported as an error and a looser one reported as a warning.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RuleDeck {
+    /// Rules in the order they are checked
+    pub rules: Vec<DrcRule>,
+}
+
+impl RuleDeck {
+    /// Create an empty rule deck
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { rules: Vec::new() }
+    }
+
+    /// Add a rule with the given severity
+    #[must_use]
+    pub fn with_rule(mut self, rule: DesignRule, severity: Severity) -> Self {
+        self.rules.push(DrcRule { rule, severity });
+        self
+    }
+
+    /// Rule deck matching the clearances a geometry configuration was generated with
+    ///
+    /// Spacing and wall distance use the wall clearance, bends must not be
+    /// tighter than the channel width, and junctions narrower than 30° are
+    /// reported as warnings.
+    #[must_use]
+    pub fn from_geometry_config(config: &GeometryConfig) -> Self {
+        Self::new()
+            .with_rule(DesignRule::MinFeatureWidth { min_width: constants::DEFAULT_MIN_FEATURE_WIDTH }, Severity::Error)
+            .with_rule(DesignRule::MinChannelSpacing { min_spacing: config.wall_clearance }, Severity::Error)
+            .with_rule(DesignRule::MinWallDistance { min_distance: config.wall_clearance }, Severity::Error)
+            .with_rule(DesignRule::MinBendRadius { min_radius: config.channel_width }, Severity::Warning)
+            .with_rule(
+                DesignRule::MinJunctionAngle { min_angle_degrees: constants::DEFAULT_MIN_JUNCTION_ANGLE_DEGREES },
+                Severity::Warning,
+            )
+    }
+
+    /// Number of rules in the deck
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.rules.len()
+    }
+
+    /// Whether the deck has no rules
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Validate every rule in the deck
+    ///
+    /// # Errors
+    ///
+    /// Returns the first invalid rule limit.
+    pub fn validate(&self) -> ConfigurationResult<()> {
+        self.rules.iter().try_for_each(|rule| rule.rule.validate())
+    }
+}
+
+impl Default for RuleDeck {
+    fn default() -> Self {
+        Self::from_geometry_config(&GeometryConfig::default())
+    }
+}
+
+/// A broken design rule, located in the layout
+#[derive(Debug, Clone, PartialEq)]
+pub struct DrcViolation {
+    /// The rule that was broken
+    pub rule: DesignRule,
+    /// Severity from the rule deck
+    pub severity: Severity,
+    /// IDs of the channels involved
+    pub channel_ids: Vec<usize>,
+    /// ID of the junction node, for junction angle violations
+    pub node_id: Option<usize>,
+    /// Location of the worst offense
+    pub location: Point2D,
+    /// Closest points between the two channels, for spacing violations
+    pub extent: Option<(Point2D, Point2D)>,
+    /// Measured value (mm, or degrees for junction angles)
+    pub measured: f64,
+    /// Required value from the rule (mm, or degrees for junction angles)
+    pub required: f64,
+}
+
+impl DrcViolation {
+    /// Geometry error for violations where channels physically overlap
+    ///
+    /// Returns [`GeometryError::OverlappingChannels`] with the closest points of
+    /// the two channels when their edges touch or cross, and `None` otherwise.
+    #[must_use]
+    pub fn to_geometry_error(&self) -> Option<GeometryError> {
+        match (self.rule, self.extent) {
+            (DesignRule::MinChannelSpacing { .. }, Some((first, second))) if self.measured <= 0.0 => {
+                Some(GeometryError::OverlappingChannels { x1: first.0, y1: first.1, x2: second.0, y2: second.1 })
+            }
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for DrcViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let channels: Vec<String> = self.channel_ids.iter().map(ToString::to_string).collect();
+        write!(
+            f,
+            "[{}] {} at ({:.3}, {:.3}) on channel(s) {}: measured {:.3}, required {:.3}",
+            self.severity,
+            self.rule.name(),
+            self.location.0,
+            self.location.1,
+            channels.join(", "),
+            self.measured,
+            self.required,
+        )
+    }
+}
+
+/// Result of checking a system against a rule deck
+#[derive(Debug, Clone, PartialEq)]
+pub struct DrcReport {
+    /// Violations, most severe first, then in rule deck order
+    pub violations: Vec<DrcViolation>,
+    /// Number of rules checked
+    pub rules_checked: usize,
+    /// Number of channels checked
+    pub channels_checked: usize,
+}
+
+impl DrcReport {
+    /// Whether no rule was broken
+    #[must_use]
+    pub const fn is_clean(&self) -> bool {
+        self.violations.is_empty()
+    }
+
+    /// Whether no rule reported as [`Severity::Error`] was broken
+    #[must_use]
+    pub fn passes(&self) -> bool {
+        self.violations.iter().all(|violation| violation.severity < Severity::Error)
+    }
+
+    /// Most severe violation, if any
+    #[must_use]
+    pub fn worst_severity(&self) -> Option<Severity> {
+        self.violations.iter().map(|violation| violation.severity).max()
+    }
+
+    /// Number of violations with the given severity
+    #[must_use]
+    pub fn count(&self, severity: Severity) -> usize {
+        self.with_severity(severity).count()
+    }
+
+    /// Violations with the given severity
+    pub fn with_severity(&self, severity: Severity) -> impl Iterator<Item = &DrcViolation> {
+        self.violations.iter().filter(move |violation| violation.severity == severity)
+    }
+
+    /// Violations involving the given channel
+    pub fn for_channel(&self, channel_id: usize) -> impl Iterator<Item = &DrcViolation> {
+        self.violations.iter().filter(move |violation| violation.channel_ids.contains(&channel_id))
+    }
+
+    /// Geometry errors for every pair of physically overlapping channels
+    #[must_use]
+    pub fn overlaps(&self) -> Vec<GeometryError> {
+        self.violations.iter().filter_map(DrcViolation::to_geometry_error).collect()
+    }
+}
+
+/// Check a channel system against a rule deck
+///
+/// Each rule reports at most one violation per channel (or per channel pair
+/// or junction), located at the worst offense.
+///
+/// # Errors
+///
+/// Returns an error if a rule in the deck has an invalid limit, or the
+/// system fails [`ChannelSystem::validate`], for example because a channel
+/// refers to a missing node.
+pub fn check_design_rules(system: &ChannelSystem, deck: &RuleDeck) -> SchemeResult<DrcReport> {
+    deck.validate()?;
+    system.validate()?;
+
+    let paths = channel_paths(system);
+
+    let mut violations = Vec::new();
+    for &drc_rule in &deck.rules {
+        match drc_rule.rule {
+            DesignRule::MinFeatureWidth { min_width } => {
+                check_feature_width(system, &paths, drc_rule, min_width, &mut violations);
+            }
+            DesignRule::MinChannelSpacing { min_spacing } => {
+                check_channel_spacing(system, &paths, drc_rule, min_spacing, &mut violations);
+            }
+            DesignRule::MinWallDistance { min_distance } => {
+                check_wall_distance(system, &paths, drc_rule, min_distance, &mut violations);
+            }
+            DesignRule::MinBendRadius { min_radius } => {
+                check_bend_radius(system, &paths, drc_rule, min_radius, &mut violations);
+            }
+            DesignRule::MinJunctionAngle { min_angle_degrees } => {
+                check_junction_angles(system, &paths, drc_rule, min_angle_degrees, &mut violations);
+            }
+        }
+    }
+
+    // Stable sort keeps rule deck order within each severity
+    violations.sort_by_key(|violation| std::cmp::Reverse(violation.severity));
+
+    Ok(DrcReport {
+        violations,
+        rules_checked: deck.len(),
+        channels_checked: system.channels.len(),
+    })
+}
+
+/// Violation of a rule at a single location
+const fn violation(drc_rule: DrcRule, channel_ids: Vec<usize>, location: Point2D, measured: f64) -> DrcViolation {
+    DrcViolation {
+        rule: drc_rule.rule,
+        severity: drc_rule.severity,
+        channel_ids,
+        node_id: None,
+        location,
+        extent: None,
+        measured,
+        required: drc_rule.rule.limit(),
+    }
+}
+
+/// Narrowest width of a channel and where along the path it occurs
+fn narrowest_width(channel: &Channel, path: &[Point2D]) -> (f64, Option<Point2D>) {
+    match &channel.channel_type {
+        ChannelType::Frustum { widths, .. } => widths.iter()
+            .zip(path)
+            .fold((channel.width, None), |(best, location), (&width, &point)| {
+                if width < best { (width, Some(point)) } else { (best, location) }
+            }),
+        _ => (channel.width, None),
+    }
+}
+
+/// Midpoint along a path by arc length
+fn path_midpoint(path: &[Point2D]) -> Point2D {
+    let total: f64 = path.windows(2).map(|segment| distance(segment[0], segment[1])).sum();
+    let mut remaining = total / 2.0;
+    for segment in path.windows(2) {
+        let length = distance(segment[0], segment[1]);
+        if remaining <= length && length > 0.0 {
+            let t = remaining / length;
+            return lerp(segment[0], segment[1], t);
+        }
+        remaining -= length;
+    }
+    path.first().copied().unwrap_or((0.0, 0.0))
+}
+
+fn check_feature_width(
+    system: &ChannelSystem,
+    paths: &[Vec<Point2D>],
+    drc_rule: DrcRule,
+    min_width: f64,
+    violations: &mut Vec<DrcViolation>,
+) {
+    for (channel, path) in system.channels.iter().zip(paths) {
+        let (width, location) = narrowest_width(channel, path);
+        if width < min_width {
+            let location = location.unwrap_or_else(|| path_midpoint(path));
+            violations.push(violation(drc_rule, vec![channel.id], location, width));
+        }
+    }
+}
+
+fn check_channel_spacing(
+    system: &ChannelSystem,
+    paths: &[Vec<Point2D>],
+    drc_rule: DrcRule,
+    min_spacing: f64,
+    violations: &mut Vec<DrcViolation>,
+) {
+    let index = ChannelSpatialIndex::with_paths(system, paths).with_junction_clearance(min_spacing);
+    for proximity in index.proximities_within(min_spacing) {
+        let channel_ids = vec![system.channels[proximity.first_channel].id, system.channels[proximity.second_channel].id];
+        let location = lerp(proximity.first_point, proximity.second_point, 0.5);
+        let mut found = violation(drc_rule, channel_ids, location, proximity.spacing);
+        found.extent = Some((proximity.first_point, proximity.second_point));
+        violations.push(found);
+    }
+}
+
+/// Whether a node lies on the box boundary, i.e. is an inlet or outlet port
+fn is_port(point: Point2D, (min, max): (Point2D, Point2D)) -> bool {
+    let tolerance = constants::BOUNDARY_TOLERANCE;
+    point.0 <= min.0 + tolerance
+        || point.1 <= min.1 + tolerance
+        || point.0 >= max.0 - tolerance
+        || point.1 >= max.1 - tolerance
+}
+
+fn check_wall_distance(
+    system: &ChannelSystem,
+    paths: &[Vec<Point2D>],
+    drc_rule: DrcRule,
+    min_distance: f64,
+    violations: &mut Vec<DrcViolation>,
+) {
+    let bounds = system.bounds();
+    let (min, max) = bounds;
+    for (channel, path) in system.channels.iter().zip(paths) {
+        let half_width = channel.width / 2.0;
+        let exclusion_radius = constants::PORT_EXCLUSION_FACTOR * (channel.width + min_distance);
+        let ports: Vec<Point2D> = [channel.from_node, channel.to_node].iter()
+            .map(|&node| system.nodes[node].point)
+            .filter(|&point| is_port(point, bounds))
+            .collect();
+
+        let worst = path.iter()
+            .filter(|&&point| ports.iter().all(|&port| distance(point, port) >= exclusion_radius))
+            .map(|&point| {
+                let wall_distance = (point.0 - min.0).min(max.0 - point.0).min(point.1 - min.1).min(max.1 - point.1) - half_width;
+                (wall_distance, point)
+            })
+            .min_by(|a, b| a.0.total_cmp(&b.0));
+
+        if let Some((wall_distance, point)) = worst {
+            if wall_distance < min_distance {
+                violations.push(violation(drc_rule, vec![channel.id], point, wall_distance));
+            }
+        }
+    }
+}
+
+/// Bend radius at the middle of three points
+///
+/// This is the radius of the largest fillet that fits the corner using at most
+/// half of the shorter adjacent segment, which matches the radius of curvature
+/// for finely sampled curves and stays small for sharp corners between long
+/// segments. Straight continuations have infinite radius.
+fn bend_radius(a: Point2D, b: Point2D, c: Point2D) -> f64 {
+    let incoming = (b.0 - a.0, b.1 - a.1);
+    let outgoing = (c.0 - b.0, c.1 - b.1);
+    let cross = incoming.0.mul_add(outgoing.1, -(incoming.1 * outgoing.0));
+    let dot = incoming.0.mul_add(outgoing.0, incoming.1 * outgoing.1);
+    let turn = cross.atan2(dot).abs();
+    if turn <= f64::EPSILON {
+        return f64::INFINITY;
+    }
+    let shorter = distance(a, b).min(distance(b, c));
+    shorter / 2.0 / (turn / 2.0).tan()
+}
+
+fn check_bend_radius(
+    system: &ChannelSystem,
+    paths: &[Vec<Point2D>],
+    drc_rule: DrcRule,
+    min_radius: f64,
+    violations: &mut Vec<DrcViolation>,
+) {
+    for (channel, path) in system.channels.iter().zip(paths) {
+        let points: Vec<Point2D> = path.iter()
+            .fold(Vec::with_capacity(path.len()), |mut points: Vec<Point2D>, &point| {
+                if points.last().is_none_or(|&last| distance(last, point) > constants::MIN_SEGMENT_LENGTH) {
+                    points.push(point);
+                }
+                points
+            });
+
+        let tightest = points.windows(3)
+            .map(|window| (bend_radius(window[0], window[1], window[2]), window[1]))
+            .min_by(|a, b| a.0.total_cmp(&b.0));
+
+        if let Some((radius, point)) = tightest {
+            if radius < min_radius {
+                violations.push(violation(drc_rule, vec![channel.id], point, radius));
+            }
+        }
+    }
+}
+
+/// Direction of a channel leaving a node, sampled a short distance along the path
+fn departure_direction(path: &[Point2D], node: Point2D, sample_distance: f64) -> Option<(f64, f64)> {
+    let (&first, &last) = (path.first()?, path.last()?);
+    let sample = if distance(first, node) <= distance(last, node) {
+        path.iter().find(|&&point| distance(point, node) >= sample_distance).copied().unwrap_or(last)
+    } else {
+        path.iter().rev().find(|&&point| distance(point, node) >= sample_distance).copied().unwrap_or(first)
+    };
+    let length = distance(sample, node);
+    (length > constants::MIN_SEGMENT_LENGTH).then(|| ((sample.0 - node.0) / length, (sample.1 - node.1) / length))
+}
+
+fn check_junction_angles(
+    system: &ChannelSystem,
+    paths: &[Vec<Point2D>],
+    drc_rule: DrcRule,
+    min_angle_degrees: f64,
+    violations: &mut Vec<DrcViolation>,
+) {
+    for (node_index, node) in system.nodes.iter().enumerate() {
+        let departures: Vec<(usize, (f64, f64))> = system.channels.iter()
+            .zip(paths)
+            .filter(|(channel, _)| channel.from_node == node_index || channel.to_node == node_index)
+            .filter_map(|(channel, path)| {
+                let sample_distance = constants::DIRECTION_SAMPLE_WIDTHS * channel.width;
+                departure_direction(path, node.point, sample_distance).map(|direction| (channel.id, direction))
+            })
+            .collect();
+
+        for (index, &(first_id, first)) in departures.iter().enumerate() {
+            for &(second_id, second) in &departures[index + 1..] {
+                let cosine = first.0.mul_add(second.0, first.1 * second.1).clamp(-1.0, 1.0);
+                let angle = cosine.acos().to_degrees();
+                if angle < min_angle_degrees {
+                    let mut found = violation(drc_rule, vec![first_id, second_id], node.point, angle);
+                    found.node_id = Some(node.id);
+                    violations.push(found);
+                }
+            }
+        }
+    }
+}
+
+fn distance(a: Point2D, b: Point2D) -> f64 {
+    (b.0 - a.0).hypot(b.1 - a.1)
+}
+
+fn lerp(a: Point2D, b: Point2D, t: f64) -> Point2D {
+    (t.mul_add(b.0 - a.0, a.0), t.mul_add(b.1 - a.1, a.1))
+}
@@ -0,0 +1,208 @@
+//! `geometry/format.rs` - Channel System File Format
+//!
+//! Versioning, migration and schema of the JSON written by
+//! `ChannelSystem::to_json`. Documents carry a `format_version` field;
+//! documents without one were written before versioning and are version 1.
+//! Loading upgrades older documents one version at a time and reports
+//! newer or malformed documents as [`GeometryError`]s naming the version.
+//!
+//! # Versions
+//!
+//! - `1`: unversioned documents; node and channel metadata was not saved
+//! - `2`: adds `format_version` and optional `metadata` on nodes and channels
+//!
+//! # Examples
+//!
+//! ```rust
+//! use scheme::geometry::format::{migrate, SYSTEM_FORMAT_VERSION};
+//! use scheme::geometry::ChannelSystem;
+//!
+//! let legacy = r#"{"box_dims": [200.0, 100.0], "nodes": [], "channels": [], "box_outline": []}"#;
+//! let upgraded = migrate(serde_json::from_str(legacy).unwrap()).unwrap();
+//! assert_eq!(upgraded["format_version"], SYSTEM_FORMAT_VERSION);
+//! assert!(ChannelSystem::from_json(legacy).is_ok());
+//! ```
+
+use crate::error::{GeometryError, GeometryResult};
+use crate::geometry::types::ChannelSystem;
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+
+/// Current version of the channel system JSON format
+pub const SYSTEM_FORMAT_VERSION: u32 = 2;
+
+/// Name of the version field in saved documents
+const VERSION_FIELD: &str = "format_version";
+
+/// Upgrade of a document by one version
+type Migration = fn(&mut Map<String, Value>);
+
+/// Migrations in order; entry `i` upgrades version `i + 1` to `i + 2`
+const MIGRATIONS: [Migration; SYSTEM_FORMAT_VERSION as usize - 1] = [migrate_v1_to_v2];
+
+/// Version 1 ignored `metadata` on nodes and channels, so any such field in
+/// a version 1 document is dropped rather than read as version 2 metadata
+fn migrate_v1_to_v2(document: &mut Map<String, Value>) {
+    for key in ["nodes", "channels"] {
+        if let Some(Value::Array(items)) = document.get_mut(key) {
+            for item in items.iter_mut().filter_map(Value::as_object_mut) {
+                item.remove("metadata");
+            }
+        }
+    }
+}
+
+/// A channel system written with the current format version
+#[derive(Serialize)]
+pub(crate) struct VersionedSystem<'a> {
+    pub format_version: u32,
+    #[serde(flatten)]
+    pub system: &'a ChannelSystem,
+}
+
+impl<'a> VersionedSystem<'a> {
+    pub const fn new(system: &'a ChannelSystem) -> Self {
+        Self { format_version: SYSTEM_FORMAT_VERSION, system }
+    }
+}
+
+/// Format version of a document, `1` if it has no version field
+///
+/// # Errors
+///
+/// Returns an error if the document is not an object or its version is not
+/// a positive integer.
+pub fn document_version(document: &Value) -> GeometryResult<u32> {
+    let object = document
+        .as_object()
+        .ok_or_else(|| GeometryError::invalid_document("a channel system must be a JSON object"))?;
+    object.get(VERSION_FIELD).map_or(Ok(1), |version| {
+        version
+            .as_u64()
+            .and_then(|version| u32::try_from(version).ok())
+            .filter(|version| *version > 0)
+            .ok_or_else(|| {
+                GeometryError::invalid_document(&format!("{VERSION_FIELD} must be a positive integer, got {version}"))
+            })
+    })
+}
+
+/// Upgrade a document to the current format version
+///
+/// # Errors
+///
+/// Returns [`GeometryError::UnsupportedFormatVersion`] for documents newer
+/// than [`SYSTEM_FORMAT_VERSION`] and [`GeometryError::InvalidDocument`] if
+/// the document has no valid version.
+pub fn migrate(mut document: Value) -> GeometryResult<Value> {
+    let version = document_version(&document)?;
+    if version > SYSTEM_FORMAT_VERSION {
+        return Err(GeometryError::UnsupportedFormatVersion { found: version, supported: SYSTEM_FORMAT_VERSION });
+    }
+    if let Some(object) = document.as_object_mut() {
+        for migration in &MIGRATIONS[version as usize - 1..] {
+            migration(object);
+        }
+        object.insert(VERSION_FIELD.to_string(), json!(SYSTEM_FORMAT_VERSION));
+    }
+    Ok(document)
+}
+
+/// Load a channel system from JSON of any supported format version
+pub(crate) fn load(json: &str) -> GeometryResult<ChannelSystem> {
+    let document: Value = serde_json::from_str(json)
+        .map_err(|error| GeometryError::invalid_document(&format!("not valid JSON: {error}")))?;
+    let version = document_version(&document)?;
+    let mut document = migrate(document)?;
+    if let Some(object) = document.as_object_mut() {
+        object.remove(VERSION_FIELD);
+    }
+    serde_json::from_value(document)
+        .map_err(|error| GeometryError::invalid_document(&format!("format version {version}: {error}")))
+}
+
+/// JSON Schema (draft 2020-12) of the current format version
+///
+/// The same schema is published as `schemas/channel_system.schema.json`.
+#[must_use]
+pub fn json_schema() -> Value {
+    let path = json!({ "type": "array", "items": { "$ref": "#/$defs/point" }, "minItems": 2 });
+    json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "title": "Channel system",
+        "description": "Microfluidic channel system written by ChannelSystem::to_json",
+        "type": "object",
+        "required": [VERSION_FIELD, "box_dims", "nodes", "channels", "box_outline"],
+        "properties": {
+            VERSION_FIELD: { "const": SYSTEM_FORMAT_VERSION },
+            "box_dims": { "$ref": "#/$defs/point" },
+            "nodes": { "type": "array", "items": { "$ref": "#/$defs/node" } },
+            "channels": { "type": "array", "items": { "$ref": "#/$defs/channel" } },
+            "box_outline": {
+                "type": "array",
+                "items": { "type": "array", "prefixItems": [{ "$ref": "#/$defs/point" }, { "$ref": "#/$defs/point" }], "items": false, "minItems": 2 }
+            }
+        },
+        "$defs": {
+            "point": {
+                "type": "array",
+                "prefixItems": [{ "type": "number" }, { "type": "number" }],
+                "items": false,
+                "minItems": 2
+            },
+            "metadata": {
+                "description": "Metadata keyed by registered type name; unknown types are kept as-is",
+                "type": "object"
+            },
+            "node": {
+                "type": "object",
+                "required": ["id", "point"],
+                "properties": {
+                    "id": { "type": "integer", "minimum": 0 },
+                    "point": { "$ref": "#/$defs/point" },
+                    "metadata": { "$ref": "#/$defs/metadata" }
+                }
+            },
+            "channel": {
+                "type": "object",
+                "required": ["id", "from_node", "to_node", "width", "height", "channel_type"],
+                "properties": {
+                    "id": { "type": "integer", "minimum": 0 },
+                    "from_node": { "type": "integer", "minimum": 0 },
+                    "to_node": { "type": "integer", "minimum": 0 },
+                    "width": { "type": "number" },
+                    "height": { "type": "number" },
+                    "channel_type": { "$ref": "#/$defs/channel_type" },
+                    "metadata": { "$ref": "#/$defs/metadata" }
+                }
+            },
+            "channel_type": {
+                "oneOf": [
+                    { "const": "Straight" },
+                    { "type": "object", "required": ["SmoothStraight"], "additionalProperties": false, "properties": {
+                        "SmoothStraight": { "type": "object", "required": ["path"], "properties": { "path": path } }
+                    } },
+                    { "type": "object", "required": ["Serpentine"], "additionalProperties": false, "properties": {
+                        "Serpentine": { "type": "object", "required": ["path"], "properties": { "path": path } }
+                    } },
+                    { "type": "object", "required": ["Arc"], "additionalProperties": false, "properties": {
+                        "Arc": { "type": "object", "required": ["path"], "properties": { "path": path } }
+                    } },
+                    { "type": "object", "required": ["Frustum"], "additionalProperties": false, "properties": {
+                        "Frustum": {
+                            "type": "object",
+                            "required": ["path", "widths", "inlet_width", "throat_width", "outlet_width"],
+                            "properties": {
+                                "path": path,
+                                "widths": { "type": "array", "items": { "type": "number" } },
+                                "inlet_width": { "type": "number" },
+                                "throat_width": { "type": "number" },
+                                "outlet_width": { "type": "number" }
+                            }
+                        }
+                    } }
+                ]
+            }
+        }
+    })
+}
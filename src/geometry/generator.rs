@@ -11,10 +11,14 @@
 //! to strategy objects, promoting loose coupling and extensibility.
 
 use super::types::{Channel, ChannelSystem, ChannelType, Node, Point2D, SplitType};
-use super::strategies::ChannelTypeFactory;
-use super::metadata::{OptimizationMetadata, PerformanceMetadata};
-use super::builders::{ChannelBuilder, NodeBuilder};
+use super::strategies::{ChannelTypeFactory, TargetedChannel};
+use super::metadata::{
+    CollisionAvoidanceMetadata, OptimizationMetadata, ParametricPathMetadata, PerformanceMetadata, TargetMetadata,
+};
+use super::builders::{ChannelBuilder, ChannelExt, NodeBuilder};
+use super::collision_detection::{CollisionDetectionSystem, SystemAvoidanceConfig, SystemAvoidanceReport};
 use crate::config::{ChannelTypeConfig, GeometryConfig};
+use crate::error::{ConfigurationResult, GeometryError, GeometryResult, SchemeResult};
 use std::collections::HashMap;
 use std::time::Instant;
 
@@ -25,6 +29,8 @@ pub struct MetadataConfig {
     pub track_performance: bool,
     /// Whether to track optimization metadata for serpentine channels
     pub track_optimization: bool,
+    /// Whether to keep the analytic path of each channel as `ParametricPathMetadata`
+    pub track_parametric_paths: bool,
 }
 
 /// Internal geometry generator that builds channel systems incrementally
@@ -52,7 +58,7 @@ impl GeometryGenerator {
     fn new(
         box_dims: (f64, f64),
         config: GeometryConfig,
-        channel_type_config: ChannelTypeConfig,
+        channel_type_config: &ChannelTypeConfig,
         total_branches: usize,
     ) -> Self {
         Self {
@@ -63,7 +69,7 @@ impl GeometryGenerator {
             channel_counter: 0,
             point_to_node_id: HashMap::new(),
             config,
-            channel_type_config,
+            channel_type_config: *channel_type_config,
             total_branches,
             metadata_config: None,
             generation_start_time: None,
@@ -73,7 +79,7 @@ impl GeometryGenerator {
     fn new_with_metadata(
         box_dims: (f64, f64),
         config: GeometryConfig,
-        channel_type_config: ChannelTypeConfig,
+        channel_type_config: &ChannelTypeConfig,
         total_branches: usize,
         metadata_config: MetadataConfig,
     ) -> Self {
@@ -85,7 +91,7 @@ impl GeometryGenerator {
             channel_counter: 0,
             point_to_node_id: HashMap::new(),
             config,
-            channel_type_config,
+            channel_type_config: *channel_type_config,
             total_branches,
             metadata_config: Some(metadata_config),
             generation_start_time: Some(Instant::now()),
@@ -136,7 +142,12 @@ impl GeometryGenerator {
         id
     }
 
-    fn determine_channel_type(&self, p1: Point2D, p2: Point2D, neighbor_info: Option<&[f64]>) -> ChannelType {
+    fn determine_channel_type(
+        &self,
+        p1: Point2D,
+        p2: Point2D,
+        neighbor_info: Option<&[f64]>,
+    ) -> TargetedChannel {
         let strategy = ChannelTypeFactory::create_strategy(
             &self.channel_type_config,
             p1,
@@ -144,7 +155,7 @@ impl GeometryGenerator {
             self.box_dims,
         );
 
-        strategy.create_channel(
+        strategy.create_targeted_channel(
             p1,
             p2,
             &self.config,
@@ -169,19 +180,25 @@ impl GeometryGenerator {
 
 
     fn add_channel_with_neighbors(&mut self, p1: Point2D, p2: Point2D, neighbor_y_coords: &[f64]) {
-        let channel_type = self.determine_channel_type(p1, p2, Some(neighbor_y_coords));
-        self.add_channel_with_type(p1, p2, Some(channel_type));
+        let channel = self.determine_channel_type(p1, p2, Some(neighbor_y_coords));
+        self.add_channel_with_type(p1, p2, Some(channel));
     }
 
-    fn add_channel_with_type(&mut self, p1: Point2D, p2: Point2D, channel_type: Option<ChannelType>) {
+    fn add_channel_with_type(
+        &mut self,
+        p1: Point2D,
+        p2: Point2D,
+        channel_type: Option<TargetedChannel>,
+    ) {
         let from_id = self.get_or_create_node(p1);
         let to_id = self.get_or_create_node(p2);
         let id = self.channel_counter;
 
-        let final_channel_type = channel_type.unwrap_or_else(|| self.determine_channel_type(p1, p2, None));
+        let (final_channel_type, parametric_path, target_result) =
+            channel_type.unwrap_or_else(|| self.determine_channel_type(p1, p2, None));
 
         // Create channel with optional metadata
-        let channel = if let Some(ref metadata_config) = self.metadata_config {
+        let mut channel = if let Some(ref metadata_config) = self.metadata_config {
             let mut channel_builder = ChannelBuilder::new(
                 id,
                 from_id,
@@ -224,6 +241,13 @@ impl GeometryGenerator {
                 }
             }
 
+            // Keep the analytic path if enabled and the strategy provides one
+            if metadata_config.track_parametric_paths {
+                if let Some(path) = parametric_path {
+                    channel_builder = channel_builder.with_metadata(ParametricPathMetadata { path });
+                }
+            }
+
             channel_builder.build()
         } else {
             // Fast path for no metadata
@@ -238,11 +262,33 @@ impl GeometryGenerator {
             }
         };
 
+        // Record how close a targeted channel came to its target
+        if let Some(result) = target_result {
+            channel.add_metadata(TargetMetadata {
+                target: result.target,
+                achieved_value: result.achieved_value,
+                relative_error: result.relative_error,
+                feasibility: result.feasibility,
+            });
+        }
+
         self.channels.push(channel);
         self.channel_counter += 1;
     }
 
-    fn generate(mut self, splits: &[SplitType]) -> ChannelSystem {
+    fn generate(self, splits: &[SplitType]) -> ChannelSystem {
+        let config = self.config;
+        let mut system = self.generate_paths(splits);
+        if let Some(avoidance_config) = &config.generation.collision_avoidance {
+            // Invalid settings are rejected by `try_create_geometry`; here they skip the pass
+            if let Ok(report) = CollisionDetectionSystem::default().resolve_system_collisions(&mut system, &config, avoidance_config) {
+                record_collision_avoidance(&mut system, &report);
+            }
+        }
+        system
+    }
+
+    fn generate_paths(mut self, splits: &[SplitType]) -> ChannelSystem {
         let (length, width) = self.box_dims;
 
         if splits.is_empty() {
@@ -271,6 +317,18 @@ impl GeometryGenerator {
         self.finalize()
     }
 
+    fn generate_with_collision_avoidance(
+        self,
+        splits: &[SplitType],
+        avoidance_config: &SystemAvoidanceConfig,
+    ) -> ConfigurationResult<(ChannelSystem, SystemAvoidanceReport)> {
+        let config = self.config;
+        let mut system = self.generate_paths(splits);
+        let report = CollisionDetectionSystem::default()
+            .resolve_system_collisions(&mut system, &config, avoidance_config)?;
+        Ok((system, report))
+    }
+
     fn generate_first_half(&self, splits: &[SplitType]) -> Vec<(Point2D, Point2D)> {
         let (length, width) = self.box_dims;
         let effective_width = (-2.0f64).mul_add(self.config.wall_clearance, width);
@@ -491,8 +549,127 @@ pub fn create_geometry(
     config: &GeometryConfig,
     channel_type_config: &ChannelTypeConfig,
 ) -> ChannelSystem {
-    let total_branches = splits.iter().map(SplitType::branch_count).product::<usize>().max(1);
-    GeometryGenerator::new(box_dims, *config, *channel_type_config, total_branches).generate(splits)
+    let total_branches = total_branches(splits).unwrap_or(usize::MAX);
+    GeometryGenerator::new(box_dims, *config, channel_type_config, total_branches).generate(splits)
+}
+
+/// Creates a channel system after checking that the inputs can produce one
+///
+/// `create_geometry` always returns a system, even for inputs that make no
+/// sense. This checks, before generating, that:
+/// - the box dimensions are positive and finite
+/// - the geometry and channel type configurations are valid
+/// - the wall clearance leaves room for a channel
+/// - the split pattern keeps branches at least one channel width apart and
+///   horizontal segments at least one channel width long
+///
+/// The generated system is then checked with [`ChannelSystem::validate`].
+///
+/// # Arguments
+///
+/// * `box_dims` - Dimensions of the containing box (width, height)
+/// * `splits` - Array of split types defining the branching pattern
+/// * `config` - Geometry configuration (channel dimensions, clearances)
+/// * `channel_type_config` - Configuration for channel type generation
+///
+/// # Errors
+///
+/// - [`GeometryError::InvalidBoxDimensions`] for non-positive or non-finite dimensions
+/// - A configuration error for the first invalid configuration value
+/// - [`GeometryError::InsufficientSpace`] if the wall clearance leaves less
+///   than one channel width
+/// - [`GeometryError::InvalidSplitPattern`] if the splits crowd branches or
+///   segments below one channel width
+///
+/// # Examples
+///
+/// ```rust
+/// use scheme::{
+///     error::{GeometryError, SchemeError},
+///     geometry::{generator::try_create_geometry, SplitType},
+///     config::{GeometryConfig, ChannelTypeConfig},
+/// };
+///
+/// let system = try_create_geometry(
+///     (200.0, 100.0),
+///     &[SplitType::Bifurcation],
+///     &GeometryConfig::default(),
+///     &ChannelTypeConfig::AllStraight,
+/// )?;
+/// assert_eq!(system.channels.len(), 10);
+///
+/// let crowded = try_create_geometry(
+///     (200.0, 10.0),
+///     &[SplitType::Trifurcation; 3],
+///     &GeometryConfig::default(),
+///     &ChannelTypeConfig::AllStraight,
+/// );
+/// assert!(matches!(crowded, Err(SchemeError::Geometry(GeometryError::InvalidSplitPattern { .. }))));
+/// # Ok::<(), SchemeError>(())
+/// ```
+pub fn try_create_geometry(
+    box_dims: (f64, f64),
+    splits: &[SplitType],
+    config: &GeometryConfig,
+    channel_type_config: &ChannelTypeConfig,
+) -> SchemeResult<ChannelSystem> {
+    let (length, width) = box_dims;
+    if !(length.is_finite() && width.is_finite() && length > 0.0 && width > 0.0) {
+        return Err(GeometryError::invalid_box_dimensions(length, width).into());
+    }
+    config.validate()?;
+    channel_type_config.validate()?;
+    check_feasibility(box_dims, splits, config)?;
+
+    let system = create_geometry(box_dims, splits, config, channel_type_config);
+    system.validate()?;
+    Ok(system)
+}
+
+/// Number of parallel branches after all `splits`, or `None` if it overflows
+fn total_branches(splits: &[SplitType]) -> Option<usize> {
+    splits.iter().try_fold(1_usize, |total, split| total.checked_mul(split.branch_count()))
+}
+
+/// Check that the branches of `splits` fit in the box with room for each channel
+#[allow(clippy::cast_precision_loss)]
+fn check_feasibility(box_dims: (f64, f64), splits: &[SplitType], config: &GeometryConfig) -> GeometryResult<()> {
+    let (length, width) = box_dims;
+    let available = (-2.0f64).mul_add(config.wall_clearance, width);
+    if available < config.channel_width {
+        return Err(GeometryError::insufficient_space(
+            2.0f64.mul_add(config.wall_clearance, config.channel_width),
+            width,
+        ));
+    }
+
+    let Some(total_branches) = total_branches(splits) else {
+        return Err(GeometryError::InvalidSplitPattern {
+            reason: format!("{} split(s) give more branches than can be counted", splits.len()),
+        });
+    };
+    let spacing = available / total_branches as f64;
+    if spacing < config.channel_width {
+        return Err(GeometryError::InvalidSplitPattern {
+            reason: format!(
+                "{} split(s) give {total_branches} branches spaced {spacing:.3} apart, less than the channel width {}",
+                splits.len(),
+                config.channel_width,
+            ),
+        });
+    }
+
+    let segment_length = length / 2.0 / (splits.len() as f64).mul_add(2.0, 1.0);
+    if segment_length < config.channel_width {
+        return Err(GeometryError::InvalidSplitPattern {
+            reason: format!(
+                "{} split(s) leave segments {segment_length:.3} long, shorter than the channel width {}",
+                splits.len(),
+                config.channel_width,
+            ),
+        });
+    }
+    Ok(())
 }
 
 /// Creates a complete 2D microfluidic channel system with metadata support
@@ -524,6 +701,7 @@ pub fn create_geometry(
 /// let metadata_config = MetadataConfig {
 ///     track_performance: true,
 ///     track_optimization: true,
+///     track_parametric_paths: false,
 /// };
 ///
 /// let system = create_geometry_with_metadata(
@@ -542,16 +720,92 @@ pub fn create_geometry_with_metadata(
     channel_type_config: &ChannelTypeConfig,
     metadata_config: &MetadataConfig,
 ) -> ChannelSystem {
-    let total_branches = splits.iter().map(SplitType::branch_count).product::<usize>().max(1);
+    let total_branches = total_branches(splits).unwrap_or(usize::MAX);
     GeometryGenerator::new_with_metadata(
         box_dims,
         *config,
-        *channel_type_config,
+        channel_type_config,
         total_branches,
         metadata_config.clone()
     ).generate(splits)
 }
 
+/// Creates a channel system and resolves collisions between its finished paths
+///
+/// This generates the system exactly like `create_geometry`, then runs a
+/// post-generation pass that measures the real distances between all channel
+/// paths and iteratively shrinks the amplitude or curvature of offending
+/// serpentine and arc channels until the design is clean or the iteration
+/// limit is reached.
+///
+/// # Arguments
+///
+/// * `box_dims` - Dimensions of the containing box (width, height)
+/// * `splits` - Array of split types defining the branching pattern
+/// * `config` - Geometry configuration (channel dimensions, clearances)
+/// * `channel_type_config` - Configuration for channel type generation
+/// * `avoidance_config` - Configuration for the collision avoidance pass
+///
+/// # Returns
+///
+/// The adjusted `ChannelSystem` together with a report of which channels
+/// were changed and how many collisions remain.
+///
+/// # Errors
+///
+/// Returns an error if the avoidance configuration is invalid.
+///
+/// # Examples
+///
+/// ```rust
+/// use scheme::{
+///     geometry::{
+///         collision_detection::SystemAvoidanceConfig,
+///         generator::create_geometry_with_collision_avoidance,
+///         SplitType,
+///     },
+///     config::{ChannelTypeConfig, GeometryConfig, SerpentineConfig},
+/// };
+///
+/// let (system, report) = create_geometry_with_collision_avoidance(
+///     (200.0, 100.0),
+///     &[SplitType::Bifurcation],
+///     &GeometryConfig::default(),
+///     &ChannelTypeConfig::AllSerpentine(SerpentineConfig::default()),
+///     &SystemAvoidanceConfig::default(),
+/// )?;
+/// assert!(report.is_clean());
+/// # Ok::<(), scheme::error::ConfigurationError>(())
+/// ```
+pub fn create_geometry_with_collision_avoidance(
+    box_dims: (f64, f64),
+    splits: &[SplitType],
+    config: &GeometryConfig,
+    channel_type_config: &ChannelTypeConfig,
+    avoidance_config: &SystemAvoidanceConfig,
+) -> ConfigurationResult<(ChannelSystem, SystemAvoidanceReport)> {
+    let total_branches = total_branches(splits).unwrap_or(usize::MAX);
+    GeometryGenerator::new(box_dims, *config, channel_type_config, total_branches)
+        .generate_with_collision_avoidance(splits, avoidance_config)
+}
+
+/// Attach the outcome of the avoidance pass to the channels it concerns
+fn record_collision_avoidance(system: &mut ChannelSystem, report: &SystemAvoidanceReport) {
+    for channel in &mut system.channels {
+        let adjustment = report.adjustments.iter().find(|adjustment| adjustment.channel_id == channel.id);
+        let unresolved = report.unresolved_channels.contains(&channel.id);
+        if adjustment.is_none() && !unresolved {
+            continue;
+        }
+        channel.add_metadata(CollisionAvoidanceMetadata {
+            amplitude_scale: adjustment.map_or(1.0, |adjustment| adjustment.amplitude_scale),
+            rounds: adjustment.map_or(0, |adjustment| adjustment.rounds),
+            length_change: adjustment.map_or(0.0, |adjustment| adjustment.final_length - adjustment.initial_length),
+            unresolved,
+        });
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -563,6 +817,7 @@ mod tests {
         let metadata_config = MetadataConfig {
             track_performance: true,
             track_optimization: false,
+            track_parametric_paths: false,
         };
 
         let system = create_geometry_with_metadata(
@@ -0,0 +1,299 @@
+//! `geometry/hierarchy.rs` - Reusable Cells and Instances
+//!
+//! Large chips repeat the same sub-design many times. Instead of duplicating
+//! every path in a flat [`ChannelSystem`], a design can be described as a
+//! [`CellLibrary`] of named [`Cell`]s. A cell is a reusable channel system
+//! definition that may itself place other cells through [`Instance`]s; an
+//! instance places a cell with a [`Transform`] and optional parameter
+//! overrides.
+//!
+//! The hierarchy is preserved when the library is saved as JSON and is only
+//! expanded when [`CellLibrary::flatten`] produces the flat system needed for
+//! rendering and analysis.
+//!
+//! # Limitations
+//!
+//! [`CellLibrary::to_json`] is the only output that keeps cells and
+//! instances. Every other output works on a flat [`ChannelSystem`]:
+//! rendered images, [`ChannelSystem::to_json`] and the files written by the
+//! command-line tool all contain one copy of each instance's channels. There
+//! is no hierarchical export to layout formats with cell references.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use scheme::config::{ChannelTypeConfig, GeometryConfig};
+//! use scheme::geometry::generator::create_geometry;
+//! use scheme::geometry::hierarchy::{Cell, CellLibrary, Instance};
+//! use scheme::geometry::transform::Transform;
+//! use scheme::geometry::SplitType;
+//!
+//! let splitter = create_geometry((100.0, 50.0), &[SplitType::Bifurcation], &GeometryConfig::default(), &ChannelTypeConfig::AllStraight);
+//!
+//! let library = CellLibrary::new("chip")
+//!     .with_cell(Cell::new("splitter", splitter))
+//!     .with_cell(
+//!         Cell::empty("chip", (100.0, 150.0))
+//!             .with_instance(Instance::new("splitter", Transform::identity()))
+//!             .with_instance(Instance::new("splitter", Transform::translation(0.0, 100.0)).with_channel_width(0.5)),
+//!     );
+//!
+//! let chip = library.flatten()?;
+//! assert_eq!(chip.box_dims, (100.0, 150.0));
+//! assert_eq!(chip.channels.len(), 2 * library.cell("splitter").unwrap().system.channels.len());
+//! # Ok::<(), scheme::error::GeometryError>(())
+//! ```
+
+use crate::error::{GeometryError, GeometryResult};
+use crate::geometry::{transform::Transform, ChannelSystem};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// Parameters of an instance that replace those of its cell
+///
+/// Overrides apply in the cell's own frame, before the instance transform,
+/// and to everything the cell contains, including nested instances. Where
+/// nested instances override the same parameter, the outermost override
+/// wins.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct InstanceOverrides {
+    /// Width of every channel (mm); frustum width profiles are kept
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub channel_width: Option<f64>,
+    /// Height of every channel (mm)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub channel_height: Option<f64>,
+}
+
+impl InstanceOverrides {
+    /// Apply the overrides to every channel of `system`
+    fn apply(&self, system: &mut ChannelSystem) {
+        for channel in &mut system.channels {
+            if let Some(width) = self.channel_width {
+                channel.width = width;
+            }
+            if let Some(height) = self.channel_height {
+                channel.height = height;
+            }
+        }
+    }
+}
+
+/// Placement of a cell inside another cell
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Instance {
+    /// Name of the placed cell
+    pub cell: String,
+    /// Transform from the placed cell's frame into the containing cell's frame
+    pub transform: Transform,
+    /// Parameter overrides for this instance
+    #[serde(default)]
+    pub overrides: InstanceOverrides,
+}
+
+impl Instance {
+    /// Place the cell named `cell` with `transform`
+    #[must_use]
+    pub fn new(cell: &str, transform: Transform) -> Self {
+        Self {
+            cell: cell.to_string(),
+            transform,
+            overrides: InstanceOverrides::default(),
+        }
+    }
+
+    /// Override the width of the instance's channels
+    #[must_use]
+    pub const fn with_channel_width(mut self, width: f64) -> Self {
+        self.overrides.channel_width = Some(width);
+        self
+    }
+
+    /// Override the height of the instance's channels
+    #[must_use]
+    pub const fn with_channel_height(mut self, height: f64) -> Self {
+        self.overrides.channel_height = Some(height);
+        self
+    }
+}
+
+/// Named, reusable channel system definition
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Cell {
+    /// Unique name of the cell within its library
+    pub name: String,
+    /// Geometry defined directly in the cell
+    pub system: ChannelSystem,
+    /// Other cells placed in this cell
+    #[serde(default)]
+    pub instances: Vec<Instance>,
+}
+
+impl Cell {
+    /// Cell defined by `system`
+    #[must_use]
+    pub fn new(name: &str, system: ChannelSystem) -> Self {
+        Self {
+            name: name.to_string(),
+            system,
+            instances: Vec::new(),
+        }
+    }
+
+    /// Cell without geometry of its own, inside a box from the origin to `box_dims`
+    #[must_use]
+    pub fn empty(name: &str, box_dims: (f64, f64)) -> Self {
+        let (length, width) = box_dims;
+        Self::new(
+            name,
+            ChannelSystem {
+                box_dims,
+                nodes: Vec::new(),
+                channels: Vec::new(),
+                box_outline: vec![
+                    ((0.0, 0.0), (length, 0.0)),
+                    ((length, 0.0), (length, width)),
+                    ((length, width), (0.0, width)),
+                    ((0.0, width), (0.0, 0.0)),
+                ],
+            },
+        )
+    }
+
+    /// Place another cell in this cell
+    #[must_use]
+    pub fn with_instance(mut self, instance: Instance) -> Self {
+        self.instances.push(instance);
+        self
+    }
+}
+
+/// Library of cells with a designated top cell
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CellLibrary {
+    /// Name of the cell that represents the whole design
+    pub top: String,
+    /// All cells of the design
+    pub cells: Vec<Cell>,
+}
+
+impl CellLibrary {
+    /// Create an empty library whose design is the cell named `top`
+    #[must_use]
+    pub fn new(top: &str) -> Self {
+        Self {
+            top: top.to_string(),
+            cells: Vec::new(),
+        }
+    }
+
+    /// Add a cell to the library
+    #[must_use]
+    pub fn with_cell(mut self, cell: Cell) -> Self {
+        self.cells.push(cell);
+        self
+    }
+
+    /// Cell named `name`
+    #[must_use]
+    pub fn cell(&self, name: &str) -> Option<&Cell> {
+        self.cells.iter().find(|cell| cell.name == name)
+    }
+
+    /// Check that the hierarchy can be flattened
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GeometryError::InvalidHierarchy`] if cell names are not
+    /// unique, the top cell or an instanced cell does not exist, or a cell
+    /// contains itself.
+    pub fn validate(&self) -> GeometryResult<()> {
+        let mut names = HashSet::new();
+        if let Some(duplicate) = self.cells.iter().find(|cell| !names.insert(cell.name.as_str())) {
+            return Err(GeometryError::invalid_hierarchy(&format!("cell '{}' is defined more than once", duplicate.name)));
+        }
+        let mut finished = HashSet::new();
+        self.visit(&self.top, &mut Vec::new(), &mut finished)
+    }
+
+    /// Expand the top cell into a flat channel system
+    ///
+    /// The result has the top cell's box and contains the geometry of every
+    /// instance, transformed into the top cell's frame and with renumbered
+    /// node and channel IDs. Box outlines of instanced cells are dropped.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GeometryError::InvalidHierarchy`] if the library does not
+    /// [validate](Self::validate).
+    pub fn flatten(&self) -> GeometryResult<ChannelSystem> {
+        self.flatten_cell(&self.top)
+    }
+
+    /// Expand the cell named `name` into a flat channel system
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GeometryError::InvalidHierarchy`] if the library does not
+    /// [validate](Self::validate) or has no cell named `name`.
+    pub fn flatten_cell(&self, name: &str) -> GeometryResult<ChannelSystem> {
+        self.validate()?;
+        let mut finished = HashSet::new();
+        self.visit(name, &mut Vec::new(), &mut finished)?;
+        Ok(self.expand(name))
+    }
+
+    /// Export the library, with its hierarchy, to JSON format
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if serialization fails.
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(self)
+    }
+
+    /// Import a library from JSON format
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the JSON does not describe a cell library.
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    /// Check that `name` and everything it instances exist without cycles
+    fn visit<'a>(&'a self, name: &'a str, path: &mut Vec<&'a str>, finished: &mut HashSet<&'a str>) -> GeometryResult<()> {
+        if finished.contains(name) {
+            return Ok(());
+        }
+        if path.contains(&name) {
+            return Err(GeometryError::invalid_hierarchy(&format!(
+                "cell '{name}' contains itself through {}",
+                path.join(" -> ")
+            )));
+        }
+        let cell = self
+            .cell(name)
+            .ok_or_else(|| GeometryError::invalid_hierarchy(&format!("unknown cell '{name}'")))?;
+        path.push(name);
+        for instance in &cell.instances {
+            self.visit(&instance.cell, path, finished)?;
+        }
+        path.pop();
+        finished.insert(name);
+        Ok(())
+    }
+
+    /// Flat system of a cell that has been checked by [`Self::visit`]
+    fn expand(&self, name: &str) -> ChannelSystem {
+        let cell = self.cell(name).expect("cells are checked before they are expanded");
+        let mut system = cell.system.clone();
+        for instance in &cell.instances {
+            let mut placed = self.expand(&instance.cell);
+            instance.overrides.apply(&mut placed);
+            placed.transform(&instance.transform);
+            system.append(&placed);
+        }
+        system
+    }
+}
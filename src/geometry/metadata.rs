@@ -3,10 +3,22 @@
 //! This module provides a flexible metadata system that allows for easy addition
 //! of new tracking variables without requiring changes to core data structures.
 //! It uses trait-based extensibility with type-safe metadata storage.
+//!
+//! Metadata is serialized through a [`MetadataRegistry`] that maps type names
+//! to serializers. Built-in types are always registered; user-defined types
+//! are added with [`register_metadata`]. Entries of unregistered types are
+//! kept as opaque JSON when loading, so they survive a round trip.
 
 use std::any::{Any, TypeId};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fmt::Debug;
+use std::sync::{OnceLock, PoisonError, RwLock};
+use serde::de::{DeserializeOwned, Error as _};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use crate::config::OptimizationTarget;
+use crate::geometry::optimization::TargetFeasibility;
+use crate::geometry::parametric::ParametricPath;
+use crate::geometry::path_analysis::CurvatureHistogram;
 
 /// Base trait for all metadata types
 ///
@@ -29,10 +41,12 @@ pub trait Metadata: Any + Debug + Send + Sync {
 /// Metadata storage container
 ///
 /// This container provides type-safe storage and retrieval of metadata
-/// using `TypeId` as keys for efficient lookup.
+/// using `TypeId` as keys for efficient lookup. Loaded entries whose type
+/// is not registered are kept as opaque JSON, keyed by type name.
 #[derive(Debug)]
 pub struct MetadataContainer {
     data: HashMap<TypeId, Box<dyn Metadata>>,
+    opaque: BTreeMap<String, serde_json::Value>,
 }
 
 impl MetadataContainer {
@@ -41,6 +55,7 @@ impl MetadataContainer {
     pub fn new() -> Self {
         Self {
             data: HashMap::new(),
+            opaque: BTreeMap::new(),
         }
     }
     
@@ -81,16 +96,33 @@ impl MetadataContainer {
             .collect()
     }
     
+    /// Get serialized metadata of an unregistered type by name
+    #[must_use]
+    pub fn get_opaque(&self, type_name: &str) -> Option<&serde_json::Value> {
+        self.opaque.get(type_name)
+    }
+    
+    /// Store serialized metadata without a registered type
+    pub fn insert_opaque(&mut self, type_name: &str, value: serde_json::Value) {
+        self.opaque.insert(type_name.to_string(), value);
+    }
+    
+    /// Get the type names of all opaque metadata entries
+    #[must_use]
+    pub fn opaque_types(&self) -> Vec<&str> {
+        self.opaque.keys().map(String::as_str).collect()
+    }
+    
     /// Check if container is empty
     #[must_use]
     pub fn is_empty(&self) -> bool {
-        self.data.is_empty()
+        self.data.is_empty() && self.opaque.is_empty()
     }
     
-    /// Get number of metadata entries
+    /// Get number of metadata entries, including opaque ones
     #[must_use]
     pub fn len(&self) -> usize {
-        self.data.len()
+        self.data.len() + self.opaque.len()
     }
 }
 
@@ -100,6 +132,7 @@ impl Clone for MetadataContainer {
         for (type_id, metadata) in &self.data {
             new_container.data.insert(*type_id, metadata.clone_metadata());
         }
+        new_container.opaque.clone_from(&self.opaque);
         new_container
     }
 }
@@ -111,7 +144,7 @@ impl Default for MetadataContainer {
 }
 
 /// Flow-related metadata for channels
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct FlowMetadata {
     /// Flow rate in μL/min
     pub flow_rate: f64,
@@ -142,7 +175,7 @@ impl Metadata for FlowMetadata {
 }
 
 /// Thermal metadata for channels
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ThermalMetadata {
     /// Temperature in Celsius
     pub temperature: f64,
@@ -171,7 +204,7 @@ impl Metadata for ThermalMetadata {
 }
 
 /// Manufacturing tolerance metadata
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ManufacturingMetadata {
     /// Width tolerance in micrometers
     pub width_tolerance: f64,
@@ -202,7 +235,7 @@ impl Metadata for ManufacturingMetadata {
 }
 
 /// Optimization history metadata
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct OptimizationMetadata {
     /// Original channel length before optimization
     pub original_length: f64,
@@ -237,7 +270,7 @@ impl Metadata for OptimizationMetadata {
 }
 
 /// Runtime performance metadata
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct PerformanceMetadata {
     /// Generation time in microseconds
     pub generation_time_us: u64,
@@ -265,6 +298,413 @@ impl Metadata for PerformanceMetadata {
     }
 }
 
+/// Curvature analysis metadata for channels
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CurvatureMetadata {
+    /// Smallest bend radius in mm (infinite for straight channels)
+    #[serde(with = "infinite_as_null")]
+    pub min_radius: f64,
+    /// Largest curvature magnitude in 1/mm
+    pub max_curvature: f64,
+    /// Length-weighted mean curvature magnitude in 1/mm
+    pub mean_curvature: f64,
+    /// Sum of absolute heading changes in radians
+    pub total_turning_angle: f64,
+    /// Number of turns along the channel
+    pub turn_count: usize,
+    /// Distribution of curvature magnitude along the channel
+    pub histogram: CurvatureHistogram,
+}
+
+impl Metadata for CurvatureMetadata {
+    fn metadata_type_name(&self) -> &'static str {
+        "CurvatureMetadata"
+    }
+    
+    fn clone_metadata(&self) -> Box<dyn Metadata> {
+        Box::new(self.clone())
+    }
+    
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+/// Dean flow metadata for channels
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DeanMetadata {
+    /// Flow rate in μL/min used for the analysis
+    pub flow_rate: f64,
+    /// Reynolds number with the nominal cross-section
+    pub reynolds_number: f64,
+    /// Largest Dean number along the channel
+    pub max_dean_number: f64,
+    /// Length-weighted mean Dean number
+    pub mean_dean_number: f64,
+    /// Dean number profile as (arc length in mm, Dean number) pairs
+    pub profile: Vec<(f64, f64)>,
+}
+
+impl Metadata for DeanMetadata {
+    fn metadata_type_name(&self) -> &'static str {
+        "DeanMetadata"
+    }
+    
+    fn clone_metadata(&self) -> Box<dyn Metadata> {
+        Box::new(self.clone())
+    }
+    
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+/// Analytic path metadata for channels
+///
+/// Keeps the exact curve a channel path was sampled from, so the channel can
+/// be resampled or measured exactly after generation.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ParametricPathMetadata {
+    /// Analytic centerline of the channel
+    pub path: ParametricPath,
+}
+
+impl Metadata for ParametricPathMetadata {
+    fn metadata_type_name(&self) -> &'static str {
+        "ParametricPathMetadata"
+    }
+    
+    fn clone_metadata(&self) -> Box<dyn Metadata> {
+        Box::new(self.clone())
+    }
+    
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+/// Optimization target metadata for channels
+///
+/// Attached to serpentine channels tuned toward an [`OptimizationTarget`],
+/// recording how close the generated channel came to it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TargetMetadata {
+    /// The target the channel was optimized for
+    pub target: OptimizationTarget,
+    /// Value achieved by the channel, in the target's unit
+    pub achieved_value: f64,
+    /// Relative deviation of the achieved value from the target
+    pub relative_error: f64,
+    /// Whether the target was reached, or the achievable limit when it was not
+    pub feasibility: TargetFeasibility,
+}
+
+impl TargetMetadata {
+    /// Whether the target was met within its tolerance
+    #[must_use]
+    pub fn is_reached(&self) -> bool {
+        self.feasibility == TargetFeasibility::Reached
+    }
+}
+
+impl Metadata for TargetMetadata {
+    fn metadata_type_name(&self) -> &'static str {
+        "TargetMetadata"
+    }
+    
+    fn clone_metadata(&self) -> Box<dyn Metadata> {
+        Box::new(self.clone())
+    }
+    
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+/// Collision avoidance metadata for channels
+///
+/// Attached by the post-generation collision avoidance pass to every channel
+/// it changed or could not clear.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CollisionAvoidanceMetadata {
+    /// Fraction of the original amplitude (or curvature) that remains
+    pub amplitude_scale: f64,
+    /// Number of rounds in which the channel was shrunk
+    pub rounds: usize,
+    /// Change of the path length in mm (negative when shortened)
+    pub length_change: f64,
+    /// Whether the channel still collides after the pass
+    pub unresolved: bool,
+}
+
+impl Metadata for CollisionAvoidanceMetadata {
+    fn metadata_type_name(&self) -> &'static str {
+        "CollisionAvoidanceMetadata"
+    }
+    
+    fn clone_metadata(&self) -> Box<dyn Metadata> {
+        Box::new(self.clone())
+    }
+    
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+/// Serializes infinite radii as `null`, since JSON has no infinity
+mod infinite_as_null {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    #[allow(clippy::trivially_copy_pass_by_ref)]
+    pub fn serialize<S: Serializer>(value: &f64, serializer: S) -> Result<S::Ok, S::Error> {
+        if value.is_finite() {
+            serializer.serialize_f64(*value)
+        } else {
+            serializer.serialize_none()
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<f64, D::Error> {
+        Ok(Option::<f64>::deserialize(deserializer)?.unwrap_or(f64::INFINITY))
+    }
+}
+
+type SerializeFn = fn(&dyn Metadata) -> serde_json::Result<serde_json::Value>;
+type DeserializeFn = fn(serde_json::Value) -> serde_json::Result<Box<dyn Metadata>>;
+
+/// Serializer and deserializer of one registered metadata type
+#[derive(Debug, Clone, Copy)]
+struct MetadataSerializer {
+    type_id: TypeId,
+    serialize: SerializeFn,
+    deserialize: DeserializeFn,
+}
+
+fn serialize_entry<T: Metadata + Serialize>(metadata: &dyn Metadata) -> serde_json::Result<serde_json::Value> {
+    metadata
+        .as_any()
+        .downcast_ref::<T>()
+        .map_or(Ok(serde_json::Value::Null), serde_json::to_value)
+}
+
+fn deserialize_entry<T: Metadata + DeserializeOwned>(value: serde_json::Value) -> serde_json::Result<Box<dyn Metadata>> {
+    Ok(Box::new(serde_json::from_value::<T>(value)?))
+}
+
+/// Registry of metadata types that can be serialized
+///
+/// Containers are serialized as a JSON object keyed by registered type name.
+/// Entries of unregistered types are skipped when saving, since they cannot
+/// be serialized, and kept as opaque JSON when loading.
+#[derive(Debug, Clone)]
+pub struct MetadataRegistry {
+    by_name: HashMap<&'static str, MetadataSerializer>,
+    names: HashMap<TypeId, &'static str>,
+}
+
+impl MetadataRegistry {
+    /// Create an empty registry
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            by_name: HashMap::new(),
+            names: HashMap::new(),
+        }
+    }
+
+    /// Create a registry with every built-in metadata type
+    #[must_use]
+    pub fn with_builtins() -> Self {
+        let mut registry = Self::new();
+        registry.register::<FlowMetadata>("FlowMetadata");
+        registry.register::<ThermalMetadata>("ThermalMetadata");
+        registry.register::<ManufacturingMetadata>("ManufacturingMetadata");
+        registry.register::<OptimizationMetadata>("OptimizationMetadata");
+        registry.register::<PerformanceMetadata>("PerformanceMetadata");
+        registry.register::<CurvatureMetadata>("CurvatureMetadata");
+        registry.register::<DeanMetadata>("DeanMetadata");
+        registry.register::<ParametricPathMetadata>("ParametricPathMetadata");
+        registry.register::<TargetMetadata>("TargetMetadata");
+        registry.register::<CollisionAvoidanceMetadata>("CollisionAvoidanceMetadata");
+        registry
+    }
+
+    /// Register a metadata type under the given name
+    ///
+    /// Registering a name or type again replaces the earlier registration.
+    pub fn register<T: Metadata + Serialize + DeserializeOwned>(&mut self, type_name: &'static str) {
+        let serializer = MetadataSerializer {
+            type_id: TypeId::of::<T>(),
+            serialize: serialize_entry::<T>,
+            deserialize: deserialize_entry::<T>,
+        };
+        if let Some(previous) = self.by_name.insert(type_name, serializer) {
+            self.names.remove(&previous.type_id);
+        }
+        if let Some(previous) = self.names.insert(serializer.type_id, type_name) {
+            if previous != type_name {
+                self.by_name.remove(previous);
+            }
+        }
+    }
+
+    /// Check if a type name is registered
+    #[must_use]
+    pub fn is_registered(&self, type_name: &str) -> bool {
+        self.by_name.contains_key(type_name)
+    }
+
+    /// Serialize a container to a JSON object keyed by type name
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a registered type fails to serialize.
+    pub fn to_value(&self, container: &MetadataContainer) -> serde_json::Result<serde_json::Value> {
+        let mut object: serde_json::Map<String, serde_json::Value> = container
+            .opaque
+            .iter()
+            .map(|(name, value)| (name.clone(), value.clone()))
+            .collect();
+        for (type_id, metadata) in &container.data {
+            if let Some(name) = self.names.get(type_id) {
+                object.insert((*name).to_string(), (self.by_name[name].serialize)(metadata.as_ref())?);
+            }
+        }
+        Ok(serde_json::Value::Object(object))
+    }
+
+    /// Deserialize a container from a JSON object keyed by type name
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the value is not an object or an entry of a
+    /// registered type does not match that type.
+    pub fn from_value(&self, value: serde_json::Value) -> serde_json::Result<MetadataContainer> {
+        let serde_json::Value::Object(object) = value else {
+            return Err(serde_json::Error::custom("metadata must be a JSON object keyed by type name"));
+        };
+        let mut container = MetadataContainer::new();
+        for (name, value) in object {
+            container.opaque.insert(name, value);
+        }
+        self.resolve(&mut container)?;
+        Ok(container)
+    }
+
+    /// Convert opaque entries of registered types into typed metadata
+    ///
+    /// Use this after registering a type to decode entries that were loaded
+    /// before the registration.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an entry does not match its registered type; the
+    /// entry is kept as opaque JSON.
+    pub fn resolve(&self, container: &mut MetadataContainer) -> serde_json::Result<()> {
+        let registered: Vec<String> = container.opaque.keys().filter(|name| self.is_registered(name)).cloned().collect();
+        for name in registered {
+            let serializer = self.by_name[name.as_str()];
+            let value = container.opaque[&name].clone();
+            let metadata = (serializer.deserialize)(value)?;
+            container.opaque.remove(&name);
+            container.data.insert(serializer.type_id, metadata);
+        }
+        Ok(())
+    }
+}
+
+impl Default for MetadataRegistry {
+    fn default() -> Self {
+        Self::with_builtins()
+    }
+}
+
+/// Registry used when nodes and channels are serialized
+fn global_registry() -> &'static RwLock<MetadataRegistry> {
+    static REGISTRY: OnceLock<RwLock<MetadataRegistry>> = OnceLock::new();
+    REGISTRY.get_or_init(|| RwLock::new(MetadataRegistry::with_builtins()))
+}
+
+/// Register a user-defined metadata type for serialization
+///
+/// After registration, metadata of this type is saved by
+/// `ChannelSystem::to_json` and restored by `ChannelSystem::from_json`.
+///
+/// # Examples
+///
+/// ```rust
+/// use scheme::geometry::metadata::{register_metadata, Metadata};
+/// use scheme::impl_metadata;
+/// use serde::{Deserialize, Serialize};
+/// use std::any::Any;
+///
+/// #[derive(Debug, Clone, Serialize, Deserialize)]
+/// struct BatchMetadata {
+///     batch: String,
+/// }
+///
+/// impl_metadata!(BatchMetadata, "BatchMetadata");
+///
+/// register_metadata::<BatchMetadata>("BatchMetadata");
+/// ```
+pub fn register_metadata<T: Metadata + Serialize + DeserializeOwned>(type_name: &'static str) {
+    global_registry()
+        .write()
+        .unwrap_or_else(PoisonError::into_inner)
+        .register::<T>(type_name);
+}
+
+/// Serde adapter for the `metadata` field of nodes and channels
+pub(crate) mod container_serde {
+    use super::{global_registry, Deserialize, Deserializer, MetadataContainer, PoisonError, Serialize, Serializer};
+    use serde::ser::Error as _;
+
+    #[allow(clippy::ref_option)]
+    pub fn serialize<S: Serializer>(metadata: &Option<MetadataContainer>, serializer: S) -> Result<S::Ok, S::Error> {
+        let value = match metadata {
+            Some(container) => global_registry()
+                .read()
+                .unwrap_or_else(PoisonError::into_inner)
+                .to_value(container)
+                .map_err(S::Error::custom)?,
+            None => serde_json::Value::Null,
+        };
+        value.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<MetadataContainer>, D::Error> {
+        Option::<serde_json::Value>::deserialize(deserializer)?
+            .map(|value| {
+                global_registry()
+                    .read()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .from_value(value)
+                    .map_err(serde::de::Error::custom)
+            })
+            .transpose()
+    }
+}
+
 /// Convenience macro for implementing Metadata trait
 #[macro_export]
 macro_rules! impl_metadata {
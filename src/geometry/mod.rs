@@ -11,7 +11,22 @@
 //! - `generator`: Main geometry generation logic with optional metadata support
 //! - `metadata`: Extensible metadata system for tracking additional information
 //! - `builders`: Builder pattern implementations for nodes and channels
+//! - `array`: Step-and-repeat die arrays on wafers and plates
+//! - `composition`: Placement and stitching of channel systems into one chip
+//! - `dean_flow`: Dean number profiles along curved channels
+//! - `drc`: Design rule checking against a fabrication rule deck
+//! - `format`: Versioning, migration and JSON Schema of saved channel systems
+//! - `hierarchy`: Reusable cells placed by transformed instances
+//! - `objectives`: Composable objective functions for serpentine optimization
 //! - `optimization`: Optimization algorithms for serpentine channels
+//! - `optimizers`: Pluggable optimizer trait and built-in search algorithms
+//! - `parametric`: Analytic parametric channel paths with exact evaluators
+//! - `pareto`: Multi-objective NSGA-II optimization returning a Pareto front
+//! - `path_analysis`: Curvature, bend-radius and turn analysis of channel paths
+//! - `sampling`: Adaptive, curvature-based sampling of channel paths
+//! - `spatial_index`: Exact channel-to-channel distance and intersection queries
+//! - `system_optimization`: Joint optimization of all serpentine channels in a system
+//! - `transform`: Translation, rotation, scaling and mirroring of channel geometry
 //!
 //! # Design Patterns
 //!
@@ -21,13 +36,28 @@
 //! - **Builder Pattern**: For constructing complex geometries and metadata
 
 pub mod adaptive_collision;
+pub mod array;
 pub mod builders;
 pub mod collision_detection;
+pub mod composition;
+pub mod dean_flow;
+pub mod drc;
+pub mod format;
 pub mod generator;
+pub mod hierarchy;
 pub mod metadata;
+pub mod objectives;
 pub mod optimization;
+pub mod optimizers;
+pub mod parametric;
+pub mod pareto;
+pub mod path_analysis;
+pub mod sampling;
+pub mod spatial_index;
 pub mod strategies;
+pub mod system_optimization;
 pub mod state_integration;
+pub mod transform;
 pub mod types;
 
 pub use self::{
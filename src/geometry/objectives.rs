@@ -0,0 +1,613 @@
+//! Composable objective functions for serpentine optimization
+//!
+//! By default serpentine optimization maximizes channel length minus a
+//! clearance penalty. This module lets that objective be replaced by a
+//! weighted sum of terms measured on each candidate path: length, curvature,
+//! clearances, turn count, hydraulic resistance, footprint and user-defined
+//! functions.
+//!
+//! Two representations are provided:
+//! - [`ObjectiveSpec`]: a `Copy` list of up to [`MAX_OBJECTIVE_TERMS`]
+//!   weighted terms that can be stored in
+//!   [`SerpentineConfig`](crate::config::SerpentineConfig)
+//! - [`SerpentineObjective`]: an objective set that additionally accepts
+//!   capturing closures, kept outside the configuration and passed to
+//!   [`optimize_serpentine_with_objective`](crate::geometry::optimization::optimize_serpentine_with_objective)
+//!
+//! Custom terms cannot be saved: serializing a configuration whose objective
+//! contains an [`ObjectiveTerm::Custom`] term fails, like serializing a custom
+//! channel type does.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use scheme::config::SerpentineConfig;
+//! use scheme::geometry::objectives::{ObjectiveSpec, ObjectiveTerm, WeightedTerm};
+//!
+//! // Maximize length, but penalize bends tighter than 2 mm radius
+//! const GENTLE_BENDS: ObjectiveSpec = ObjectiveSpec::new(&[
+//!     WeightedTerm::new(ObjectiveTerm::PathLength, 1.0),
+//!     WeightedTerm::new(ObjectiveTerm::MinRadiusShortfall { min_radius: 2.0 }, -1000.0),
+//!     WeightedTerm::new(ObjectiveTerm::ClearanceViolation, -1000.0),
+//! ]);
+//!
+//! let config = SerpentineConfig::default().with_objective(GENTLE_BENDS);
+//! assert!(config.optimization_enabled);
+//! ```
+
+use crate::config::GeometryConfig;
+use crate::error::{ConfigurationError, ConfigurationResult};
+use crate::geometry::optimization::{
+    calculate_hydraulic_resistance, calculate_min_neighbor_distance, calculate_min_self_spacing,
+    calculate_min_wall_distance, calculate_path_length,
+};
+use crate::geometry::types::Point2D;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+/// Objective evaluation constants
+mod constants {
+    /// Minimum lateral excursion, as a fraction of channel width, for a crest to count as a turn
+    pub const TURN_AMPLITUDE_FRACTION: f64 = 0.25;
+
+    /// Segments shorter than this are ignored when estimating curvature (mm)
+    pub const MIN_SEGMENT_LENGTH: f64 = 1e-9;
+
+    /// Clearance penalty weight matching the default length optimization
+    pub const DEFAULT_CLEARANCE_PENALTY_WEIGHT: f64 = 1000.0;
+}
+
+/// Geometric measurements of a candidate serpentine path
+///
+/// Objective terms are evaluated against these metrics, so they are computed
+/// once per candidate.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PathMetrics {
+    /// Total centerline length (mm)
+    pub path_length: f64,
+    /// Largest discrete curvature along the path (1/mm)
+    pub max_curvature: f64,
+    /// Smallest radius of curvature along the path (mm, infinite for straight paths)
+    pub min_radius_of_curvature: f64,
+    /// Number of crests and troughs of the serpentine
+    pub turn_count: usize,
+    /// Minimum distance from the channel edge to the box walls (mm)
+    pub min_wall_distance: f64,
+    /// Minimum distance from the channel edge to neighboring channels (mm)
+    pub min_neighbor_distance: f64,
+    /// Minimum spacing between channel edges of distant parts of the path itself (mm)
+    pub min_self_spacing: f64,
+    /// Total shortfall of wall and neighbor distances below the required clearance (mm)
+    pub clearance_violation: f64,
+    /// Shortfall of the path's spacing to itself below the required clearance (mm)
+    pub self_spacing_violation: f64,
+    /// Area of the path's bounding box including the channel width (mm²)
+    pub footprint_area: f64,
+    /// Channel width (mm)
+    pub channel_width: f64,
+    /// Channel height (mm)
+    pub channel_height: f64,
+}
+
+impl PathMetrics {
+    /// Measure a path
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Channel centerline
+    /// * `geometry_config` - Geometry configuration (channel size and clearance)
+    /// * `box_dims` - Box dimensions
+    /// * `neighbor_info` - Optional neighbor channel positions
+    #[must_use]
+    pub fn from_path(
+        path: &[Point2D],
+        geometry_config: &GeometryConfig,
+        box_dims: (f64, f64),
+        neighbor_info: Option<&[f64]>,
+    ) -> Self {
+        let channel_width = geometry_config.channel_width;
+        let min_wall_distance = calculate_min_wall_distance(path, box_dims, channel_width);
+        let min_neighbor_distance = neighbor_info.map_or(f64::INFINITY, |neighbors| {
+            calculate_min_neighbor_distance(path, neighbors, channel_width)
+        });
+        let clearance = geometry_config.wall_clearance;
+        let clearance_violation = (clearance - min_wall_distance).max(0.0) + (clearance - min_neighbor_distance).max(0.0);
+        let min_self_spacing = calculate_min_self_spacing(path, channel_width, clearance);
+        let max_curvature = max_discrete_curvature(path);
+
+        Self {
+            path_length: calculate_path_length(path),
+            max_curvature,
+            min_radius_of_curvature: if max_curvature > 0.0 { 1.0 / max_curvature } else { f64::INFINITY },
+            turn_count: count_turns(path, channel_width * constants::TURN_AMPLITUDE_FRACTION),
+            min_wall_distance,
+            min_neighbor_distance,
+            min_self_spacing,
+            clearance_violation,
+            self_spacing_violation: (clearance - min_self_spacing).max(0.0),
+            footprint_area: bounding_box_area(path, channel_width),
+            channel_width,
+            channel_height: geometry_config.channel_height,
+        }
+    }
+
+    /// Hydraulic resistance of the channel for the given fluid viscosity (Pa·s/m³)
+    #[must_use]
+    pub fn hydraulic_resistance(&self, viscosity: f64) -> f64 {
+        calculate_hydraulic_resistance(self.path_length, self.channel_width, self.channel_height, viscosity)
+    }
+}
+
+/// A single quantity that can be weighted into a serpentine objective
+///
+/// Objectives are maximized, so quantities that should be minimized take a
+/// negative weight.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum ObjectiveTerm {
+    /// Centerline length (mm)
+    PathLength,
+    /// Largest curvature along the path (1/mm)
+    MaxCurvature,
+    /// Shortfall of the smallest bend radius below `min_radius` (mm, zero when satisfied)
+    MinRadiusShortfall {
+        /// Smallest acceptable bend radius (mm)
+        min_radius: f64,
+    },
+    /// Minimum clearance to the box walls (mm)
+    WallClearance,
+    /// Minimum clearance to neighboring channels (mm)
+    NeighborClearance,
+    /// Total shortfall of wall and neighbor clearances (mm, zero when satisfied)
+    ClearanceViolation,
+    /// Shortfall of the path's clearance to itself (mm, zero when satisfied)
+    SelfSpacingViolation,
+    /// Number of serpentine turns
+    TurnCount,
+    /// Hydraulic resistance (Pa·s/m³)
+    HydraulicResistance {
+        /// Dynamic viscosity of the fluid (Pa·s)
+        viscosity: f64,
+    },
+    /// Bounding-box footprint of the channel (mm²)
+    Footprint,
+    /// User-defined function of the path metrics and centerline
+    ///
+    /// Functions cannot be saved, so objectives using this term fail to
+    /// serialize.
+    #[serde(skip)]
+    Custom(fn(&PathMetrics, &[Point2D]) -> f64),
+}
+
+impl ObjectiveTerm {
+    /// Evaluate this term for a measured path
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn evaluate(&self, metrics: &PathMetrics, path: &[Point2D]) -> f64 {
+        match *self {
+            Self::PathLength => metrics.path_length,
+            Self::MaxCurvature => metrics.max_curvature,
+            Self::MinRadiusShortfall { min_radius } => (min_radius - metrics.min_radius_of_curvature).max(0.0),
+            Self::WallClearance => metrics.min_wall_distance,
+            Self::NeighborClearance => metrics.min_neighbor_distance,
+            Self::ClearanceViolation => metrics.clearance_violation,
+            Self::SelfSpacingViolation => metrics.self_spacing_violation,
+            Self::TurnCount => metrics.turn_count as f64,
+            Self::HydraulicResistance { viscosity } => metrics.hydraulic_resistance(viscosity),
+            Self::Footprint => metrics.footprint_area,
+            Self::Custom(function) => function(metrics, path),
+        }
+    }
+
+    /// Human-readable name of the quantity, including its unit
+    #[must_use]
+    pub const fn label(&self) -> &'static str {
+        match self {
+            Self::PathLength => "Path length (mm)",
+            Self::MaxCurvature => "Max curvature (1/mm)",
+            Self::MinRadiusShortfall { .. } => "Bend radius shortfall (mm)",
+            Self::WallClearance => "Wall clearance (mm)",
+            Self::NeighborClearance => "Neighbor clearance (mm)",
+            Self::ClearanceViolation => "Clearance violation (mm)",
+            Self::SelfSpacingViolation => "Self-spacing violation (mm)",
+            Self::TurnCount => "Turn count",
+            Self::HydraulicResistance { .. } => "Hydraulic resistance (Pa·s/m³)",
+            Self::Footprint => "Footprint (mm²)",
+            Self::Custom(_) => "Custom objective",
+        }
+    }
+
+    /// Validate the term's parameters
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a term parameter is not positive and finite.
+    pub fn validate(&self) -> ConfigurationResult<()> {
+        let parameter = match *self {
+            Self::MinRadiusShortfall { min_radius } => Some(("objective.min_radius", min_radius)),
+            Self::HydraulicResistance { viscosity } => Some(("objective.viscosity", viscosity)),
+            _ => None,
+        };
+        if let Some((field, value)) = parameter {
+            if !value.is_finite() || value <= 0.0 {
+                return Err(ConfigurationError::invalid_serpentine_config(
+                    field,
+                    value,
+                    "Must be a positive, finite value",
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// An objective term together with its weight
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct WeightedTerm {
+    /// Quantity to evaluate
+    pub term: ObjectiveTerm,
+    /// Weight applied to the quantity (negative to minimize)
+    pub weight: f64,
+}
+
+impl WeightedTerm {
+    /// Create a weighted term
+    #[must_use]
+    pub const fn new(term: ObjectiveTerm, weight: f64) -> Self {
+        Self { term, weight }
+    }
+}
+
+/// Terms of the default objective: path length minus a heavy clearance penalty
+const MAXIMIZE_LENGTH_TERMS: &[WeightedTerm] = &[
+    WeightedTerm::new(ObjectiveTerm::PathLength, 1.0),
+    WeightedTerm::new(ObjectiveTerm::ClearanceViolation, -constants::DEFAULT_CLEARANCE_PENALTY_WEIGHT),
+];
+
+/// Maximum number of weighted terms in an [`ObjectiveSpec`]
+pub const MAX_OBJECTIVE_TERMS: usize = 8;
+
+/// Placeholder filling the unused slots of an [`ObjectiveSpec`]
+const UNUSED_TERM: WeightedTerm = WeightedTerm::new(ObjectiveTerm::PathLength, 0.0);
+
+/// A `Copy` weighted-sum objective that can be stored in a serpentine configuration
+///
+/// Holds up to [`MAX_OBJECTIVE_TERMS`] weighted terms inline so the
+/// configuration stays `Copy`. Use [`SerpentineObjective`] for objectives
+/// that need capturing closures.
+///
+/// Serializes as the list of its weighted terms. Functions cannot be saved,
+/// so specifications containing an [`ObjectiveTerm::Custom`] term fail to
+/// serialize.
+#[derive(Clone, Copy, Serialize, Deserialize)]
+#[serde(into = "Vec<WeightedTerm>", from = "Vec<WeightedTerm>")]
+pub struct ObjectiveSpec {
+    terms: [WeightedTerm; MAX_OBJECTIVE_TERMS],
+    len: usize,
+}
+
+impl From<Vec<WeightedTerm>> for ObjectiveSpec {
+    fn from(terms: Vec<WeightedTerm>) -> Self {
+        Self::from_terms(terms)
+    }
+}
+
+impl From<ObjectiveSpec> for Vec<WeightedTerm> {
+    fn from(spec: ObjectiveSpec) -> Self {
+        spec.terms().to_vec()
+    }
+}
+
+impl std::fmt::Debug for ObjectiveSpec {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ObjectiveSpec").field("terms", &self.terms()).finish()
+    }
+}
+
+impl Default for ObjectiveSpec {
+    fn default() -> Self {
+        Self::maximize_length()
+    }
+}
+
+impl ObjectiveSpec {
+    /// Create an objective specification from a list of terms
+    ///
+    /// Terms beyond [`MAX_OBJECTIVE_TERMS`] are not stored, and
+    /// [`Self::validate`] rejects the specification.
+    #[must_use]
+    pub const fn new(terms: &[WeightedTerm]) -> Self {
+        let mut stored = [UNUSED_TERM; MAX_OBJECTIVE_TERMS];
+        let mut index = 0;
+        while index < terms.len() && index < MAX_OBJECTIVE_TERMS {
+            stored[index] = terms[index];
+            index += 1;
+        }
+        Self { terms: stored, len: terms.len() }
+    }
+
+    /// Create an objective specification from terms built at runtime
+    #[must_use]
+    pub fn from_terms(terms: Vec<WeightedTerm>) -> Self {
+        let mut spec = Self { len: terms.len(), ..Self::new(&[]) };
+        for (slot, weighted) in spec.terms.iter_mut().zip(terms) {
+            *slot = weighted;
+        }
+        spec
+    }
+
+    /// The default objective: path length minus a heavy clearance penalty
+    #[must_use]
+    pub const fn maximize_length() -> Self {
+        Self::new(MAXIMIZE_LENGTH_TERMS)
+    }
+
+    /// The weighted terms
+    #[must_use]
+    pub const fn terms(&self) -> &[WeightedTerm] {
+        let stored = if self.len < MAX_OBJECTIVE_TERMS { self.len } else { MAX_OBJECTIVE_TERMS };
+        self.terms.split_at(stored).0
+    }
+
+    /// Number of weighted terms the specification was created with
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether the specification has no terms
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Evaluate the weighted sum for a measured path
+    #[must_use]
+    pub fn evaluate(&self, metrics: &PathMetrics, path: &[Point2D]) -> f64 {
+        self.terms().iter().map(|weighted| weighted.weight * weighted.term.evaluate(metrics, path)).sum()
+    }
+
+    /// Validate the objective specification
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the specification has no terms or more than
+    /// [`MAX_OBJECTIVE_TERMS`], any weight is not finite, or a term parameter
+    /// is not positive and finite.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn validate(&self) -> ConfigurationResult<()> {
+        if self.is_empty() {
+            return Err(ConfigurationError::MissingConfiguration {
+                field: "objective.terms".to_string(),
+            });
+        }
+
+        if self.len > MAX_OBJECTIVE_TERMS {
+            return Err(ConfigurationError::invalid_serpentine_config(
+                "objective.terms",
+                self.len as f64,
+                &format!("Must have at most {MAX_OBJECTIVE_TERMS} terms"),
+            ));
+        }
+
+        for weighted in self.terms() {
+            if !weighted.weight.is_finite() {
+                return Err(ConfigurationError::invalid_serpentine_config(
+                    "objective.weight",
+                    weighted.weight,
+                    "Must be finite",
+                ));
+            }
+
+            weighted.term.validate()?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Shared user-defined objective closure
+type ObjectiveClosure = Arc<dyn Fn(&PathMetrics, &[Point2D]) -> f64 + Send + Sync>;
+
+/// A serpentine objective set supporting capturing closures
+///
+/// Closures are shared between clones, so the set can be reused for many
+/// channels. It is not part of any configuration and is never serialized.
+///
+/// # Examples
+///
+/// ```rust
+/// use scheme::geometry::objectives::{ObjectiveTerm, SerpentineObjective};
+///
+/// let max_footprint = 2500.0;
+/// let objective = SerpentineObjective::new()
+///     .with_term(ObjectiveTerm::TurnCount, 1.0)
+///     .with_closure(1.0, move |metrics, _path| {
+///         -(metrics.footprint_area - max_footprint).max(0.0)
+///     });
+/// assert_eq!(objective.len(), 2);
+/// ```
+#[derive(Clone, Default)]
+pub struct SerpentineObjective {
+    terms: Vec<WeightedTerm>,
+    closures: Vec<(f64, ObjectiveClosure)>,
+}
+
+impl std::fmt::Debug for SerpentineObjective {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SerpentineObjective")
+            .field("terms", &self.terms)
+            .field("closures", &self.closures.len())
+            .finish()
+    }
+}
+
+impl SerpentineObjective {
+    /// Create an empty objective
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a weighted built-in term
+    #[must_use]
+    pub fn with_term(mut self, term: ObjectiveTerm, weight: f64) -> Self {
+        self.terms.push(WeightedTerm::new(term, weight));
+        self
+    }
+
+    /// Add a weighted user closure
+    #[must_use]
+    pub fn with_closure<F>(mut self, weight: f64, closure: F) -> Self
+    where
+        F: Fn(&PathMetrics, &[Point2D]) -> f64 + Send + Sync + 'static,
+    {
+        self.closures.push((weight, Arc::new(closure)));
+        self
+    }
+
+    /// Number of terms and closures
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.terms.len() + self.closures.len()
+    }
+
+    /// Whether the objective has no terms
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Evaluate the weighted sum for a measured path
+    #[must_use]
+    pub fn evaluate(&self, metrics: &PathMetrics, path: &[Point2D]) -> f64 {
+        let term_score: f64 = self.terms.iter()
+            .map(|weighted| weighted.weight * weighted.term.evaluate(metrics, path))
+            .sum();
+        let closure_score: f64 = self.closures.iter()
+            .map(|(weight, closure)| weight * closure(metrics, path))
+            .sum();
+        term_score + closure_score
+    }
+}
+
+impl From<ObjectiveSpec> for SerpentineObjective {
+    fn from(spec: ObjectiveSpec) -> Self {
+        Self {
+            terms: spec.terms().to_vec(),
+            closures: Vec::new(),
+        }
+    }
+}
+
+/// Largest discrete curvature along a path using the circumscribed circle of each point triple
+fn max_discrete_curvature(path: &[Point2D]) -> f64 {
+    path.windows(3)
+        .filter_map(|window| {
+            let (a, b, c) = (window[0], window[1], window[2]);
+            let ab = (b.0 - a.0).hypot(b.1 - a.1);
+            let bc = (c.0 - b.0).hypot(c.1 - b.1);
+            let ca = (a.0 - c.0).hypot(a.1 - c.1);
+            if ab < constants::MIN_SEGMENT_LENGTH || bc < constants::MIN_SEGMENT_LENGTH || ca < constants::MIN_SEGMENT_LENGTH {
+                return None;
+            }
+            let twice_area = (b.0 - a.0).mul_add(c.1 - a.1, -((b.1 - a.1) * (c.0 - a.0))).abs();
+            Some(2.0 * twice_area / (ab * bc * ca))
+        })
+        .fold(0.0, f64::max)
+}
+
+/// Count crests and troughs whose lateral excursion from the chord exceeds `min_amplitude`
+fn count_turns(path: &[Point2D], min_amplitude: f64) -> usize {
+    let (Some(&start), Some(&end)) = (path.first(), path.last()) else {
+        return 0;
+    };
+    let chord = (end.0 - start.0, end.1 - start.1);
+    let chord_length = chord.0.hypot(chord.1);
+    if chord_length < constants::MIN_SEGMENT_LENGTH {
+        return 0;
+    }
+
+    let offsets: Vec<f64> = path.iter()
+        .map(|&(x, y)| chord.0.mul_add(y - start.1, -(chord.1 * (x - start.0))) / chord_length)
+        .collect();
+
+    // Track the last non-zero direction so flat crests are counted once
+    let mut turns = 0;
+    let mut last_direction = 0_i8;
+    for index in 1..offsets.len() {
+        let delta = offsets[index] - offsets[index - 1];
+        if delta.abs() < f64::EPSILON {
+            continue;
+        }
+        let direction: i8 = if delta > 0.0 { 1 } else { -1 };
+        if last_direction != 0 && direction != last_direction && offsets[index - 1].abs() > min_amplitude {
+            turns += 1;
+        }
+        last_direction = direction;
+    }
+    turns
+}
+
+/// Bounding box area of a path widened by the channel width
+fn bounding_box_area(path: &[Point2D], channel_width: f64) -> f64 {
+    if path.is_empty() {
+        return 0.0;
+    }
+    let (min_x, max_x, min_y, max_y) = path.iter().fold(
+        (f64::INFINITY, f64::NEG_INFINITY, f64::INFINITY, f64::NEG_INFINITY),
+        |(min_x, max_x, min_y, max_y), &(x, y)| (min_x.min(x), max_x.max(x), min_y.min(y), max_y.max(y)),
+    );
+    (max_x - min_x + channel_width) * (max_y - min_y + channel_width)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sine_path(amplitude: f64, half_periods: f64) -> Vec<Point2D> {
+        (0..=400)
+            .map(|i| {
+                let t = f64::from(i) / 400.0;
+                (100.0 * t, amplitude.mul_add((std::f64::consts::PI * half_periods * t).sin(), 50.0))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_turn_count_and_curvature() {
+        let path = sine_path(5.0, 6.0);
+        let metrics = PathMetrics::from_path(&path, &GeometryConfig::default(), (100.0, 100.0), None);
+        assert_eq!(metrics.turn_count, 6);
+
+        // Sine curvature peaks at A·k² where k = π·half_periods / length
+        let k = std::f64::consts::PI * 6.0 / 100.0;
+        assert!((metrics.max_curvature - 5.0 * k * k).abs() / (5.0 * k * k) < 0.01);
+
+        let straight = vec![(0.0, 50.0), (50.0, 50.0), (100.0, 50.0)];
+        let metrics = PathMetrics::from_path(&straight, &GeometryConfig::default(), (100.0, 100.0), None);
+        assert_eq!(metrics.turn_count, 0);
+        assert!(metrics.min_radius_of_curvature.is_infinite());
+    }
+
+    #[test]
+    fn test_spec_validation() {
+        assert!(ObjectiveSpec::new(&[]).validate().is_err());
+        assert!(ObjectiveSpec::maximize_length().validate().is_ok());
+
+        let runtime = ObjectiveSpec::from_terms(vec![WeightedTerm::new(ObjectiveTerm::TurnCount, 1.0)]);
+        assert_eq!(runtime.len(), 1);
+        assert!(runtime.validate().is_ok());
+
+        const NEGATIVE_RADIUS: ObjectiveSpec = ObjectiveSpec::new(&[
+            WeightedTerm::new(ObjectiveTerm::MinRadiusShortfall { min_radius: -1.0 }, 1.0),
+        ]);
+        assert!(NEGATIVE_RADIUS.validate().is_err());
+        const NAN_WEIGHT: ObjectiveSpec = ObjectiveSpec::new(&[WeightedTerm::new(ObjectiveTerm::PathLength, f64::NAN)]);
+        assert!(NAN_WEIGHT.validate().is_err());
+
+        let too_many = ObjectiveSpec::from_terms(vec![WeightedTerm::new(ObjectiveTerm::PathLength, 1.0); MAX_OBJECTIVE_TERMS + 1]);
+        assert_eq!(too_many.len(), MAX_OBJECTIVE_TERMS + 1);
+        assert_eq!(too_many.terms().len(), MAX_OBJECTIVE_TERMS);
+        assert!(too_many.validate().is_err());
+    }
+}
@@ -5,8 +5,16 @@
 //! multi-channel compatibility.
 
 use crate::geometry::types::Point2D;
-use crate::config::{GeometryConfig, SerpentineConfig, OptimizationProfile};
+use crate::geometry::strategies::{ChannelGenerationContext, SerpentineChannelStrategy};
+use crate::geometry::objectives::{PathMetrics, SerpentineObjective};
+use crate::geometry::spatial_index::path_self_approach;
+use crate::geometry::optimizers::{
+    GridSearchOptimizer, MultiStartNelderMeadOptimizer, NelderMeadOptimizer, OptimizationBudget, Optimizer,
+    ParameterBounds, ParticleSwarmOptimizer, SimulatedAnnealingOptimizer,
+};
+use crate::config::{GeometryConfig, SerpentineConfig, OptimizationProfile, OptimizationTarget};
 use crate::config_constants::ConstantsRegistry;
+use serde::{Deserialize, Serialize};
 
 /// Optimization algorithm constants
 mod constants {
@@ -16,15 +24,6 @@ mod constants {
     /// Penalty multiplier for constraint violations
     pub const CONSTRAINT_PENALTY_MULTIPLIER: f64 = 1000.0;
 
-    /// Small penalty tolerance for validity checking
-    pub const PENALTY_TOLERANCE: f64 = 1.0;
-
-    /// Nelder-Mead algorithm coefficients
-    pub const REFLECTION_COEFFICIENT: f64 = 1.0;
-    pub const EXPANSION_COEFFICIENT: f64 = 2.0;
-    pub const CONTRACTION_COEFFICIENT: f64 = 0.5;
-    pub const SHRINK_COEFFICIENT: f64 = 0.5;
-
     /// Parameter bounds for optimization
     pub const MIN_WAVELENGTH_FACTOR: f64 = 0.5;
     pub const MAX_WAVELENGTH_FACTOR: f64 = 5.0;
@@ -33,10 +32,19 @@ mod constants {
     pub const MIN_FILL_FACTOR: f64 = 0.1;
     pub const MAX_FILL_FACTOR: f64 = 0.95;
 
-    /// Simplex initialization perturbation factors
-    pub const WAVELENGTH_PERTURBATION: f64 = 1.1;
-    pub const WAVE_DENSITY_PERTURBATION: f64 = 1.1;
-    pub const FILL_FACTOR_PERTURBATION: f64 = 1.05;
+    /// Simplex initialization perturbation factors (relative)
+    pub const WAVELENGTH_PERTURBATION: f64 = 0.1;
+    pub const WAVE_DENSITY_PERTURBATION: f64 = 0.1;
+    pub const FILL_FACTOR_PERTURBATION: f64 = 0.05;
+
+    /// Starting points for the thorough (multi-start) profile
+    pub const THOROUGH_STARTING_POINTS: [[f64; 3]; 5] = [
+        [1.0, 1.0, 0.7],
+        [2.0, 2.0, 0.8],
+        [3.0, 3.0, 0.9],
+        [4.0, 1.5, 0.85],
+        [1.5, 4.0, 0.75],
+    ];
 
     /// Wave shape parameters
     pub const SQUARE_WAVE_SHARPNESS: f64 = 5.0;
@@ -51,6 +59,18 @@ mod constants {
     /// Distance normalization bounds
     pub const MIN_DISTANCE_NORMALIZATION: f64 = 0.1;
     pub const MAX_DISTANCE_NORMALIZATION: f64 = 1.0;
+
+    /// Additional wave density factors explored when searching for long targets
+    pub const TARGET_EXTENDED_WAVE_DENSITY_FACTORS: [f64; 4] = [0.5, 4.0, 6.0, 8.0];
+    /// Maximum bisection steps when solving for a target fill factor
+    pub const TARGET_BISECTION_ITERATIONS: usize = 40;
+    /// Fraction of the target tolerance at which bisection stops early
+    pub const TARGET_PRECISION_FRACTION: f64 = 0.1;
+    /// Distance below which a neighbor position is treated as the channel itself
+    pub const SELF_NEIGHBOR_TOLERANCE: f64 = 0.1;
+
+    /// Rectangular duct resistance correction (Bruus approximation)
+    pub const RECTANGULAR_DUCT_CORRECTION: f64 = 0.63;
 }
 
 /// Calculate the total path length of a serpentine channel
@@ -138,6 +158,51 @@ pub fn calculate_min_neighbor_distance(
         .fold(f64::INFINITY, f64::min)
 }
 
+/// Calculate the minimum spacing between distant parts of the same path
+///
+/// Adjacent half-waves of a tight serpentine fold back toward each other;
+/// this measures the gap between their channel edges. Only approaches closer
+/// than `channel_width + wall_clearance` are searched for (see
+/// [`path_self_approach`]).
+///
+/// # Arguments
+/// * `path` - Vector of points defining the channel path
+/// * `channel_width` - Width of the channel
+/// * `wall_clearance` - Required spacing between channel edges
+///
+/// # Returns
+/// Minimum edge-to-edge spacing of the path to itself, or infinity if no
+/// distant parts come within the required spacing
+#[must_use]
+pub fn calculate_min_self_spacing(path: &[Point2D], channel_width: f64, wall_clearance: f64) -> f64 {
+    path_self_approach(path, channel_width + wall_clearance)
+        .map_or(f64::INFINITY, |approach| approach.centerline_distance - channel_width)
+}
+
+/// Calculate the hydraulic resistance of a rectangular channel
+///
+/// Uses the standard approximation `R = 12·μ·L / (w·h³·(1 − 0.63·h/w))`,
+/// where `h` is the smaller of the two cross-section dimensions.
+///
+/// # Arguments
+/// * `length` - Channel length (mm)
+/// * `width` - Channel width (mm)
+/// * `height` - Channel height (mm)
+/// * `viscosity` - Dynamic viscosity of the fluid (Pa·s)
+///
+/// # Returns
+/// Hydraulic resistance in Pa·s/m³
+#[must_use]
+pub fn calculate_hydraulic_resistance(length: f64, width: f64, height: f64, viscosity: f64) -> f64 {
+    use crate::config::constants::MM_TO_M;
+
+    let (wide, narrow) = if width >= height { (width, height) } else { (height, width) };
+    let (length_m, wide_m, narrow_m) = (length * MM_TO_M, wide * MM_TO_M, narrow * MM_TO_M);
+    let correction = (-constants::RECTANGULAR_DUCT_CORRECTION).mul_add(narrow_m / wide_m, 1.0);
+
+    12.0 * viscosity * length_m / (wide_m * narrow_m.powi(3) * correction)
+}
+
 /// Optimization parameters for serpentine channel generation
 ///
 /// These parameters control the shape and density of serpentine channels
@@ -166,6 +231,8 @@ pub struct OptimizationResult {
     pub min_wall_distance: f64,
     /// Minimum distance to any neighboring channel
     pub min_neighbor_distance: f64,
+    /// Minimum spacing between distant parts of the path itself
+    pub min_self_spacing: f64,
     /// Whether the optimization result meets all constraints
     pub is_valid: bool,
     /// Number of optimization iterations performed
@@ -197,6 +264,10 @@ pub struct OptimizationStats {
 // type ParameterCache = HashMap<String, (f64, f64, f64)>; // key -> (length, wall_dist, neighbor_dist)
 /// Optimize serpentine parameters to maximize channel length using advanced algorithms
 ///
+/// The algorithm is selected by the configuration's optimization profile; see
+/// [`optimizer_for_profile`]. Use [`optimize_serpentine_parameters_with`] to
+/// run a custom [`Optimizer`].
+///
 /// # Arguments
 /// * `p1` - Start point of the channel
 /// * `p2` - End point of the channel
@@ -216,295 +287,111 @@ pub fn optimize_serpentine_parameters(
     box_dims: (f64, f64),
     neighbor_info: Option<&[f64]>,
 ) -> OptimizationResult {
-    let start_time = std::time::Instant::now();
-
-    match serpentine_config.optimization_profile {
-        OptimizationProfile::Fast => optimize_fast(
-            p1, p2, geometry_config, serpentine_config, box_dims, neighbor_info, start_time
-        ),
-        OptimizationProfile::Balanced => optimize_nelder_mead(
-            p1, p2, geometry_config, serpentine_config, box_dims, neighbor_info, start_time
-        ),
-        OptimizationProfile::Thorough => optimize_thorough(
-            p1, p2, geometry_config, serpentine_config, box_dims, neighbor_info, start_time
-        ),
-    }
+    let optimizer = optimizer_for_profile(serpentine_config.optimization_profile);
+    let context = ChannelGenerationContext::new(geometry_config, box_dims, 0, neighbor_info);
+
+    optimize_serpentine_parameters_with(
+        optimizer.as_ref(),
+        p1,
+        p2,
+        serpentine_config,
+        &context,
+        &OptimizationBudget::default(),
+    )
 }
 
-/// Fast optimization using limited grid search
-fn optimize_fast(
-    p1: Point2D,
-    p2: Point2D,
-    geometry_config: &GeometryConfig,
-    serpentine_config: &SerpentineConfig,
-    box_dims: (f64, f64),
-    neighbor_info: Option<&[f64]>,
-    start_time: std::time::Instant,
-) -> OptimizationResult {
-    // Fast optimization with limited parameter exploration
-    let min_clearance = geometry_config.wall_clearance;
-    let channel_width = geometry_config.channel_width;
-
-    // Get configurable parameter search ranges from constants registry
-    let constants = ConstantsRegistry::new();
-    let wavelength_factors = constants.get_fast_wavelength_factors();
-    let wave_density_factors = constants.get_fast_wave_density_factors();
-    let fill_factors = constants.get_fast_fill_factors();
-    
-    let mut best_result = OptimizationResult {
-        params: OptimizationParams {
-            wavelength_factor: serpentine_config.wavelength_factor,
-            wave_density_factor: serpentine_config.wave_density_factor,
-            fill_factor: serpentine_config.fill_factor,
-        },
-        path_length: 0.0,
-        min_wall_distance: 0.0,
-        min_neighbor_distance: 0.0,
-        is_valid: false,
-        iterations: 0,
-        optimization_time: std::time::Duration::from_secs(0),
+/// Built-in optimizer used for an optimization profile
+///
+/// # Arguments
+/// * `profile` - Optimization profile
+///
+/// # Returns
+/// Grid search for `Fast`, Nelder-Mead for `Balanced`, multi-start Nelder-Mead
+/// for `Thorough`, and the matching global optimizer for the stochastic profiles
+#[must_use]
+pub fn optimizer_for_profile(profile: OptimizationProfile) -> Box<dyn Optimizer> {
+    let nelder_mead = NelderMeadOptimizer {
+        perturbations: vec![
+            constants::WAVELENGTH_PERTURBATION,
+            constants::WAVE_DENSITY_PERTURBATION,
+            constants::FILL_FACTOR_PERTURBATION,
+        ],
+        ..NelderMeadOptimizer::default()
     };
 
-    let mut iterations = 0;
-    
-    // Grid search over parameter combinations
-    for &wavelength_factor in wavelength_factors {
-        for &wave_density_factor in wave_density_factors {
-            for &fill_factor in fill_factors {
-                iterations += 1;
-
-                // Create test configuration without cloning the entire config
-                let test_config = SerpentineConfig {
-                    wavelength_factor,
-                    wave_density_factor,
-                    fill_factor,
-                    gaussian_width_factor: serpentine_config.gaussian_width_factor,
-                    wave_phase_direction: serpentine_config.wave_phase_direction,
-                    wave_shape: serpentine_config.wave_shape,
-                    optimization_enabled: false, // Disable nested optimization
-                    target_fill_ratio: serpentine_config.target_fill_ratio,
-                    optimization_profile: serpentine_config.optimization_profile,
-                    adaptive_config: serpentine_config.adaptive_config,
-                };
-
-                // Generate test path using simplified serpentine generation logic
-                let test_path = generate_simplified_serpentine_path(
-                    p1, p2, geometry_config, &test_config, box_dims, neighbor_info
-                );
-
-                // Calculate metrics
-                let path_length = calculate_path_length(&test_path);
-                let min_wall_distance = calculate_min_wall_distance(&test_path, box_dims, channel_width);
-                let min_neighbor_distance = neighbor_info.map_or(f64::INFINITY, |neighbors| calculate_min_neighbor_distance(&test_path, neighbors, channel_width));
-
-                // Use penalty-based constraint handling for better optimization
-                let penalty = calculate_constraint_penalty(min_wall_distance, min_neighbor_distance, min_clearance);
-                let objective_score = path_length - penalty;
-
-                // Update best result if this is better
-                if objective_score > best_result.path_length {
-                    let is_valid = penalty < constants::PENALTY_TOLERANCE;
-                    best_result = OptimizationResult {
-                        params: OptimizationParams {
-                            wavelength_factor,
-                            wave_density_factor,
-                            fill_factor,
-                        },
-                        path_length: objective_score,
-                        min_wall_distance,
-                        min_neighbor_distance,
-                        is_valid,
-                        iterations,
-                        optimization_time: start_time.elapsed(),
-                    };
-                }
-            }
+    match profile {
+        OptimizationProfile::Fast => {
+            let registry = ConstantsRegistry::new();
+            Box::new(GridSearchOptimizer::new(vec![
+                registry.get_fast_wavelength_factors().clone(),
+                registry.get_fast_wave_density_factors().clone(),
+                registry.get_fast_fill_factors().clone(),
+            ]))
         }
+        OptimizationProfile::Balanced => Box::new(nelder_mead),
+        OptimizationProfile::Thorough => Box::new(MultiStartNelderMeadOptimizer {
+            local: nelder_mead,
+            starts: constants::THOROUGH_STARTING_POINTS.iter().map(|start| start.to_vec()).collect(),
+        }),
+        OptimizationProfile::SimulatedAnnealing => Box::new(SimulatedAnnealingOptimizer::default()),
+        OptimizationProfile::ParticleSwarm => Box::new(ParticleSwarmOptimizer::default()),
     }
-    
-    // If no improvement found, return original parameters
-    if best_result.path_length <= constants::MIN_PATH_LENGTH_THRESHOLD {
-        let original_path = generate_simplified_serpentine_path(
-            p1, p2, geometry_config, serpentine_config, box_dims, neighbor_info
-        );
-
-        best_result = OptimizationResult {
-            params: OptimizationParams {
-                wavelength_factor: serpentine_config.wavelength_factor,
-                wave_density_factor: serpentine_config.wave_density_factor,
-                fill_factor: serpentine_config.fill_factor,
-            },
-            path_length: calculate_path_length(&original_path),
-            min_wall_distance: calculate_min_wall_distance(&original_path, box_dims, channel_width),
-            min_neighbor_distance: neighbor_info.map_or(f64::INFINITY, |neighbors| calculate_min_neighbor_distance(&original_path, neighbors, channel_width)),
-            is_valid: true, // Assume original parameters are valid
-            iterations,
-            optimization_time: start_time.elapsed(),
-        };
-    }
-
-    best_result
 }
 
-/// Calculate penalty for constraint violations
+/// Bounds of the serpentine optimization parameters
+///
+/// Parameters are ordered as wavelength factor, wave density factor and fill factor.
 #[must_use]
-fn calculate_constraint_penalty(wall_distance: f64, neighbor_distance: f64, min_clearance: f64) -> f64 {
-    let mut penalty = 0.0;
-
-    // Heavy penalty for wall clearance violations
-    if wall_distance < min_clearance {
-        penalty += (min_clearance - wall_distance) * constants::CONSTRAINT_PENALTY_MULTIPLIER;
-    }
-
-    // Heavy penalty for neighbor clearance violations
-    if neighbor_distance < min_clearance {
-        penalty += (min_clearance - neighbor_distance) * constants::CONSTRAINT_PENALTY_MULTIPLIER;
+pub fn serpentine_parameter_bounds() -> ParameterBounds {
+    ParameterBounds {
+        lower: vec![constants::MIN_WAVELENGTH_FACTOR, constants::MIN_WAVE_DENSITY_FACTOR, constants::MIN_FILL_FACTOR],
+        upper: vec![constants::MAX_WAVELENGTH_FACTOR, constants::MAX_WAVE_DENSITY_FACTOR, constants::MAX_FILL_FACTOR],
     }
-
-    penalty
 }
 
-/// Balanced optimization using Nelder-Mead simplex algorithm
-fn optimize_nelder_mead(
+/// Optimize serpentine parameters with a specific optimizer
+///
+/// The objective is the configuration's custom [`ObjectiveSpec`](crate::geometry::objectives::ObjectiveSpec)
+/// when one is set, otherwise the channel length minus a penalty for wall and
+/// neighbor clearance violations, and for self-spacing violations when
+/// `avoid_self_intersections` is set. Parameters are searched within
+/// [`serpentine_parameter_bounds`], starting from the configured values.
+///
+/// # Arguments
+/// * `optimizer` - Optimization algorithm to use
+/// * `p1` - Start point of the channel
+/// * `p2` - End point of the channel
+/// * `serpentine_config` - Serpentine configuration providing the starting parameters
+/// * `context` - Channel generation context (geometry, box, neighbors)
+/// * `budget` - Evaluation and iteration limits
+///
+/// # Returns
+/// Optimized parameters and the metrics of the resulting path
+#[must_use]
+pub fn optimize_serpentine_parameters_with(
+    optimizer: &dyn Optimizer,
     p1: Point2D,
     p2: Point2D,
-    geometry_config: &GeometryConfig,
     serpentine_config: &SerpentineConfig,
-    box_dims: (f64, f64),
-    neighbor_info: Option<&[f64]>,
-    start_time: std::time::Instant,
+    context: &ChannelGenerationContext,
+    budget: &OptimizationBudget,
 ) -> OptimizationResult {
-    // Start with current parameters as initial guess
-    let initial_params = [
-        serpentine_config.wavelength_factor,
-        serpentine_config.wave_density_factor,
-        serpentine_config.fill_factor,
-    ];
-
-    // Create initial simplex (triangle in 3D parameter space)
-    let mut simplex = [
-        initial_params,
-        [initial_params[0] * constants::WAVELENGTH_PERTURBATION, initial_params[1], initial_params[2]],
-        [initial_params[0], initial_params[1] * constants::WAVE_DENSITY_PERTURBATION, initial_params[2]],
-        [initial_params[0], initial_params[1], initial_params[2] * constants::FILL_FACTOR_PERTURBATION],
-    ];
-
-    // Evaluate initial simplex
-    let mut scores: Vec<f64> = simplex.iter()
-        .map(|params| evaluate_objective_function(
-            *params, p1, p2, geometry_config, serpentine_config, box_dims, neighbor_info
-        ))
-        .collect();
-
-    let constants = crate::config_constants::ConstantsRegistry::new();
-    let max_iterations = constants.get_max_optimization_iterations();
-    let tolerance = constants.get_optimization_tolerance();
-    let mut iterations = 0;
-
-    // Nelder-Mead algorithm parameters
-    let alpha = constants::REFLECTION_COEFFICIENT;
-    let gamma = constants::EXPANSION_COEFFICIENT;
-    let rho = constants::CONTRACTION_COEFFICIENT;
-    let sigma = constants::SHRINK_COEFFICIENT;
-
-    for _ in 0..max_iterations {
-        iterations += 1;
-
-        // Sort simplex by scores (best to worst)
-        let mut indices: Vec<usize> = (0..simplex.len()).collect();
-        indices.sort_by(|&a, &b| {
-            scores[b].partial_cmp(&scores[a])
-                .unwrap_or(std::cmp::Ordering::Equal) // Handle NaN values gracefully
-        });
-
-        let best_idx = indices[0];
-        let worst_idx = indices[indices.len() - 1];
-        let second_worst_idx = indices[indices.len() - 2];
-
-        // Check for convergence
-        let score_range = scores[best_idx] - scores[worst_idx];
-        if score_range < tolerance {
-            break;
-        }
-
-        // Calculate centroid of all points except worst
-        let mut centroid = [0.0; 3];
-        for &idx in &indices[..indices.len()-1] {
-            for i in 0..3 {
-                centroid[i] += simplex[idx][i];
-            }
-        }
-        for i in 0..3 {
-            centroid[i] /= (simplex.len() - 1) as f64;
-        }
-
-        // Reflection
-        let mut reflected = [0.0; 3];
-        for i in 0..3 {
-            reflected[i] = centroid[i] + alpha * (centroid[i] - simplex[worst_idx][i]);
-        }
-        let reflected_score = evaluate_objective_function(
-            reflected, p1, p2, geometry_config, serpentine_config, box_dims, neighbor_info
-        );
-
-        if reflected_score > scores[second_worst_idx] && reflected_score <= scores[best_idx] {
-            // Accept reflection
-            simplex[worst_idx] = reflected;
-            scores[worst_idx] = reflected_score;
-        } else if reflected_score > scores[best_idx] {
-            // Try expansion
-            let mut expanded = [0.0; 3];
-            for i in 0..3 {
-                expanded[i] = centroid[i] + gamma * (reflected[i] - centroid[i]);
-            }
-            let expanded_score = evaluate_objective_function(
-                expanded, p1, p2, geometry_config, serpentine_config, box_dims, neighbor_info
-            );
-
-            if expanded_score > reflected_score {
-                simplex[worst_idx] = expanded;
-                scores[worst_idx] = expanded_score;
-            } else {
-                simplex[worst_idx] = reflected;
-                scores[worst_idx] = reflected_score;
-            }
-        } else {
-            // Try contraction
-            let mut contracted = [0.0; 3];
-            for i in 0..3 {
-                contracted[i] = centroid[i] + rho * (simplex[worst_idx][i] - centroid[i]);
-            }
-            let contracted_score = evaluate_objective_function(
-                contracted, p1, p2, geometry_config, serpentine_config, box_dims, neighbor_info
-            );
-
-            if contracted_score > scores[worst_idx] {
-                simplex[worst_idx] = contracted;
-                scores[worst_idx] = contracted_score;
-            } else {
-                // Shrink simplex
-                for i in 1..simplex.len() {
-                    for j in 0..3 {
-                        simplex[i][j] = simplex[best_idx][j] + sigma * (simplex[i][j] - simplex[best_idx][j]);
-                    }
-                    scores[i] = evaluate_objective_function(
-                        simplex[i], p1, p2, geometry_config, serpentine_config, box_dims, neighbor_info
-                    );
-                }
-            }
-        }
+    if let Some(spec) = serpentine_config.objective {
+        let objective = SerpentineObjective::from(spec);
+        return optimize_serpentine_with_objective(optimizer, p1, p2, serpentine_config, context, &objective, budget);
     }
 
-    // Find best result
-    let best_idx = scores.iter()
-        .enumerate()
-        .max_by(|(_, a), (_, b)| {
-            a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal) // Handle NaN values gracefully
-        })
-        .map(|(idx, _)| idx)
-        .unwrap_or(0); // Default to first element if no maximum found
+    let start_time = std::time::Instant::now();
+    let geometry_config = context.geometry_config;
+    let box_dims = context.box_dims;
+    let neighbor_info = context.neighbor_info;
+    let score = |params: [f64; 3]| evaluate_objective_function(
+        params, p1, p2, geometry_config, serpentine_config, box_dims, neighbor_info
+    );
 
-    let best_params = simplex[best_idx];
+    let (best_params, iterations) = run_serpentine_optimization(
+        optimizer, serpentine_config, budget, &score, constants::MIN_PATH_LENGTH_THRESHOLD
+    );
     let best_config = SerpentineConfig {
         wavelength_factor: best_params[0],
         wave_density_factor: best_params[1],
@@ -519,11 +406,12 @@ fn optimize_nelder_mead(
 
     let path_length = calculate_path_length(&final_path);
     let min_wall_distance = calculate_min_wall_distance(&final_path, box_dims, geometry_config.channel_width);
-    let min_neighbor_distance = if let Some(neighbors) = neighbor_info {
+    let min_neighbor_distance = neighbor_info.map_or(f64::INFINITY, |neighbors| {
         calculate_min_neighbor_distance(&final_path, neighbors, geometry_config.channel_width)
-    } else {
-        f64::INFINITY
-    };
+    });
+    let min_self_spacing = calculate_min_self_spacing(
+        &final_path, geometry_config.channel_width, geometry_config.wall_clearance
+    );
 
     OptimizationResult {
         params: OptimizationParams {
@@ -534,12 +422,189 @@ fn optimize_nelder_mead(
         path_length,
         min_wall_distance,
         min_neighbor_distance,
-        is_valid: min_wall_distance >= geometry_config.wall_clearance && min_neighbor_distance >= geometry_config.wall_clearance,
+        min_self_spacing,
+        is_valid: min_wall_distance >= geometry_config.wall_clearance
+            && min_neighbor_distance >= geometry_config.wall_clearance
+            && (!serpentine_config.avoid_self_intersections || min_self_spacing >= geometry_config.wall_clearance),
+        iterations,
+        optimization_time: start_time.elapsed(),
+    }
+}
+
+/// Optimize serpentine parameters against a custom objective
+///
+/// Each candidate is generated with the real serpentine strategy, measured
+/// into [`PathMetrics`] (with the channel's own midline removed from the
+/// neighbor list and the ports excluded from the wall distance) and scored by
+/// `objective`, which is maximized.
+///
+/// # Arguments
+/// * `optimizer` - Optimization algorithm to use
+/// * `p1` - Start point of the channel
+/// * `p2` - End point of the channel
+/// * `serpentine_config` - Serpentine configuration providing the starting parameters
+/// * `context` - Channel generation context (geometry, box, neighbors)
+/// * `objective` - Objective to maximize
+/// * `budget` - Evaluation and iteration limits
+///
+/// # Returns
+/// Optimized parameters and the metrics of the resulting path
+#[must_use]
+pub fn optimize_serpentine_with_objective(
+    optimizer: &dyn Optimizer,
+    p1: Point2D,
+    p2: Point2D,
+    serpentine_config: &SerpentineConfig,
+    context: &ChannelGenerationContext,
+    objective: &SerpentineObjective,
+    budget: &OptimizationBudget,
+) -> OptimizationResult {
+    let start_time = std::time::Instant::now();
+    let score = |params: [f64; 3]| {
+        let (metrics, path) = measure_serpentine_candidate(params, p1, p2, serpentine_config, context);
+        objective.evaluate(&metrics, &path)
+    };
+
+    let (best_params, iterations) = run_serpentine_optimization(
+        optimizer, serpentine_config, budget, &score, f64::NEG_INFINITY
+    );
+    let (metrics, _) = measure_serpentine_candidate(best_params, p1, p2, serpentine_config, context);
+
+    OptimizationResult {
+        params: OptimizationParams {
+            wavelength_factor: best_params[0],
+            wave_density_factor: best_params[1],
+            fill_factor: best_params[2],
+        },
+        path_length: metrics.path_length,
+        min_wall_distance: metrics.min_wall_distance,
+        min_neighbor_distance: metrics.min_neighbor_distance,
+        min_self_spacing: metrics.min_self_spacing,
+        is_valid: metrics.clearance_violation <= 0.0,
         iterations,
         optimization_time: start_time.elapsed(),
     }
 }
 
+/// Generate and measure the serpentine path for a parameter set
+///
+/// The path is produced by the serpentine strategy itself, so the metrics
+/// match the geometry that generation would output for these parameters.
+/// When the configuration sets `avoid_self_intersections`, the self-spacing
+/// shortfall is counted in the clearance violation.
+///
+/// # Arguments
+/// * `params` - Wavelength factor, wave density factor and fill factor
+/// * `p1` - Start point of the channel
+/// * `p2` - End point of the channel
+/// * `serpentine_config` - Serpentine configuration providing the remaining parameters
+/// * `context` - Channel generation context (geometry, box, neighbors)
+///
+/// # Returns
+/// The path metrics and the generated centerline
+#[must_use]
+pub fn measure_serpentine_candidate(
+    params: [f64; 3],
+    p1: Point2D,
+    p2: Point2D,
+    serpentine_config: &SerpentineConfig,
+    context: &ChannelGenerationContext,
+) -> (PathMetrics, Vec<Point2D>) {
+    let bounded = serpentine_parameter_bounds().clamp(&params);
+    let test_config = SerpentineConfig {
+        wavelength_factor: bounded[0],
+        wave_density_factor: bounded[1],
+        fill_factor: bounded[2],
+        optimization_enabled: false, // Disable nested optimization
+        optimization_target: None,
+        objective: None,
+        ..*serpentine_config
+    };
+    let path = SerpentineChannelStrategy::new(test_config).generate_serpentine_path_for_optimization(
+        p1,
+        p2,
+        context.geometry_config,
+        context.box_dims,
+        context.total_branches,
+        context.neighbor_info,
+    );
+
+    let neighbors = neighbors_excluding_self(p1, p2, context.neighbor_info);
+    let mut metrics = PathMetrics::from_path(&path, context.geometry_config, context.box_dims, Some(&neighbors));
+    metrics.min_wall_distance = calculate_min_wall_distance_excluding_ports(
+        &path,
+        context.box_dims,
+        context.geometry_config.channel_width,
+        context.geometry_config.wall_clearance,
+    );
+    metrics.clearance_violation = clearance_shortfall(
+        metrics.min_wall_distance,
+        metrics.min_neighbor_distance,
+        context.geometry_config.wall_clearance,
+    );
+    if serpentine_config.avoid_self_intersections {
+        metrics.clearance_violation += metrics.self_spacing_violation;
+    }
+
+    (metrics, path)
+}
+
+/// Run an optimizer over the serpentine parameters
+///
+/// Results scoring at or below `keep_initial_below` fall back to the configured
+/// parameters. Returns the chosen parameters and the optimizer's iteration count.
+fn run_serpentine_optimization(
+    optimizer: &dyn Optimizer,
+    serpentine_config: &SerpentineConfig,
+    budget: &OptimizationBudget,
+    score: &dyn Fn([f64; 3]) -> f64,
+    keep_initial_below: f64,
+) -> ([f64; 3], usize) {
+    let initial = [
+        serpentine_config.wavelength_factor,
+        serpentine_config.wave_density_factor,
+        serpentine_config.fill_factor,
+    ];
+    let objective = |params: &[f64]| score([params[0], params[1], params[2]]);
+    let outcome = optimizer.optimize(&objective, &serpentine_parameter_bounds(), &initial, budget);
+
+    // If no improvement found, keep the original parameters
+    let best_params = if outcome.best_score > keep_initial_below {
+        [outcome.best_params[0], outcome.best_params[1], outcome.best_params[2]]
+    } else {
+        initial
+    };
+    (best_params, outcome.iterations)
+}
+
+/// Total shortfall of wall and neighbor distances below the required clearance
+fn clearance_shortfall(wall_distance: f64, neighbor_distance: f64, min_clearance: f64) -> f64 {
+    (min_clearance - wall_distance).max(0.0) + (min_clearance - neighbor_distance).max(0.0)
+}
+
+/// Calculate penalty for constraint violations
+#[must_use]
+fn calculate_constraint_penalty(wall_distance: f64, neighbor_distance: f64, self_spacing: f64, min_clearance: f64) -> f64 {
+    let mut penalty = 0.0;
+
+    // Heavy penalty for wall clearance violations
+    if wall_distance < min_clearance {
+        penalty += (min_clearance - wall_distance) * constants::CONSTRAINT_PENALTY_MULTIPLIER;
+    }
+
+    // Heavy penalty for neighbor clearance violations
+    if neighbor_distance < min_clearance {
+        penalty += (min_clearance - neighbor_distance) * constants::CONSTRAINT_PENALTY_MULTIPLIER;
+    }
+
+    // Heavy penalty for the path folding back onto itself
+    if self_spacing < min_clearance {
+        penalty += (min_clearance - self_spacing) * constants::CONSTRAINT_PENALTY_MULTIPLIER;
+    }
+
+    penalty
+}
+
 /// Evaluate objective function for optimization (length - penalties)
 #[must_use]
 fn evaluate_objective_function(
@@ -577,10 +642,17 @@ fn evaluate_objective_function(
         f64::INFINITY
     };
 
+    let min_self_spacing = if serpentine_config.avoid_self_intersections {
+        calculate_min_self_spacing(&test_path, geometry_config.channel_width, geometry_config.wall_clearance)
+    } else {
+        f64::INFINITY
+    };
+
     // Calculate penalty
     let penalty = calculate_constraint_penalty(
         min_wall_distance,
         min_neighbor_distance,
+        min_self_spacing,
         geometry_config.wall_clearance
     );
 
@@ -588,73 +660,6 @@ fn evaluate_objective_function(
     path_length - penalty
 }
 
-/// Thorough optimization using multi-start Nelder-Mead with extensive exploration
-fn optimize_thorough(
-    p1: Point2D,
-    p2: Point2D,
-    geometry_config: &GeometryConfig,
-    serpentine_config: &SerpentineConfig,
-    box_dims: (f64, f64),
-    neighbor_info: Option<&[f64]>,
-    start_time: std::time::Instant,
-) -> OptimizationResult {
-    let mut best_result = OptimizationResult {
-        params: OptimizationParams {
-            wavelength_factor: serpentine_config.wavelength_factor,
-            wave_density_factor: serpentine_config.wave_density_factor,
-            fill_factor: serpentine_config.fill_factor,
-        },
-        path_length: constants::MIN_PATH_LENGTH_THRESHOLD,
-        min_wall_distance: constants::MIN_PATH_LENGTH_THRESHOLD,
-        min_neighbor_distance: constants::MIN_PATH_LENGTH_THRESHOLD,
-        is_valid: false,
-        iterations: 0,
-        optimization_time: std::time::Duration::from_secs(0),
-    };
-
-    // Multiple starting points for thorough exploration
-    let starting_points = vec![
-        [serpentine_config.wavelength_factor, serpentine_config.wave_density_factor, serpentine_config.fill_factor],
-        [1.0, 1.0, 0.7],
-        [2.0, 2.0, 0.8],
-        [3.0, 3.0, 0.9],
-        [4.0, 1.5, 0.85],
-        [1.5, 4.0, 0.75],
-    ];
-
-    let mut total_iterations = 0;
-
-    for start_point in starting_points {
-        // Create temporary config for this starting point
-        let temp_config = SerpentineConfig {
-            wavelength_factor: start_point[0],
-            wave_density_factor: start_point[1],
-            fill_factor: start_point[2],
-            ..*serpentine_config
-        };
-
-        // Run Nelder-Mead from this starting point
-        let result = optimize_nelder_mead(
-            p1, p2, geometry_config, &temp_config, box_dims, neighbor_info, start_time
-        );
-
-        total_iterations += result.iterations;
-
-        // Keep the best result
-        if result.path_length > best_result.path_length {
-            best_result = result;
-        }
-    }
-
-    // Update total iterations
-    best_result.iterations = total_iterations;
-    best_result.optimization_time = start_time.elapsed();
-
-    best_result
-}
-
-
-
 /// Optimized serpentine path generation with aggressive amplitude calculation
 #[must_use]
 fn generate_simplified_serpentine_path(
@@ -1020,3 +1025,352 @@ fn calculate_improved_envelope_for_optimization(
         dome_envelope
     }
 }
+
+/// Whether a targeted optimization could reach its target
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum TargetFeasibility {
+    /// The target was met within its tolerance
+    Reached,
+    /// The target is below the smallest value achievable between the endpoints
+    BelowMinimum {
+        /// Smallest achievable value, in the target's unit
+        minimum: f64,
+    },
+    /// The target exceeds the largest value achievable within the clearance constraints
+    AboveMaximum {
+        /// Largest achievable value, in the target's unit
+        maximum: f64,
+    },
+    /// The target lies within the achievable range but no parameter set hit it within tolerance
+    NotConverged,
+}
+
+/// Result of a targeted serpentine optimization
+///
+/// Reports the parameters that came closest to the target together with the
+/// achieved value and whether the target could be reached at all.
+#[derive(Debug, Clone)]
+pub struct TargetedOptimizationResult {
+    /// The parameters that came closest to the target
+    pub params: OptimizationParams,
+    /// The target that was optimized for
+    pub target: OptimizationTarget,
+    /// Value achieved by the best parameters, in the target's unit
+    pub achieved_value: f64,
+    /// Relative deviation of the achieved value from the target
+    pub relative_error: f64,
+    /// Total length of the resulting serpentine path
+    pub path_length: f64,
+    /// Minimum distance to any wall boundary
+    pub min_wall_distance: f64,
+    /// Minimum distance to any neighboring channel
+    pub min_neighbor_distance: f64,
+    /// Minimum spacing between distant parts of the path itself
+    pub min_self_spacing: f64,
+    /// Whether the target could be reached within the clearance constraints
+    pub feasibility: TargetFeasibility,
+    /// Number of path evaluations performed
+    pub iterations: usize,
+    /// Total time spent on optimization
+    pub optimization_time: std::time::Duration,
+}
+
+impl TargetedOptimizationResult {
+    /// Whether the target was met within its tolerance
+    #[must_use]
+    pub fn is_reached(&self) -> bool {
+        self.feasibility == TargetFeasibility::Reached
+    }
+}
+
+/// Single path evaluation performed during a targeted search
+#[derive(Debug, Clone)]
+struct TargetEvaluation {
+    params: OptimizationParams,
+    path_length: f64,
+    min_wall_distance: f64,
+    min_neighbor_distance: f64,
+    min_self_spacing: f64,
+    is_valid: bool,
+}
+
+/// Minimum wall distance of a path, ignoring the walls its own ports sit on
+///
+/// Inlet and outlet nodes lie on the chip boundary, so the walls they touch
+/// cannot be used as clearance constraints for the channel itself.
+pub(crate) fn calculate_min_wall_distance_excluding_ports(
+    path: &[Point2D],
+    box_dims: (f64, f64),
+    channel_width: f64,
+    wall_clearance: f64,
+) -> f64 {
+    calculate_min_wall_distance_within(path, ((0.0, 0.0), box_dims), channel_width, wall_clearance)
+}
+
+/// Minimum wall distance of a path inside `bounds`, ignoring the walls its own ports sit on
+///
+/// Like [`calculate_min_wall_distance_excluding_ports`], for boxes that do
+/// not start at the origin, such as translated or imported systems.
+pub(crate) fn calculate_min_wall_distance_within(
+    path: &[Point2D],
+    bounds: (Point2D, Point2D),
+    channel_width: f64,
+    wall_clearance: f64,
+) -> f64 {
+    let ((min_x, min_y), (max_x, max_y)) = bounds;
+    let half_channel_width = channel_width / 2.0;
+    let wall_distances = |&(x, y): &Point2D| {
+        [
+            x - min_x - half_channel_width,
+            max_x - x - half_channel_width,
+            y - min_y - half_channel_width,
+            max_y - y - half_channel_width,
+        ]
+    };
+
+    let (Some(first), Some(last)) = (path.first(), path.last()) else {
+        return f64::INFINITY;
+    };
+    let (first_walls, last_walls) = (wall_distances(first), wall_distances(last));
+    let port_walls: Vec<bool> = first_walls.iter()
+        .zip(last_walls)
+        .map(|(&a, b)| a.min(b) < wall_clearance)
+        .collect();
+
+    path.iter()
+        .flat_map(|point| {
+            wall_distances(point)
+                .into_iter()
+                .zip(port_walls.iter())
+                .filter(|&(_, &is_port_wall)| !is_port_wall)
+                .map(|(distance, _)| distance)
+        })
+        .fold(f64::INFINITY, f64::min)
+}
+
+/// Neighbor positions without the channel's own midline
+///
+/// The generator lists every channel midline, including the channel being
+/// generated, so it has to be removed before measuring neighbor clearance.
+pub(crate) fn neighbors_excluding_self(p1: Point2D, p2: Point2D, neighbor_info: Option<&[f64]>) -> Vec<f64> {
+    let channel_center_y = f64::midpoint(p1.1, p2.1);
+    neighbor_info.map_or_else(Vec::new, |positions| {
+        positions.iter()
+            .copied()
+            .filter(|&y| (y - channel_center_y).abs() > constants::SELF_NEIGHBOR_TOLERANCE)
+            .collect()
+    })
+}
+
+/// Shared state for a targeted search over serpentine parameters
+struct TargetSearch<'a> {
+    p1: Point2D,
+    p2: Point2D,
+    serpentine_config: &'a SerpentineConfig,
+    context: &'a ChannelGenerationContext<'a>,
+    neighbors: Vec<f64>,
+    evaluations: usize,
+}
+
+impl TargetSearch<'_> {
+    /// Generate the real serpentine path for the given parameters and measure it
+    fn evaluate(&mut self, wavelength_factor: f64, wave_density_factor: f64, fill_factor: f64) -> TargetEvaluation {
+        self.evaluations += 1;
+
+        let test_config = SerpentineConfig {
+            wavelength_factor,
+            wave_density_factor,
+            fill_factor,
+            optimization_enabled: false, // Disable nested optimization
+            optimization_target: None,
+            ..*self.serpentine_config
+        };
+        let path = SerpentineChannelStrategy::new(test_config).generate_serpentine_path_for_optimization(
+            self.p1,
+            self.p2,
+            self.context.geometry_config,
+            self.context.box_dims,
+            self.context.total_branches,
+            self.context.neighbor_info,
+        );
+
+        let geometry_config = self.context.geometry_config;
+        let min_wall_distance = calculate_min_wall_distance_excluding_ports(
+            &path,
+            self.context.box_dims,
+            geometry_config.channel_width,
+            geometry_config.wall_clearance,
+        );
+        let min_neighbor_distance = calculate_min_neighbor_distance(&path, &self.neighbors, geometry_config.channel_width);
+        let min_self_spacing = calculate_min_self_spacing(&path, geometry_config.channel_width, geometry_config.wall_clearance);
+
+        TargetEvaluation {
+            params: OptimizationParams { wavelength_factor, wave_density_factor, fill_factor },
+            path_length: calculate_path_length(&path),
+            min_wall_distance,
+            min_neighbor_distance,
+            min_self_spacing,
+            is_valid: min_wall_distance >= geometry_config.wall_clearance
+                && min_neighbor_distance >= geometry_config.wall_clearance
+                && (!self.serpentine_config.avoid_self_intersections
+                    || min_self_spacing >= geometry_config.wall_clearance),
+        }
+    }
+
+    /// Find the largest fill factor that still satisfies the clearance constraints
+    fn max_valid_fill(&mut self, wavelength_factor: f64, wave_density_factor: f64, min_fill: TargetEvaluation) -> TargetEvaluation {
+        let at_max_fill = self.evaluate(wavelength_factor, wave_density_factor, constants::MAX_FILL_FACTOR);
+        if at_max_fill.is_valid {
+            return at_max_fill;
+        }
+
+        let mut best = min_fill;
+        let mut invalid_fill = constants::MAX_FILL_FACTOR;
+        for _ in 0..constants::TARGET_BISECTION_ITERATIONS / 2 {
+            let fill = f64::midpoint(best.params.fill_factor, invalid_fill);
+            let evaluation = self.evaluate(wavelength_factor, wave_density_factor, fill);
+            if evaluation.is_valid {
+                best = evaluation;
+            } else {
+                invalid_fill = fill;
+            }
+        }
+        best
+    }
+}
+
+/// Tune serpentine parameters so the channel meets a length or hydraulic resistance target
+///
+/// The search generates real serpentine paths, so the reported length matches
+/// the channel produced by the strategy. Wavelength and wave density are
+/// scanned starting from the configured values, and for each combination the
+/// fill factor is solved by bisection within the range allowed by the wall and
+/// neighbor clearance constraints.
+///
+/// # Arguments
+/// * `p1` - Start point of the channel
+/// * `p2` - End point of the channel
+/// * `serpentine_config` - Serpentine configuration providing the starting parameters
+/// * `context` - Channel generation context (geometry, box, neighbors)
+/// * `target` - Length or hydraulic resistance to reach
+///
+/// # Returns
+/// The closest parameters found and whether the target could be reached.
+/// When it cannot, the feasibility reports the achievable bound.
+#[must_use]
+pub fn optimize_serpentine_for_target(
+    p1: Point2D,
+    p2: Point2D,
+    serpentine_config: &SerpentineConfig,
+    context: &ChannelGenerationContext,
+    target: OptimizationTarget,
+) -> TargetedOptimizationResult {
+    use crate::config::constants::{MAX_WAVE_DENSITY_FACTOR, MAX_WAVELENGTH_FACTOR, MIN_WAVE_DENSITY_FACTOR, MIN_WAVELENGTH_FACTOR};
+
+    let start_time = std::time::Instant::now();
+    let geometry_config = context.geometry_config;
+    let target_length = target.required_length(geometry_config);
+    let tolerance = target.tolerance();
+    let relative_error = |length: f64| (length - target_length).abs() / target_length;
+
+    let neighbors = neighbors_excluding_self(p1, p2, context.neighbor_info);
+
+    let mut search = TargetSearch { p1, p2, serpentine_config, context, neighbors, evaluations: 0 };
+
+    // Candidate (wavelength, density) combinations, starting with the configured shape
+    let registry = ConstantsRegistry::new();
+    let mut candidates = vec![(serpentine_config.wavelength_factor, serpentine_config.wave_density_factor)];
+    for &wavelength_factor in registry.get_fast_wavelength_factors() {
+        for wave_density_factor in registry.get_fast_wave_density_factors().iter().copied().chain(constants::TARGET_EXTENDED_WAVE_DENSITY_FACTORS) {
+            let candidate = (
+                wavelength_factor.clamp(MIN_WAVELENGTH_FACTOR, MAX_WAVELENGTH_FACTOR),
+                wave_density_factor.clamp(MIN_WAVE_DENSITY_FACTOR, MAX_WAVE_DENSITY_FACTOR),
+            );
+            if !candidates.contains(&candidate) {
+                candidates.push(candidate);
+            }
+        }
+    }
+
+    let mut best: Option<TargetEvaluation> = None;
+    let mut min_length = f64::INFINITY;
+    let mut max_length = f64::NEG_INFINITY;
+
+    for (wavelength_factor, wave_density_factor) in candidates {
+        let low = search.evaluate(wavelength_factor, wave_density_factor, constants::MIN_FILL_FACTOR);
+        if !low.is_valid {
+            continue;
+        }
+        let high = search.max_valid_fill(wavelength_factor, wave_density_factor, low.clone());
+
+        min_length = min_length.min(low.path_length).min(high.path_length);
+        max_length = max_length.max(low.path_length).max(high.path_length);
+
+        // Bisection on fill factor between the smallest and largest valid fills,
+        // when the lengths at those fills bracket the target
+        let increasing = high.path_length >= low.path_length;
+        let in_range = target_length >= low.path_length.min(high.path_length)
+            && target_length <= low.path_length.max(high.path_length);
+        let (mut lower, mut upper) = (low.params.fill_factor, high.params.fill_factor);
+        let mut closest = if relative_error(low.path_length) <= relative_error(high.path_length) { low } else { high };
+
+        if in_range {
+            for _ in 0..constants::TARGET_BISECTION_ITERATIONS {
+                if relative_error(closest.path_length) <= tolerance * constants::TARGET_PRECISION_FRACTION {
+                    break;
+                }
+                let fill = f64::midpoint(lower, upper);
+                let evaluation = search.evaluate(wavelength_factor, wave_density_factor, fill);
+                if (evaluation.path_length < target_length) == increasing {
+                    lower = fill;
+                } else {
+                    upper = fill;
+                }
+                if evaluation.is_valid && relative_error(evaluation.path_length) < relative_error(closest.path_length) {
+                    closest = evaluation;
+                }
+            }
+        }
+
+        if best.as_ref().is_none_or(|current| relative_error(closest.path_length) < relative_error(current.path_length)) {
+            best = Some(closest);
+        }
+        if best.as_ref().is_some_and(|current| relative_error(current.path_length) <= tolerance) {
+            break;
+        }
+    }
+
+    // Without any valid candidate, fall back to the configured parameters
+    let best = best.unwrap_or_else(|| search.evaluate(
+        serpentine_config.wavelength_factor,
+        serpentine_config.wave_density_factor,
+        serpentine_config.fill_factor,
+    ));
+    let error = relative_error(best.path_length);
+
+    let feasibility = if error <= tolerance {
+        TargetFeasibility::Reached
+    } else if target_length < min_length {
+        TargetFeasibility::BelowMinimum { minimum: target.value_for_length(min_length, geometry_config) }
+    } else if target_length > max_length {
+        TargetFeasibility::AboveMaximum {
+            maximum: target.value_for_length(max_length.max(best.path_length), geometry_config),
+        }
+    } else {
+        TargetFeasibility::NotConverged
+    };
+
+    TargetedOptimizationResult {
+        params: best.params,
+        target,
+        achieved_value: target.value_for_length(best.path_length, geometry_config),
+        relative_error: error,
+        path_length: best.path_length,
+        min_wall_distance: best.min_wall_distance,
+        min_neighbor_distance: best.min_neighbor_distance,
+        min_self_spacing: best.min_self_spacing,
+        feasibility,
+        iterations: search.evaluations,
+        optimization_time: start_time.elapsed(),
+    }
+}
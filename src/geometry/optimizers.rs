@@ -0,0 +1,880 @@
+//! Pluggable optimization algorithms
+//!
+//! This module defines the [`Optimizer`] trait used by serpentine parameter
+//! optimization together with the built-in algorithms. Each optimizer
+//! receives an objective to maximize, the parameter bounds and an evaluation
+//! budget, so new algorithms can be plugged in without touching the
+//! serpentine-specific code.
+//!
+//! # Built-in Optimizers
+//!
+//! - [`GridSearchOptimizer`]: exhaustive search over a fixed grid (`Fast` profile)
+//! - [`NelderMeadOptimizer`]: local simplex search (`Balanced` profile)
+//! - [`MultiStartNelderMeadOptimizer`]: Nelder-Mead from several starts (`Thorough` profile)
+//! - [`SimulatedAnnealingOptimizer`]: global stochastic search with a cooling schedule
+//! - [`ParticleSwarmOptimizer`]: global population-based search
+
+use crate::config_constants::ConstantsRegistry;
+use crate::error::{ConfigurationError, ConfigurationResult};
+use std::cell::Cell;
+
+/// Default constants for the stochastic optimizers
+mod constants {
+    /// Nelder-Mead algorithm coefficients
+    pub const REFLECTION_COEFFICIENT: f64 = 1.0;
+    pub const EXPANSION_COEFFICIENT: f64 = 2.0;
+    pub const CONTRACTION_COEFFICIENT: f64 = 0.5;
+    pub const SHRINK_COEFFICIENT: f64 = 0.5;
+
+    /// Default relative simplex perturbation for Nelder-Mead initialization
+    pub const SIMPLEX_PERTURBATION: f64 = 0.1;
+
+    /// Default evaluation budget for a single optimization run
+    pub const DEFAULT_MAX_EVALUATIONS: usize = 600;
+
+    /// Simulated annealing defaults
+    pub const DEFAULT_INITIAL_TEMPERATURE: f64 = 10.0;
+    pub const DEFAULT_COOLING_RATE: f64 = 0.97;
+    pub const DEFAULT_STEP_FRACTION: f64 = 0.2;
+    pub const MIN_STEP_FRACTION: f64 = 0.01;
+    pub const DEFAULT_MOVES_PER_TEMPERATURE: usize = 10;
+
+    /// Particle swarm defaults
+    pub const DEFAULT_SWARM_SIZE: usize = 12;
+    pub const DEFAULT_INERTIA: f64 = 0.7;
+    pub const DEFAULT_COGNITIVE_WEIGHT: f64 = 1.5;
+    pub const DEFAULT_SOCIAL_WEIGHT: f64 = 1.5;
+    pub const MAX_VELOCITY_FRACTION: f64 = 0.25;
+
+    /// Default seed for reproducible stochastic optimizers
+    pub const DEFAULT_SEED: u64 = 0x5EED_CAFE_F00D_D00D;
+}
+
+/// Inclusive lower and upper bounds for each optimization parameter
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParameterBounds {
+    /// Lower bound for each parameter
+    pub lower: Vec<f64>,
+    /// Upper bound for each parameter
+    pub upper: Vec<f64>,
+}
+
+impl ParameterBounds {
+    /// Create new parameter bounds with validation
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the bound vectors are empty, differ in length,
+    /// contain non-finite values, or if any lower bound exceeds its upper bound.
+    pub fn new(lower: Vec<f64>, upper: Vec<f64>) -> ConfigurationResult<Self> {
+        if lower.is_empty() || lower.len() != upper.len() {
+            return Err(ConfigurationError::ConflictingValues {
+                conflict: format!(
+                    "Parameter bounds need the same non-zero number of lower ({}) and upper ({}) values",
+                    lower.len(),
+                    upper.len()
+                ),
+            });
+        }
+
+        for (index, (&low, &high)) in lower.iter().zip(&upper).enumerate() {
+            if !low.is_finite() || !high.is_finite() || low > high {
+                return Err(ConfigurationError::ConflictingValues {
+                    conflict: format!("Invalid bounds for parameter {index}: [{low}, {high}]"),
+                });
+            }
+        }
+
+        Ok(Self { lower, upper })
+    }
+
+    /// Number of parameters described by these bounds
+    #[must_use]
+    pub const fn dimensions(&self) -> usize {
+        self.lower.len()
+    }
+
+    /// Width of the bounded range for a parameter
+    #[must_use]
+    pub fn span(&self, index: usize) -> f64 {
+        self.upper[index] - self.lower[index]
+    }
+
+    /// Center of the bounded region
+    #[must_use]
+    pub fn center(&self) -> Vec<f64> {
+        self.lower.iter().zip(&self.upper).map(|(&low, &high)| f64::midpoint(low, high)).collect()
+    }
+
+    /// Project a point onto the bounded region
+    #[must_use]
+    pub fn clamp(&self, params: &[f64]) -> Vec<f64> {
+        params.iter()
+            .zip(self.lower.iter().zip(&self.upper))
+            .map(|(&value, (&low, &high))| value.clamp(low, high))
+            .collect()
+    }
+}
+
+/// Evaluation budget for an optimization run
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OptimizationBudget {
+    /// Maximum number of objective evaluations
+    pub max_evaluations: usize,
+    /// Maximum number of algorithm iterations (generations, simplex steps, ...)
+    pub max_iterations: usize,
+    /// Convergence tolerance on the objective value
+    pub tolerance: f64,
+}
+
+impl OptimizationBudget {
+    /// Create a new optimization budget
+    #[must_use]
+    pub const fn new(max_evaluations: usize, max_iterations: usize, tolerance: f64) -> Self {
+        Self { max_evaluations, max_iterations, tolerance }
+    }
+}
+
+impl Default for OptimizationBudget {
+    fn default() -> Self {
+        let registry = ConstantsRegistry::new();
+        Self {
+            max_evaluations: constants::DEFAULT_MAX_EVALUATIONS,
+            max_iterations: registry.get_max_optimization_iterations(),
+            tolerance: registry.get_optimization_tolerance(),
+        }
+    }
+}
+
+/// Outcome of an optimization run
+#[derive(Debug, Clone)]
+pub struct OptimizerOutcome {
+    /// Best parameters found (within bounds)
+    pub best_params: Vec<f64>,
+    /// Objective value of the best parameters
+    pub best_score: f64,
+    /// Number of objective evaluations performed
+    pub evaluations: usize,
+    /// Number of algorithm iterations performed
+    pub iterations: usize,
+}
+
+/// A pluggable optimization algorithm
+///
+/// Optimizers maximize the objective over the bounded parameter space and
+/// must stop once the budget's evaluation or iteration limit is reached.
+///
+/// # Examples
+///
+/// ```rust
+/// use scheme::geometry::optimizers::{
+///     OptimizationBudget, Optimizer, ParameterBounds, ParticleSwarmOptimizer,
+/// };
+///
+/// let bounds = ParameterBounds::new(vec![-5.0, -5.0], vec![5.0, 5.0]).unwrap();
+/// let objective = |x: &[f64]| -((x[0] - 1.0).powi(2) + (x[1] + 2.0).powi(2));
+///
+/// let outcome = ParticleSwarmOptimizer::default().optimize(
+///     &objective,
+///     &bounds,
+///     &[0.0, 0.0],
+///     &OptimizationBudget::default(),
+/// );
+/// assert!((outcome.best_params[0] - 1.0).abs() < 0.1);
+/// ```
+pub trait Optimizer: std::fmt::Debug {
+    /// Human-readable name of the algorithm
+    fn name(&self) -> &'static str;
+
+    /// Maximize `objective` within `bounds`, starting from `initial`
+    ///
+    /// # Arguments
+    ///
+    /// * `objective` - Function to maximize
+    /// * `bounds` - Bounds for each parameter
+    /// * `initial` - Initial guess (projected onto the bounds if outside)
+    /// * `budget` - Evaluation and iteration limits
+    fn optimize(
+        &self,
+        objective: &dyn Fn(&[f64]) -> f64,
+        bounds: &ParameterBounds,
+        initial: &[f64],
+        budget: &OptimizationBudget,
+    ) -> OptimizerOutcome;
+}
+
+/// Objective wrapper that clamps inputs, counts evaluations and tracks the best point
+struct TrackedObjective<'a> {
+    objective: &'a dyn Fn(&[f64]) -> f64,
+    bounds: &'a ParameterBounds,
+    max_evaluations: usize,
+    evaluations: Cell<usize>,
+    best: std::cell::RefCell<(Vec<f64>, f64)>,
+}
+
+impl<'a> TrackedObjective<'a> {
+    fn new(objective: &'a dyn Fn(&[f64]) -> f64, bounds: &'a ParameterBounds, initial: &[f64], max_evaluations: usize) -> Self {
+        Self {
+            objective,
+            bounds,
+            max_evaluations,
+            evaluations: Cell::new(0),
+            best: std::cell::RefCell::new((bounds.clamp(initial), f64::NEG_INFINITY)),
+        }
+    }
+
+    /// Evaluate at the clamped point; NaN scores are treated as the worst possible value
+    fn evaluate(&self, params: &[f64]) -> f64 {
+        let clamped = self.bounds.clamp(params);
+        self.evaluations.set(self.evaluations.get() + 1);
+        let score = (self.objective)(&clamped);
+        let score = if score.is_nan() { f64::NEG_INFINITY } else { score };
+
+        let mut best = self.best.borrow_mut();
+        if score > best.1 {
+            *best = (clamped, score);
+        }
+        score
+    }
+
+    const fn exhausted(&self) -> bool {
+        self.evaluations.get() >= self.max_evaluations
+    }
+
+    fn into_outcome(self, iterations: usize) -> OptimizerOutcome {
+        let (best_params, best_score) = self.best.into_inner();
+        OptimizerOutcome {
+            best_params,
+            best_score,
+            evaluations: self.evaluations.get(),
+            iterations,
+        }
+    }
+}
+
+/// Small deterministic pseudo-random generator (`SplitMix64`)
+///
+/// Keeps the stochastic optimizers reproducible without an external dependency.
+#[derive(Debug, Clone)]
+pub(crate) struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    pub(crate) const fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub(crate) const fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform sample in `[0, 1)`
+    #[allow(clippy::cast_precision_loss)]
+    pub(crate) fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1_u64 << 53) as f64
+    }
+
+    /// Uniform sample in `[low, high)`
+    pub(crate) fn uniform(&mut self, low: f64, high: f64) -> f64 {
+        (high - low).mul_add(self.next_f64(), low)
+    }
+
+    /// Uniform index in `[0, len)`
+    #[allow(clippy::cast_possible_truncation)]
+    pub(crate) const fn index(&mut self, len: usize) -> usize {
+        (self.next_u64() % len as u64) as usize
+    }
+}
+
+/// Exhaustive search over a fixed grid of parameter values
+///
+/// Used by the `Fast` optimization profile. Each axis lists the values to try
+/// for one parameter; every combination is evaluated until the evaluation
+/// budget runs out. Each evaluated grid point counts as one iteration.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GridSearchOptimizer {
+    /// Values to evaluate for each parameter
+    pub axes: Vec<Vec<f64>>,
+}
+
+impl GridSearchOptimizer {
+    /// Create a grid search over the given axes
+    #[must_use]
+    pub const fn new(axes: Vec<Vec<f64>>) -> Self {
+        Self { axes }
+    }
+}
+
+impl Optimizer for GridSearchOptimizer {
+    fn name(&self) -> &'static str {
+        "grid_search"
+    }
+
+    fn optimize(
+        &self,
+        objective: &dyn Fn(&[f64]) -> f64,
+        bounds: &ParameterBounds,
+        initial: &[f64],
+        budget: &OptimizationBudget,
+    ) -> OptimizerOutcome {
+        let tracked = TrackedObjective::new(objective, bounds, initial, budget.max_evaluations);
+        let mut indices = vec![0_usize; self.axes.len()];
+        let mut iterations = 0;
+
+        if self.axes.iter().any(Vec::is_empty) {
+            return tracked.into_outcome(iterations);
+        }
+
+        // Odometer-style iteration over all combinations
+        'grid: loop {
+            if tracked.exhausted() {
+                break;
+            }
+            let point: Vec<f64> = indices.iter().zip(&self.axes).map(|(&i, axis)| axis[i]).collect();
+            tracked.evaluate(&point);
+            iterations += 1;
+
+            for (axis_index, axis) in self.axes.iter().enumerate().rev() {
+                indices[axis_index] += 1;
+                if indices[axis_index] < axis.len() {
+                    continue 'grid;
+                }
+                indices[axis_index] = 0;
+            }
+            break;
+        }
+
+        tracked.into_outcome(iterations)
+    }
+}
+
+/// Nelder-Mead simplex search
+///
+/// Used by the `Balanced` optimization profile. The initial simplex is built
+/// by perturbing each parameter of the starting point by a relative factor.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NelderMeadOptimizer {
+    /// Reflection coefficient (alpha)
+    pub reflection: f64,
+    /// Expansion coefficient (gamma)
+    pub expansion: f64,
+    /// Contraction coefficient (rho)
+    pub contraction: f64,
+    /// Shrink coefficient (sigma)
+    pub shrink: f64,
+    /// Relative perturbation applied to each parameter for the initial
+    /// simplex; parameters beyond the end of the list use its last value
+    pub perturbations: Vec<f64>,
+}
+
+impl Default for NelderMeadOptimizer {
+    fn default() -> Self {
+        Self {
+            reflection: constants::REFLECTION_COEFFICIENT,
+            expansion: constants::EXPANSION_COEFFICIENT,
+            contraction: constants::CONTRACTION_COEFFICIENT,
+            shrink: constants::SHRINK_COEFFICIENT,
+            perturbations: vec![constants::SIMPLEX_PERTURBATION],
+        }
+    }
+}
+
+impl NelderMeadOptimizer {
+    /// Relative perturbation for a parameter index (falls back to the last configured value)
+    fn perturbation(&self, index: usize) -> f64 {
+        self.perturbations
+            .get(index)
+            .or_else(|| self.perturbations.last())
+            .copied()
+            .unwrap_or(constants::SIMPLEX_PERTURBATION)
+    }
+
+    /// Run the simplex search on an already tracked objective, returning the iteration count
+    fn run(&self, tracked: &TrackedObjective, initial: &[f64], budget: &OptimizationBudget) -> usize {
+        let dimensions = initial.len();
+
+        // Create initial simplex: the start point plus one perturbed vertex per dimension
+        let mut simplex = vec![initial.to_vec()];
+        for index in 0..dimensions {
+            let mut vertex = initial.to_vec();
+            vertex[index] = if vertex[index].abs() > f64::EPSILON {
+                vertex[index] * (1.0 + self.perturbation(index))
+            } else {
+                self.perturbation(index)
+            };
+            simplex.push(vertex);
+        }
+        let mut scores = Vec::with_capacity(simplex.len());
+        for vertex in &simplex {
+            if tracked.exhausted() {
+                return 0;
+            }
+            scores.push(tracked.evaluate(vertex));
+        }
+
+        let mut iterations = 0;
+        while iterations < budget.max_iterations && !tracked.exhausted() {
+            iterations += 1;
+
+            // Sort simplex by scores (best to worst)
+            let mut order: Vec<usize> = (0..simplex.len()).collect();
+            order.sort_by(|&a, &b| scores[b].partial_cmp(&scores[a]).unwrap_or(std::cmp::Ordering::Equal));
+            let best = order[0];
+            let worst = order[order.len() - 1];
+            let second_worst = order[order.len() - 2];
+
+            // Check for convergence
+            if scores[best] - scores[worst] < budget.tolerance {
+                break;
+            }
+
+            // Centroid of all points except the worst
+            let mut centroid = vec![0.0; dimensions];
+            for &index in &order[..order.len() - 1] {
+                for (sum, value) in centroid.iter_mut().zip(&simplex[index]) {
+                    *sum += value;
+                }
+            }
+            #[allow(clippy::cast_precision_loss)]
+            let count = dimensions as f64;
+            for sum in &mut centroid {
+                *sum /= count;
+            }
+
+            let blend = |from: &[f64], to: &[f64], factor: f64| -> Vec<f64> {
+                from.iter().zip(to).map(|(&a, &b)| factor.mul_add(b - a, a)).collect()
+            };
+
+            // Reflection
+            let reflected = blend(&centroid, &simplex[worst], -self.reflection);
+            let reflected_score = tracked.evaluate(&reflected);
+
+            if reflected_score > scores[second_worst] && reflected_score <= scores[best] {
+                simplex[worst] = reflected;
+                scores[worst] = reflected_score;
+            } else if reflected_score > scores[best] {
+                // Try expansion
+                if tracked.exhausted() {
+                    simplex[worst] = reflected;
+                    scores[worst] = reflected_score;
+                    break;
+                }
+                let expanded = blend(&centroid, &reflected, self.expansion);
+                let expanded_score = tracked.evaluate(&expanded);
+                if expanded_score > reflected_score {
+                    simplex[worst] = expanded;
+                    scores[worst] = expanded_score;
+                } else {
+                    simplex[worst] = reflected;
+                    scores[worst] = reflected_score;
+                }
+            } else {
+                // Try contraction
+                if tracked.exhausted() {
+                    break;
+                }
+                let contracted = blend(&centroid, &simplex[worst], self.contraction);
+                let contracted_score = tracked.evaluate(&contracted);
+                if contracted_score > scores[worst] {
+                    simplex[worst] = contracted;
+                    scores[worst] = contracted_score;
+                } else {
+                    // Shrink simplex towards the best vertex
+                    let best_vertex = simplex[best].clone();
+                    for index in 0..simplex.len() {
+                        if tracked.exhausted() {
+                            break;
+                        }
+                        if index != best {
+                            simplex[index] = blend(&best_vertex, &simplex[index], self.shrink);
+                            scores[index] = tracked.evaluate(&simplex[index]);
+                        }
+                    }
+                }
+            }
+        }
+
+        iterations
+    }
+}
+
+impl Optimizer for NelderMeadOptimizer {
+    fn name(&self) -> &'static str {
+        "nelder_mead"
+    }
+
+    fn optimize(
+        &self,
+        objective: &dyn Fn(&[f64]) -> f64,
+        bounds: &ParameterBounds,
+        initial: &[f64],
+        budget: &OptimizationBudget,
+    ) -> OptimizerOutcome {
+        let tracked = TrackedObjective::new(objective, bounds, initial, budget.max_evaluations);
+        let iterations = self.run(&tracked, initial, budget);
+        tracked.into_outcome(iterations)
+    }
+}
+
+/// Nelder-Mead restarted from several starting points
+///
+/// Used by the `Thorough` optimization profile. The caller's initial guess is
+/// always tried first, followed by the configured starting points.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MultiStartNelderMeadOptimizer {
+    /// Local optimizer run from each start
+    pub local: NelderMeadOptimizer,
+    /// Additional starting points
+    pub starts: Vec<Vec<f64>>,
+}
+
+impl MultiStartNelderMeadOptimizer {
+    /// Create a multi-start optimizer with the given additional starting points
+    #[must_use]
+    pub fn new(starts: Vec<Vec<f64>>) -> Self {
+        Self { local: NelderMeadOptimizer::default(), starts }
+    }
+}
+
+impl Optimizer for MultiStartNelderMeadOptimizer {
+    fn name(&self) -> &'static str {
+        "multi_start_nelder_mead"
+    }
+
+    fn optimize(
+        &self,
+        objective: &dyn Fn(&[f64]) -> f64,
+        bounds: &ParameterBounds,
+        initial: &[f64],
+        budget: &OptimizationBudget,
+    ) -> OptimizerOutcome {
+        let tracked = TrackedObjective::new(objective, bounds, initial, budget.max_evaluations);
+        let mut iterations = 0;
+
+        for start in std::iter::once(initial).chain(self.starts.iter().map(Vec::as_slice)) {
+            if tracked.exhausted() {
+                break;
+            }
+            iterations += self.local.run(&tracked, start, budget);
+        }
+
+        tracked.into_outcome(iterations)
+    }
+}
+
+/// Simulated annealing with a geometric cooling schedule
+///
+/// At each temperature level a fixed number of candidate moves is drawn
+/// uniformly within a neighbourhood that shrinks as the temperature drops. Worse candidates are accepted with probability
+/// `exp(Δ / T)`, which lets the search escape local optima early on.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SimulatedAnnealingOptimizer {
+    /// Starting temperature, in objective units
+    pub initial_temperature: f64,
+    /// Multiplicative cooling factor applied every iteration (0 to 1)
+    pub cooling_rate: f64,
+    /// Initial neighbourhood size as a fraction of each parameter's range
+    pub step_fraction: f64,
+    /// Number of candidate moves evaluated at each temperature level
+    pub moves_per_temperature: usize,
+    /// Seed for the pseudo-random generator
+    pub seed: u64,
+}
+
+impl Default for SimulatedAnnealingOptimizer {
+    fn default() -> Self {
+        Self {
+            initial_temperature: constants::DEFAULT_INITIAL_TEMPERATURE,
+            cooling_rate: constants::DEFAULT_COOLING_RATE,
+            step_fraction: constants::DEFAULT_STEP_FRACTION,
+            moves_per_temperature: constants::DEFAULT_MOVES_PER_TEMPERATURE,
+            seed: constants::DEFAULT_SEED,
+        }
+    }
+}
+
+impl SimulatedAnnealingOptimizer {
+    /// Use a different random seed
+    #[must_use]
+    pub const fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+}
+
+impl Optimizer for SimulatedAnnealingOptimizer {
+    fn name(&self) -> &'static str {
+        "simulated_annealing"
+    }
+
+    fn optimize(
+        &self,
+        objective: &dyn Fn(&[f64]) -> f64,
+        bounds: &ParameterBounds,
+        initial: &[f64],
+        budget: &OptimizationBudget,
+    ) -> OptimizerOutcome {
+        let tracked = TrackedObjective::new(objective, bounds, initial, budget.max_evaluations);
+        let mut rng = SplitMix64::new(self.seed);
+
+        let mut current = bounds.clamp(initial);
+        let mut current_score = tracked.evaluate(&current);
+        let mut temperature = self.initial_temperature;
+        let mut step_fraction = self.step_fraction;
+        let mut iterations = 0;
+
+        while iterations < budget.max_iterations && !tracked.exhausted() {
+            iterations += 1;
+
+            for _ in 0..self.moves_per_temperature.max(1) {
+                if tracked.exhausted() {
+                    break;
+                }
+
+                let candidate: Vec<f64> = current.iter()
+                    .enumerate()
+                    .map(|(index, &value)| {
+                        let step = bounds.span(index) * step_fraction;
+                        rng.uniform(value - step, value + step)
+                    })
+                    .collect();
+                let candidate = bounds.clamp(&candidate);
+                let candidate_score = tracked.evaluate(&candidate);
+
+                let delta = candidate_score - current_score;
+                let accept = delta >= 0.0
+                    || (temperature > 0.0 && rng.next_f64() < (delta / temperature).exp());
+                if accept {
+                    current = candidate;
+                    current_score = candidate_score;
+                }
+            }
+
+            temperature *= self.cooling_rate;
+            step_fraction = (step_fraction * self.cooling_rate).max(constants::MIN_STEP_FRACTION);
+        }
+
+        tracked.into_outcome(iterations)
+    }
+}
+
+/// Particle swarm optimization
+///
+/// A swarm of particles explores the bounded space; each particle is pulled
+/// toward its own best position and the best position found by the swarm.
+/// The caller's initial guess seeds the first particle.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ParticleSwarmOptimizer {
+    /// Number of particles in the swarm
+    pub swarm_size: usize,
+    /// Velocity damping factor
+    pub inertia: f64,
+    /// Attraction toward each particle's own best position
+    pub cognitive_weight: f64,
+    /// Attraction toward the swarm's best position
+    pub social_weight: f64,
+    /// Seed for the pseudo-random generator
+    pub seed: u64,
+}
+
+impl Default for ParticleSwarmOptimizer {
+    fn default() -> Self {
+        Self {
+            swarm_size: constants::DEFAULT_SWARM_SIZE,
+            inertia: constants::DEFAULT_INERTIA,
+            cognitive_weight: constants::DEFAULT_COGNITIVE_WEIGHT,
+            social_weight: constants::DEFAULT_SOCIAL_WEIGHT,
+            seed: constants::DEFAULT_SEED,
+        }
+    }
+}
+
+impl ParticleSwarmOptimizer {
+    /// Use a different random seed
+    #[must_use]
+    pub const fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+}
+
+impl Optimizer for ParticleSwarmOptimizer {
+    fn name(&self) -> &'static str {
+        "particle_swarm"
+    }
+
+    fn optimize(
+        &self,
+        objective: &dyn Fn(&[f64]) -> f64,
+        bounds: &ParameterBounds,
+        initial: &[f64],
+        budget: &OptimizationBudget,
+    ) -> OptimizerOutcome {
+        let tracked = TrackedObjective::new(objective, bounds, initial, budget.max_evaluations);
+        let mut rng = SplitMix64::new(self.seed);
+        let dimensions = bounds.dimensions();
+        let max_velocity: Vec<f64> = (0..dimensions)
+            .map(|index| bounds.span(index) * constants::MAX_VELOCITY_FRACTION)
+            .collect();
+
+        // Initialize the swarm, seeding the first particle with the initial guess
+        let mut positions: Vec<Vec<f64>> = (0..self.swarm_size.max(1))
+            .map(|particle| {
+                if particle == 0 {
+                    bounds.clamp(initial)
+                } else {
+                    (0..dimensions).map(|index| rng.uniform(bounds.lower[index], bounds.upper[index])).collect()
+                }
+            })
+            .collect();
+        let mut velocities: Vec<Vec<f64>> = positions.iter()
+            .map(|_| max_velocity.iter().map(|&limit| rng.uniform(-limit, limit)).collect())
+            .collect();
+
+        let mut personal_best = positions.clone();
+        let mut personal_scores = Vec::with_capacity(positions.len());
+        for position in &positions {
+            if tracked.exhausted() {
+                personal_scores.push(f64::NEG_INFINITY);
+            } else {
+                personal_scores.push(tracked.evaluate(position));
+            }
+        }
+
+        let mut iterations = 0;
+        while iterations < budget.max_iterations && !tracked.exhausted() {
+            iterations += 1;
+            let (global_best, _) = tracked.best.borrow().clone();
+
+            for particle in 0..positions.len() {
+                if tracked.exhausted() {
+                    break;
+                }
+
+                for index in 0..dimensions {
+                    let cognitive = self.cognitive_weight * rng.next_f64() * (personal_best[particle][index] - positions[particle][index]);
+                    let social = self.social_weight * rng.next_f64() * (global_best[index] - positions[particle][index]);
+                    let velocity = self.inertia.mul_add(velocities[particle][index], cognitive + social);
+                    velocities[particle][index] = velocity.clamp(-max_velocity[index], max_velocity[index]);
+                    positions[particle][index] += velocities[particle][index];
+                }
+                positions[particle] = bounds.clamp(&positions[particle]);
+
+                let score = tracked.evaluate(&positions[particle]);
+                if score > personal_scores[particle] {
+                    personal_scores[particle] = score;
+                    personal_best[particle].clone_from(&positions[particle]);
+                }
+            }
+        }
+
+        tracked.into_outcome(iterations)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn quadratic(x: &[f64]) -> f64 {
+        -((x[0] - 1.0).powi(2) + (x[1] + 2.0).powi(2))
+    }
+
+    fn bounds() -> ParameterBounds {
+        ParameterBounds::new(vec![-5.0, -5.0], vec![5.0, 5.0]).unwrap()
+    }
+
+    #[test]
+    fn test_parameter_bounds_validation() {
+        assert!(ParameterBounds::new(vec![], vec![]).is_err());
+        assert!(ParameterBounds::new(vec![0.0], vec![1.0, 2.0]).is_err());
+        assert!(ParameterBounds::new(vec![2.0], vec![1.0]).is_err());
+        assert!(ParameterBounds::new(vec![f64::NAN], vec![1.0]).is_err());
+
+        let bounds = bounds();
+        assert_eq!(bounds.clamp(&[10.0, -10.0]), vec![5.0, -5.0]);
+        assert_eq!(bounds.center(), vec![0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_all_optimizers_find_quadratic_maximum() {
+        let optimizers: Vec<Box<dyn Optimizer>> = vec![
+            Box::new(GridSearchOptimizer::new(vec![vec![-1.0, 0.0, 1.0, 2.0], vec![-3.0, -2.0, -1.0]])),
+            Box::new(NelderMeadOptimizer::default()),
+            Box::new(MultiStartNelderMeadOptimizer::new(vec![vec![4.0, 4.0], vec![-4.0, -4.0]])),
+            Box::new(SimulatedAnnealingOptimizer::default()),
+            Box::new(ParticleSwarmOptimizer::default()),
+        ];
+
+        for optimizer in optimizers {
+            let outcome = optimizer.optimize(&quadratic, &bounds(), &[3.0, 3.0], &OptimizationBudget::default());
+            assert!(outcome.best_score > -0.05, "{} converged to {:?}", optimizer.name(), outcome.best_params);
+            assert!(outcome.evaluations > 0);
+        }
+    }
+
+    #[test]
+    fn test_budget_limits_evaluations() {
+        let optimizers: Vec<Box<dyn Optimizer>> = vec![
+            Box::new(NelderMeadOptimizer::default()),
+            Box::new(MultiStartNelderMeadOptimizer::new(vec![vec![4.0, 4.0]])),
+            Box::new(SimulatedAnnealingOptimizer::default()),
+            Box::new(ParticleSwarmOptimizer::default()),
+        ];
+
+        // Budgets smaller than the initial simplex or population included
+        for max_evaluations in [2, 25] {
+            let budget = OptimizationBudget::new(max_evaluations, 1000, 0.0);
+            for optimizer in &optimizers {
+                let outcome = optimizer.optimize(&quadratic, &bounds(), &[0.0, 0.0], &budget);
+                assert_eq!(outcome.evaluations, max_evaluations, "{} did not stop at the budget", optimizer.name());
+            }
+        }
+    }
+
+    #[test]
+    fn test_nelder_mead_in_five_dimensions() {
+        let optimum = [0.0, 1.0, 2.0, 3.0, 4.0];
+        let objective = |x: &[f64]| -x.iter().zip(optimum).map(|(value, target)| (value - target).powi(2)).sum::<f64>();
+        let bounds = ParameterBounds::new(vec![-5.0; 5], vec![5.0; 5]).unwrap();
+        let budget = OptimizationBudget::new(5000, 5000, 1e-12);
+        let optimizer = NelderMeadOptimizer { perturbations: vec![0.5, 0.2], ..NelderMeadOptimizer::default() };
+
+        let outcome = optimizer.optimize(&objective, &bounds, &[1.0; 5], &budget);
+        assert!(outcome.best_score > -1e-3, "converged to {:?}", outcome.best_params);
+    }
+
+    #[test]
+    fn test_stochastic_optimizers_are_reproducible() {
+        let budget = OptimizationBudget::new(200, 200, 0.0);
+        let first = ParticleSwarmOptimizer::default().optimize(&quadratic, &bounds(), &[0.0, 0.0], &budget);
+        let second = ParticleSwarmOptimizer::default().optimize(&quadratic, &bounds(), &[0.0, 0.0], &budget);
+        assert_eq!(first.best_params, second.best_params);
+
+        let first = SimulatedAnnealingOptimizer::default().with_seed(7).optimize(&quadratic, &bounds(), &[0.0, 0.0], &budget);
+        let second = SimulatedAnnealingOptimizer::default().with_seed(7).optimize(&quadratic, &bounds(), &[0.0, 0.0], &budget);
+        assert_eq!(first.best_params, second.best_params);
+    }
+
+    #[test]
+    fn test_results_stay_within_bounds() {
+        // Maximum lies outside the bounds; optimizers must report a bounded point
+        let objective = |x: &[f64]| x[0] + x[1];
+        let optimizers: Vec<Box<dyn Optimizer>> = vec![
+            Box::new(NelderMeadOptimizer::default()),
+            Box::new(SimulatedAnnealingOptimizer::default()),
+            Box::new(ParticleSwarmOptimizer::default()),
+        ];
+
+        for optimizer in optimizers {
+            let outcome = optimizer.optimize(&objective, &bounds(), &[0.0, 0.0], &OptimizationBudget::default());
+            assert!(outcome.best_params.iter().all(|value| (-5.0..=5.0).contains(value)));
+            assert!(outcome.best_score > 9.0, "{} reached {}", optimizer.name(), outcome.best_score);
+        }
+    }
+}
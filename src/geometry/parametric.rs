@@ -0,0 +1,533 @@
+//! `geometry/parametric.rs` - Analytic Parametric Channel Paths
+//!
+//! [`ChannelType`](crate::geometry::ChannelType) stores a sampled path, which
+//! fixes the resolution at generation time and loses the curve it was sampled
+//! from. A [`ParametricPath`] keeps that curve: a line, a wave with an
+//! amplitude envelope (serpentine and smooth straight channels), a Bezier
+//! curve (arc channels) or a frustum profile. It can be evaluated at any
+//! parameter `t` in `[0, 1]` for position, tangent, curvature and arc length,
+//! and discretized on demand at a fixed point count or adaptively.
+//!
+//! The built-in strategies build their paths from a `ParametricPath`, so the
+//! sampled path of a channel is exactly a discretization of its analytic path.
+//! Generation keeps the analytic paths as [`ParametricPathMetadata`] when
+//! [`MetadataConfig::track_parametric_paths`] is set.
+//!
+//! [`ParametricPathMetadata`]: crate::geometry::metadata::ParametricPathMetadata
+//! [`MetadataConfig::track_parametric_paths`]: crate::geometry::generator::MetadataConfig::track_parametric_paths
+//!
+//! # Examples
+//!
+//! ```rust
+//! use scheme::geometry::parametric::ParametricPath;
+//!
+//! let arc = ParametricPath::Bezier {
+//!     control_points: vec![(0.0, 0.0), (10.0, 10.0), (20.0, 0.0)],
+//! };
+//!
+//! assert_eq!(arc.position(0.5), (10.0, 5.0));
+//! assert!(arc.curvature(0.5) < 0.0); // Clockwise at the apex
+//! assert!(arc.length() > 20.0);
+//!
+//! let coarse = arc.discretize(5);
+//! let fine = arc.discretize(500);
+//! assert_eq!((coarse.len(), fine.len()), (5, 500));
+//! ```
+
+use crate::config::{AdaptiveSamplingConfig, AdaptiveSerpentineConfig, FrustumConfig, WaveShape};
+use crate::config_constants::ConstantsRegistry;
+use crate::geometry::{
+    sampling::sample_parameters,
+    strategies::{AdaptiveGaussianEnvelopeCalculator, EnvelopeCalculator, EnvelopeContext},
+    transform::Transform,
+    Point2D,
+};
+use serde::{Deserialize, Serialize};
+use std::f64::consts::PI;
+
+/// Parametric path constants
+mod constants {
+    /// Parameter step for the first derivative of wave envelopes
+    pub const ENVELOPE_FIRST_DERIVATIVE_STEP: f64 = 1e-6;
+
+    /// Parameter step for the second derivative of wave envelopes
+    pub const ENVELOPE_SECOND_DERIVATIVE_STEP: f64 = 1e-4;
+
+    /// Speeds below this are treated as a stationary point (mm per unit parameter)
+    pub const MIN_SPEED: f64 = 1e-12;
+
+    /// Gauss-Legendre panels per initial sampling segment when integrating arc length
+    pub const PANELS_PER_SEGMENT: usize = 8;
+
+    /// Minimum number of Gauss-Legendre panels over the whole parameter range
+    pub const MIN_PANELS: usize = 16;
+
+    /// Nodes of 5-point Gauss-Legendre quadrature on `[-1, 1]`
+    pub const GAUSS_NODES: [f64; 5] = [
+        -0.906_179_845_938_664,
+        -0.538_469_310_105_683_1,
+        0.0,
+        0.538_469_310_105_683_1,
+        0.906_179_845_938_664,
+    ];
+
+    /// Weights of 5-point Gauss-Legendre quadrature on `[-1, 1]`
+    pub const GAUSS_WEIGHTS: [f64; 5] = [
+        0.236_926_885_056_189_1,
+        0.478_628_670_499_366_5,
+        0.568_888_888_888_888_9,
+        0.478_628_670_499_366_5,
+        0.236_926_885_056_189_1,
+    ];
+}
+
+/// Amplitude envelope of a [`ParametricPath::Wave`]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum WaveEnvelope {
+    /// Dome-shaped envelope of serpentine channels, see
+    /// [`AdaptiveGaussianEnvelopeCalculator`]
+    AdaptiveGaussian {
+        /// Gaussian width factor of the serpentine configuration
+        gaussian_width_factor: f64,
+        /// Adaptive behavior of the serpentine configuration
+        adaptive_config: AdaptiveSerpentineConfig,
+        /// Start-to-end vector of the channel the envelope was shaped for,
+        /// kept as is when the path is transformed
+        channel_vector: (f64, f64),
+    },
+    /// Smoothstep ramps at both ends of smooth straight channels
+    Transition {
+        /// Length of each ramp as a fraction of the path length
+        transition_fraction: f64,
+    },
+}
+
+impl WaveEnvelope {
+    /// Envelope value at parameter `t`
+    #[must_use]
+    #[allow(clippy::suboptimal_flops, clippy::imprecise_flops)]
+    pub fn value(&self, t: f64) -> f64 {
+        match *self {
+            Self::AdaptiveGaussian { gaussian_width_factor, adaptive_config, channel_vector: (dx, dy) } => {
+                let context = EnvelopeContext {
+                    channel_length: dx.hypot(dy),
+                    direction: (dx, dy),
+                    node_distance: (dx * dx + dy * dy).sqrt(),
+                    adaptive_config,
+                    gaussian_width_factor,
+                };
+                AdaptiveGaussianEnvelopeCalculator.calculate_envelope(t, &context)
+            }
+            Self::Transition { transition_fraction } => {
+                let smoothstep = |x: f64| x * x * (3.0 - 2.0 * x);
+                let start_transition = if t < transition_fraction { smoothstep(t / transition_fraction) } else { 1.0 };
+                let end_transition = if t > 1.0 - transition_fraction {
+                    smoothstep((1.0 - t) / transition_fraction)
+                } else {
+                    1.0
+                };
+                start_transition * end_transition
+            }
+        }
+    }
+}
+
+/// Analytic description of a channel centerline
+///
+/// Every variant is parameterized by `t` in `[0, 1]`, running from the start
+/// to the end point, which are hit exactly.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ParametricPath {
+    /// Straight line
+    Line {
+        /// Start point
+        start: Point2D,
+        /// End point
+        end: Point2D,
+    },
+    /// Wave around the line from `start` to `end`, displaced perpendicular
+    /// to it by `amplitude * envelope(t) * shape(π * half_periods * t + phase_offset)`
+    Wave {
+        /// Start point
+        start: Point2D,
+        /// End point
+        end: Point2D,
+        /// Peak displacement from the line, positive to the left of the
+        /// start-to-end direction (mm)
+        amplitude: f64,
+        /// Number of half-periods between start and end
+        half_periods: f64,
+        /// Phase offset of the wave (radians)
+        phase_offset: f64,
+        /// Wave shape
+        shape: WaveShape,
+        /// Amplitude envelope along the wave
+        envelope: WaveEnvelope,
+    },
+    /// Bezier curve through its first and last control points; needs at
+    /// least one control point
+    Bezier {
+        /// Control points
+        control_points: Vec<Point2D>,
+    },
+    /// Straight centerline with a tapered width profile
+    Frustum {
+        /// Start point
+        start: Point2D,
+        /// End point
+        end: Point2D,
+        /// Width profile along the centerline
+        profile: FrustumConfig,
+    },
+}
+
+impl ParametricPath {
+    /// Start point of the path
+    #[must_use]
+    pub fn start(&self) -> Point2D {
+        match self {
+            Self::Line { start, .. } | Self::Wave { start, .. } | Self::Frustum { start, .. } => *start,
+            Self::Bezier { control_points } => control_points.first().copied().unwrap_or_default(),
+        }
+    }
+
+    /// End point of the path
+    #[must_use]
+    pub fn end(&self) -> Point2D {
+        match self {
+            Self::Line { end, .. } | Self::Wave { end, .. } | Self::Frustum { end, .. } => *end,
+            Self::Bezier { control_points } => control_points.last().copied().unwrap_or_default(),
+        }
+    }
+
+    /// Position at parameter `t`, clamped to the endpoints outside `[0, 1]`
+    #[must_use]
+    #[allow(clippy::suboptimal_flops)]
+    pub fn position(&self, t: f64) -> Point2D {
+        if t <= 0.0 {
+            return self.start();
+        }
+        if t >= 1.0 {
+            return self.end();
+        }
+
+        match self {
+            Self::Line { start, end } | Self::Frustum { start, end, .. } => {
+                (start.0 + t * (end.0 - start.0), start.1 + t * (end.1 - start.1))
+            }
+            Self::Wave { start, end, amplitude, .. } => {
+                let dx = end.0 - start.0;
+                let dy = end.1 - start.1;
+                let (perp_x, perp_y) = self.wave_normal();
+                let wave_amplitude = amplitude * self.wave_envelope(t) * self.wave_shape(t).0;
+                (start.0 + t * dx + wave_amplitude * perp_x, start.1 + t * dy + wave_amplitude * perp_y)
+            }
+            Self::Bezier { control_points } => match control_points.as_slice() {
+                [p1, control, p2] => {
+                    // Quadratic Bezier formula: B(t) = (1-t)²P₀ + 2(1-t)tP₁ + t²P₂
+                    let t_inv = 1.0 - t;
+                    (
+                        t_inv * t_inv * p1.0 + 2.0 * t_inv * t * control.0 + t * t * p2.0,
+                        t_inv * t_inv * p1.1 + 2.0 * t_inv * t * control.1 + t * t * p2.1,
+                    )
+                }
+                points => de_casteljau(points, t),
+            },
+        }
+    }
+
+    /// First derivative of the position with respect to `t`
+    #[must_use]
+    pub fn derivative(&self, t: f64) -> (f64, f64) {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Self::Line { start, end } | Self::Frustum { start, end, .. } => (end.0 - start.0, end.1 - start.1),
+            Self::Wave { start, end, amplitude, .. } => {
+                let (normal_x, normal_y) = self.wave_normal();
+                let (shape, shape_slope, _) = self.wave_shape(t);
+                let slope = amplitude * self.envelope_slope(t).mul_add(shape, self.wave_envelope(t) * shape_slope);
+                (normal_x.mul_add(slope, end.0 - start.0), normal_y.mul_add(slope, end.1 - start.1))
+            }
+            Self::Bezier { control_points } => de_casteljau(&hodograph(control_points), t),
+        }
+    }
+
+    /// Second derivative of the position with respect to `t`
+    #[must_use]
+    pub fn second_derivative(&self, t: f64) -> (f64, f64) {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Self::Line { .. } | Self::Frustum { .. } => (0.0, 0.0),
+            Self::Wave { amplitude, .. } => {
+                let (normal_x, normal_y) = self.wave_normal();
+                let envelope = self.wave_envelope(t);
+                let envelope_slope = self.envelope_slope(t);
+                let (shape, shape_slope, shape_bend) = self.wave_shape(t);
+                let bend = amplitude
+                    * self.envelope_bend(t).mul_add(shape, (2.0 * envelope_slope).mul_add(shape_slope, envelope * shape_bend));
+                (normal_x * bend, normal_y * bend)
+            }
+            Self::Bezier { control_points } => de_casteljau(&hodograph(&hodograph(control_points)), t),
+        }
+    }
+
+    /// Unit tangent at parameter `t`, or the chord direction where the path
+    /// is stationary
+    #[must_use]
+    pub fn tangent(&self, t: f64) -> (f64, f64) {
+        let (dx, dy) = self.derivative(t);
+        let speed = dx.hypot(dy);
+        if speed >= constants::MIN_SPEED {
+            return (dx / speed, dy / speed);
+        }
+        let (start, end) = (self.start(), self.end());
+        let chord = (end.0 - start.0).hypot(end.1 - start.1).max(constants::MIN_SPEED);
+        ((end.0 - start.0) / chord, (end.1 - start.1) / chord)
+    }
+
+    /// Unit normal at parameter `t`, pointing to the left of the direction of travel
+    #[must_use]
+    pub fn normal(&self, t: f64) -> (f64, f64) {
+        let (tx, ty) = self.tangent(t);
+        (-ty, tx)
+    }
+
+    /// Point offset from the path by `distance` along its normal at `t`
+    ///
+    /// Positive distances offset to the left; half the channel width on either
+    /// side gives the exact channel walls.
+    #[must_use]
+    pub fn offset(&self, t: f64, distance: f64) -> Point2D {
+        let (x, y) = self.position(t);
+        let (nx, ny) = self.normal(t);
+        (nx.mul_add(distance, x), ny.mul_add(distance, y))
+    }
+
+    /// Signed curvature (1/mm) at parameter `t`, positive for left turns
+    #[must_use]
+    pub fn curvature(&self, t: f64) -> f64 {
+        let (dx, dy) = self.derivative(t);
+        let (ddx, ddy) = self.second_derivative(t);
+        let speed = dx.hypot(dy);
+        if speed < constants::MIN_SPEED {
+            return 0.0;
+        }
+        dx.mul_add(ddy, -(dy * ddx)) / speed.powi(3)
+    }
+
+    /// Arc length (mm) from the start of the path to parameter `t`
+    ///
+    /// Integrated with composite Gauss-Legendre quadrature over panels fine
+    /// enough to resolve every half-wave, so the result is exact to well below
+    /// fabrication tolerances.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub fn arc_length(&self, t: f64) -> f64 {
+        let t = t.clamp(0.0, 1.0);
+        if let Self::Line { start, end } | Self::Frustum { start, end, .. } = self {
+            return t * (end.0 - start.0).hypot(end.1 - start.1);
+        }
+
+        let total_panels = (constants::PANELS_PER_SEGMENT * self.min_segments()).max(constants::MIN_PANELS);
+        let panels = ((total_panels as f64 * t).ceil() as usize).max(1);
+        let width = t / panels as f64;
+        (0..panels)
+            .map(|panel| {
+                let center = (panel as f64 + 0.5) * width;
+                constants::GAUSS_NODES
+                    .iter()
+                    .zip(constants::GAUSS_WEIGHTS)
+                    .map(|(&node, weight)| {
+                        let (dx, dy) = self.derivative(node.mul_add(width / 2.0, center));
+                        weight * dx.hypot(dy)
+                    })
+                    .sum::<f64>()
+                    * width
+                    / 2.0
+            })
+            .sum()
+    }
+
+    /// Total arc length of the path (mm)
+    #[must_use]
+    pub fn length(&self) -> f64 {
+        self.arc_length(1.0)
+    }
+
+    /// Channel width at parameter `t` for frustum paths
+    #[must_use]
+    pub fn width_at(&self, t: f64) -> Option<f64> {
+        match self {
+            Self::Frustum { profile, .. } => Some(profile.width_at_position(t.clamp(0.0, 1.0))),
+            _ => None,
+        }
+    }
+
+    /// `points` evenly spaced parameters from 0 to 1
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn uniform_parameters(points: usize) -> Vec<f64> {
+        let points = points.max(2);
+        (0..points).map(|i| i as f64 / (points - 1) as f64).collect()
+    }
+
+    /// Parameters at which the path is sampled adaptively
+    ///
+    /// Frustum paths are sampled along a channel wall, so points follow the
+    /// width profile even though the centerline is straight.
+    #[must_use]
+    pub fn adaptive_parameters(&self, config: &AdaptiveSamplingConfig) -> Vec<f64> {
+        match self {
+            Self::Frustum { .. } => sample_parameters(|t| self.offset(t, self.width_at(t).unwrap_or(0.0) / 2.0), 2, config),
+            _ => sample_parameters(|t| self.position(t), self.min_segments(), config),
+        }
+    }
+
+    /// Positions at the given parameters
+    #[must_use]
+    pub fn points_at(&self, parameters: &[f64]) -> Vec<Point2D> {
+        parameters.iter().map(|&t| self.position(t)).collect()
+    }
+
+    /// Discretize the path at `points` evenly spaced parameters (at least two)
+    #[must_use]
+    pub fn discretize(&self, points: usize) -> Vec<Point2D> {
+        self.points_at(&Self::uniform_parameters(points))
+    }
+
+    /// Discretize the path adaptively within the given tolerances
+    #[must_use]
+    pub fn sample(&self, config: &AdaptiveSamplingConfig) -> Vec<Point2D> {
+        self.points_at(&self.adaptive_parameters(config))
+    }
+
+    /// Image of the path under `transform`
+    ///
+    /// Positions follow the transform exactly; wave amplitudes and frustum
+    /// widths scale with it, and mirroring flips the side a wave starts on.
+    #[must_use]
+    pub fn transformed(&self, transform: &Transform) -> Self {
+        let scale = transform.scale_factor();
+        match self {
+            Self::Line { start, end } => Self::Line {
+                start: transform.apply(*start),
+                end: transform.apply(*end),
+            },
+            Self::Wave { start, end, amplitude, half_periods, phase_offset, shape, envelope } => Self::Wave {
+                start: transform.apply(*start),
+                end: transform.apply(*end),
+                amplitude: if transform.is_reflection() { -amplitude * scale } else { amplitude * scale },
+                half_periods: *half_periods,
+                phase_offset: *phase_offset,
+                shape: *shape,
+                envelope: *envelope,
+            },
+            Self::Bezier { control_points } => Self::Bezier {
+                control_points: control_points.iter().map(|&point| transform.apply(point)).collect(),
+            },
+            Self::Frustum { start, end, profile } => Self::Frustum {
+                start: transform.apply(*start),
+                end: transform.apply(*end),
+                profile: FrustumConfig {
+                    inlet_width: profile.inlet_width * scale,
+                    throat_width: profile.throat_width * scale,
+                    outlet_width: profile.outlet_width * scale,
+                    ..*profile
+                },
+            },
+        }
+    }
+
+    /// Initial segments for adaptive sampling, two per half-wave so no
+    /// segment spans a full oscillation
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn min_segments(&self) -> usize {
+        match self {
+            Self::Line { .. } => 1,
+            Self::Frustum { .. } => 2,
+            Self::Wave { half_periods, .. } => 2 * (half_periods.ceil().max(1.0) as usize),
+            Self::Bezier { control_points } => control_points.len().saturating_sub(1).max(2),
+        }
+    }
+
+    /// Unit normal of the line a wave oscillates around
+    fn wave_normal(&self) -> (f64, f64) {
+        let (start, end) = (self.start(), self.end());
+        let dx = end.0 - start.0;
+        let dy = end.1 - start.1;
+        let length = dx.hypot(dy);
+        (-dy / length, dx / length)
+    }
+
+    /// Envelope of a wave at `t`
+    fn wave_envelope(&self, t: f64) -> f64 {
+        match self {
+            Self::Wave { envelope, .. } => envelope.value(t),
+            _ => 1.0,
+        }
+    }
+
+    /// Derivative of the envelope of a wave by central differences
+    fn envelope_slope(&self, t: f64) -> f64 {
+        let step = constants::ENVELOPE_FIRST_DERIVATIVE_STEP;
+        let (before, after) = ((t - step).max(0.0), (t + step).min(1.0));
+        (self.wave_envelope(after) - self.wave_envelope(before)) / (after - before)
+    }
+
+    /// Second derivative of the envelope of a wave by central differences
+    fn envelope_bend(&self, t: f64) -> f64 {
+        let step = constants::ENVELOPE_SECOND_DERIVATIVE_STEP;
+        let t = t.clamp(step, 1.0 - step);
+        (2.0f64.mul_add(-self.wave_envelope(t), self.wave_envelope(t + step)) + self.wave_envelope(t - step)) / (step * step)
+    }
+
+    /// Wave shape at `t` with its first and second derivatives with respect to `t`
+    fn wave_shape(&self, t: f64) -> (f64, f64, f64) {
+        let Self::Wave { half_periods, phase_offset, shape, .. } = self else {
+            return (0.0, 0.0, 0.0);
+        };
+        let frequency = PI * half_periods;
+        let phase = PI * half_periods * t + phase_offset;
+        let (sin, cos) = phase.sin_cos();
+        match shape {
+            WaveShape::Sine => (sin, frequency * cos, -frequency * frequency * sin),
+            WaveShape::Square => {
+                // Smooth square wave tanh(k·sin φ)
+                let sharpness = ConstantsRegistry::new().get_square_wave_sharpness();
+                let value = (sharpness * sin).tanh();
+                let sech_squared = value.mul_add(-value, 1.0);
+                let inner_slope = sharpness * cos;
+                let slope = sech_squared * inner_slope;
+                let bend = (-2.0 * value * slope).mul_add(inner_slope, sech_squared * (-sharpness * sin));
+                (value, frequency * slope, frequency * frequency * bend)
+            }
+        }
+    }
+}
+
+/// Point at `t` of the Bezier curve with the given control points
+fn de_casteljau(points: &[Point2D], t: f64) -> Point2D {
+    let mut points = points.to_vec();
+    while points.len() > 1 {
+        points = points
+            .windows(2)
+            .map(|pair| (t.mul_add(pair[1].0 - pair[0].0, pair[0].0), t.mul_add(pair[1].1 - pair[0].1, pair[0].1)))
+            .collect();
+    }
+    points.first().copied().unwrap_or_default()
+}
+
+/// Control points of the derivative of a Bezier curve
+#[allow(clippy::cast_precision_loss)]
+fn hodograph(points: &[Point2D]) -> Vec<Point2D> {
+    let degree = points.len().saturating_sub(1) as f64;
+    let derivative: Vec<Point2D> = points
+        .windows(2)
+        .map(|pair| (degree * (pair[1].0 - pair[0].0), degree * (pair[1].1 - pair[0].1)))
+        .collect();
+    if derivative.is_empty() {
+        vec![(0.0, 0.0)]
+    } else {
+        derivative
+    }
+}
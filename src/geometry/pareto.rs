@@ -0,0 +1,778 @@
+//! Multi-objective Pareto optimization of serpentine parameters
+//!
+//! Serpentine design usually trades channel length (mixing) against pressure
+//! drop and footprint. Instead of collapsing these into a single score, this
+//! module runs NSGA-II over the wave parameters (wavelength factor, wave
+//! density factor and fill factor) and returns the set of non-dominated
+//! designs, the Pareto front.
+//!
+//! Clearance constraints are handled by constrained domination: a design that
+//! respects the wall and neighbor clearances always dominates one that does
+//! not, and infeasible designs are ranked by their total clearance violation.
+//!
+//! Designs are picked from the front with [`ParetoFront::select_knee`],
+//! [`ParetoFront::select_weighted`], [`ParetoFront::select_constrained`] or
+//! [`ParetoFront::best_for`], and the front can be rendered with
+//! [`plot_pareto_front`](crate::visualizations::pareto::plot_pareto_front).
+//!
+//! # Examples
+//!
+//! ```rust
+//! use scheme::config::{GeometryConfig, SerpentineConfig};
+//! use scheme::geometry::objectives::ObjectiveTerm;
+//! use scheme::geometry::pareto::{optimize_serpentine_pareto, Nsga2Config, ParetoObjective};
+//! use scheme::geometry::strategies::ChannelGenerationContext;
+//!
+//! let geometry_config = GeometryConfig::default();
+//! let context = ChannelGenerationContext::new(&geometry_config, (200.0, 100.0), 1, None);
+//! let objectives = [
+//!     ParetoObjective::maximize(ObjectiveTerm::PathLength),
+//!     ParetoObjective::minimize(ObjectiveTerm::Footprint),
+//! ];
+//! let config = Nsga2Config { population_size: 8, generations: 2, ..Nsga2Config::default() };
+//!
+//! let front = optimize_serpentine_pareto(
+//!     (0.0, 50.0), (200.0, 50.0), &SerpentineConfig::default(), &context, &objectives, &config,
+//! ).unwrap();
+//!
+//! let knee = front.select_knee().unwrap();
+//! let chosen = knee.to_config(&SerpentineConfig::default());
+//! assert!(!chosen.optimization_enabled);
+//! ```
+
+use crate::config::SerpentineConfig;
+use crate::error::{ConfigurationError, ConfigurationResult};
+use crate::geometry::objectives::{ObjectiveTerm, PathMetrics};
+use crate::geometry::optimization::{measure_serpentine_candidate, serpentine_parameter_bounds, OptimizationParams};
+use crate::geometry::optimizers::{ParameterBounds, SplitMix64};
+use crate::geometry::strategies::ChannelGenerationContext;
+use crate::geometry::types::Point2D;
+use std::cmp::Ordering;
+
+/// NSGA-II constants
+mod constants {
+    /// Default number of designs per generation
+    pub const DEFAULT_POPULATION_SIZE: usize = 24;
+
+    /// Default number of generations
+    pub const DEFAULT_GENERATIONS: usize = 20;
+
+    /// Default probability of recombining two parents
+    pub const DEFAULT_CROSSOVER_PROBABILITY: f64 = 0.9;
+
+    /// Default distribution index of simulated binary crossover
+    pub const DEFAULT_CROSSOVER_DISTRIBUTION_INDEX: f64 = 15.0;
+
+    /// Default distribution index of polynomial mutation
+    pub const DEFAULT_MUTATION_DISTRIBUTION_INDEX: f64 = 20.0;
+
+    /// Default random seed for reproducible fronts
+    pub const DEFAULT_SEED: u64 = 0x5EED_2002;
+
+    /// Smallest population that still allows tournament selection
+    pub const MIN_POPULATION_SIZE: usize = 4;
+
+    /// Minimum number of objectives for a multi-objective search
+    pub const MIN_OBJECTIVES: usize = 2;
+
+    /// Relative tolerance for treating two objective vectors as duplicates
+    pub const DUPLICATE_TOLERANCE: f64 = 1e-9;
+}
+
+/// Whether an objective should be maximized or minimized
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ObjectiveSense {
+    /// Larger values are better
+    Maximize,
+    /// Smaller values are better
+    Minimize,
+}
+
+/// One axis of a multi-objective optimization
+#[derive(Debug, Clone, Copy)]
+pub struct ParetoObjective {
+    /// Quantity measured on each candidate path
+    pub term: ObjectiveTerm,
+    /// Optimization direction
+    pub sense: ObjectiveSense,
+}
+
+impl ParetoObjective {
+    /// Objective that maximizes `term`
+    #[must_use]
+    pub const fn maximize(term: ObjectiveTerm) -> Self {
+        Self { term, sense: ObjectiveSense::Maximize }
+    }
+
+    /// Objective that minimizes `term`
+    #[must_use]
+    pub const fn minimize(term: ObjectiveTerm) -> Self {
+        Self { term, sense: ObjectiveSense::Minimize }
+    }
+
+    /// Human-readable name of the objective, including its unit
+    #[must_use]
+    pub const fn label(&self) -> &'static str {
+        self.term.label()
+    }
+
+    /// Convert a measured value into a cost, where smaller is better
+    fn cost(&self, value: f64) -> f64 {
+        if value.is_nan() {
+            return f64::INFINITY;
+        }
+        match self.sense {
+            ObjectiveSense::Maximize => -value,
+            ObjectiveSense::Minimize => value,
+        }
+    }
+
+    /// Whether `value` is at least as good as `limit`
+    fn satisfies(&self, value: f64, limit: f64) -> bool {
+        match self.sense {
+            ObjectiveSense::Maximize => value >= limit,
+            ObjectiveSense::Minimize => value <= limit,
+        }
+    }
+}
+
+/// NSGA-II settings
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Nsga2Config {
+    /// Number of designs per generation
+    pub population_size: usize,
+    /// Number of generations after the initial population
+    pub generations: usize,
+    /// Probability of recombining two parents (0.0 to 1.0)
+    pub crossover_probability: f64,
+    /// Distribution index of simulated binary crossover (larger keeps children closer to parents)
+    pub crossover_distribution_index: f64,
+    /// Distribution index of polynomial mutation (larger gives smaller mutations)
+    pub mutation_distribution_index: f64,
+    /// Random seed
+    pub seed: u64,
+}
+
+impl Default for Nsga2Config {
+    fn default() -> Self {
+        Self {
+            population_size: constants::DEFAULT_POPULATION_SIZE,
+            generations: constants::DEFAULT_GENERATIONS,
+            crossover_probability: constants::DEFAULT_CROSSOVER_PROBABILITY,
+            crossover_distribution_index: constants::DEFAULT_CROSSOVER_DISTRIBUTION_INDEX,
+            mutation_distribution_index: constants::DEFAULT_MUTATION_DISTRIBUTION_INDEX,
+            seed: constants::DEFAULT_SEED,
+        }
+    }
+}
+
+impl Nsga2Config {
+    /// Use a different random seed
+    #[must_use]
+    pub const fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Total number of candidate evaluations this configuration performs
+    #[must_use]
+    pub const fn evaluations(&self) -> usize {
+        self.population_size * (self.generations + 1)
+    }
+
+    /// Validate the configuration
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the population is too small or a probability or
+    /// distribution index is out of range.
+    pub fn validate(&self) -> ConfigurationResult<()> {
+        if self.population_size < constants::MIN_POPULATION_SIZE {
+            return Err(ConfigurationError::invalid_generation_config(
+                "pareto.population_size",
+                &format!("Must be at least {}", constants::MIN_POPULATION_SIZE),
+            ));
+        }
+        if !(0.0..=1.0).contains(&self.crossover_probability) {
+            return Err(ConfigurationError::invalid_generation_config(
+                "pareto.crossover_probability",
+                "Must be between 0.0 and 1.0",
+            ));
+        }
+        for (field, value) in [
+            ("pareto.crossover_distribution_index", self.crossover_distribution_index),
+            ("pareto.mutation_distribution_index", self.mutation_distribution_index),
+        ] {
+            if !value.is_finite() || value < 0.0 {
+                return Err(ConfigurationError::invalid_generation_config(
+                    field,
+                    "Must be a non-negative, finite value",
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A design on the Pareto front
+#[derive(Debug, Clone)]
+pub struct ParetoSolution {
+    /// Serpentine wave parameters of the design
+    pub params: OptimizationParams,
+    /// Measured value of each objective, in the order they were given
+    pub values: Vec<f64>,
+    /// Geometric measurements of the design's path
+    pub metrics: PathMetrics,
+    /// Whether the design respects the wall and neighbor clearances
+    pub feasible: bool,
+}
+
+impl ParetoSolution {
+    /// Apply this design's parameters to a serpentine configuration
+    ///
+    /// Optimization is disabled in the returned configuration so that
+    /// generation reproduces exactly this design.
+    #[must_use]
+    pub const fn to_config(&self, base: &SerpentineConfig) -> SerpentineConfig {
+        SerpentineConfig {
+            wavelength_factor: self.params.wavelength_factor,
+            wave_density_factor: self.params.wave_density_factor,
+            fill_factor: self.params.fill_factor,
+            optimization_enabled: false,
+            optimization_target: None,
+            objective: None,
+            ..*base
+        }
+    }
+}
+
+/// Non-dominated designs found by a multi-objective optimization
+#[derive(Debug, Clone)]
+pub struct ParetoFront {
+    /// Objectives the front was optimized for
+    pub objectives: Vec<ParetoObjective>,
+    /// Non-dominated designs, sorted by the first objective's value
+    pub solutions: Vec<ParetoSolution>,
+    /// Number of candidate evaluations performed
+    pub evaluations: usize,
+    /// Number of generations performed
+    pub generations: usize,
+}
+
+impl ParetoFront {
+    /// Number of designs on the front
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.solutions.len()
+    }
+
+    /// Whether the front has no designs
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.solutions.is_empty()
+    }
+
+    /// The design with the best value for one objective
+    #[must_use]
+    pub fn best_for(&self, objective_index: usize) -> Option<&ParetoSolution> {
+        let objective = self.objectives.get(objective_index)?;
+        self.solutions.iter().min_by(|a, b| {
+            objective.cost(a.values[objective_index]).total_cmp(&objective.cost(b.values[objective_index]))
+        })
+    }
+
+    /// The knee design: closest to the ideal point with objectives normalized to the front
+    #[must_use]
+    pub fn select_knee(&self) -> Option<&ParetoSolution> {
+        let normalized = self.normalized_costs();
+        self.solutions.iter()
+            .zip(&normalized)
+            .min_by(|(_, a), (_, b)| squared_norm(a).total_cmp(&squared_norm(b)))
+            .map(|(solution, _)| solution)
+    }
+
+    /// The design minimizing a weighted sum of normalized objectives
+    ///
+    /// Each objective is scaled to `[0, 1]` across the front (0 is best), so
+    /// weights express relative importance independent of units. Returns
+    /// `None` if the number of weights does not match the objectives.
+    #[must_use]
+    pub fn select_weighted(&self, weights: &[f64]) -> Option<&ParetoSolution> {
+        if weights.len() != self.objectives.len() {
+            return None;
+        }
+        let normalized = self.normalized_costs();
+        let weighted = |costs: &[f64]| -> f64 { costs.iter().zip(weights).map(|(cost, weight)| cost * weight).sum() };
+        self.solutions.iter()
+            .zip(&normalized)
+            .min_by(|(_, a), (_, b)| weighted(a).total_cmp(&weighted(b)))
+            .map(|(solution, _)| solution)
+    }
+
+    /// The best design for one objective among those meeting limits on others
+    ///
+    /// Each limit `(index, value)` requires objective `index` to be at least as
+    /// good as `value` in its own sense, e.g. a maximum resistance for a
+    /// minimized resistance objective.
+    #[must_use]
+    pub fn select_constrained(&self, objective_index: usize, limits: &[(usize, f64)]) -> Option<&ParetoSolution> {
+        let objective = self.objectives.get(objective_index)?;
+        if limits.iter().any(|&(index, _)| index >= self.objectives.len()) {
+            return None;
+        }
+        self.solutions.iter()
+            .filter(|solution| {
+                limits.iter().all(|&(index, limit)| self.objectives[index].satisfies(solution.values[index], limit))
+            })
+            .min_by(|a, b| {
+                objective.cost(a.values[objective_index]).total_cmp(&objective.cost(b.values[objective_index]))
+            })
+    }
+
+    /// Objective values of every design for two objectives, for plotting
+    #[must_use]
+    pub fn points(&self, x_objective: usize, y_objective: usize) -> Vec<Point2D> {
+        if x_objective >= self.objectives.len() || y_objective >= self.objectives.len() {
+            return Vec::new();
+        }
+        self.solutions.iter()
+            .map(|solution| (solution.values[x_objective], solution.values[y_objective]))
+            .collect()
+    }
+
+    /// Costs of every design scaled to `[0, 1]` per objective (0 is best)
+    fn normalized_costs(&self) -> Vec<Vec<f64>> {
+        let costs: Vec<Vec<f64>> = self.solutions.iter()
+            .map(|solution| {
+                self.objectives.iter().zip(&solution.values).map(|(objective, &value)| objective.cost(value)).collect()
+            })
+            .collect();
+
+        let ranges: Vec<(f64, f64)> = (0..self.objectives.len())
+            .map(|index| {
+                costs.iter().fold((f64::INFINITY, f64::NEG_INFINITY), |(low, high), cost| {
+                    (low.min(cost[index]), high.max(cost[index]))
+                })
+            })
+            .collect();
+
+        costs.into_iter()
+            .map(|cost| {
+                cost.iter().zip(&ranges)
+                    .map(|(&value, &(low, high))| if high > low { (value - low) / (high - low) } else { 0.0 })
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+/// Squared Euclidean norm
+fn squared_norm(values: &[f64]) -> f64 {
+    values.iter().map(|value| value * value).sum()
+}
+
+/// A candidate design during the search
+#[derive(Debug, Clone)]
+struct Individual {
+    genes: Vec<f64>,
+    values: Vec<f64>,
+    costs: Vec<f64>,
+    violation: f64,
+    metrics: PathMetrics,
+    rank: usize,
+    crowding: f64,
+}
+
+impl Individual {
+    /// Constrained domination: feasibility first, then Pareto dominance on costs
+    fn dominates(&self, other: &Self) -> bool {
+        let self_feasible = self.violation <= 0.0;
+        let other_feasible = other.violation <= 0.0;
+        match (self_feasible, other_feasible) {
+            (true, false) => true,
+            (false, true) => false,
+            (false, false) => self.violation < other.violation,
+            (true, true) => {
+                self.costs.iter().zip(&other.costs).all(|(a, b)| a <= b)
+                    && self.costs.iter().zip(&other.costs).any(|(a, b)| a < b)
+            }
+        }
+    }
+
+    /// Crowded-comparison order: lower rank first, then larger crowding distance
+    fn crowded_cmp(&self, other: &Self) -> Ordering {
+        self.rank.cmp(&other.rank).then_with(|| other.crowding.total_cmp(&self.crowding))
+    }
+}
+
+/// Evaluation context shared by all candidates of one search
+struct ParetoSearch<'a> {
+    p1: Point2D,
+    p2: Point2D,
+    serpentine_config: &'a SerpentineConfig,
+    context: &'a ChannelGenerationContext<'a>,
+    objectives: &'a [ParetoObjective],
+    bounds: ParameterBounds,
+    config: &'a Nsga2Config,
+    rng: SplitMix64,
+    evaluations: usize,
+}
+
+impl ParetoSearch<'_> {
+    /// Generate, measure and score a candidate
+    fn evaluate(&mut self, genes: &[f64]) -> Individual {
+        self.evaluations += 1;
+        let genes = self.bounds.clamp(genes);
+        let (metrics, path) = measure_serpentine_candidate(
+            [genes[0], genes[1], genes[2]], self.p1, self.p2, self.serpentine_config, self.context,
+        );
+        let values: Vec<f64> = self.objectives.iter().map(|objective| objective.term.evaluate(&metrics, &path)).collect();
+        let costs = self.objectives.iter().zip(&values).map(|(objective, &value)| objective.cost(value)).collect();
+
+        Individual {
+            genes,
+            values,
+            costs,
+            violation: metrics.clearance_violation,
+            metrics,
+            rank: 0,
+            crowding: 0.0,
+        }
+    }
+
+    /// Binary tournament using the crowded-comparison order
+    fn tournament<'p>(&mut self, population: &'p [Individual]) -> &'p Individual {
+        let first = &population[self.rng.index(population.len())];
+        let second = &population[self.rng.index(population.len())];
+        if second.crowded_cmp(first) == Ordering::Less { second } else { first }
+    }
+
+    /// Simulated binary crossover of two parents
+    fn crossover(&mut self, first: &[f64], second: &[f64]) -> (Vec<f64>, Vec<f64>) {
+        if self.rng.next_f64() >= self.config.crossover_probability {
+            return (first.to_vec(), second.to_vec());
+        }
+
+        let exponent = 1.0 / (self.config.crossover_distribution_index + 1.0);
+        let mut child_a = first.to_vec();
+        let mut child_b = second.to_vec();
+        for index in 0..first.len() {
+            let u = self.rng.next_f64();
+            let beta = if u <= 0.5 {
+                (2.0 * u).powf(exponent)
+            } else {
+                (1.0 / (2.0 * (1.0 - u))).powf(exponent)
+            };
+            let mean = 0.5 * (first[index] + second[index]);
+            let half_difference = 0.5 * beta * (first[index] - second[index]);
+            child_a[index] = mean + half_difference;
+            child_b[index] = mean - half_difference;
+        }
+        (child_a, child_b)
+    }
+
+    /// Polynomial mutation, each gene mutated with probability 1/dimensions
+    #[allow(clippy::cast_precision_loss)]
+    fn mutate(&mut self, genes: &mut [f64]) {
+        let probability = 1.0 / genes.len() as f64;
+        let exponent = 1.0 / (self.config.mutation_distribution_index + 1.0);
+        for (index, gene) in genes.iter_mut().enumerate() {
+            if self.rng.next_f64() >= probability {
+                continue;
+            }
+            let u = self.rng.next_f64();
+            let delta = if u < 0.5 {
+                (2.0 * u).powf(exponent) - 1.0
+            } else {
+                1.0 - (2.0 * (1.0 - u)).powf(exponent)
+            };
+            *gene = delta.mul_add(self.bounds.span(index), *gene);
+        }
+    }
+
+    /// Create a full generation of offspring from the current population
+    fn offspring(&mut self, population: &[Individual]) -> Vec<Individual> {
+        let mut children = Vec::with_capacity(population.len());
+        while children.len() < population.len() {
+            let first = self.tournament(population).genes.clone();
+            let second = self.tournament(population).genes.clone();
+            let (mut child_a, mut child_b) = self.crossover(&first, &second);
+            self.mutate(&mut child_a);
+            self.mutate(&mut child_b);
+
+            children.push(self.evaluate(&child_a));
+            if children.len() < population.len() {
+                children.push(self.evaluate(&child_b));
+            }
+        }
+        children
+    }
+}
+
+/// Sort a population into non-dominated fronts, assigning each individual's rank
+fn non_dominated_sort(population: &mut [Individual]) -> Vec<Vec<usize>> {
+    let count = population.len();
+    let mut dominated_by: Vec<Vec<usize>> = vec![Vec::new(); count];
+    let mut domination_count = vec![0_usize; count];
+    let mut fronts: Vec<Vec<usize>> = vec![Vec::new()];
+
+    for i in 0..count {
+        for j in (i + 1)..count {
+            if population[i].dominates(&population[j]) {
+                dominated_by[i].push(j);
+                domination_count[j] += 1;
+            } else if population[j].dominates(&population[i]) {
+                dominated_by[j].push(i);
+                domination_count[i] += 1;
+            }
+        }
+    }
+
+    for (index, &dominations) in domination_count.iter().enumerate() {
+        if dominations == 0 {
+            population[index].rank = 0;
+            fronts[0].push(index);
+        }
+    }
+
+    let mut current = 0;
+    while !fronts[current].is_empty() {
+        let mut next = Vec::new();
+        for &index in &fronts[current] {
+            for &dominated in &dominated_by[index] {
+                domination_count[dominated] -= 1;
+                if domination_count[dominated] == 0 {
+                    population[dominated].rank = current + 1;
+                    next.push(dominated);
+                }
+            }
+        }
+        current += 1;
+        fronts.push(next);
+    }
+    fronts.pop();
+    fronts
+}
+
+/// Assign crowding distances to the individuals of one front
+fn assign_crowding_distance(population: &mut [Individual], front: &[usize]) {
+    for &index in front {
+        population[index].crowding = 0.0;
+    }
+    if front.len() <= 2 {
+        for &index in front {
+            population[index].crowding = f64::INFINITY;
+        }
+        return;
+    }
+
+    let objective_count = population[front[0]].costs.len();
+    let mut order = front.to_vec();
+    for objective in 0..objective_count {
+        order.sort_by(|&a, &b| population[a].costs[objective].total_cmp(&population[b].costs[objective]));
+        let low = population[order[0]].costs[objective];
+        let high = population[order[order.len() - 1]].costs[objective];
+        population[order[0]].crowding = f64::INFINITY;
+        population[order[order.len() - 1]].crowding = f64::INFINITY;
+
+        let range = high - low;
+        if !range.is_finite() || range <= 0.0 {
+            continue;
+        }
+        for window in 1..order.len() - 1 {
+            let gap = population[order[window + 1]].costs[objective] - population[order[window - 1]].costs[objective];
+            population[order[window]].crowding += gap / range;
+        }
+    }
+}
+
+/// Select the next generation from parents and offspring by rank and crowding
+fn select_survivors(mut combined: Vec<Individual>, size: usize) -> Vec<Individual> {
+    let fronts = non_dominated_sort(&mut combined);
+    for front in &fronts {
+        assign_crowding_distance(&mut combined, front);
+    }
+
+    let mut order: Vec<usize> = (0..combined.len()).collect();
+    order.sort_by(|&a, &b| combined[a].crowded_cmp(&combined[b]));
+    order.truncate(size);
+
+    let mut slots: Vec<Option<Individual>> = combined.into_iter().map(Some).collect();
+    order.into_iter().filter_map(|index| slots[index].take()).collect()
+}
+
+/// Whether two cost vectors are the same design for practical purposes
+fn is_duplicate(a: &[f64], b: &[f64]) -> bool {
+    a.iter().zip(b).all(|(x, y)| (x - y).abs() <= constants::DUPLICATE_TOLERANCE * x.abs().max(y.abs()).max(1.0))
+}
+
+/// Compute the Pareto front of serpentine wave parameters with NSGA-II
+///
+/// Candidates are generated with the real serpentine strategy and measured
+/// as in [`measure_serpentine_candidate`]. The initial population contains the
+/// configuration's own parameters; the remaining designs are sampled
+/// uniformly within [`serpentine_parameter_bounds`].
+///
+/// # Arguments
+/// * `p1` - Start point of the channel
+/// * `p2` - End point of the channel
+/// * `serpentine_config` - Serpentine configuration providing the starting parameters
+/// * `context` - Channel generation context (geometry, box, neighbors)
+/// * `objectives` - Objectives to trade off (at least two)
+/// * `config` - NSGA-II settings
+///
+/// # Returns
+/// The non-dominated designs of the final population. When no design
+/// respects the clearances, the front holds the least-violating designs with
+/// `feasible` set to false.
+///
+/// # Errors
+///
+/// Returns an error if fewer than two objectives are given, an objective term
+/// is invalid, or the NSGA-II configuration is invalid.
+pub fn optimize_serpentine_pareto(
+    p1: Point2D,
+    p2: Point2D,
+    serpentine_config: &SerpentineConfig,
+    context: &ChannelGenerationContext,
+    objectives: &[ParetoObjective],
+    config: &Nsga2Config,
+) -> ConfigurationResult<ParetoFront> {
+    if objectives.len() < constants::MIN_OBJECTIVES {
+        return Err(ConfigurationError::invalid_generation_config(
+            "pareto.objectives",
+            &format!("At least {} objectives are required", constants::MIN_OBJECTIVES),
+        ));
+    }
+    for objective in objectives {
+        objective.term.validate()?;
+    }
+    config.validate()?;
+
+    let mut search = ParetoSearch {
+        p1,
+        p2,
+        serpentine_config,
+        context,
+        objectives,
+        bounds: serpentine_parameter_bounds(),
+        config,
+        rng: SplitMix64::new(config.seed),
+        evaluations: 0,
+    };
+
+    // Seed the population with the configured design, then sample uniformly
+    let mut population = Vec::with_capacity(config.population_size);
+    population.push(search.evaluate(&[
+        serpentine_config.wavelength_factor,
+        serpentine_config.wave_density_factor,
+        serpentine_config.fill_factor,
+    ]));
+    while population.len() < config.population_size {
+        let genes: Vec<f64> = (0..search.bounds.dimensions())
+            .map(|index| search.rng.uniform(search.bounds.lower[index], search.bounds.upper[index]))
+            .collect();
+        population.push(search.evaluate(&genes));
+    }
+    population = select_survivors(population, config.population_size);
+
+    for _ in 0..config.generations {
+        let mut combined = search.offspring(&population);
+        combined.append(&mut population);
+        population = select_survivors(combined, config.population_size);
+    }
+
+    // Keep the first front, without duplicate designs
+    let mut front: Vec<Individual> = Vec::new();
+    for individual in population.into_iter().filter(|individual| individual.rank == 0) {
+        if !front.iter().any(|kept| is_duplicate(&kept.costs, &individual.costs)) {
+            front.push(individual);
+        }
+    }
+    front.sort_by(|a, b| a.values[0].total_cmp(&b.values[0]));
+
+    Ok(ParetoFront {
+        objectives: objectives.to_vec(),
+        solutions: front.into_iter()
+            .map(|individual| ParetoSolution {
+                params: OptimizationParams {
+                    wavelength_factor: individual.genes[0],
+                    wave_density_factor: individual.genes[1],
+                    fill_factor: individual.genes[2],
+                },
+                feasible: individual.violation <= 0.0,
+                values: individual.values,
+                metrics: individual.metrics,
+            })
+            .collect(),
+        evaluations: search.evaluations,
+        generations: config.generations,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn individual(costs: &[f64], violation: f64) -> Individual {
+        Individual {
+            genes: vec![0.0; 3],
+            values: costs.to_vec(),
+            costs: costs.to_vec(),
+            violation,
+            metrics: PathMetrics {
+                path_length: 0.0,
+                max_curvature: 0.0,
+                min_radius_of_curvature: f64::INFINITY,
+                turn_count: 0,
+                min_wall_distance: 0.0,
+                min_neighbor_distance: f64::INFINITY,
+                min_self_spacing: f64::INFINITY,
+                clearance_violation: violation,
+                self_spacing_violation: 0.0,
+                footprint_area: 0.0,
+                channel_width: 1.0,
+                channel_height: 0.5,
+            },
+            rank: 0,
+            crowding: 0.0,
+        }
+    }
+
+    #[test]
+    fn test_constrained_domination() {
+        let feasible = individual(&[5.0, 5.0], 0.0);
+        let infeasible = individual(&[0.0, 0.0], 0.1);
+        let worse_infeasible = individual(&[0.0, 0.0], 0.5);
+        let better = individual(&[4.0, 5.0], 0.0);
+
+        assert!(feasible.dominates(&infeasible));
+        assert!(!infeasible.dominates(&feasible));
+        assert!(infeasible.dominates(&worse_infeasible));
+        assert!(better.dominates(&feasible));
+        assert!(!feasible.dominates(&better));
+        assert!(!feasible.dominates(&feasible.clone()));
+    }
+
+    #[test]
+    fn test_non_dominated_sort_ranks() {
+        let mut population = vec![
+            individual(&[1.0, 4.0], 0.0),
+            individual(&[2.0, 2.0], 0.0),
+            individual(&[4.0, 1.0], 0.0),
+            individual(&[3.0, 3.0], 0.0),
+            individual(&[5.0, 5.0], 0.0),
+        ];
+        let fronts = non_dominated_sort(&mut population);
+
+        assert_eq!(fronts.len(), 3);
+        assert_eq!(fronts[0], vec![0, 1, 2]);
+        assert_eq!(population[3].rank, 1);
+        assert_eq!(population[4].rank, 2);
+
+        assign_crowding_distance(&mut population, &fronts[0]);
+        assert!(population[0].crowding.is_infinite());
+        assert!(population[2].crowding.is_infinite());
+        assert!(population[1].crowding.is_finite() && population[1].crowding > 0.0);
+    }
+}
@@ -0,0 +1,335 @@
+//! `geometry/path_analysis.rs` - Curvature and Bend-Radius Analysis
+//!
+//! Path length alone (see [`calculate_path_length`]) says nothing about how
+//! tightly a channel bends. This module measures the local curvature at every
+//! point of a channel centerline, so serpentines and arcs can be checked for
+//! manufacturability (minimum bend radius) and for the secondary-flow effects
+//! that tight bends cause.
+//!
+//! Curvature at an interior path point is the inverse radius of the circle
+//! through it and its two neighbors. It is signed: positive for left
+//! (counter-clockwise) turns and negative for right turns. End points have no
+//! curvature.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use scheme::config::{ChannelTypeConfig, GeometryConfig, SerpentineConfig};
+//! use scheme::geometry::{generator::create_geometry, SplitType};
+//! use scheme::geometry::path_analysis::analyze_system_curvature;
+//!
+//! let system = create_geometry(
+//!     (200.0, 100.0),
+//!     &[SplitType::Bifurcation],
+//!     &GeometryConfig::default(),
+//!     &ChannelTypeConfig::AllSerpentine(SerpentineConfig::default()),
+//! );
+//!
+//! for (channel, profile) in system.channels.iter().zip(analyze_system_curvature(&system)) {
+//!     println!("channel {}: min radius {:.2} mm, {} turns", channel.id, profile.min_radius, profile.turn_count());
+//! }
+//! ```
+
+use crate::geometry::{
+    builders::ChannelExt,
+    metadata::CurvatureMetadata,
+    optimization::calculate_path_length,
+    spatial_index::channel_paths,
+    types::{ChannelSystem, Point2D},
+};
+use serde::{Deserialize, Serialize};
+
+/// Path analysis constants
+mod constants {
+    /// Segments shorter than this are treated as repeated points (mm)
+    pub const MIN_SEGMENT_LENGTH: f64 = 1e-12;
+
+    /// Accumulated turning angle a bend needs to count as a turn (degrees)
+    pub const MIN_TURN_ANGLE_DEGREES: f64 = 10.0;
+}
+
+/// Curvature at one point of a path
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CurvatureSample {
+    /// Distance along the path from its start (mm)
+    pub arc_length: f64,
+
+    /// Path point
+    pub point: Point2D,
+
+    /// Signed curvature (1/mm), positive for left turns
+    pub curvature: f64,
+
+    /// Signed change of heading at this point (radians)
+    pub turning_angle: f64,
+}
+
+impl CurvatureSample {
+    /// Bend radius at this point (mm), infinite where the path is straight
+    #[must_use]
+    pub fn radius(&self) -> f64 {
+        1.0 / self.curvature.abs()
+    }
+}
+
+/// A stretch of path that keeps turning in the same direction
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Turn {
+    /// Distance along the path where the turn starts (mm)
+    pub start_arc_length: f64,
+
+    /// Distance along the path where the turn ends (mm)
+    pub end_arc_length: f64,
+
+    /// Signed heading change over the turn (radians), positive for left turns
+    pub angle: f64,
+
+    /// Tightest bend radius within the turn (mm)
+    pub min_radius: f64,
+}
+
+/// Distribution of curvature magnitude along a path
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct CurvatureHistogram {
+    /// Bin edges in 1/mm; bin `i` covers `bin_edges[i]..bin_edges[i + 1]`
+    pub bin_edges: Vec<f64>,
+
+    /// Number of path points in each bin
+    pub counts: Vec<usize>,
+
+    /// Path length represented by each bin (mm)
+    pub lengths: Vec<f64>,
+}
+
+impl CurvatureHistogram {
+    /// Number of bins
+    #[must_use]
+    pub const fn bin_count(&self) -> usize {
+        self.counts.len()
+    }
+}
+
+/// Local curvature along a path and the statistics derived from it
+#[derive(Debug, Clone, PartialEq)]
+pub struct CurvatureProfile {
+    /// Curvature at every path point, in path order
+    pub samples: Vec<CurvatureSample>,
+
+    /// Total path length (mm)
+    pub length: f64,
+
+    /// Smallest bend radius along the path (mm), infinite for straight paths
+    pub min_radius: f64,
+
+    /// Point with the smallest bend radius, if the path bends at all
+    pub min_radius_location: Option<Point2D>,
+
+    /// Largest curvature magnitude (1/mm)
+    pub max_curvature: f64,
+
+    /// Length-weighted mean curvature magnitude (1/mm)
+    pub mean_curvature: f64,
+
+    /// Sum of absolute heading changes (radians)
+    pub total_turning_angle: f64,
+
+    /// Bends of at least 10° in a consistent direction, in path order
+    pub turns: Vec<Turn>,
+}
+
+impl CurvatureProfile {
+    /// Number of turns along the path
+    #[must_use]
+    pub const fn turn_count(&self) -> usize {
+        self.turns.len()
+    }
+
+    /// Number of turns whose tightest radius is below `radius`
+    #[must_use]
+    pub fn turns_tighter_than(&self, radius: f64) -> usize {
+        self.turns.iter().filter(|turn| turn.min_radius < radius).count()
+    }
+
+    /// Histogram of curvature magnitude with `bin_count` equal bins from zero
+    /// to the maximum curvature
+    ///
+    /// Each path point is weighted by half the length of its adjacent
+    /// segments, so `lengths` sums to the path length.
+    #[must_use]
+    pub fn histogram(&self, bin_count: usize) -> CurvatureHistogram {
+        if bin_count == 0 {
+            return CurvatureHistogram::default();
+        }
+
+        let upper = if self.max_curvature > 0.0 { self.max_curvature } else { 1.0 };
+        #[allow(clippy::cast_precision_loss)]
+        let bin_width = upper / bin_count as f64;
+        #[allow(clippy::cast_precision_loss)]
+        let bin_edges = (0..=bin_count).map(|bin| bin as f64 * bin_width).collect();
+        let mut counts = vec![0; bin_count];
+        let mut lengths = vec![0.0; bin_count];
+
+        for (index, sample) in self.samples.iter().enumerate() {
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            let bin = ((sample.curvature.abs() / bin_width) as usize).min(bin_count - 1);
+            counts[bin] += 1;
+            lengths[bin] += self.sample_weight(index);
+        }
+
+        CurvatureHistogram { bin_edges, counts, lengths }
+    }
+
+    /// Summary of this profile for storage in channel metadata
+    #[must_use]
+    pub fn to_metadata(&self, histogram_bins: usize) -> CurvatureMetadata {
+        CurvatureMetadata {
+            min_radius: self.min_radius,
+            max_curvature: self.max_curvature,
+            mean_curvature: self.mean_curvature,
+            total_turning_angle: self.total_turning_angle,
+            turn_count: self.turn_count(),
+            histogram: self.histogram(histogram_bins),
+        }
+    }
+
+    /// Path length represented by one sample: half of each adjacent segment
+    pub(crate) fn sample_weight(&self, index: usize) -> f64 {
+        let before = index.checked_sub(1).map_or(0.0, |previous| {
+            self.samples[index].arc_length - self.samples[previous].arc_length
+        });
+        let after = self.samples.get(index + 1).map_or(0.0, |next| {
+            next.arc_length - self.samples[index].arc_length
+        });
+        f64::midpoint(before, after)
+    }
+}
+
+/// Compute the local curvature along a path
+///
+/// # Arguments
+/// * `path` - Centerline points of a channel
+///
+/// # Returns
+/// The curvature at every point together with the minimum radius, mean
+/// curvature, total turning and the individual turns of the path
+#[must_use]
+pub fn analyze_path_curvature(path: &[Point2D]) -> CurvatureProfile {
+    let mut samples = Vec::with_capacity(path.len());
+    let mut arc_length = 0.0;
+
+    for (index, &point) in path.iter().enumerate() {
+        if index > 0 {
+            arc_length += distance(path[index - 1], point);
+        }
+        let (curvature, turning_angle) = match (index.checked_sub(1), path.get(index + 1)) {
+            (Some(previous), Some(&next)) => vertex_curvature(path[previous], point, next),
+            _ => (0.0, 0.0),
+        };
+        samples.push(CurvatureSample { arc_length, point, curvature, turning_angle });
+    }
+
+    let tightest = samples.iter()
+        .filter(|sample| sample.curvature != 0.0)
+        .max_by(|a, b| a.curvature.abs().total_cmp(&b.curvature.abs()));
+    let max_curvature = tightest.map_or(0.0, |sample| sample.curvature.abs());
+    let min_radius_location = tightest.map(|sample| sample.point);
+    let total_turning_angle = samples.iter().map(|sample| sample.turning_angle.abs()).sum();
+
+    let mut profile = CurvatureProfile {
+        turns: find_turns(&samples),
+        samples,
+        length: calculate_path_length(path),
+        min_radius: 1.0 / max_curvature,
+        min_radius_location,
+        max_curvature,
+        mean_curvature: 0.0,
+        total_turning_angle,
+    };
+    if profile.length > 0.0 {
+        profile.mean_curvature = (0..profile.samples.len())
+            .map(|index| profile.samples[index].curvature.abs() * profile.sample_weight(index))
+            .sum::<f64>()
+            / profile.length;
+    }
+    profile
+}
+
+/// Curvature profile of every channel in a system, in channel order
+///
+/// Straight channels are analyzed as the line between their nodes.
+#[must_use]
+pub fn analyze_system_curvature(system: &ChannelSystem) -> Vec<CurvatureProfile> {
+    channel_paths(system).iter().map(|path| analyze_path_curvature(path)).collect()
+}
+
+/// Analyze every channel of a system and store the results as
+/// [`CurvatureMetadata`] on each channel
+///
+/// # Arguments
+/// * `system` - Channel system to annotate
+/// * `histogram_bins` - Number of bins in each stored curvature histogram
+pub fn attach_curvature_metadata(system: &mut ChannelSystem, histogram_bins: usize) {
+    let profiles = analyze_system_curvature(system);
+    for (channel, profile) in system.channels.iter_mut().zip(profiles) {
+        channel.add_metadata(profile.to_metadata(histogram_bins));
+    }
+}
+
+/// Signed curvature and heading change at `point` between its neighbors
+fn vertex_curvature(previous: Point2D, point: Point2D, next: Point2D) -> (f64, f64) {
+    let incoming = (point.0 - previous.0, point.1 - previous.1);
+    let outgoing = (next.0 - point.0, next.1 - point.1);
+    let chord = distance(previous, next);
+    let (incoming_length, outgoing_length) = (incoming.0.hypot(incoming.1), outgoing.0.hypot(outgoing.1));
+    if incoming_length < constants::MIN_SEGMENT_LENGTH || outgoing_length < constants::MIN_SEGMENT_LENGTH {
+        return (0.0, 0.0);
+    }
+
+    let cross = incoming.0.mul_add(outgoing.1, -(incoming.1 * outgoing.0));
+    let dot = incoming.0.mul_add(outgoing.0, incoming.1 * outgoing.1);
+    let turning_angle = cross.atan2(dot);
+    if chord < constants::MIN_SEGMENT_LENGTH {
+        // The path doubles back on itself
+        return (f64::INFINITY.copysign(turning_angle), turning_angle);
+    }
+
+    (2.0 * cross / (incoming_length * outgoing_length * chord), turning_angle)
+}
+
+/// Group consecutive samples that turn in the same direction into turns
+fn find_turns(samples: &[CurvatureSample]) -> Vec<Turn> {
+    let min_angle = constants::MIN_TURN_ANGLE_DEGREES.to_radians();
+    let mut turns = Vec::new();
+    let mut current: Option<Turn> = None;
+
+    for sample in samples {
+        if sample.turning_angle == 0.0 {
+            continue;
+        }
+        match &mut current {
+            Some(turn) if turn.angle.signum() == sample.turning_angle.signum() => {
+                turn.end_arc_length = sample.arc_length;
+                turn.angle += sample.turning_angle;
+                turn.min_radius = turn.min_radius.min(sample.radius());
+            }
+            _ => {
+                if let Some(turn) = current.take().filter(|turn| turn.angle.abs() >= min_angle) {
+                    turns.push(turn);
+                }
+                current = Some(Turn {
+                    start_arc_length: sample.arc_length,
+                    end_arc_length: sample.arc_length,
+                    angle: sample.turning_angle,
+                    min_radius: sample.radius(),
+                });
+            }
+        }
+    }
+    turns.extend(current.filter(|turn| turn.angle.abs() >= min_angle));
+    turns
+}
+
+/// Euclidean distance between two points
+fn distance(a: Point2D, b: Point2D) -> f64 {
+    (b.0 - a.0).hypot(b.1 - a.1)
+}
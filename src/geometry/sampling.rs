@@ -0,0 +1,167 @@
+//! `geometry/sampling.rs` - Adaptive, Curvature-Based Path Sampling
+//!
+//! Strategies normally sample their curves at a fixed number of points
+//! ([`GeometryGenerationConfig::serpentine_points`] and friends), which wastes
+//! points on straight stretches and undersamples tight bends. The samplers in
+//! this module place points where the curve needs them instead: a segment is
+//! split until the curve stays within a chord-error tolerance of it and turns
+//! by no more than an angular tolerance across it.
+//!
+//! [`sample_curve`] works on an analytic curve given as a function of a
+//! parameter in `[0, 1]`; all built-in path-producing strategies use it when
+//! [`GeometryGenerationConfig::adaptive_sampling`] is set. [`simplify_path`]
+//! applies the same tolerances to an already sampled path, such as one passed
+//! to a custom channel. Both hit the path endpoints exactly.
+//!
+//! [`GeometryGenerationConfig::serpentine_points`]: crate::config::GeometryGenerationConfig::serpentine_points
+//! [`GeometryGenerationConfig::adaptive_sampling`]: crate::config::GeometryGenerationConfig::adaptive_sampling
+//!
+//! # Examples
+//!
+//! ```rust
+//! use scheme::config::AdaptiveSamplingConfig;
+//! use scheme::geometry::sampling::sample_curve;
+//! use std::f64::consts::PI;
+//!
+//! // Quarter circle of radius 10 mm
+//! let arc = |t: f64| (10.0 * (t * PI / 2.0).cos(), 10.0 * (t * PI / 2.0).sin());
+//! let path = sample_curve(arc, 1, &AdaptiveSamplingConfig::default());
+//!
+//! assert_eq!(path.first(), Some(&(10.0, 0.0)));
+//! assert!(path.len() > 2 && path.len() < 50);
+//! ```
+
+use crate::config::AdaptiveSamplingConfig;
+use crate::geometry::{spatial_index::closest_point_on_segment, Point2D};
+
+/// Sampling constants
+mod constants {
+    /// Maximum number of times a parameter interval is halved
+    pub const MAX_SUBDIVISION_DEPTH: usize = 20;
+
+    /// Segments shorter than this have no direction (mm)
+    pub const MIN_SEGMENT_LENGTH: f64 = 1e-12;
+}
+
+/// Sample a parametric curve adaptively
+///
+/// `curve` maps a parameter in `[0, 1]` to a point. The parameter range is
+/// first split into `min_segments` equal intervals, each of which is then
+/// halved until its chord is within `config` of the curve. Each interval is
+/// judged by its midpoint, so `min_segments` should be large enough that no
+/// interval spans a full oscillation of the curve (e.g. two per half-wave of a
+/// serpentine).
+///
+/// The first and last points are exactly `curve(0.0)` and `curve(1.0)`.
+#[must_use]
+pub fn sample_curve<F>(curve: F, min_segments: usize, config: &AdaptiveSamplingConfig) -> Vec<Point2D>
+where
+    F: Fn(f64) -> Point2D,
+{
+    sample(&curve, min_segments, config).into_iter().map(|(_, point)| point).collect()
+}
+
+/// Parameters at which [`sample_curve`] would place its points
+///
+/// Useful when other per-point quantities, such as channel widths, have to be
+/// evaluated at the same parameters as the path.
+#[must_use]
+pub fn sample_parameters<F>(curve: F, min_segments: usize, config: &AdaptiveSamplingConfig) -> Vec<f64>
+where
+    F: Fn(f64) -> Point2D,
+{
+    sample(&curve, min_segments, config).into_iter().map(|(t, _)| t).collect()
+}
+
+/// Remove path points that are not needed to stay within the tolerances
+///
+/// Each point that is kept starts a segment that is extended over as many
+/// original points as possible, as long as every skipped point stays within
+/// `max_chord_error` of it and the original path turns by no more than
+/// `max_turn_angle` along it. Sampled points are never moved, and the first
+/// and last points are always kept.
+#[must_use]
+pub fn simplify_path(path: &[Point2D], config: &AdaptiveSamplingConfig) -> Vec<Point2D> {
+    if path.len() <= 2 {
+        return path.to_vec();
+    }
+
+    let mut simplified = vec![path[0]];
+    let mut anchor = 0;
+    let mut turning = 0.0;
+    for end in 2..path.len() {
+        turning += turn_angle(path[end - 2], path[end - 1], path[end]);
+        let within_chord_error = path[anchor + 1..end].iter().all(|&point| {
+            distance(point, closest_point_on_segment(point, path[anchor], path[end])) <= config.max_chord_error
+        });
+        if !within_chord_error || turning > config.max_turn_angle {
+            anchor = end - 1;
+            turning = 0.0;
+            simplified.push(path[anchor]);
+        }
+    }
+    simplified.push(path[path.len() - 1]);
+    simplified
+}
+
+/// Adaptively sampled `(parameter, point)` pairs of `curve`
+#[allow(clippy::cast_precision_loss)]
+fn sample<F>(curve: &F, min_segments: usize, config: &AdaptiveSamplingConfig) -> Vec<(f64, Point2D)>
+where
+    F: Fn(f64) -> Point2D,
+{
+    let segments = min_segments.max(1);
+    let mut samples = vec![(0.0, curve(0.0))];
+    for segment in 1..=segments {
+        let start = samples[samples.len() - 1];
+        let t = segment as f64 / segments as f64;
+        refine(curve, start, (t, curve(t)), 0, config, &mut samples);
+    }
+    samples
+}
+
+/// Append the samples of the interval between `start` and `end`, excluding `start`
+fn refine<F>(
+    curve: &F,
+    start: (f64, Point2D),
+    end: (f64, Point2D),
+    depth: usize,
+    config: &AdaptiveSamplingConfig,
+    samples: &mut Vec<(f64, Point2D)>,
+) where
+    F: Fn(f64) -> Point2D,
+{
+    let t = f64::midpoint(start.0, end.0);
+    let middle = (t, curve(t));
+
+    // The chord between two samples turns by about twice the angle between
+    // its two half-chords over the curve
+    let chord_error = distance(middle.1, closest_point_on_segment(middle.1, start.1, end.1));
+    let turn = 2.0 * turn_angle(start.1, middle.1, end.1);
+
+    if depth < constants::MAX_SUBDIVISION_DEPTH && (chord_error > config.max_chord_error || turn > config.max_turn_angle) {
+        refine(curve, start, middle, depth + 1, config, samples);
+        refine(curve, middle, end, depth + 1, config, samples);
+    } else {
+        samples.push(end);
+    }
+}
+
+/// Absolute change of direction at `b` when walking from `a` through `b` to `c`
+fn turn_angle(a: Point2D, b: Point2D, c: Point2D) -> f64 {
+    let incoming = (b.0 - a.0, b.1 - a.1);
+    let outgoing = (c.0 - b.0, c.1 - b.1);
+    if incoming.0.hypot(incoming.1) < constants::MIN_SEGMENT_LENGTH
+        || outgoing.0.hypot(outgoing.1) < constants::MIN_SEGMENT_LENGTH
+    {
+        return 0.0;
+    }
+    let cross = incoming.0.mul_add(outgoing.1, -(incoming.1 * outgoing.0));
+    let dot = incoming.0.mul_add(outgoing.0, incoming.1 * outgoing.1);
+    cross.atan2(dot).abs()
+}
+
+/// Euclidean distance between two points
+fn distance(a: Point2D, b: Point2D) -> f64 {
+    (b.0 - a.0).hypot(b.1 - a.1)
+}
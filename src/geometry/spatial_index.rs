@@ -0,0 +1,621 @@
+//! `geometry/spatial_index.rs` - Exact Channel Proximity Queries
+//!
+//! Generation-time collision checks reason about neighbors through their
+//! y-coordinates, which misses angled arcs, serpentines in different stages
+//! and channels that don't run horizontally. This module measures the true
+//! distance between channel centerline polylines and finds where they cross.
+//!
+//! Segments are bucketed in a uniform grid so that each segment is only
+//! compared with segments in nearby cells, which keeps checks fast for designs
+//! with thousands of segments.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use scheme::config::{ChannelTypeConfig, GeometryConfig, SerpentineConfig};
+//! use scheme::geometry::{generator::create_geometry, SplitType};
+//! use scheme::geometry::spatial_index::ChannelSpatialIndex;
+//!
+//! let system = create_geometry(
+//!     (200.0, 100.0),
+//!     &[SplitType::Bifurcation],
+//!     &GeometryConfig::default(),
+//!     &ChannelTypeConfig::AllSerpentine(SerpentineConfig::default()),
+//! );
+//!
+//! let index = ChannelSpatialIndex::new(&system);
+//! assert!(index.intersections().is_empty());
+//! for proximity in index.proximities_within(0.5) {
+//!     println!("channels {} and {} are {:.2} mm apart", proximity.first_channel, proximity.second_channel, proximity.spacing);
+//! }
+//! ```
+
+use crate::geometry::types::{ChannelSystem, ChannelType, Point2D};
+use std::collections::HashMap;
+use std::ops::Range;
+
+/// Spatial index constants
+mod constants {
+    /// Radius around a node, in multiples of channel width plus junction
+    /// clearance, within which channels meeting at that node are not checked
+    /// against each other
+    pub const JUNCTION_EXCLUSION_FACTOR: f64 = 4.0;
+
+    /// Grid cell size in multiples of the mean segment length
+    pub const CELL_SIZE_SEGMENT_FACTOR: f64 = 2.0;
+
+    /// Maximum number of grid cells along the longer side of the layout
+    pub const MAX_CELLS_PER_SIDE: f64 = 1024.0;
+
+    /// Smallest allowed grid cell size (mm)
+    pub const MIN_CELL_SIZE: f64 = 1e-6;
+
+    /// Contacts closer than this are the same intersection (mm)
+    pub const CONTACT_TOLERANCE: f64 = 1e-9;
+
+    /// Separation along a path, in multiples of the search distance, below
+    /// which two points belong to the same bend rather than a self-approach
+    pub const SELF_APPROACH_ARC_FACTOR: f64 = std::f64::consts::FRAC_PI_2;
+}
+
+/// Closest approach between two channels
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ChannelProximity {
+    /// Index of the first channel in `ChannelSystem::channels`
+    pub first_channel: usize,
+    /// Index of the second channel in `ChannelSystem::channels`
+    pub second_channel: usize,
+    /// Distance between the channel centerlines (mm)
+    pub centerline_distance: f64,
+    /// Distance between the channel edges, negative when they overlap (mm)
+    pub spacing: f64,
+    /// Closest point on the first channel's centerline
+    pub first_point: Point2D,
+    /// Closest point on the second channel's centerline
+    pub second_point: Point2D,
+}
+
+/// A point where two channel centerlines cross
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ChannelIntersection {
+    /// Index of the first channel in `ChannelSystem::channels`
+    pub first_channel: usize,
+    /// Index of the second channel in `ChannelSystem::channels`
+    pub second_channel: usize,
+    /// Index of the crossing segment in the first channel's path
+    pub first_segment: usize,
+    /// Index of the crossing segment in the second channel's path
+    pub second_segment: usize,
+    /// Crossing point
+    pub point: Point2D,
+}
+
+/// Closest approach between two distant parts of the same path
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SelfApproach {
+    /// Index of the earlier segment in the path
+    pub first_segment: usize,
+    /// Index of the later segment in the path
+    pub second_segment: usize,
+    /// Distance between the two parts of the centerline (mm)
+    pub centerline_distance: f64,
+    /// Distance along the path between the two closest points (mm)
+    pub arc_separation: f64,
+    /// Closest point on the earlier segment
+    pub first_point: Point2D,
+    /// Closest point on the later segment
+    pub second_point: Point2D,
+}
+
+/// A path segment stored in the index
+#[derive(Debug, Clone, Copy)]
+struct IndexedSegment {
+    channel: usize,
+    segment: usize,
+    start: Point2D,
+    end: Point2D,
+}
+
+/// Uniform grid of segment bounding boxes
+#[derive(Debug, Clone)]
+struct SegmentGrid {
+    origin: Point2D,
+    cell_size: f64,
+    max_cell: (i64, i64),
+    cells: HashMap<(i64, i64), Vec<usize>>,
+}
+
+impl SegmentGrid {
+    fn new(segments: &[IndexedSegment]) -> Self {
+        let (min, max) = segments.iter().fold(
+            ((f64::INFINITY, f64::INFINITY), (f64::NEG_INFINITY, f64::NEG_INFINITY)),
+            |(min, max), segment| {
+                (
+                    (min.0.min(segment.start.0).min(segment.end.0), min.1.min(segment.start.1).min(segment.end.1)),
+                    (max.0.max(segment.start.0).max(segment.end.0), max.1.max(segment.start.1).max(segment.end.1)),
+                )
+            },
+        );
+        if segments.is_empty() {
+            return Self { origin: (0.0, 0.0), cell_size: 1.0, max_cell: (0, 0), cells: HashMap::new() };
+        }
+
+        #[allow(clippy::cast_precision_loss)]
+        let mean_length = segments.iter().map(|segment| distance(segment.start, segment.end)).sum::<f64>()
+            / segments.len() as f64;
+        let extent = (max.0 - min.0).max(max.1 - min.1);
+        let cell_size = (mean_length * constants::CELL_SIZE_SEGMENT_FACTOR)
+            .max(extent / constants::MAX_CELLS_PER_SIDE)
+            .max(constants::MIN_CELL_SIZE);
+
+        let mut grid = Self { origin: min, cell_size, max_cell: (0, 0), cells: HashMap::new() };
+        grid.max_cell = grid.cell(max);
+        for (index, segment) in segments.iter().enumerate() {
+            let (low, high) = segment_bounds(segment.start, segment.end, 0.0);
+            for cell in grid.cells_in(low, high) {
+                grid.cells.entry(cell).or_default().push(index);
+            }
+        }
+        grid
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    fn cell(&self, point: Point2D) -> (i64, i64) {
+        (
+            ((point.0 - self.origin.0) / self.cell_size).floor() as i64,
+            ((point.1 - self.origin.1) / self.cell_size).floor() as i64,
+        )
+    }
+
+    /// Cells overlapping a box, clamped to the occupied part of the grid
+    fn cells_in(&self, low: Point2D, high: Point2D) -> impl Iterator<Item = (i64, i64)> {
+        let (low_x, low_y) = self.cell(low);
+        let (high_x, high_y) = self.cell(high);
+        let (low_x, low_y) = (low_x.max(0), low_y.max(0));
+        let (high_x, high_y) = (high_x.min(self.max_cell.0), high_y.min(self.max_cell.1));
+        (low_x..=high_x).flat_map(move |x| (low_y..=high_y).map(move |y| (x, y)))
+    }
+
+    /// Indices of segments whose cells overlap a box, possibly with duplicates
+    fn candidates(&self, low: Point2D, high: Point2D) -> impl Iterator<Item = usize> + '_ {
+        self.cells_in(low, high)
+            .filter_map(|cell| self.cells.get(&cell))
+            .flatten()
+            .copied()
+    }
+}
+
+/// Spatial index over the centerline segments of every channel in a system
+#[derive(Debug, Clone)]
+pub struct ChannelSpatialIndex {
+    junction_clearance: f64,
+    widths: Vec<f64>,
+    nodes: Vec<[Point2D; 2]>,
+    node_ids: Vec<[usize; 2]>,
+    segments: Vec<IndexedSegment>,
+    channel_segments: Vec<Range<usize>>,
+    grid: SegmentGrid,
+}
+
+impl ChannelSpatialIndex {
+    /// Index the channel paths of a system
+    ///
+    /// Straight channels are indexed as the line between their nodes.
+    #[must_use]
+    pub fn new(system: &ChannelSystem) -> Self {
+        Self::with_paths(system, &channel_paths(system))
+    }
+
+    /// Index replacement paths for the channels of a system
+    ///
+    /// `paths[i]` is used as the centerline of `system.channels[i]`, which lets
+    /// optimizers check candidate paths without rebuilding the system.
+    #[must_use]
+    pub fn with_paths(system: &ChannelSystem, paths: &[Vec<Point2D>]) -> Self {
+        let segments: Vec<IndexedSegment> = paths.iter()
+            .enumerate()
+            .flat_map(|(channel, path)| {
+                path.windows(2).enumerate().map(move |(segment, points)| IndexedSegment {
+                    channel,
+                    segment,
+                    start: points[0],
+                    end: points[1],
+                })
+            })
+            .collect();
+        let mut start = 0;
+        let channel_segments = paths.iter()
+            .map(|path| {
+                let range = start..start + path.len().saturating_sub(1);
+                start = range.end;
+                range
+            })
+            .collect();
+
+        Self {
+            junction_clearance: 0.0,
+            widths: system.channels.iter().map(|channel| channel.width).collect(),
+            nodes: system.channels.iter()
+                .map(|channel| [system.nodes[channel.from_node].point, system.nodes[channel.to_node].point])
+                .collect(),
+            node_ids: system.channels.iter().map(|channel| [channel.from_node, channel.to_node]).collect(),
+            grid: SegmentGrid::new(&segments),
+            segments,
+            channel_segments,
+        }
+    }
+
+    /// Set the clearance used to size the region around shared nodes that is not checked
+    ///
+    /// Connected channels are ignored within `4 × (width + clearance)` of
+    /// the node they share. Defaults to zero.
+    #[must_use]
+    pub const fn with_junction_clearance(mut self, clearance: f64) -> Self {
+        self.junction_clearance = clearance;
+        self
+    }
+
+    /// Number of indexed segments
+    #[must_use]
+    pub const fn segment_count(&self) -> usize {
+        self.segments.len()
+    }
+
+    /// Whether two segments of connected channels are next to the node they share
+    fn near_shared_node(&self, a: &IndexedSegment, b: &IndexedSegment) -> bool {
+        let exclusion_radius = constants::JUNCTION_EXCLUSION_FACTOR
+            * (self.widths[a.channel].max(self.widths[b.channel]) + self.junction_clearance);
+        (0..2)
+            .filter(|&end| self.node_ids[b.channel].contains(&self.node_ids[a.channel][end]))
+            .map(|end| self.nodes[a.channel][end])
+            .any(|node| [a.start, a.end, b.start, b.end].iter().any(|&point| distance(point, node) < exclusion_radius))
+    }
+
+    /// Closest approach of every pair of channels whose edges are closer than `max_spacing`
+    ///
+    /// Channels that meet at a node necessarily touch there, so segments of
+    /// connected channels near their shared node are ignored. Each pair is
+    /// reported once, at its closest approach, ordered by channel indices.
+    #[must_use]
+    pub fn proximities_within(&self, max_spacing: f64) -> Vec<ChannelProximity> {
+        let max_width = self.widths.iter().copied().fold(0.0, f64::max);
+        let mut closest: HashMap<(usize, usize), ChannelProximity> = HashMap::new();
+        let mut seen = vec![usize::MAX; self.segments.len()];
+
+        for (index, a) in self.segments.iter().enumerate() {
+            let reach = max_spacing + f64::midpoint(self.widths[a.channel], max_width);
+            let (low, high) = segment_bounds(a.start, a.end, reach);
+            for candidate in self.grid.candidates(low, high) {
+                let b = &self.segments[candidate];
+                if b.channel <= a.channel || seen[candidate] == index {
+                    continue;
+                }
+                seen[candidate] = index;
+
+                let half_widths = f64::midpoint(self.widths[a.channel], self.widths[b.channel]);
+                let pair = (a.channel, b.channel);
+                let limit = closest.get(&pair).map_or(max_spacing + half_widths, |found| found.centerline_distance);
+                let (low_b, high_b) = segment_bounds(b.start, b.end, 0.0);
+                if low_b.0 > a.start.0.max(a.end.0) + limit
+                    || high_b.0 < a.start.0.min(a.end.0) - limit
+                    || low_b.1 > a.start.1.max(a.end.1) + limit
+                    || high_b.1 < a.start.1.min(a.end.1) - limit
+                {
+                    continue;
+                }
+
+                if self.near_shared_node(a, b) {
+                    continue;
+                }
+
+                let (centerline_distance, first_point, second_point) =
+                    segment_closest_points(a.start, a.end, b.start, b.end);
+                if centerline_distance - half_widths < max_spacing && centerline_distance < limit {
+                    closest.insert(pair, ChannelProximity {
+                        first_channel: a.channel,
+                        second_channel: b.channel,
+                        centerline_distance,
+                        spacing: centerline_distance - half_widths,
+                        first_point,
+                        second_point,
+                    });
+                }
+            }
+        }
+
+        let mut proximities: Vec<ChannelProximity> = closest.into_values().collect();
+        proximities.sort_by_key(|proximity| (proximity.first_channel, proximity.second_channel));
+        proximities
+    }
+
+    /// Closest approach between two channels, given as indices into `ChannelSystem::channels`
+    ///
+    /// Returns `None` if either channel has no segments, or if every segment
+    /// of one is next to a node shared with the other.
+    #[must_use]
+    pub fn channel_distance(&self, first: usize, second: usize) -> Option<ChannelProximity> {
+        let (first, second) = (first.min(second), first.max(second));
+        let half_widths = f64::midpoint(self.widths[first], self.widths[second]);
+        let mut closest: Option<ChannelProximity> = None;
+
+        for a in &self.segments[self.channel_segments[first].clone()] {
+            for b in &self.segments[self.channel_segments[second].clone()] {
+                if self.near_shared_node(a, b) {
+                    continue;
+                }
+                let (centerline_distance, first_point, second_point) =
+                    segment_closest_points(a.start, a.end, b.start, b.end);
+                if closest.is_none_or(|found| centerline_distance < found.centerline_distance) {
+                    closest = Some(ChannelProximity {
+                        first_channel: first,
+                        second_channel: second,
+                        centerline_distance,
+                        spacing: centerline_distance - half_widths,
+                        first_point,
+                        second_point,
+                    });
+                }
+            }
+        }
+        closest
+    }
+
+    /// Every point where the centerlines of two different channels cross or touch
+    ///
+    /// Contacts within half a channel width of a node shared by both channels
+    /// are where connected channels meet, so they are not reported. A crossing
+    /// through a path vertex is reported once. Collinear overlaps are not
+    /// reported as intersections, but show up in [`Self::proximities_within`].
+    #[must_use]
+    pub fn intersections(&self) -> Vec<ChannelIntersection> {
+        let mut intersections = Vec::new();
+        let mut seen = vec![usize::MAX; self.segments.len()];
+
+        for (index, a) in self.segments.iter().enumerate() {
+            let (low, high) = segment_bounds(a.start, a.end, 0.0);
+            for candidate in self.grid.candidates(low, high) {
+                let b = &self.segments[candidate];
+                if b.channel <= a.channel || seen[candidate] == index {
+                    continue;
+                }
+                seen[candidate] = index;
+
+                let Some(point) = segment_contact(a.start, a.end, b.start, b.end) else {
+                    continue;
+                };
+                let tolerance = self.widths[a.channel].max(self.widths[b.channel]) / 2.0;
+                let at_shared_node = (0..2)
+                    .filter(|&end| self.node_ids[b.channel].contains(&self.node_ids[a.channel][end]))
+                    .any(|end| distance(self.nodes[a.channel][end], point) < tolerance);
+                let duplicate = intersections.iter().any(|found: &ChannelIntersection| {
+                    found.first_channel == a.channel
+                        && found.second_channel == b.channel
+                        && distance(found.point, point) <= constants::CONTACT_TOLERANCE
+                });
+                if !at_shared_node && !duplicate {
+                    intersections.push(ChannelIntersection {
+                        first_channel: a.channel,
+                        second_channel: b.channel,
+                        first_segment: a.segment,
+                        second_segment: b.segment,
+                        point,
+                    });
+                }
+            }
+        }
+
+        intersections.sort_by_key(|intersection| {
+            (intersection.first_channel, intersection.second_channel, intersection.first_segment, intersection.second_segment)
+        });
+        intersections
+    }
+}
+
+/// Centerline paths of every channel, using the node positions for straight channels
+#[must_use]
+pub fn channel_paths(system: &ChannelSystem) -> Vec<Vec<Point2D>> {
+    system.channels.iter()
+        .map(|channel| match &channel.channel_type {
+            ChannelType::Straight => vec![system.nodes[channel.from_node].point, system.nodes[channel.to_node].point],
+            ChannelType::SmoothStraight { path }
+            | ChannelType::Serpentine { path }
+            | ChannelType::Arc { path }
+            | ChannelType::Frustum { path, .. } => path.clone(),
+        })
+        .collect()
+}
+
+/// Closest approach between parts of one path that are far apart along it
+///
+/// A path folding back on itself, such as adjacent half-waves of a tight
+/// serpentine, can come closer to itself than its own width. Points within
+/// `π/2 × max_distance` of each other along the path belong to the same bend
+/// (a half circle of diameter `max_distance` is that long), so they are not
+/// compared.
+///
+/// Segments are swept in order along the longer axis of the path, so only
+/// segments whose extents overlap within `max_distance` are compared exactly.
+///
+/// # Arguments
+/// * `path` - Centerline points
+/// * `max_distance` - Centerline distance below which an approach is reported (mm)
+///
+/// # Returns
+/// The closest such approach, or `None` if no distant parts of the path come
+/// within `max_distance` of each other
+#[must_use]
+pub fn path_self_approach(path: &[Point2D], max_distance: f64) -> Option<SelfApproach> {
+    let bounds: Vec<(Point2D, Point2D)> = path.windows(2)
+        .map(|points| segment_bounds(points[0], points[1], 0.0))
+        .collect();
+    let arc_lengths: Vec<f64> = std::iter::once(0.0)
+        .chain(path.windows(2).scan(0.0, |length, points| {
+            *length += distance(points[0], points[1]);
+            Some(*length)
+        }))
+        .collect();
+    let min_separation = constants::SELF_APPROACH_ARC_FACTOR * max_distance;
+
+    // Sweep along the longer axis of the path
+    let (low, high) = bounds.iter().fold(
+        ((f64::INFINITY, f64::INFINITY), (f64::NEG_INFINITY, f64::NEG_INFINITY)),
+        |(low, high), &(segment_low, segment_high)| {
+            ((low.0.min(segment_low.0), low.1.min(segment_low.1)), (high.0.max(segment_high.0), high.1.max(segment_high.1)))
+        },
+    );
+    let sweep_x = high.0 - low.0 >= high.1 - low.1;
+    let along = |point: Point2D| if sweep_x { point.0 } else { point.1 };
+    let across = |point: Point2D| if sweep_x { point.1 } else { point.0 };
+    let mut order: Vec<usize> = (0..bounds.len()).collect();
+    order.sort_by(|&a, &b| along(bounds[a].0).total_cmp(&along(bounds[b].0)));
+
+    let mut closest: Option<SelfApproach> = None;
+    for (position, &first) in order.iter().enumerate() {
+        let reach = along(bounds[first].1) + max_distance;
+        for &second in order[position + 1..].iter().take_while(|&&other| along(bounds[other].0) <= reach) {
+            let (first, second) = (first.min(second), first.max(second));
+            // Skip segments too close along the path to ever qualify, or too far across the sweep
+            if second <= first + 1
+                || arc_lengths[second + 1] - arc_lengths[first] < min_separation
+                || across(bounds[second].0) > across(bounds[first].1) + max_distance
+                || across(bounds[first].0) > across(bounds[second].1) + max_distance
+            {
+                continue;
+            }
+
+            let (a_start, a_end, b_start, b_end) = (path[first], path[first + 1], path[second], path[second + 1]);
+            let (centerline_distance, first_point, second_point) = segment_closest_points(a_start, a_end, b_start, b_end);
+            if centerline_distance >= max_distance
+                || closest.is_some_and(|found| centerline_distance >= found.centerline_distance)
+            {
+                continue;
+            }
+            let arc_separation = (arc_lengths[second] + distance(b_start, second_point))
+                - (arc_lengths[first] + distance(a_start, first_point));
+            if arc_separation >= min_separation {
+                closest = Some(SelfApproach {
+                    first_segment: first,
+                    second_segment: second,
+                    centerline_distance,
+                    arc_separation,
+                    first_point,
+                    second_point,
+                });
+            }
+        }
+    }
+    closest
+}
+
+/// Euclidean distance between two points
+fn distance(a: Point2D, b: Point2D) -> f64 {
+    (b.0 - a.0).hypot(b.1 - a.1)
+}
+
+/// Point a fraction `t` of the way from `a` to `b`
+fn lerp(a: Point2D, b: Point2D, t: f64) -> Point2D {
+    (t.mul_add(b.0 - a.0, a.0), t.mul_add(b.1 - a.1, a.1))
+}
+
+/// Bounding box of a segment, expanded by `margin`
+fn segment_bounds(start: Point2D, end: Point2D, margin: f64) -> (Point2D, Point2D) {
+    (
+        (start.0.min(end.0) - margin, start.1.min(end.1) - margin),
+        (start.0.max(end.0) + margin, start.1.max(end.1) + margin),
+    )
+}
+
+/// Signed area orientation of three points
+fn orientation(a: Point2D, b: Point2D, c: Point2D) -> f64 {
+    (b.0 - a.0).mul_add(c.1 - a.1, -((b.1 - a.1) * (c.0 - a.0)))
+}
+
+/// Closest point on a segment to a point
+#[must_use]
+pub fn closest_point_on_segment(point: Point2D, start: Point2D, end: Point2D) -> Point2D {
+    let dx = end.0 - start.0;
+    let dy = end.1 - start.1;
+    let length_squared = dx.mul_add(dx, dy * dy);
+    if length_squared <= 0.0 {
+        return start;
+    }
+    let t = ((point.0 - start.0).mul_add(dx, (point.1 - start.1) * dy) / length_squared).clamp(0.0, 1.0);
+    lerp(start, end, t)
+}
+
+/// Crossing point of two segments, if they properly cross
+///
+/// Segments that only touch at an endpoint, or that are collinear, do not cross.
+#[must_use]
+pub fn segment_intersection(a1: Point2D, a2: Point2D, b1: Point2D, b2: Point2D) -> Option<Point2D> {
+    let d1 = orientation(a1, a2, b1);
+    let d2 = orientation(a1, a2, b2);
+    let d3 = orientation(b1, b2, a1);
+    let d4 = orientation(b1, b2, a2);
+    (d1 * d2 < 0.0 && d3 * d4 < 0.0).then(|| lerp(a1, a2, d3 / (d3 - d4)))
+}
+
+/// Point where two segments cross or touch, if any
+///
+/// Unlike [`segment_intersection`], an endpoint lying on the other segment
+/// counts. Collinear segments have no single contact point and return `None`.
+fn segment_contact(a1: Point2D, a2: Point2D, b1: Point2D, b2: Point2D) -> Option<Point2D> {
+    if let Some(crossing) = segment_intersection(a1, a2, b1, b2) {
+        return Some(crossing);
+    }
+    let d1 = orientation(a1, a2, b1);
+    let d2 = orientation(a1, a2, b2);
+    let d3 = orientation(b1, b2, a1);
+    let d4 = orientation(b1, b2, a2);
+    if (d1 == 0.0 && d2 == 0.0) || d1 * d2 > 0.0 || d3 * d4 > 0.0 {
+        return None;
+    }
+    [(a1, d3), (a2, d4)]
+        .into_iter()
+        .chain([(b1, d1), (b2, d2)])
+        .find(|&(_, side)| side == 0.0)
+        .map(|(point, _)| point)
+}
+
+/// Distance between two segments and the closest point on each
+#[must_use]
+pub fn segment_closest_points(a1: Point2D, a2: Point2D, b1: Point2D, b2: Point2D) -> (f64, Point2D, Point2D) {
+    if let Some(crossing) = segment_intersection(a1, a2, b1, b2) {
+        return (0.0, crossing, crossing);
+    }
+
+    [
+        (a1, closest_point_on_segment(a1, b1, b2)),
+        (a2, closest_point_on_segment(a2, b1, b2)),
+        (closest_point_on_segment(b1, a1, a2), b1),
+        (closest_point_on_segment(b2, a1, a2), b2),
+    ]
+    .into_iter()
+    .map(|(on_a, on_b)| (distance(on_a, on_b), on_a, on_b))
+    .min_by(|x, y| x.0.total_cmp(&y.0))
+    .unwrap_or((f64::INFINITY, a1, b1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_segment_closest_points() {
+        let (distance, on_a, on_b) = segment_closest_points((0.0, 0.0), (10.0, 0.0), (4.0, 3.0), (6.0, 5.0));
+        assert!((distance - 3.0).abs() < 1e-12);
+        assert_eq!(on_a, (4.0, 0.0));
+        assert_eq!(on_b, (4.0, 3.0));
+
+        let (distance, on_a, _) = segment_closest_points((0.0, 0.0), (10.0, 10.0), (0.0, 10.0), (10.0, 0.0));
+        assert!(distance.abs() < 1e-12);
+        assert!((on_a.0 - 5.0).abs() < 1e-12 && (on_a.1 - 5.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_segment_intersection_excludes_touching() {
+        assert!(segment_intersection((0.0, 0.0), (1.0, 0.0), (1.0, 0.0), (2.0, 1.0)).is_none());
+        assert!(segment_intersection((0.0, 0.0), (2.0, 0.0), (1.0, -1.0), (1.0, 1.0)).is_some());
+        assert!(segment_intersection((0.0, 0.0), (2.0, 0.0), (1.0, 0.0), (3.0, 0.0)).is_none());
+    }
+}
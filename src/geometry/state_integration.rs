@@ -316,6 +316,9 @@ impl SerpentineParameters {
             target_fill_ratio: self.target_fill_ratio,
             optimization_profile: OptimizationProfile::Balanced, // Default
             adaptive_config: crate::config::AdaptiveSerpentineConfig::default(),
+            optimization_target: None,
+            objective: None,
+            avoid_self_intersections: false,
         }
     }
     
@@ -5,9 +5,15 @@
 //! with new channel types while adhering to SOLID principles.
 
 use crate::geometry::{ChannelType, Point2D};
-use crate::geometry::optimization::optimize_serpentine_parameters;
-use crate::config::{ArcConfig, ChannelTypeConfig, GeometryConfig, SerpentineConfig, FrustumConfig, constants};
+use crate::geometry::optimization::{
+    calculate_min_self_spacing, optimize_serpentine_for_target, optimize_serpentine_parameters, optimize_serpentine_parameters_with,
+    optimizer_for_profile, TargetedOptimizationResult,
+};
+use crate::geometry::optimizers::OptimizationBudget;
+use crate::geometry::parametric::{ParametricPath, WaveEnvelope};
+use crate::config::{ArcConfig, ChannelTypeConfig, WaveShape, GeometryConfig, SerpentineConfig, FrustumConfig, constants};
 use crate::config_constants::ConstantsRegistry;
+use serde::{Deserialize, Serialize};
 use crate::state_management::bilateral_symmetry::{
     SymmetryContext, BilateralSymmetryConfig, BilateralPhaseDirectionCalculator
 };
@@ -28,6 +34,22 @@ pub struct ChannelGenerationContext<'a> {
     pub neighbor_info: Option<&'a [f64]>,
 }
 
+/// Discretize an analytic channel path for the given generation config
+///
+/// Uses adaptive sampling when the generation config enables it and
+/// `fixed_points` evenly spaced parameters otherwise.
+fn discretize_path(
+    curve: ParametricPath,
+    fixed_points: usize,
+    geometry_config: &GeometryConfig,
+) -> (ParametricPath, Vec<Point2D>) {
+    let path = geometry_config.generation.adaptive_sampling.as_ref().map_or_else(
+        || curve.discretize(fixed_points),
+        |sampling| curve.sample(sampling),
+    );
+    (curve, path)
+}
+
 /// Space metrics for amplitude calculation
 #[derive(Debug, Clone)]
 struct SpaceMetrics {
@@ -223,8 +245,49 @@ pub trait ChannelTypeStrategy {
         total_branches: usize,
         neighbor_info: Option<&[f64]>,
     ) -> ChannelType;
+
+    /// Create a channel type together with the analytic path it was sampled from
+    ///
+    /// Strategies that build their path from a [`ParametricPath`] return it
+    /// alongside the channel type so the exact curve can be kept after
+    /// generation. The default implementation provides no analytic path.
+    fn create_parametric_channel(
+        &self,
+        from: Point2D,
+        to: Point2D,
+        geometry_config: &GeometryConfig,
+        box_dims: (f64, f64),
+        total_branches: usize,
+        neighbor_info: Option<&[f64]>,
+    ) -> (ChannelType, Option<ParametricPath>) {
+        let channel_type = self.create_channel(from, to, geometry_config, box_dims, total_branches, neighbor_info);
+        (channel_type, None)
+    }
+
+    /// Create a channel type, its analytic path and the outcome of its targeted optimization
+    ///
+    /// Strategies that tune channels toward an
+    /// [`OptimizationTarget`](crate::config::OptimizationTarget) return the
+    /// result so the generator can record whether the target was reached. The
+    /// default implementation performs no targeted optimization.
+    fn create_targeted_channel(
+        &self,
+        from: Point2D,
+        to: Point2D,
+        geometry_config: &GeometryConfig,
+        box_dims: (f64, f64),
+        total_branches: usize,
+        neighbor_info: Option<&[f64]>,
+    ) -> TargetedChannel {
+        let (channel_type, path) =
+            self.create_parametric_channel(from, to, geometry_config, box_dims, total_branches, neighbor_info);
+        (channel_type, path, None)
+    }
 }
 
+/// Channel type, analytic path and targeted optimization result of a generated channel
+pub type TargetedChannel = (ChannelType, Option<ParametricPath>, Option<TargetedOptimizationResult>);
+
 /// Strategy for creating straight channels
 #[derive(Debug, Clone)]
 pub struct StraightChannelStrategy;
@@ -241,6 +304,18 @@ impl ChannelTypeStrategy for StraightChannelStrategy {
     ) -> ChannelType {
         ChannelType::Straight
     }
+
+    fn create_parametric_channel(
+        &self,
+        from: Point2D,
+        to: Point2D,
+        _geometry_config: &GeometryConfig,
+        _box_dims: (f64, f64),
+        _total_branches: usize,
+        _neighbor_info: Option<&[f64]>,
+    ) -> (ChannelType, Option<ParametricPath>) {
+        (ChannelType::Straight, Some(ParametricPath::Line { start: from, end: to }))
+    }
 }
 
 /// Strategy for creating smooth straight channels with transition zones
@@ -272,7 +347,8 @@ pub struct SmoothStraightChannelStrategy {
 ///     wave_multiplier: 1.5,
 /// };
 /// ```
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct SmoothTransitionConfig {
     /// Length of transition zone as fraction of channel length (0.0 to 0.5)
     pub transition_length_factor: f64,
@@ -386,6 +462,18 @@ impl SmoothStraightChannelStrategy {
 
 impl ChannelTypeStrategy for SmoothStraightChannelStrategy {
     fn create_channel(
+        &self,
+        from: Point2D,
+        to: Point2D,
+        geometry_config: &GeometryConfig,
+        box_dims: (f64, f64),
+        total_branches: usize,
+        neighbor_info: Option<&[f64]>,
+    ) -> ChannelType {
+        self.create_parametric_channel(from, to, geometry_config, box_dims, total_branches, neighbor_info).0
+    }
+
+    fn create_parametric_channel(
         &self,
         from: Point2D,
         to: Point2D,
@@ -393,9 +481,9 @@ impl ChannelTypeStrategy for SmoothStraightChannelStrategy {
         _box_dims: (f64, f64),
         _total_branches: usize,
         _neighbor_info: Option<&[f64]>,
-    ) -> ChannelType {
-        let path = self.generate_smooth_straight_path(from, to, geometry_config);
-        ChannelType::SmoothStraight { path }
+    ) -> (ChannelType, Option<ParametricPath>) {
+        let (curve, path) = self.generate_smooth_straight_path(from, to, geometry_config);
+        (ChannelType::SmoothStraight { path }, Some(curve))
     }
 }
 
@@ -406,7 +494,7 @@ impl SmoothStraightChannelStrategy {
         p1: Point2D,
         p2: Point2D,
         geometry_config: &GeometryConfig,
-    ) -> Vec<Point2D> {
+    ) -> (ParametricPath, Vec<Point2D>) {
         let dx = p2.0 - p1.0;
         let dy = p2.1 - p1.1;
         let channel_length = (dx * dx + dy * dy).sqrt();
@@ -414,7 +502,7 @@ impl SmoothStraightChannelStrategy {
         let constants = ConstantsRegistry::new();
         // For very short channels, just return straight line
         if channel_length < geometry_config.channel_width * constants.get_short_channel_width_multiplier() {
-            return vec![p1, p2];
+            return (ParametricPath::Line { start: p1, end: p2 }, vec![p1, p2]);
         }
 
         let transition_length = channel_length * self.transition_config.transition_length_factor;
@@ -425,62 +513,19 @@ impl SmoothStraightChannelStrategy {
         let middle_points = geometry_config.generation.smooth_straight_middle_points;
         let total_points = transition_points * 2 + middle_points;
 
-        let mut path = Vec::with_capacity(total_points);
-
-        // Perpendicular direction for wave displacement
-        let perp_x = -dy / channel_length;
-        let perp_y = dx / channel_length;
-
-        for i in 0..total_points {
-            let t = i as f64 / (total_points - 1) as f64;
-
-            // Base position along the line
-            let base_x = p1.0 + t * dx;
-            let base_y = p1.1 + t * dy;
-
-            // Calculate smooth transition amplitude
-            let amplitude = self.calculate_transition_amplitude(t, transition_length / channel_length, max_amplitude);
-
-            // Apply small wave for smooth transition
-            let wave_phase = std::f64::consts::PI * self.transition_config.wave_multiplier * t;
-            let wave_amplitude = amplitude * wave_phase.sin();
-
-            let x = base_x + wave_amplitude * perp_x;
-            let y = base_y + wave_amplitude * perp_y;
-
-            // Ensure exact endpoints
-            if i == 0 {
-                path.push(p1);
-            } else if i == total_points - 1 {
-                path.push(p2);
-            } else {
-                path.push((x, y));
-            }
-        }
-
-        path
-    }
-
-    /// Calculate transition amplitude that smoothly goes to zero at endpoints
-    fn calculate_transition_amplitude(&self, t: f64, transition_factor: f64, max_amplitude: f64) -> f64 {
-        // Create smooth transitions at both ends
-        let start_transition = if t < transition_factor {
-            // Smooth ramp up from 0 to 1 using smoothstep
-            let local_t = t / transition_factor;
-            local_t * local_t * (3.0 - 2.0 * local_t)
-        } else {
-            1.0
-        };
-
-        let end_transition = if t > (1.0 - transition_factor) {
-            // Smooth ramp down from 1 to 0 using smoothstep
-            let local_t = (1.0 - t) / transition_factor;
-            local_t * local_t * (3.0 - 2.0 * local_t)
-        } else {
-            1.0
+        // Small wave whose amplitude ramps smoothly to zero at both endpoints
+        let curve = ParametricPath::Wave {
+            start: p1,
+            end: p2,
+            amplitude: max_amplitude,
+            half_periods: self.transition_config.wave_multiplier,
+            phase_offset: 0.0,
+            shape: WaveShape::Sine,
+            envelope: WaveEnvelope::Transition {
+                transition_fraction: transition_length / channel_length,
+            },
         };
-
-        max_amplitude * start_transition * end_transition
+        discretize_path(curve, total_points, geometry_config)
     }
 }
 
@@ -520,6 +565,32 @@ impl ChannelTypeStrategy for SerpentineChannelStrategy {
         total_branches: usize,
         neighbor_info: Option<&[f64]>,
     ) -> ChannelType {
+        self.create_parametric_channel(from, to, geometry_config, box_dims, total_branches, neighbor_info).0
+    }
+
+    fn create_parametric_channel(
+        &self,
+        from: Point2D,
+        to: Point2D,
+        geometry_config: &GeometryConfig,
+        box_dims: (f64, f64),
+        total_branches: usize,
+        neighbor_info: Option<&[f64]>,
+    ) -> (ChannelType, Option<ParametricPath>) {
+        let (channel_type, curve, _) =
+            self.create_targeted_channel(from, to, geometry_config, box_dims, total_branches, neighbor_info);
+        (channel_type, curve)
+    }
+
+    fn create_targeted_channel(
+        &self,
+        from: Point2D,
+        to: Point2D,
+        geometry_config: &GeometryConfig,
+        box_dims: (f64, f64),
+        total_branches: usize,
+        neighbor_info: Option<&[f64]>,
+    ) -> TargetedChannel {
         let context = ChannelGenerationContext::new(
             geometry_config,
             box_dims,
@@ -527,36 +598,17 @@ impl ChannelTypeStrategy for SerpentineChannelStrategy {
             neighbor_info,
         );
 
-        let path = if self.config.optimization_enabled {
+        let (curve, path, target_result) = if self.config.optimization_enabled {
             self.generate_optimized_serpentine_path(from, to, &context)
         } else {
-            self.generate_serpentine_path(from, to, &context)
+            let (curve, path) = self.generate_checked_serpentine_path(from, to, &context);
+            (curve, path, None)
         };
-        ChannelType::Serpentine { path }
+        (ChannelType::Serpentine { path }, Some(curve), target_result)
     }
 }
 
 impl SerpentineChannelStrategy {
-    /// Calculate wave amplitude based on wave shape and phase
-    fn calculate_wave_amplitude(&self, wave_phase: f64, phase_offset: f64) -> f64 {
-        use crate::config::WaveShape;
-
-        match self.config.wave_shape {
-            WaveShape::Sine => {
-                // Smooth sine wave
-                (wave_phase + phase_offset).sin()
-            }
-            WaveShape::Square => {
-                // Square wave with smooth transitions
-                let sine_value = (wave_phase + phase_offset).sin();
-                // Use tanh to create smooth square wave transitions
-                let constants = ConstantsRegistry::new();
-                let sharpness = constants.get_square_wave_sharpness(); // Configurable transition sharpness
-                (sharpness * sine_value).tanh()
-            }
-        }
-    }
-
     /// Calculate maximum safe amplitude using advanced adaptive algorithms
     fn calculate_adaptive_amplitude(
         &self,
@@ -765,7 +817,13 @@ impl SerpentineChannelStrategy {
         p1: Point2D,
         p2: Point2D,
         context: &ChannelGenerationContext,
-    ) -> Vec<Point2D> {
+    ) -> (ParametricPath, Vec<Point2D>) {
+        let curve = self.serpentine_curve(p1, p2, context);
+        discretize_path(curve, context.geometry_config.generation.serpentine_points, context.geometry_config)
+    }
+
+    /// Analytic serpentine wave between two points
+    fn serpentine_curve(&self, p1: Point2D, p2: Point2D, context: &ChannelGenerationContext) -> ParametricPath {
         // Check if amplitude is below threshold - if so, return straight line
         let initial_wavelength = self.config.wavelength_factor * context.geometry_config.channel_width;
         let wavelength = self.validate_wavelength_for_diameter(initial_wavelength, context.geometry_config.channel_width);
@@ -773,22 +831,13 @@ impl SerpentineChannelStrategy {
 
         if amplitude <= 0.0 {
             // Return straight line when amplitude is too small for meaningful serpentines
-            return self.generate_straight_line_path(p1, p2, context.geometry_config.generation.serpentine_points);
+            return ParametricPath::Line { start: p1, end: p2 };
         }
 
-        let n_points = context.geometry_config.generation.serpentine_points;
-
-        let dx = p2.0 - p1.0;
-        let dy = p2.1 - p1.1;
-        let channel_length = dx.hypot(dy); // More efficient than sqrt(dx*dx + dy*dy)
-        let _angle = dy.atan2(dx);
-
-        let constants = ConstantsRegistry::new();
-        let _branch_factor = (context.total_branches as f64).powf(constants.get_branch_factor_exponent()).max(1.0);
+        let channel_length = (p2.0 - p1.0).hypot(p2.1 - p1.1); // More efficient than sqrt(dx*dx + dy*dy)
 
         // Calculate number of periods to ensure complete wave cycles
-        let initial_wavelength = self.config.wavelength_factor * context.geometry_config.channel_width;
-        let base_wavelength = self.validate_wavelength_for_diameter(initial_wavelength, context.geometry_config.channel_width);
+        let base_wavelength = wavelength;
 
         // For smooth endpoint transitions, use half-periods to ensure zero amplitude at endpoints
         // Scale the number of periods with channel length and ensure minimum complete cycles
@@ -798,126 +847,59 @@ impl SerpentineChannelStrategy {
         let half_periods = (base_periods * 2.0).round().max(1.0);
 
         // Calculate amplitude with advanced adaptive algorithms
-        let initial_amplitude = self.calculate_adaptive_amplitude(p1, p2, context, base_wavelength);
-
-        // Calculate wave phase direction for perfect mirror symmetry
-        let phase_direction = self.calculate_wave_phase_direction(p1, p2, context.box_dims);
+        let initial_amplitude = amplitude;
 
-        // Gaussian envelope parameters
-        // Note: sigma and center are now calculated in the improved envelope function
+        // Simple validation: ensure minimum turn radius
+        let channel_diameter = 0.45;
+        let min_turn_radius = self.calculate_minimum_turn_radius(channel_diameter);
+        let min_amplitude_for_turns = min_turn_radius * 2.0;
+        let validated_amplitude = initial_amplitude.max(min_amplitude_for_turns);
 
-        // Pre-allocate path with exact capacity
-        let mut path = Vec::with_capacity(n_points);
+        // Only adopt the validated amplitude when it differs noticeably
+        let final_amplitude = if (validated_amplitude - initial_amplitude).abs() > 0.1 {
+            validated_amplitude
+        } else {
+            initial_amplitude
+        };
 
-        for i in 0..n_points {
-            let t = i as f64 / (n_points - 1) as f64;
+        // Calculate wave phase direction for perfect mirror symmetry
+        let phase_direction = self.calculate_wave_phase_direction(p1, p2, context.box_dims);
 
-            // Base position along the line
-            let base_x = p1.0 + t * dx;
-            let base_y = p1.1 + t * dy;
+        // Apply phase direction correctly for bilateral mirror symmetry
+        // phase_direction determines the initial phase offset, not frequency scaling
+        let phase_offset = if phase_direction > 0.0 {
+            0.0 // Positive phase: start with sine wave (0 phase)
+        } else {
+            std::f64::consts::PI // Negative phase: start with inverted sine wave (π phase)
+        };
 
-            // Use the improved envelope that respects adaptive configuration
-            let node_distance = (dx * dx + dy * dy).sqrt();
-            let envelope_context = EnvelopeContext {
-                channel_length,
-                direction: (dx, dy),
-                node_distance,
-                adaptive_config: self.config.adaptive_config,
+        // Serpentine wave with half-periods to ensure zero amplitude at endpoints,
+        // using the improved envelope that respects adaptive configuration
+        ParametricPath::Wave {
+            start: p1,
+            end: p2,
+            amplitude: final_amplitude,
+            half_periods,
+            phase_offset,
+            shape: self.config.wave_shape,
+            envelope: WaveEnvelope::AdaptiveGaussian {
                 gaussian_width_factor: self.config.gaussian_width_factor,
-            };
-            let improved_envelope_calc = AdaptiveGaussianEnvelopeCalculator;
-            let envelope = improved_envelope_calc.calculate_envelope(t, &envelope_context);
-
-            // Serpentine wave with half-periods to ensure zero amplitude at endpoints
-            let wave_phase = std::f64::consts::PI * half_periods * t;
-
-            // Apply phase direction correctly for bilateral mirror symmetry
-            // phase_direction determines the initial phase offset, not frequency scaling
-            let phase_offset = if phase_direction > 0.0 {
-                0.0 // Positive phase: start with sine wave (0 phase)
-            } else {
-                std::f64::consts::PI // Negative phase: start with inverted sine wave (π phase)
-            };
-
-            let wave_amplitude = initial_amplitude * envelope * self.calculate_wave_amplitude(wave_phase, phase_offset);
-
-            // Apply perpendicular offset
-            let perp_x = -dy / channel_length;
-            let perp_y = dx / channel_length;
-
-            let x = base_x + wave_amplitude * perp_x;
-            let y = base_y + wave_amplitude * perp_y;
-
-            // Ensure exact endpoint matching for first and last points to maintain precision
-            // The smooth envelope should make wave_amplitude ≈ 0 at endpoints, but we ensure exactness
-            if i == 0 {
-                path.push(p1);
-            } else if i == n_points - 1 {
-                path.push(p2);
-            } else {
-                path.push((x, y));
-            }
-        }
-
-        // Simple validation: ensure minimum turn radius
-        let channel_diameter = 0.45;
-        let min_turn_radius = self.calculate_minimum_turn_radius(channel_diameter);
-        let min_amplitude_for_turns = min_turn_radius * 2.0;
-        let final_amplitude = initial_amplitude.max(min_amplitude_for_turns);
-
-        // If amplitude was adjusted, regenerate the path
-        if (final_amplitude - initial_amplitude).abs() > 0.1 {
-            // Regenerate path with validated amplitude
-            path.clear();
-            path.reserve(n_points);
-
-            for i in 0..n_points {
-                let t = i as f64 / (n_points - 1) as f64;
-
-                let base_x = p1.0 + t * dx;
-                let base_y = p1.1 + t * dy;
-
-                let node_distance = (dx * dx + dy * dy).sqrt();
-                let envelope_context = EnvelopeContext {
-                    channel_length,
-                    direction: (dx, dy),
-                    node_distance,
-                    adaptive_config: self.config.adaptive_config,
-                    gaussian_width_factor: self.config.gaussian_width_factor,
-                };
-                let improved_envelope_calc = AdaptiveGaussianEnvelopeCalculator;
-                let envelope = improved_envelope_calc.calculate_envelope(t, &envelope_context);
-
-                let wave_phase = std::f64::consts::PI * half_periods * t;
-                let phase_offset = if phase_direction > 0.0 { 0.0 } else { std::f64::consts::PI };
-                let wave_amplitude = final_amplitude * envelope * self.calculate_wave_amplitude(wave_phase, phase_offset);
-
-                let perp_x = -dy / channel_length;
-                let perp_y = dx / channel_length;
-
-                let x = base_x + wave_amplitude * perp_x;
-                let y = base_y + wave_amplitude * perp_y;
-
-                if i == 0 {
-                    path.push(p1);
-                } else if i == n_points - 1 {
-                    path.push(p2);
-                } else {
-                    path.push((x, y));
-                }
-            }
+                adaptive_config: self.config.adaptive_config,
+                channel_vector: (p2.0 - p1.0, p2.1 - p1.1),
+            },
         }
-
-        path
     }
 
     /// Generate an optimized serpentine path between two points
+    ///
+    /// Also returns the targeted optimization result when the configuration
+    /// sets an optimization target.
     fn generate_optimized_serpentine_path(
         &self,
         p1: Point2D,
         p2: Point2D,
         context: &ChannelGenerationContext,
-    ) -> Vec<Point2D> {
+    ) -> (ParametricPath, Vec<Point2D>, Option<TargetedOptimizationResult>) {
         // Check if amplitude is below threshold - if so, return straight line
         let initial_wavelength = self.config.wavelength_factor * context.geometry_config.channel_width;
         let wavelength = self.validate_wavelength_for_diameter(initial_wavelength, context.geometry_config.channel_width);
@@ -925,24 +907,42 @@ impl SerpentineChannelStrategy {
 
         if amplitude <= 0.0 {
             // Return straight line when amplitude is too small for meaningful serpentines
-            return self.generate_straight_line_path(p1, p2, context.geometry_config.generation.serpentine_points);
+            let line = ParametricPath::Line { start: p1, end: p2 };
+            let (curve, path) = discretize_path(line, context.geometry_config.generation.serpentine_points, context.geometry_config);
+            return (curve, path, None);
         }
 
-        // Run optimization to find best parameters
-        let optimization_result = optimize_serpentine_parameters(
-            p1,
-            p2,
-            context.geometry_config,
-            &self.config,
-            context.box_dims,
-            context.neighbor_info,
-        );
+        // Run optimization to find best parameters, tuning toward a target or
+        // custom objective when one is set
+        let mut target_result = None;
+        let optimized_params = match (self.config.optimization_target, self.config.objective) {
+            (Some(target), _) => target_result
+                .insert(optimize_serpentine_for_target(p1, p2, &self.config, context, target))
+                .params
+                .clone(),
+            (None, Some(_)) => optimize_serpentine_parameters_with(
+                optimizer_for_profile(self.config.optimization_profile).as_ref(),
+                p1,
+                p2,
+                &self.config,
+                context,
+                &OptimizationBudget::default(),
+            ).params,
+            (None, None) => optimize_serpentine_parameters(
+                p1,
+                p2,
+                context.geometry_config,
+                &self.config,
+                context.box_dims,
+                context.neighbor_info,
+            ).params,
+        };
 
         // Create optimized configuration without full clone
         let optimized_config = SerpentineConfig {
-            wavelength_factor: optimization_result.params.wavelength_factor,
-            wave_density_factor: optimization_result.params.wave_density_factor,
-            fill_factor: optimization_result.params.fill_factor,
+            wavelength_factor: optimized_params.wavelength_factor,
+            wave_density_factor: optimized_params.wave_density_factor,
+            fill_factor: optimized_params.fill_factor,
             gaussian_width_factor: self.config.gaussian_width_factor,
             wave_phase_direction: self.config.wave_phase_direction,
             wave_shape: self.config.wave_shape,
@@ -950,11 +950,60 @@ impl SerpentineChannelStrategy {
             target_fill_ratio: self.config.target_fill_ratio,
             optimization_profile: self.config.optimization_profile,
             adaptive_config: self.config.adaptive_config,
+            optimization_target: self.config.optimization_target,
+            objective: self.config.objective,
+            avoid_self_intersections: self.config.avoid_self_intersections,
         };
 
         // Generate path with optimized parameters using temporary strategy
         let temp_strategy = SerpentineChannelStrategy::new(optimized_config);
-        temp_strategy.generate_serpentine_path(p1, p2, context)
+        let (curve, path) = temp_strategy.generate_checked_serpentine_path(p1, p2, context);
+        (curve, path, target_result)
+    }
+
+    /// Generate a serpentine path, correcting self-intersections if configured
+    ///
+    /// With `avoid_self_intersections` set, a path that comes closer to itself
+    /// than the wall clearance is regenerated with relaxed wave parameters:
+    /// the wave density is lowered first, since it sets how tightly half-waves
+    /// are packed, and the fill factor once the density reaches its minimum.
+    fn generate_checked_serpentine_path(
+        &self,
+        p1: Point2D,
+        p2: Point2D,
+        context: &ChannelGenerationContext,
+    ) -> (ParametricPath, Vec<Point2D>) {
+        let generated = self.generate_serpentine_path(p1, p2, context);
+        if !self.config.avoid_self_intersections {
+            return generated;
+        }
+
+        let geometry_config = context.geometry_config;
+        let is_clear = |path: &[Point2D]| {
+            calculate_min_self_spacing(path, geometry_config.channel_width, geometry_config.wall_clearance)
+                >= geometry_config.wall_clearance
+        };
+        if is_clear(&generated.1) {
+            return generated;
+        }
+
+        let mut config = self.config;
+        let mut corrected = generated;
+        for _ in 0..constants::SELF_INTERSECTION_CORRECTION_STEPS {
+            if config.wave_density_factor > constants::MIN_WAVE_DENSITY_FACTOR {
+                config.wave_density_factor = (config.wave_density_factor * constants::SELF_INTERSECTION_RELAXATION)
+                    .max(constants::MIN_WAVE_DENSITY_FACTOR);
+            } else if config.fill_factor > constants::MIN_FILL_FACTOR {
+                config.fill_factor = (config.fill_factor * constants::SELF_INTERSECTION_RELAXATION).max(constants::MIN_FILL_FACTOR);
+            } else {
+                break;
+            }
+            corrected = Self::new(config).generate_serpentine_path(p1, p2, context);
+            if is_clear(&corrected.1) {
+                break;
+            }
+        }
+        corrected
     }
 
     /// Generate serpentine path for optimization purposes (public interface)
@@ -973,20 +1022,7 @@ impl SerpentineChannelStrategy {
             total_branches,
             neighbor_info,
         );
-        self.generate_serpentine_path(p1, p2, &context)
-    }
-
-    /// Generate a straight line path when serpentine amplitude is too small
-    fn generate_straight_line_path(&self, p1: Point2D, p2: Point2D, n_points: usize) -> Vec<Point2D> {
-        let dx = p2.0 - p1.0;
-        let dy = p2.1 - p1.1;
-
-        (0..n_points)
-            .map(|i| {
-                let t = i as f64 / (n_points - 1) as f64;
-                (p1.0 + t * dx, p1.1 + t * dy)
-            })
-            .collect()
+        self.generate_serpentine_path(p1, p2, &context).1
     }
 
     /// Calculate wave phase direction for perfect bilateral mirror symmetry using enhanced symmetry system
@@ -1062,13 +1098,26 @@ impl ChannelTypeStrategy for ArcChannelStrategy {
         &self,
         from: Point2D,
         to: Point2D,
-        _geometry_config: &GeometryConfig,
+        geometry_config: &GeometryConfig,
         box_dims: (f64, f64),
         total_branches: usize,
         neighbor_info: Option<&[f64]>,
     ) -> ChannelType {
-        let path = self.generate_arc_path_with_collision_prevention(from, to, box_dims, total_branches, neighbor_info);
-        ChannelType::Arc { path }
+        self.create_parametric_channel(from, to, geometry_config, box_dims, total_branches, neighbor_info).0
+    }
+
+    fn create_parametric_channel(
+        &self,
+        from: Point2D,
+        to: Point2D,
+        geometry_config: &GeometryConfig,
+        box_dims: (f64, f64),
+        total_branches: usize,
+        neighbor_info: Option<&[f64]>,
+    ) -> (ChannelType, Option<ParametricPath>) {
+        let context = ChannelGenerationContext::new(geometry_config, box_dims, total_branches, neighbor_info);
+        let (curve, path) = self.generate_arc_path_with_collision_prevention(from, to, &context);
+        (ChannelType::Arc { path }, Some(curve))
     }
 }
 
@@ -1078,16 +1127,20 @@ impl ArcChannelStrategy {
         &self,
         p1: Point2D,
         p2: Point2D,
-        box_dims: (f64, f64),
-        total_branches: usize,
-        neighbor_info: Option<&[f64]>,
-    ) -> Vec<Point2D> {
+        context: &ChannelGenerationContext,
+    ) -> (ParametricPath, Vec<Point2D>) {
         if !self.config.enable_collision_prevention {
-            return self.generate_arc_path(p1, p2, box_dims);
+            return self.generate_arc_path(p1, p2, context);
         }
 
         // Calculate adaptive curvature factor based on proximity to neighbors
-        let adaptive_curvature = self.calculate_adaptive_curvature(p1, p2, box_dims, total_branches, neighbor_info);
+        let adaptive_curvature = self.calculate_adaptive_curvature(
+            p1,
+            p2,
+            context.box_dims,
+            context.total_branches,
+            context.neighbor_info,
+        );
 
         // Create temporary config with adaptive curvature
         let adaptive_config = ArcConfig {
@@ -1097,11 +1150,11 @@ impl ArcChannelStrategy {
 
         // Generate path with adaptive curvature
         let temp_strategy = ArcChannelStrategy::new(adaptive_config);
-        temp_strategy.generate_arc_path(p1, p2, box_dims)
+        temp_strategy.generate_arc_path(p1, p2, context)
     }
 
     /// Generate a smooth arc path between two points using zero-copy techniques
-    fn generate_arc_path(&self, p1: Point2D, p2: Point2D, box_dims: (f64, f64)) -> Vec<Point2D> {
+    fn generate_arc_path(&self, p1: Point2D, p2: Point2D, context: &ChannelGenerationContext) -> (ParametricPath, Vec<Point2D>) {
         let constants = ConstantsRegistry::new();
         let num_points = self.config.smoothness + 2;
 
@@ -1111,18 +1164,15 @@ impl ArcChannelStrategy {
 
         // For very short channels or zero curvature, return straight line
         if distance < constants.get_geometric_tolerance() || self.config.curvature_factor < constants.get_geometric_tolerance() {
-            return vec![p1, p2];
+            return (ParametricPath::Line { start: p1, end: p2 }, vec![p1, p2]);
         }
 
-        // Pre-allocate with exact capacity to avoid reallocations
-        let mut path = Vec::with_capacity(num_points);
-        
         // Calculate control point for the arc
         let mid_x = (p1.0 + p2.0) / 2.0;
         let mid_y = (p1.1 + p2.1) / 2.0;
         
         // Calculate directional arc curvature
-        let arc_direction = self.calculate_arc_direction(p1, p2, box_dims);
+        let arc_direction = self.calculate_arc_direction(p1, p2, context.box_dims);
         
         // Calculate perpendicular direction for arc curvature
         let perp_x = -dy / distance;
@@ -1140,21 +1190,10 @@ impl ArcChannelStrategy {
         let control_y = mid_y + directed_perp_y * arc_height;
         
         // Generate points along the quadratic Bezier curve
-        path.push(p1);
-        
-        for i in 1..self.config.smoothness + 1 {
-            let t = i as f64 / (self.config.smoothness + 1) as f64;
-            let t_inv = 1.0 - t;
-            
-            // Quadratic Bezier formula: B(t) = (1-t)²P₀ + 2(1-t)tP₁ + t²P₂
-            let x = t_inv * t_inv * p1.0 + 2.0 * t_inv * t * control_x + t * t * p2.0;
-            let y = t_inv * t_inv * p1.1 + 2.0 * t_inv * t * control_y + t * t * p2.1;
-            
-            path.push((x, y));
-        }
-        
-        path.push(p2);
-        path
+        let curve = ParametricPath::Bezier {
+            control_points: vec![p1, (control_x, control_y), p2],
+        };
+        discretize_path(curve, num_points, context.geometry_config)
     }
 
     /// Calculate arc direction based on channel position and context
@@ -1383,11 +1422,11 @@ impl ChannelTypeFactory {
             }
 
             ChannelTypeConfig::Adaptive { serpentine_config, arc_config, frustum_config } => {
-                Self::create_adaptive_strategy(from, to, box_dims, *serpentine_config, *arc_config, *frustum_config)
+                Self::create_adaptive_strategy(from, to, box_dims, serpentine_config, *arc_config, *frustum_config)
             }
 
             ChannelTypeConfig::SmoothSerpentineWithTransitions { serpentine_config, smooth_straight_config } => {
-                Self::create_smooth_serpentine_strategy(from, to, box_dims, *serpentine_config, *smooth_straight_config)
+                Self::create_smooth_serpentine_strategy(from, to, box_dims, serpentine_config, *smooth_straight_config)
             }
 
             ChannelTypeConfig::Custom(func) => {
@@ -1403,7 +1442,7 @@ impl ChannelTypeFactory {
         from: Point2D,
         to: Point2D,
         box_dims: (f64, f64),
-        serpentine_config: SerpentineConfig,
+        serpentine_config: &SerpentineConfig,
         arc_config: ArcConfig,
         frustum_config: FrustumConfig,
     ) -> Box<dyn ChannelTypeStrategy> {
@@ -1417,7 +1456,7 @@ impl ChannelTypeFactory {
         if length > box_dims.0 * constants.get_long_horizontal_threshold()
             && dy.abs() < dx.abs() * constants.get_horizontal_angle_threshold() {
             // Long horizontal channel - use serpentine for mixing
-            Box::new(SerpentineChannelStrategy::new(serpentine_config))
+            Box::new(SerpentineChannelStrategy::new(*serpentine_config))
         } else if length > box_dims.0 * constants.get_frustum_min_length_threshold()
             && length < box_dims.0 * constants.get_frustum_max_length_threshold()
             && dy.abs() < dx.abs() * constants.get_frustum_angle_threshold() {
@@ -1438,7 +1477,7 @@ impl ChannelTypeFactory {
         from: Point2D,
         to: Point2D,
         box_dims: (f64, f64),
-        serpentine_config: SerpentineConfig,
+        serpentine_config: &SerpentineConfig,
         smooth_straight_config: SmoothTransitionConfig,
     ) -> Box<dyn ChannelTypeStrategy> {
         let constants = ConstantsRegistry::new();
@@ -1451,7 +1490,7 @@ impl ChannelTypeFactory {
         if length > box_dims.0 * constants.get_long_horizontal_threshold()
             && dy.abs() < dx.abs() * constants.get_horizontal_angle_threshold() {
             // Long horizontal channel - use serpentine
-            Box::new(SerpentineChannelStrategy::new(serpentine_config))
+            Box::new(SerpentineChannelStrategy::new(*serpentine_config))
         } else {
             // Junction connectors and short channels - use smooth straight
             Box::new(SmoothStraightChannelStrategy::new(smooth_straight_config))
@@ -1627,23 +1666,53 @@ impl ChannelTypeStrategy for FrustumChannelStrategy {
         &self,
         from: Point2D,
         to: Point2D,
-        _geometry_config: &GeometryConfig,
+        geometry_config: &GeometryConfig,
+        box_dims: (f64, f64),
+        total_branches: usize,
+        neighbor_info: Option<&[f64]>,
+    ) -> ChannelType {
+        self.create_parametric_channel(from, to, geometry_config, box_dims, total_branches, neighbor_info).0
+    }
+
+    fn create_parametric_channel(
+        &self,
+        from: Point2D,
+        to: Point2D,
+        geometry_config: &GeometryConfig,
         _box_dims: (f64, f64),
         _total_branches: usize,
         _neighbor_info: Option<&[f64]>,
-    ) -> ChannelType {
-        // Generate the centerline path
-        let path = self.generate_centerline_path(from, to);
+    ) -> (ChannelType, Option<ParametricPath>) {
+        let curve = ParametricPath::Frustum {
+            start: from,
+            end: to,
+            profile: self.config,
+        };
 
-        // Generate the width profile
-        let widths = self.generate_width_profile(path.len());
+        let (path, widths) = geometry_config.generation.adaptive_sampling.as_ref().map_or_else(
+            || {
+                // Generate the centerline path
+                let path = self.generate_centerline_path(from, to);
+
+                // Generate the width profile
+                let widths = self.generate_width_profile(path.len());
+                (path, widths)
+            },
+            |sampling| {
+                // Sample where the channel walls bend and evaluate widths at the same parameters
+                let parameters = curve.adaptive_parameters(sampling);
+                let widths = parameters.iter().map(|&t| self.config.width_at_position(t)).collect();
+                (curve.points_at(&parameters), widths)
+            },
+        );
 
-        ChannelType::Frustum {
+        let channel_type = ChannelType::Frustum {
             path,
             widths,
             inlet_width: self.config.inlet_width,
             throat_width: self.config.throat_width,
             outlet_width: self.config.outlet_width,
-        }
+        };
+        (channel_type, Some(curve))
     }
 }
@@ -0,0 +1,411 @@
+//! System-wide joint optimization of serpentine channels
+//!
+//! Per-channel optimization only sees neighboring channels through the
+//! y-coordinates in `neighbor_info`, so each serpentine greedily takes as much
+//! space as it can. This module optimizes the wave parameters of every
+//! serpentine channel of a [`ChannelSystem`] together, measuring the real
+//! edge-to-edge distance between every pair of channel paths and to the box
+//! walls, and maximizing either the total or the minimum serpentine length.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use scheme::config::{ChannelTypeConfig, GeometryConfig, SerpentineConfig};
+//! use scheme::geometry::{generator::create_geometry, SplitType};
+//! use scheme::geometry::optimizers::OptimizationBudget;
+//! use scheme::geometry::system_optimization::{
+//!     optimize_channel_system, SystemObjective, SystemOptimizationConfig,
+//! };
+//!
+//! let geometry_config = GeometryConfig::default();
+//! let serpentine_config = SerpentineConfig::default();
+//! let system = create_geometry(
+//!     (200.0, 100.0),
+//!     &[SplitType::Bifurcation],
+//!     &geometry_config,
+//!     &ChannelTypeConfig::AllSerpentine(serpentine_config),
+//! );
+//!
+//! let config = SystemOptimizationConfig {
+//!     objective: SystemObjective::MinimumLength,
+//!     budget: OptimizationBudget::new(40, 10, 1e-6),
+//!     ..SystemOptimizationConfig::default()
+//! };
+//! let result = optimize_channel_system(&system, &geometry_config, &serpentine_config, &config);
+//! assert_eq!(result.system.channels.len(), system.channels.len());
+//! ```
+
+use crate::config::{GeometryConfig, SerpentineConfig};
+use crate::geometry::optimization::{
+    calculate_min_wall_distance_excluding_ports, calculate_path_length, serpentine_parameter_bounds,
+    OptimizationParams,
+};
+use crate::geometry::builders::ChannelExt;
+use crate::geometry::metadata::ParametricPathMetadata;
+use crate::geometry::optimizers::{OptimizationBudget, Optimizer, ParameterBounds, ParticleSwarmOptimizer};
+use crate::geometry::spatial_index::{channel_paths, ChannelSpatialIndex};
+use crate::geometry::strategies::SerpentineChannelStrategy;
+use crate::geometry::types::{ChannelSystem, ChannelType, Point2D};
+use std::time::Duration;
+
+/// System optimization constants
+mod constants {
+    /// Penalty applied per millimeter of clearance violation
+    pub const DEFAULT_CLEARANCE_PENALTY_WEIGHT: f64 = 1000.0;
+
+    /// Tolerance for comparing node positions and the box center (mm)
+    pub const POSITION_TOLERANCE: f64 = 1e-6;
+
+    /// Number of wave parameters per serpentine channel
+    pub const PARAMETERS_PER_CHANNEL: usize = 3;
+}
+
+/// Quantity maximized by the system optimizer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SystemObjective {
+    /// Sum of all serpentine channel lengths
+    TotalLength,
+    /// Length of the shortest serpentine channel, balancing branches
+    MinimumLength,
+}
+
+/// Settings for system-wide optimization
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SystemOptimizationConfig {
+    /// Quantity to maximize
+    pub objective: SystemObjective,
+    /// Evaluation and iteration limits for the optimizer
+    pub budget: OptimizationBudget,
+    /// Penalty per millimeter of wall or channel-to-channel clearance violation
+    pub clearance_penalty_weight: f64,
+}
+
+impl Default for SystemOptimizationConfig {
+    fn default() -> Self {
+        Self {
+            objective: SystemObjective::TotalLength,
+            budget: OptimizationBudget::default(),
+            clearance_penalty_weight: constants::DEFAULT_CLEARANCE_PENALTY_WEIGHT,
+        }
+    }
+}
+
+/// Optimized parameters of one serpentine channel
+#[derive(Debug, Clone)]
+pub struct ChannelOptimization {
+    /// ID of the channel in the system
+    pub channel_id: usize,
+    /// Optimized wave parameters
+    pub params: OptimizationParams,
+    /// Path length before optimization (mm)
+    pub initial_length: f64,
+    /// Path length after optimization (mm)
+    pub optimized_length: f64,
+}
+
+/// Result of system-wide optimization
+#[derive(Debug, Clone)]
+pub struct SystemOptimizationResult {
+    /// The system with every serpentine path regenerated from its optimized parameters
+    pub system: ChannelSystem,
+    /// Per-channel parameters and lengths, in channel order
+    pub channels: Vec<ChannelOptimization>,
+    /// Sum of the optimized serpentine lengths (mm)
+    pub total_length: f64,
+    /// Shortest optimized serpentine length (mm)
+    pub min_length: f64,
+    /// Smallest edge-to-edge distance between two channels (mm)
+    pub min_channel_spacing: f64,
+    /// Smallest distance from a serpentine channel edge to the box walls, ports excluded (mm)
+    pub min_wall_distance: f64,
+    /// Whether all wall and channel-to-channel clearances are respected
+    pub is_feasible: bool,
+    /// Number of system evaluations performed
+    pub evaluations: usize,
+    /// Number of optimizer iterations performed
+    pub iterations: usize,
+    /// Time taken for optimization
+    pub optimization_time: Duration,
+}
+
+/// Half of the box a channel lies in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BoxHalf {
+    Left,
+    Right,
+    Spanning,
+}
+
+/// A serpentine channel whose parameters are optimized
+#[derive(Debug, Clone)]
+struct SerpentineSlot {
+    channel_index: usize,
+    p1: Point2D,
+    p2: Point2D,
+    neighbors: Vec<f64>,
+}
+
+/// Measurements of one candidate system
+#[derive(Debug, Clone)]
+struct SystemEvaluation {
+    paths: Vec<Vec<Point2D>>,
+    lengths: Vec<f64>,
+    min_channel_spacing: f64,
+    min_wall_distance: f64,
+    violation: f64,
+}
+
+/// The fixed structure of a system under optimization
+struct SystemModel<'a> {
+    system: &'a ChannelSystem,
+    geometry_config: &'a GeometryConfig,
+    serpentine_config: &'a SerpentineConfig,
+    total_branches: usize,
+    slots: Vec<SerpentineSlot>,
+    fixed_paths: Vec<Vec<Point2D>>,
+    optimized: Vec<bool>,
+}
+
+impl<'a> SystemModel<'a> {
+    fn new(system: &'a ChannelSystem, geometry_config: &'a GeometryConfig, serpentine_config: &'a SerpentineConfig) -> Self {
+        let endpoints: Vec<(Point2D, Point2D)> = system.channels.iter()
+            .map(|channel| (system.nodes[channel.from_node].point, system.nodes[channel.to_node].point))
+            .collect();
+
+        // Mirror the generator: channels see the midlines of all channels in the same half of the box
+        let half_length = system.box_dims.0 / 2.0;
+        let side = |(p1, p2): (Point2D, Point2D)| {
+            if p1.0.max(p2.0) <= half_length + constants::POSITION_TOLERANCE {
+                BoxHalf::Left
+            } else if p1.0.min(p2.0) >= half_length - constants::POSITION_TOLERANCE {
+                BoxHalf::Right
+            } else {
+                BoxHalf::Spanning
+            }
+        };
+        let slots: Vec<SerpentineSlot> = system.channels.iter()
+            .enumerate()
+            .filter(|(_, channel)| matches!(channel.channel_type, ChannelType::Serpentine { .. }))
+            .map(|(channel_index, _)| {
+                let (p1, p2) = endpoints[channel_index];
+                let channel_side = side((p1, p2));
+                let neighbors = if channel_side == BoxHalf::Spanning {
+                    Vec::new()
+                } else {
+                    endpoints.iter()
+                        .filter(|&&line| side(line) == channel_side)
+                        .map(|&(a, b)| f64::midpoint(a.1, b.1))
+                        .collect()
+                };
+                SerpentineSlot { channel_index, p1, p2, neighbors }
+            })
+            .collect();
+
+        let optimized: Vec<bool> = (0..system.channels.len())
+            .map(|index| slots.iter().any(|slot| slot.channel_index == index))
+            .collect();
+
+        Self {
+            system,
+            geometry_config,
+            serpentine_config,
+            total_branches: max_concurrent_channels(&endpoints),
+            slots,
+            fixed_paths: channel_paths(system),
+            optimized,
+        }
+    }
+
+    /// Parameter bounds repeated for every serpentine channel
+    fn bounds(&self) -> ParameterBounds {
+        let single = serpentine_parameter_bounds();
+        ParameterBounds {
+            lower: single.lower.repeat(self.slots.len()),
+            upper: single.upper.repeat(self.slots.len()),
+        }
+    }
+
+    /// The configured wave parameters for every serpentine channel
+    fn initial_genes(&self) -> Vec<f64> {
+        [
+            self.serpentine_config.wavelength_factor,
+            self.serpentine_config.wave_density_factor,
+            self.serpentine_config.fill_factor,
+        ].repeat(self.slots.len())
+    }
+
+    /// Regenerate all serpentine paths and measure the system
+    ///
+    /// Only channel pairs whose spacing is below `max_spacing` are measured,
+    /// which keeps evaluation cheap; `min_channel_spacing` is infinite when no
+    /// pair is that close.
+    fn evaluate(&self, genes: &[f64], max_spacing: f64) -> SystemEvaluation {
+        let mut paths = self.fixed_paths.clone();
+        let mut lengths = Vec::with_capacity(self.slots.len());
+        let mut min_wall_distance = f64::INFINITY;
+        let mut violation = 0.0;
+        let clearance = self.geometry_config.wall_clearance;
+
+        for (slot, params) in self.slots.iter().zip(genes.chunks(constants::PARAMETERS_PER_CHANNEL)) {
+            let channel_config = SerpentineConfig {
+                wavelength_factor: params[0],
+                wave_density_factor: params[1],
+                fill_factor: params[2],
+                optimization_enabled: false, // Disable nested optimization
+                optimization_target: None,
+                objective: None,
+                ..*self.serpentine_config
+            };
+            let path = SerpentineChannelStrategy::new(channel_config).generate_serpentine_path_for_optimization(
+                slot.p1,
+                slot.p2,
+                self.geometry_config,
+                self.system.box_dims,
+                self.total_branches,
+                Some(&slot.neighbors),
+            );
+
+            let width = self.system.channels[slot.channel_index].width;
+            let wall_distance = calculate_min_wall_distance_excluding_ports(&path, self.system.box_dims, width, clearance);
+            min_wall_distance = min_wall_distance.min(wall_distance);
+            violation += (clearance - wall_distance).max(0.0);
+
+            lengths.push(calculate_path_length(&path));
+            paths[slot.channel_index] = path;
+        }
+
+        let index = ChannelSpatialIndex::with_paths(self.system, &paths).with_junction_clearance(clearance);
+        let mut min_channel_spacing = f64::INFINITY;
+        for proximity in index.proximities_within(max_spacing) {
+            if !self.optimized[proximity.first_channel] && !self.optimized[proximity.second_channel] {
+                continue;
+            }
+            min_channel_spacing = min_channel_spacing.min(proximity.spacing);
+            violation += (clearance - proximity.spacing).max(0.0);
+        }
+
+        SystemEvaluation { paths, lengths, min_channel_spacing, min_wall_distance, violation }
+    }
+}
+
+/// Largest number of channels spanning any single x position
+fn max_concurrent_channels(endpoints: &[(Point2D, Point2D)]) -> usize {
+    endpoints.iter()
+        .map(|&(p1, p2)| {
+            let x = f64::midpoint(p1.0, p2.0);
+            endpoints.iter().filter(|&&(a, b)| a.0.min(b.0) <= x && x <= a.0.max(b.0)).count()
+        })
+        .max()
+        .unwrap_or(1)
+        .max(1)
+}
+
+/// Jointly optimize all serpentine channels of a system
+///
+/// Uses a particle swarm optimizer, which handles the many parameters of a
+/// large system well. See [`optimize_channel_system_with`] to choose the
+/// algorithm.
+///
+/// # Arguments
+/// * `system` - Generated channel system to optimize
+/// * `geometry_config` - Geometry configuration used to generate the system
+/// * `serpentine_config` - Serpentine configuration used to generate the system
+/// * `config` - Objective, budget and penalty settings
+///
+/// # Returns
+/// The optimized system together with per-channel parameters and system metrics
+#[must_use]
+pub fn optimize_channel_system(
+    system: &ChannelSystem,
+    geometry_config: &GeometryConfig,
+    serpentine_config: &SerpentineConfig,
+    config: &SystemOptimizationConfig,
+) -> SystemOptimizationResult {
+    optimize_channel_system_with(&ParticleSwarmOptimizer::default(), system, geometry_config, serpentine_config, config)
+}
+
+/// Jointly optimize all serpentine channels of a system with a specific optimizer
+///
+/// Every serpentine channel contributes its wavelength factor, wave density
+/// factor and fill factor to one parameter vector. Each candidate regenerates
+/// all serpentine paths and is penalized for every millimeter by which a
+/// channel comes closer than the wall clearance to the box walls or to
+/// another channel. Non-serpentine channels are kept as they are but still
+/// count as obstacles.
+///
+/// # Arguments
+/// * `optimizer` - Optimization algorithm to use
+/// * `system` - Generated channel system to optimize
+/// * `geometry_config` - Geometry configuration used to generate the system
+/// * `serpentine_config` - Serpentine configuration used to generate the system
+/// * `config` - Objective, budget and penalty settings
+///
+/// # Returns
+/// The optimized system together with per-channel parameters and system metrics
+#[must_use]
+pub fn optimize_channel_system_with(
+    optimizer: &dyn Optimizer,
+    system: &ChannelSystem,
+    geometry_config: &GeometryConfig,
+    serpentine_config: &SerpentineConfig,
+    config: &SystemOptimizationConfig,
+) -> SystemOptimizationResult {
+    let start_time = std::time::Instant::now();
+    let model = SystemModel::new(system, geometry_config, serpentine_config);
+    let initial_lengths: Vec<f64> = model.slots.iter()
+        .map(|slot| calculate_path_length(&model.fixed_paths[slot.channel_index]))
+        .collect();
+
+    let (best_genes, evaluations, iterations) = if model.slots.is_empty() {
+        (Vec::new(), 0, 0)
+    } else {
+        let score = |genes: &[f64]| {
+            let evaluation = model.evaluate(genes, geometry_config.wall_clearance);
+            let length_score = match config.objective {
+                SystemObjective::TotalLength => evaluation.lengths.iter().sum(),
+                SystemObjective::MinimumLength => evaluation.lengths.iter().copied().fold(f64::INFINITY, f64::min),
+            };
+            config.clearance_penalty_weight.mul_add(-evaluation.violation, length_score)
+        };
+        let outcome = optimizer.optimize(&score, &model.bounds(), &model.initial_genes(), &config.budget);
+        (outcome.best_params, outcome.evaluations, outcome.iterations)
+    };
+
+    let evaluation = model.evaluate(&best_genes, f64::INFINITY);
+    let mut optimized_system = system.clone();
+    for (channel, path) in optimized_system.channels.iter_mut().zip(&evaluation.paths) {
+        if let ChannelType::Serpentine { path: channel_path } = &mut channel.channel_type {
+            channel_path.clone_from(path);
+            // The analytic curve of the original wave parameters is stale
+            channel.remove_metadata::<ParametricPathMetadata>();
+        }
+    }
+
+    let channels: Vec<ChannelOptimization> = model.slots.iter()
+        .zip(best_genes.chunks(constants::PARAMETERS_PER_CHANNEL))
+        .zip(initial_lengths.iter().zip(&evaluation.lengths))
+        .map(|((slot, params), (&initial_length, &optimized_length))| ChannelOptimization {
+            channel_id: system.channels[slot.channel_index].id,
+            params: OptimizationParams {
+                wavelength_factor: params[0],
+                wave_density_factor: params[1],
+                fill_factor: params[2],
+            },
+            initial_length,
+            optimized_length,
+        })
+        .collect();
+
+    SystemOptimizationResult {
+        system: optimized_system,
+        total_length: evaluation.lengths.iter().sum(),
+        min_length: evaluation.lengths.iter().copied().fold(f64::INFINITY, f64::min),
+        min_channel_spacing: evaluation.min_channel_spacing,
+        min_wall_distance: evaluation.min_wall_distance,
+        is_feasible: evaluation.violation <= 0.0,
+        channels,
+        evaluations,
+        iterations,
+        optimization_time: start_time.elapsed(),
+    }
+}
@@ -0,0 +1,172 @@
+//! `geometry/transform.rs` - Similarity Transforms of Channel Geometry
+//!
+//! Generated channel trees are often placed rotated, mirrored or offset on a
+//! larger chip. A [`Transform`] describes such a placement as a similarity
+//! transform: a rotation or reflection, a uniform scale and a translation.
+//! Restricting transforms to similarities keeps every geometric quantity of a
+//! channel meaningful afterwards: widths scale with the geometry, waves stay
+//! waves and arcs stay arcs.
+//!
+//! Transforms are applied to whole systems with [`ChannelSystem::transform`]
+//! and its shorthands [`ChannelSystem::translate`], [`ChannelSystem::rotate`],
+//! [`ChannelSystem::scale`] and [`ChannelSystem::mirror`].
+//!
+//! [`ChannelSystem::transform`]: crate::geometry::ChannelSystem::transform
+//! [`ChannelSystem::translate`]: crate::geometry::ChannelSystem::translate
+//! [`ChannelSystem::rotate`]: crate::geometry::ChannelSystem::rotate
+//! [`ChannelSystem::scale`]: crate::geometry::ChannelSystem::scale
+//! [`ChannelSystem::mirror`]: crate::geometry::ChannelSystem::mirror
+//!
+//! # Examples
+//!
+//! ```rust
+//! use scheme::geometry::transform::{MirrorAxis, Transform};
+//! use std::f64::consts::FRAC_PI_2;
+//!
+//! // Quarter turn about the origin, then shift 10 mm to the right
+//! let placement = Transform::rotation(FRAC_PI_2, (0.0, 0.0)).then(&Transform::translation(10.0, 0.0));
+//! let (x, y) = placement.apply((1.0, 0.0));
+//! assert!((x - 10.0).abs() < 1e-12 && (y - 1.0).abs() < 1e-12);
+//!
+//! let flip = Transform::reflection(MirrorAxis::Vertical { x: 5.0 });
+//! assert_eq!(flip.apply((2.0, 3.0)), (8.0, 3.0));
+//! ```
+
+use crate::geometry::Point2D;
+use serde::{Deserialize, Serialize};
+
+/// Line to mirror geometry across
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum MirrorAxis {
+    /// Vertical line `x = x`, swapping left and right
+    Vertical {
+        /// Position of the line on the x axis (mm)
+        x: f64,
+    },
+    /// Horizontal line `y = y`, swapping top and bottom
+    Horizontal {
+        /// Position of the line on the y axis (mm)
+        y: f64,
+    },
+}
+
+/// Similarity transform of the plane
+///
+/// Maps a point `p` to `matrix * p + translation`, where `matrix` is a
+/// rotation or reflection times a uniform scale. Transforms are built from the
+/// constructors below and combined with [`Transform::then`].
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Transform {
+    matrix: [[f64; 2]; 2],
+    translation: (f64, f64),
+}
+
+impl Default for Transform {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+impl Transform {
+    /// Transform that leaves every point in place
+    #[must_use]
+    pub const fn identity() -> Self {
+        Self {
+            matrix: [[1.0, 0.0], [0.0, 1.0]],
+            translation: (0.0, 0.0),
+        }
+    }
+
+    /// Shift by `dx` and `dy`
+    #[must_use]
+    pub const fn translation(dx: f64, dy: f64) -> Self {
+        Self {
+            matrix: [[1.0, 0.0], [0.0, 1.0]],
+            translation: (dx, dy),
+        }
+    }
+
+    /// Counterclockwise rotation by `angle` radians about `center`
+    #[must_use]
+    pub fn rotation(angle: f64, center: Point2D) -> Self {
+        let (sin, cos) = angle.sin_cos();
+        Self::about([[cos, -sin], [sin, cos]], center)
+    }
+
+    /// Uniform scale by `factor` about `center`
+    ///
+    /// `factor` should be non-zero; a negative factor additionally turns the
+    /// geometry by half a turn.
+    #[must_use]
+    pub fn scaling(factor: f64, center: Point2D) -> Self {
+        Self::about([[factor, 0.0], [0.0, factor]], center)
+    }
+
+    /// Reflection across `axis`
+    #[must_use]
+    pub const fn reflection(axis: MirrorAxis) -> Self {
+        match axis {
+            MirrorAxis::Vertical { x } => Self {
+                matrix: [[-1.0, 0.0], [0.0, 1.0]],
+                translation: (2.0 * x, 0.0),
+            },
+            MirrorAxis::Horizontal { y } => Self {
+                matrix: [[1.0, 0.0], [0.0, -1.0]],
+                translation: (0.0, 2.0 * y),
+            },
+        }
+    }
+
+    /// Transform that applies `self` first and `next` second
+    #[must_use]
+    pub fn then(&self, next: &Self) -> Self {
+        let entry = |row: usize, column: usize| {
+            next.matrix[row][0].mul_add(self.matrix[0][column], next.matrix[row][1] * self.matrix[1][column])
+        };
+        Self {
+            matrix: [[entry(0, 0), entry(0, 1)], [entry(1, 0), entry(1, 1)]],
+            translation: next.apply(self.translation),
+        }
+    }
+
+    /// Image of `point`
+    #[must_use]
+    pub const fn apply(&self, point: Point2D) -> Point2D {
+        let [[a, b], [c, d]] = self.matrix;
+        (
+            a.mul_add(point.0, b.mul_add(point.1, self.translation.0)),
+            c.mul_add(point.0, d.mul_add(point.1, self.translation.1)),
+        )
+    }
+
+    /// Factor by which lengths and widths change
+    #[must_use]
+    pub fn scale_factor(&self) -> f64 {
+        self.determinant().abs().sqrt()
+    }
+
+    /// Whether the transform mirrors the geometry, swapping left and right
+    #[must_use]
+    pub fn is_reflection(&self) -> bool {
+        self.determinant() < 0.0
+    }
+
+    /// Linear part `matrix` applied about `center`, which stays in place
+    fn about(matrix: [[f64; 2]; 2], center: Point2D) -> Self {
+        let linear = Self {
+            matrix,
+            translation: (0.0, 0.0),
+        };
+        let moved = linear.apply(center);
+        Self {
+            matrix,
+            translation: (center.0 - moved.0, center.1 - moved.1),
+        }
+    }
+
+    /// Determinant of the linear part
+    fn determinant(&self) -> f64 {
+        let [[a, b], [c, d]] = self.matrix;
+        a.mul_add(d, -(b * c))
+    }
+}
@@ -8,12 +8,20 @@
 //! allowing for easy addition of new tracking variables without breaking
 //! existing functionality.
 
-use crate::geometry::metadata::MetadataContainer;
+use crate::geometry::builders::ChannelExt;
+use crate::error::{GeometryError, GeometryResult};
+use crate::geometry::composition::{Port, PortKind};
+use crate::geometry::format::{self, VersionedSystem};
+use crate::geometry::metadata::{MetadataContainer, ParametricPathMetadata};
+use crate::geometry::transform::{MirrorAxis, Transform};
 use serde::{Deserialize, Serialize};
 
 /// A 2D point represented as (x, y) coordinates
 pub type Point2D = (f64, f64);
 
+/// Distance within which validation treats positions as equal (mm)
+const VALIDATION_TOLERANCE: f64 = 1e-6;
+
 /// A node represents a connection point in the channel system
 ///
 /// Nodes are used to define the endpoints of channels and serve as
@@ -28,7 +36,9 @@ pub struct Node {
     /// 2D coordinates of the node
     pub point: Point2D,
     /// Optional metadata container for extensible properties
-    #[serde(skip)]
+    ///
+    /// Serialized by type name; see [`crate::geometry::metadata::MetadataRegistry`].
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "crate::geometry::metadata::container_serde")]
     pub metadata: Option<MetadataContainer>,
 }
 
@@ -126,7 +136,9 @@ pub struct Channel {
     /// The type and path of this channel
     pub channel_type: ChannelType,
     /// Optional metadata container for extensible properties
-    #[serde(skip)]
+    ///
+    /// Serialized by type name; see [`crate::geometry::metadata::MetadataRegistry`].
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "crate::geometry::metadata::container_serde")]
     pub metadata: Option<MetadataContainer>,
 }
 
@@ -151,7 +163,9 @@ impl ChannelSystem {
     /// Export the channel system to JSON format
     ///
     /// This method serializes the entire channel system to a JSON string,
-    /// making it easy to save, load, or transfer channel system data.
+    /// making it easy to save, load, or transfer channel system data. The
+    /// document records the current
+    /// [`SYSTEM_FORMAT_VERSION`](crate::geometry::format::SYSTEM_FORMAT_VERSION).
     ///
     /// # Returns
     ///
@@ -176,20 +190,24 @@ impl ChannelSystem {
     /// println!("Exported system: {}", json);
     /// ```
     pub fn to_json(&self) -> Result<String, serde_json::Error> {
-        serde_json::to_string_pretty(self)
+        serde_json::to_string_pretty(&VersionedSystem::new(self))
     }
 
     /// Import a channel system from JSON format
     ///
-    /// This method deserializes a channel system from a JSON string.
+    /// This method deserializes a channel system from a JSON string. Documents
+    /// written with an older format version are migrated first; see
+    /// [`crate::geometry::format`].
     ///
     /// # Arguments
     ///
     /// * `json` - A JSON string representation of a channel system
     ///
-    /// # Returns
+    /// # Errors
     ///
-    /// A channel system instance, or an error if deserialization fails.
+    /// Returns [`GeometryError::UnsupportedFormatVersion`] for documents
+    /// written by a newer version, and [`GeometryError::InvalidDocument`]
+    /// naming the format version if the document cannot be read.
     ///
     /// # Examples
     ///
@@ -199,8 +217,23 @@ impl ChannelSystem {
     /// let json = r#"{"box_dims": [200.0, 100.0], "nodes": [], "channels": [], "box_outline": []}"#;
     /// let system = ChannelSystem::from_json(json).expect("Failed to deserialize");
     /// ```
-    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
-        serde_json::from_str(json)
+    pub fn from_json(json: &str) -> GeometryResult<Self> {
+        format::load(json)
+    }
+
+    /// Import a channel system from JSON format and validate it
+    ///
+    /// Use this for files from outside the library, so that systems that
+    /// would make drawing or analysis panic are rejected on load.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`Self::from_json`], or the first problem found
+    /// by [`Self::validate`].
+    pub fn from_json_validated(json: &str) -> GeometryResult<Self> {
+        let system = Self::from_json(json)?;
+        system.validate()?;
+        Ok(system)
     }
 
     /// Get all line segments that make up this channel system
@@ -240,14 +273,10 @@ impl ChannelSystem {
                     lines.push((from, to));
                 }
                 ChannelType::SmoothStraight { path } | ChannelType::Serpentine { path } | ChannelType::Arc { path } => {
-                    for i in 0..path.len() - 1 {
-                        lines.push((path[i], path[i + 1]));
-                    }
+                    lines.extend(path.windows(2).map(|segment| (segment[0], segment[1])));
                 }
                 ChannelType::Frustum { path, .. } => {
-                    for i in 0..path.len() - 1 {
-                        lines.push((path[i], path[i + 1]));
-                    }
+                    lines.extend(path.windows(2).map(|segment| (segment[0], segment[1])));
                 }
             }
         }
@@ -302,14 +331,10 @@ impl ChannelSystem {
                     lines.push((from, to));
                 }
                 ChannelType::SmoothStraight { path } | ChannelType::Serpentine { path } | ChannelType::Arc { path } => {
-                    for i in 0..path.len() - 1 {
-                        lines.push((path[i], path[i + 1]));
-                    }
+                    lines.extend(path.windows(2).map(|segment| (segment[0], segment[1])));
                 }
                 ChannelType::Frustum { path, .. } => {
-                    for i in 0..path.len() - 1 {
-                        lines.push((path[i], path[i + 1]));
-                    }
+                    lines.extend(path.windows(2).map(|segment| (segment[0], segment[1])));
                 }
             }
         }
@@ -357,6 +382,353 @@ impl ChannelSystem {
             })
             .collect()
     }
+
+    /// Get the axis-aligned bounds of the containing box
+    ///
+    /// Returns the minimum and maximum corners of the box outline. Generated
+    /// systems span from the origin to `box_dims`; transformed systems may be
+    /// placed anywhere.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use scheme::geometry::generator::create_geometry;
+    /// use scheme::geometry::SplitType;
+    /// use scheme::config::{GeometryConfig, ChannelTypeConfig};
+    ///
+    /// let mut system = create_geometry(
+    ///     (200.0, 100.0),
+    ///     &[SplitType::Bifurcation],
+    ///     &GeometryConfig::default(),
+    ///     &ChannelTypeConfig::AllStraight,
+    /// );
+    /// assert_eq!(system.bounds(), ((0.0, 0.0), (200.0, 100.0)));
+    ///
+    /// system.translate(50.0, 25.0);
+    /// assert_eq!(system.bounds(), ((50.0, 25.0), (250.0, 125.0)));
+    /// ```
+    #[must_use]
+    pub fn bounds(&self) -> (Point2D, Point2D) {
+        if self.box_outline.is_empty() {
+            return ((0.0, 0.0), self.box_dims);
+        }
+        self.box_outline
+            .iter()
+            .flat_map(|&segment| <[Point2D; 2]>::from(segment))
+            .fold(
+                ((f64::INFINITY, f64::INFINITY), (f64::NEG_INFINITY, f64::NEG_INFINITY)),
+                |(min, max), point| ((min.0.min(point.0), min.1.min(point.1)), (max.0.max(point.0), max.1.max(point.1))),
+            )
+    }
+
+    /// Get the open ends of the system where it can be connected to others
+    ///
+    /// A port is a node that ends exactly one channel. It is an inlet if
+    /// the channel starts there and an outlet if the channel ends there.
+    /// Ports are returned in node order. Channels referring to nodes that do
+    /// not exist are ignored; [`Self::validate`] reports them.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use scheme::geometry::composition::PortKind;
+    /// use scheme::geometry::generator::create_geometry;
+    /// use scheme::geometry::SplitType;
+    /// use scheme::config::{GeometryConfig, ChannelTypeConfig};
+    ///
+    /// let system = create_geometry(
+    ///     (200.0, 100.0),
+    ///     &[SplitType::Bifurcation],
+    ///     &GeometryConfig::default(),
+    ///     &ChannelTypeConfig::AllStraight,
+    /// );
+    ///
+    /// let ports = system.ports();
+    /// assert_eq!(ports.len(), 2);
+    /// assert_eq!((ports[0].kind, ports[0].point), (PortKind::Inlet, (0.0, 50.0)));
+    /// assert_eq!((ports[1].kind, ports[1].point), (PortKind::Outlet, (200.0, 50.0)));
+    /// ```
+    #[must_use]
+    pub fn ports(&self) -> Vec<Port> {
+        let connected: Vec<(&Channel, Point2D, Point2D)> = self
+            .channels
+            .iter()
+            .filter_map(|channel| {
+                let from = self.nodes.get(channel.from_node)?.point;
+                let to = self.nodes.get(channel.to_node)?.point;
+                Some((channel, from, to))
+            })
+            .collect();
+        let mut degrees = vec![0_usize; self.nodes.len()];
+        for (channel, ..) in &connected {
+            degrees[channel.from_node] += 1;
+            degrees[channel.to_node] += 1;
+        }
+
+        connected
+            .into_iter()
+            .flat_map(|(channel, from, to)| {
+                let (after_start, before_end) = match &channel.channel_type {
+                    ChannelType::Straight => (to, from),
+                    ChannelType::SmoothStraight { path }
+                    | ChannelType::Serpentine { path }
+                    | ChannelType::Arc { path }
+                    | ChannelType::Frustum { path, .. } => (
+                        path.get(1).copied().unwrap_or(to),
+                        path.len().checked_sub(2).map_or(from, |index| path[index]),
+                    ),
+                };
+                [
+                    (channel.from_node, PortKind::Inlet, from, after_start, channel.width),
+                    (channel.to_node, PortKind::Outlet, to, before_end, channel.width),
+                ]
+            })
+            .filter(|&(node, ..)| degrees[node] == 1)
+            .map(|(node, kind, point, inner, width)| {
+                let (dx, dy) = (point.0 - inner.0, point.1 - inner.1);
+                let length = dx.hypot(dy);
+                let direction = if length > 0.0 { (dx / length, dy / length) } else { (0.0, 0.0) };
+                (node, Port { node, point, kind, direction, width })
+            })
+            .collect::<std::collections::BTreeMap<_, _>>()
+            .into_values()
+            .collect()
+    }
+
+    /// Check the structural integrity of the system
+    ///
+    /// # Errors
+    ///
+    /// Returns the first problem found by [`Self::validation_errors`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use scheme::error::GeometryError;
+    /// use scheme::geometry::generator::create_geometry;
+    /// use scheme::geometry::SplitType;
+    /// use scheme::config::{GeometryConfig, ChannelTypeConfig};
+    ///
+    /// let mut system = create_geometry(
+    ///     (200.0, 100.0),
+    ///     &[SplitType::Bifurcation],
+    ///     &GeometryConfig::default(),
+    ///     &ChannelTypeConfig::AllStraight,
+    /// );
+    /// assert!(system.validate().is_ok());
+    ///
+    /// system.channels[0].to_node = 99;
+    /// assert!(matches!(system.validate(), Err(GeometryError::DanglingNodeReference { node_id: 99, .. })));
+    /// ```
+    pub fn validate(&self) -> GeometryResult<()> {
+        self.validation_errors().into_iter().next().map_or(Ok(()), Err)
+    }
+
+    /// Find every structural problem in the system
+    ///
+    /// Checks that node and channel IDs are unique, node IDs match their
+    /// index in the node list, channels refer to existing nodes, channel
+    /// paths have at least two points and start and end at their nodes, all
+    /// coordinates are finite, and nodes and paths lie within the bounds of
+    /// the box outline. Systems without problems can be
+    /// drawn, measured and transformed without panicking.
+    ///
+    /// Problems are reported for the outline, then nodes, then channels.
+    #[must_use]
+    pub fn validation_errors(&self) -> Vec<GeometryError> {
+        let mut errors = Vec::new();
+        let finite = |point: &Point2D| point.0.is_finite() && point.1.is_finite();
+        if let Some(point) = self.box_outline.iter().flat_map(|&segment| <[Point2D; 2]>::from(segment)).find(|point| !finite(point)) {
+            errors.push(GeometryError::invalid_point(point));
+        }
+        let (min, max) = self.bounds();
+        let outside = |point: &Point2D| {
+            point.0 < min.0 - VALIDATION_TOLERANCE
+                || point.1 < min.1 - VALIDATION_TOLERANCE
+                || point.0 > max.0 + VALIDATION_TOLERANCE
+                || point.1 > max.1 + VALIDATION_TOLERANCE
+        };
+
+        let mut node_ids = std::collections::HashSet::new();
+        for (index, node) in self.nodes.iter().enumerate() {
+            if !node_ids.insert(node.id) {
+                errors.push(GeometryError::DuplicateId { element: "node".to_string(), id: node.id });
+            } else if node.id != index {
+                // Channels refer to nodes by index, so IDs must follow the node order
+                errors.push(GeometryError::NodeIdMismatch { index, id: node.id });
+            }
+            if !finite(&node.point) {
+                errors.push(GeometryError::invalid_point(node.point));
+            } else if outside(&node.point) {
+                errors.push(GeometryError::OutsideOutline { x: node.point.0, y: node.point.1 });
+            }
+        }
+
+        let mut channel_ids = std::collections::HashSet::new();
+        for channel in &self.channels {
+            if !channel_ids.insert(channel.id) {
+                errors.push(GeometryError::DuplicateId { element: "channel".to_string(), id: channel.id });
+            }
+            let mut ends = Vec::with_capacity(2);
+            for node_id in [channel.from_node, channel.to_node] {
+                match self.nodes.get(node_id) {
+                    Some(node) => ends.push((node_id, node.point)),
+                    None => errors.push(GeometryError::DanglingNodeReference {
+                        channel_id: channel.id,
+                        node_id,
+                        node_count: self.nodes.len(),
+                    }),
+                }
+            }
+
+            let path = match &channel.channel_type {
+                ChannelType::Straight => continue,
+                ChannelType::SmoothStraight { path }
+                | ChannelType::Serpentine { path }
+                | ChannelType::Arc { path }
+                | ChannelType::Frustum { path, .. } => path,
+            };
+            if path.len() < 2 {
+                errors.push(GeometryError::InvalidChannelPath {
+                    reason: format!("channel {} has {} path point(s)", channel.id, path.len()),
+                });
+                continue;
+            }
+            if let Some(point) = path.iter().find(|point| !finite(point)) {
+                errors.push(GeometryError::invalid_point(*point));
+                continue;
+            }
+            if let Some(point) = path.iter().find(|point| outside(point)) {
+                errors.push(GeometryError::OutsideOutline { x: point.0, y: point.1 });
+            }
+            if ends.len() == 2 {
+                for ((node_id, node_point), path_point) in ends.into_iter().zip([path[0], path[path.len() - 1]]) {
+                    let distance = (path_point.0 - node_point.0).hypot(path_point.1 - node_point.1);
+                    if distance > VALIDATION_TOLERANCE {
+                        errors.push(GeometryError::PathEndpointMismatch { channel_id: channel.id, node_id, distance });
+                    }
+                }
+            }
+        }
+        errors
+    }
+
+    /// Append the nodes and channels of `other` to this system
+    ///
+    /// Node and channel IDs of `other` are renumbered to follow the existing
+    /// ones, and its channels are reconnected to the renumbered nodes. The box
+    /// outline and dimensions of this system are kept.
+    ///
+    /// Returns the index of the first appended node and channel.
+    pub fn append(&mut self, other: &Self) -> (usize, usize) {
+        let node_offset = self.nodes.len();
+        let channel_offset = self.channels.len();
+        self.nodes.extend(other.nodes.iter().cloned().enumerate().map(|(index, mut node)| {
+            node.id = node_offset + index;
+            node
+        }));
+        self.channels.extend(other.channels.iter().cloned().enumerate().map(|(index, mut channel)| {
+            channel.id = channel_offset + index;
+            channel.from_node += node_offset;
+            channel.to_node += node_offset;
+            channel
+        }));
+        (node_offset, channel_offset)
+    }
+
+    /// Apply a similarity transform to the whole system
+    ///
+    /// Moves every node, channel path and box outline segment. Channel
+    /// widths, frustum width profiles and analytic paths kept as
+    /// [`ParametricPathMetadata`] scale with the transform, and `box_dims`
+    /// becomes the size of the transformed box's bounds. Channel heights are
+    /// out of plane and stay unchanged, as does other metadata.
+    ///
+    /// Returns `self` so transforms can be chained.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use scheme::geometry::generator::create_geometry;
+    /// use scheme::geometry::transform::Transform;
+    /// use scheme::geometry::SplitType;
+    /// use scheme::config::{GeometryConfig, ChannelTypeConfig};
+    ///
+    /// let mut system = create_geometry(
+    ///     (200.0, 100.0),
+    ///     &[SplitType::Bifurcation],
+    ///     &GeometryConfig::default(),
+    ///     &ChannelTypeConfig::AllStraight,
+    /// );
+    ///
+    /// system.transform(&Transform::scaling(0.5, (0.0, 0.0)));
+    /// assert_eq!(system.box_dims, (100.0, 50.0));
+    /// ```
+    pub fn transform(&mut self, transform: &Transform) -> &mut Self {
+        let scale = transform.scale_factor();
+        for node in &mut self.nodes {
+            node.point = transform.apply(node.point);
+        }
+        for channel in &mut self.channels {
+            channel.width *= scale;
+            match &mut channel.channel_type {
+                ChannelType::Straight => {}
+                ChannelType::SmoothStraight { path } | ChannelType::Serpentine { path } | ChannelType::Arc { path } => {
+                    transform_path(path, transform);
+                }
+                ChannelType::Frustum { path, widths, inlet_width, throat_width, outlet_width } => {
+                    transform_path(path, transform);
+                    for width in widths.iter_mut().chain([inlet_width, throat_width, outlet_width]) {
+                        *width *= scale;
+                    }
+                }
+            }
+            if let Some(metadata) = channel.get_metadata_mut::<ParametricPathMetadata>() {
+                metadata.path = metadata.path.transformed(transform);
+            }
+        }
+        for (p1, p2) in &mut self.box_outline {
+            *p1 = transform.apply(*p1);
+            *p2 = transform.apply(*p2);
+        }
+        if self.box_outline.is_empty() {
+            self.box_dims = (self.box_dims.0 * scale, self.box_dims.1 * scale);
+        } else {
+            let (min, max) = self.bounds();
+            self.box_dims = (max.0 - min.0, max.1 - min.1);
+        }
+        self
+    }
+
+    /// Shift the whole system by `dx` and `dy`
+    pub fn translate(&mut self, dx: f64, dy: f64) -> &mut Self {
+        self.transform(&Transform::translation(dx, dy))
+    }
+
+    /// Rotate the whole system counterclockwise by `angle` radians about `center`
+    pub fn rotate(&mut self, angle: f64, center: Point2D) -> &mut Self {
+        self.transform(&Transform::rotation(angle, center))
+    }
+
+    /// Scale the whole system uniformly by `factor` about `center`
+    ///
+    /// Channel widths and frustum width profiles scale along with the
+    /// geometry; see [`ChannelSystem::transform`].
+    pub fn scale(&mut self, factor: f64, center: Point2D) -> &mut Self {
+        self.transform(&Transform::scaling(factor, center))
+    }
+
+    /// Mirror the whole system across `axis`
+    pub fn mirror(&mut self, axis: MirrorAxis) -> &mut Self {
+        self.transform(&Transform::reflection(axis))
+    }
+}
+
+/// Map every point of `path` through `transform`
+fn transform_path(path: &mut [Point2D], transform: &Transform) {
+    for point in path {
+        *point = transform.apply(*point);
+    }
 }
 
 /// Defines the type of channel splitting pattern
@@ -0,0 +1,435 @@
+//! import/dxf.rs - DXF Import
+//!
+//! Reads the `LINE`, `ARC`, `LWPOLYLINE` and `SPLINE` entities of an ASCII
+//! DXF drawing. Entities on the outline layer form the chip outline and
+//! entities on the channel layers become channels; entities on other layers
+//! are ignored. Any other entity type on the outline or channel layers, such
+//! as `CIRCLE`, `TEXT` or a block `INSERT`, is reported in
+//! [`Imported::skipped`].
+//!
+//! Arcs, polyline bulges and splines are flattened into
+//! [`ImportOptions::curve_segments`] straight segments per arc, bulge or
+//! spline span. Splines are evaluated from their control points, knots and
+//! weights, or follow their fit points if they have no control points.
+//! Polylines with a constant width (group code 43) keep it as their channel
+//! width; all other channels are [`ImportOptions::channel_width`] wide.
+//!
+//! Coordinates are converted to millimetres using the `$INSUNITS` header
+//! variable; unitless drawings are taken to be in millimetres. Arcs,
+//! polylines and splines drawn with a downward extrusion direction, as
+//! produced by mirroring in some CAD tools, are mirrored back into the
+//! drawing plane.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use scheme::geometry::ChannelType;
+//! use scheme::import::{dxf, ImportOptions};
+//!
+//! // Group code and value pairs, one per line in the file
+//! let drawing = "0 SECTION 2 ENTITIES \
+//!     0 LWPOLYLINE 8 OUTLINE 90 4 70 1 10 0 20 0 10 60 20 0 10 60 20 30 10 0 20 30 \
+//!     0 LINE 8 CHANNELS 10 0 20 15 11 30 21 15 \
+//!     0 ARC 8 CHANNELS 10 45 20 15 40 15 50 180 51 0 \
+//!     0 TEXT 8 CHANNELS 10 5 20 5 1 inlet \
+//!     0 ENDSEC 0 EOF"
+//!     .replace(' ', "\n");
+//!
+//! let imported = dxf::import(&drawing, &ImportOptions::default())?;
+//! let system = imported.system;
+//! assert_eq!(system.box_dims, (60.0, 30.0));
+//! assert_eq!((system.nodes.len(), system.channels.len()), (3, 2));
+//! assert!(matches!(system.channels[1].channel_type, ChannelType::Arc { .. }));
+//! assert_eq!(imported.skipped.get("TEXT"), Some(&1));
+//! # Ok::<(), scheme::error::ImportError>(())
+//! ```
+
+use super::{assemble, ImportOptions, Imported, Stroke};
+use crate::error::{ImportError, ImportResult};
+use crate::geometry::Point2D;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Name of the format for messages
+const FORMAT: &str = "DXF";
+
+/// Entities that continue the entity before them rather than standing alone
+const CONTINUATIONS: [&str; 3] = ["VERTEX", "SEQEND", "ATTRIB"];
+
+/// Bulges smaller than this are straight segments
+const MIN_BULGE: f64 = 1e-12;
+
+/// Import a channel system from ASCII DXF text
+///
+/// # Errors
+///
+/// Returns [`ImportError::ParseError`] if the text is binary DXF, is not a
+/// sequence of group codes and values, or has entities with missing or
+/// invalid values, [`ImportError::MissingOutline`] or
+/// [`ImportError::NoChannels`] if the outline or channel layers are empty,
+/// and [`ImportError::InvalidSystem`] if the channels do not form a valid
+/// system, for example because they extend beyond the outline.
+pub fn import(text: &str, options: &ImportOptions) -> ImportResult<Imported> {
+    let pairs = parse_pairs(text)?;
+    let scale = units_in_mm(&pairs);
+
+    let mut outline = Vec::new();
+    let mut strokes = Vec::new();
+    let mut skipped = BTreeMap::new();
+    for entity in entities(&pairs) {
+        let layers = [entity.layer().to_string()];
+        let on_outline = options.on_outline_layer(&layers);
+        if !on_outline && !options.on_channel_layer(&layers) {
+            continue;
+        }
+        let shape = match entity.kind {
+            "LINE" => entity.line()?,
+            "ARC" => entity.arc(options.curve_segments)?,
+            "LWPOLYLINE" => entity.polyline(options.curve_segments)?,
+            "SPLINE" => entity.spline(options.curve_segments)?,
+            kind if CONTINUATIONS.contains(&kind) => continue,
+            kind => {
+                *skipped.entry(kind.to_string()).or_default() += 1;
+                continue;
+            }
+        };
+
+        let points: Vec<Point2D> = shape.points.iter().map(|point| (point.0 * scale, point.1 * scale)).collect();
+        if on_outline {
+            outline.extend(points.windows(2).map(|pair| (pair[0], pair[1])));
+        } else {
+            strokes.push(Stroke {
+                points,
+                width: shape.width.map_or(options.channel_width, |width| width * scale),
+                curved: shape.curved,
+            });
+        }
+    }
+    assemble(&outline, strokes, skipped, options, false)
+}
+
+/// Import a channel system from an ASCII DXF file
+///
+/// # Errors
+///
+/// Returns [`ImportError::FileError`] if the file cannot be read, and the
+/// errors of [`import`] otherwise.
+pub fn import_file(path: impl AsRef<Path>, options: &ImportOptions) -> ImportResult<Imported> {
+    let path = path.as_ref();
+    let text = std::fs::read_to_string(path)
+        .map_err(|error| ImportError::file_error(&format!("cannot read '{}': {error}", path.display())))?;
+    import(&text, options)
+}
+
+/// Group code and value
+#[derive(Debug, Clone, Copy)]
+struct Pair<'a> {
+    code: i32,
+    value: &'a str,
+    /// Line of the group code in the file
+    line: usize,
+}
+
+/// Group code and value pairs up to the end of file marker
+fn parse_pairs(text: &str) -> ImportResult<Vec<Pair<'_>>> {
+    if text.starts_with("AutoCAD Binary DXF") {
+        return Err(ImportError::parse_error(FORMAT, "binary DXF is not supported; save the drawing as ASCII DXF"));
+    }
+    let mut lines = text.lines().map(str::trim).enumerate();
+    let mut pairs = Vec::new();
+    while let Some((index, code)) = lines.next() {
+        let line = index + 1;
+        let code = code.parse().map_err(|_| {
+            ImportError::parse_error(FORMAT, &format!("line {line}: expected a group code, found '{code}'"))
+        })?;
+        let (_, value) = lines.next().ok_or_else(|| {
+            ImportError::parse_error(FORMAT, &format!("line {line}: group code {code} has no value"))
+        })?;
+        if code == 0 && value == "EOF" {
+            break;
+        }
+        pairs.push(Pair { code, value, line });
+    }
+    Ok(pairs)
+}
+
+/// Millimetres per drawing unit from the `$INSUNITS` header variable
+fn units_in_mm(pairs: &[Pair]) -> f64 {
+    let units = pairs
+        .iter()
+        .position(|pair| pair.code == 9 && pair.value == "$INSUNITS")
+        .and_then(|index| pairs[index + 1..].iter().find(|pair| pair.code == 70))
+        .and_then(|pair| pair.value.parse::<i32>().ok());
+    match units {
+        Some(1) => 25.4,
+        Some(2) => 304.8,
+        Some(5) => 10.0,
+        Some(6) => 1000.0,
+        Some(8) => 25.4e-6,
+        Some(9) => 25.4e-3,
+        Some(10) => 914.4,
+        Some(13) => 1e-3,
+        Some(14) => 100.0,
+        _ => 1.0,
+    }
+}
+
+/// Entity of the `ENTITIES` section
+#[derive(Debug)]
+struct Entity<'a> {
+    kind: &'a str,
+    /// Line of the entity in the file
+    line: usize,
+    /// Pairs after the entity type
+    pairs: &'a [Pair<'a>],
+}
+
+/// Entities of the `ENTITIES` section, in drawing order
+fn entities<'a>(pairs: &'a [Pair<'a>]) -> Vec<Entity<'a>> {
+    let start = pairs
+        .windows(2)
+        .position(|window| window[0].code == 0 && window[0].value == "SECTION" && window[1].code == 2 && window[1].value == "ENTITIES")
+        .map_or(pairs.len(), |index| index + 2);
+    let section = &pairs[start..];
+    let end = section.iter().position(|pair| pair.code == 0 && pair.value == "ENDSEC").unwrap_or(section.len());
+
+    let mut entities = Vec::new();
+    let mut rest = &section[..end];
+    while let Some((first, tail)) = rest.split_first() {
+        let length = tail.iter().position(|pair| pair.code == 0).unwrap_or(tail.len());
+        if first.code == 0 {
+            entities.push(Entity { kind: first.value, line: first.line, pairs: &tail[..length] });
+        }
+        rest = &tail[length..];
+    }
+    entities
+}
+
+/// Flattened outline of an entity in drawing units
+struct Shape {
+    points: Vec<Point2D>,
+    curved: bool,
+    /// Width given by the entity itself
+    width: Option<f64>,
+}
+
+impl Entity<'_> {
+    fn error(&self, message: &str) -> ImportError {
+        ImportError::parse_error(FORMAT, &format!("{} on line {}: {message}", self.kind, self.line))
+    }
+
+    /// Layer of the entity, `0` if it names none
+    fn layer(&self) -> &str {
+        self.pairs.iter().find(|pair| pair.code == 8).map_or("0", |pair| pair.value)
+    }
+
+    fn parse(&self, pair: &Pair) -> ImportResult<f64> {
+        pair.value
+            .parse::<f64>()
+            .ok()
+            .filter(|value| value.is_finite())
+            .ok_or_else(|| self.error(&format!("invalid value '{}' for group code {}", pair.value, pair.code)))
+    }
+
+    /// All values of a group code
+    fn values(&self, code: i32) -> ImportResult<Vec<f64>> {
+        self.pairs.iter().filter(|pair| pair.code == code).map(|pair| self.parse(pair)).collect()
+    }
+
+    /// First value of a group code, or `default` if it is absent
+    fn value(&self, code: i32, default: Option<f64>) -> ImportResult<f64> {
+        self.pairs.iter().find(|pair| pair.code == code).map_or_else(
+            || default.ok_or_else(|| self.error(&format!("missing group code {code}"))),
+            |pair| self.parse(pair),
+        )
+    }
+
+    /// First value of an integer group code, or `default` if it is absent
+    fn integer(&self, code: i32, default: i64) -> ImportResult<i64> {
+        self.pairs.iter().find(|pair| pair.code == code).map_or(Ok(default), |pair| {
+            pair.value
+                .parse()
+                .map_err(|_| self.error(&format!("invalid value '{}' for group code {code}", pair.value)))
+        })
+    }
+
+    /// Points given by pairs of x and y group codes
+    fn points(&self, x_code: i32, y_code: i32) -> ImportResult<Vec<Point2D>> {
+        let mut points: Vec<Point2D> = Vec::new();
+        for pair in self.pairs {
+            if pair.code == x_code {
+                points.push((self.parse(pair)?, 0.0));
+            } else if pair.code == y_code {
+                points.last_mut().ok_or_else(|| self.error(&format!("group code {y_code} before {x_code}")))?.1 = self.parse(pair)?;
+            }
+        }
+        Ok(points)
+    }
+
+    /// Map from the entity's object coordinate system to the drawing plane
+    ///
+    /// Only the downward extrusion direction `(0, 0, -1)` is supported,
+    /// which mirrors x.
+    fn to_drawing_plane(&self, points: &mut [Point2D]) -> ImportResult<()> {
+        if self.value(230, Some(1.0))? < 0.0 {
+            for point in points {
+                point.0 = -point.0;
+            }
+        }
+        Ok(())
+    }
+
+    fn line(&self) -> ImportResult<Shape> {
+        let start = (self.value(10, None)?, self.value(20, None)?);
+        let end = (self.value(11, None)?, self.value(21, None)?);
+        Ok(Shape { points: vec![start, end], curved: false, width: None })
+    }
+
+    fn arc(&self, segments: usize) -> ImportResult<Shape> {
+        let center = (self.value(10, None)?, self.value(20, None)?);
+        let radius = self.value(40, None)?;
+        if radius <= 0.0 {
+            return Err(self.error(&format!("radius {radius} is not positive")));
+        }
+        let start = self.value(50, Some(0.0))?;
+        let sweep = (self.value(51, Some(360.0))? - start).rem_euclid(360.0);
+        let sweep = if sweep > 0.0 { sweep } else { 360.0 };
+
+        let mut points = arc_points(center, radius, start.to_radians(), sweep.to_radians(), segments);
+        self.to_drawing_plane(&mut points)?;
+        Ok(Shape { points, curved: true, width: None })
+    }
+
+    /// Lightweight polyline, with bulged segments flattened into arcs
+    fn polyline(&self, segments: usize) -> ImportResult<Shape> {
+        let mut vertices: Vec<(Point2D, f64)> = Vec::new();
+        for pair in self.pairs {
+            match pair.code {
+                10 => vertices.push(((self.parse(pair)?, 0.0), 0.0)),
+                20 | 42 => {
+                    let value = self.parse(pair)?;
+                    let vertex = vertices.last_mut().ok_or_else(|| self.error(&format!("group code {} before 10", pair.code)))?;
+                    if pair.code == 20 {
+                        vertex.0 .1 = value;
+                    } else {
+                        vertex.1 = value;
+                    }
+                }
+                _ => {}
+            }
+        }
+        if vertices.len() < 2 {
+            return Err(self.error("a polyline needs at least two vertices"));
+        }
+        if self.integer(70, 0)? & 1 == 1 {
+            vertices.push((vertices[0].0, 0.0));
+        }
+
+        let mut points = vec![vertices[0].0];
+        let mut curved = false;
+        for pair in vertices.windows(2) {
+            let ((start, bulge), (end, _)) = (pair[0], pair[1]);
+            if bulge.abs() < MIN_BULGE {
+                points.push(end);
+            } else {
+                points.extend(bulge_points(start, end, bulge, segments).into_iter().skip(1));
+                curved = true;
+            }
+        }
+        self.to_drawing_plane(&mut points)?;
+        let width = self.value(43, Some(0.0))?;
+        Ok(Shape { points, curved, width: (width > 0.0).then_some(width) })
+    }
+
+    /// B-spline or NURBS curve, or the polyline through its fit points
+    fn spline(&self, segments: usize) -> ImportResult<Shape> {
+        let controls = self.points(10, 20)?;
+        let knots = self.values(40)?;
+        let degree = self.integer(71, 3)?;
+        let degree = usize::try_from(degree).map_err(|_| self.error(&format!("invalid degree {degree}")))?;
+        if controls.len() > degree && knots.len() == controls.len() + degree + 1 {
+            let weights = self.values(41)?;
+            let weights = if weights.len() == controls.len() { weights } else { vec![1.0; controls.len()] };
+            let mut points = nurbs_points(degree, &knots, &controls, &weights, segments);
+            self.to_drawing_plane(&mut points)?;
+            return Ok(Shape { points, curved: true, width: None });
+        }
+
+        let mut fits = self.points(11, 21)?;
+        if fits.len() >= 2 {
+            self.to_drawing_plane(&mut fits)?;
+            Ok(Shape { points: fits, curved: true, width: None })
+        } else {
+            Err(self.error(&format!(
+                "{} control points and {} knots do not define a degree {degree} spline, and it has no fit points",
+                controls.len(),
+                knots.len()
+            )))
+        }
+    }
+}
+
+/// Points of a counterclockwise circular arc from `start` through `sweep`
+/// radians, including both ends
+#[allow(clippy::cast_precision_loss)]
+fn arc_points(center: Point2D, radius: f64, start: f64, sweep: f64, segments: usize) -> Vec<Point2D> {
+    let segments = segments.max(1);
+    (0..=segments)
+        .map(|step| {
+            let (sin, cos) = sweep.mul_add(step as f64 / segments as f64, start).sin_cos();
+            (radius.mul_add(cos, center.0), radius.mul_add(sin, center.1))
+        })
+        .collect()
+}
+
+/// Points of a polyline segment with a bulge, the tangent of a quarter of
+/// its included angle, positive for counterclockwise arcs
+fn bulge_points(start: Point2D, end: Point2D, bulge: f64, segments: usize) -> Vec<Point2D> {
+    let (dx, dy) = (end.0 - start.0, end.1 - start.1);
+    let offset = (1.0 / bulge - bulge) / 4.0;
+    let center = (offset.mul_add(-dy, f64::midpoint(start.0, end.0)), offset.mul_add(dx, f64::midpoint(start.1, end.1)));
+    let radius = (start.0 - center.0).hypot(start.1 - center.1);
+    let angle = (start.1 - center.1).atan2(start.0 - center.0);
+    let mut points = arc_points(center, radius, angle, 4.0 * bulge.atan(), segments);
+    let last = points.len() - 1;
+    points[last] = end;
+    points
+}
+
+/// Points of a NURBS curve, `segments` per non-empty knot span, evaluated
+/// with de Boor's algorithm in homogeneous coordinates
+#[allow(clippy::cast_precision_loss)]
+fn nurbs_points(degree: usize, knots: &[f64], controls: &[Point2D], weights: &[f64], segments: usize) -> Vec<Point2D> {
+    let count = controls.len();
+    let (first, last) = (knots[degree], knots[count]);
+    let spans = (degree..count).filter(|&span| knots[span + 1] > knots[span]).count();
+    let steps = (segments.max(1) * spans).max(1);
+
+    (0..=steps)
+        .map(|step| {
+            let u = (last - first).mul_add(step as f64 / steps as f64, first);
+            let span = (degree..count)
+                .rev()
+                .find(|&span| knots[span] <= u && knots[span] < knots[span + 1])
+                .unwrap_or(degree);
+            let mut points: Vec<(f64, f64, f64)> = (span - degree..=span)
+                .map(|index| (controls[index].0 * weights[index], controls[index].1 * weights[index], weights[index]))
+                .collect();
+            for level in 1..=degree {
+                for local in (level..=degree).rev() {
+                    let index = local + span - degree;
+                    let denominator = knots[index + degree - level + 1] - knots[index];
+                    let alpha = if denominator > 0.0 { (u - knots[index]) / denominator } else { 0.0 };
+                    let (previous, current) = (points[local - 1], points[local]);
+                    points[local] = (
+                        (current.0 - previous.0).mul_add(alpha, previous.0),
+                        (current.1 - previous.1).mul_add(alpha, previous.1),
+                        (current.2 - previous.2).mul_add(alpha, previous.2),
+                    );
+                }
+            }
+            let (x, y, weight) = points[degree];
+            (x / weight, y / weight)
+        })
+        .collect()
+}
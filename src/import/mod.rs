@@ -0,0 +1,182 @@
+//! import/mod.rs - Channel Layouts from Drawings
+//!
+//! Some designs exist only as drawings made in other tools. The importers in
+//! this module read such drawings and turn them into a [`ChannelSystem`] that
+//! can be analyzed, rendered and exported like a generated one:
+//!
+//! - `svg`: path, line and polyline elements of SVG drawings
+//! - `dxf`: LINE, ARC, LWPOLYLINE and SPLINE entities of DXF drawings
+//!
+//! Importers share the same rules. Shapes on the outline layer form the chip
+//! outline, and every other stroke becomes a channel whose centerline follows
+//! the stroke; layer names are compared ignoring ASCII case. Stroke endpoints
+//! closer than [`ImportOptions::merge_tolerance`] are merged into one node, so
+//! lines drawn to meet at a junction are connected even if they do not meet
+//! exactly. Coordinates are shifted so that the outline's lower-left corner
+//! is the origin, with y pointing up as in generated systems.
+//!
+//! Each channel's type follows its stroke: a single straight segment becomes
+//! [`ChannelType::Straight`], a chain of straight segments
+//! [`ChannelType::SmoothStraight`] and a stroke containing curves
+//! [`ChannelType::Arc`] with the curves flattened into its path.
+
+pub mod dxf;
+pub mod svg;
+
+use crate::config::GeometryConfig;
+use crate::error::{ImportError, ImportResult};
+use crate::geometry::{Channel, ChannelSystem, ChannelType, Node, Point2D};
+use std::collections::BTreeMap;
+
+/// Options shared by all importers
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportOptions {
+    /// Layer whose shapes form the chip outline
+    pub outline_layer: String,
+    /// Layers whose strokes become channels; empty for every layer other
+    /// than the outline layer
+    pub channel_layers: Vec<String>,
+    /// Distance within which stroke endpoints are merged into one node (mm)
+    pub merge_tolerance: f64,
+    /// Width of channels whose drawing does not give one, such as DXF lines
+    /// and arcs (mm)
+    pub channel_width: f64,
+    /// Height of the imported channels (mm)
+    pub channel_height: f64,
+    /// Number of straight segments each curve is flattened into
+    pub curve_segments: usize,
+}
+
+impl Default for ImportOptions {
+    fn default() -> Self {
+        Self {
+            outline_layer: "outline".to_string(),
+            channel_layers: Vec::new(),
+            merge_tolerance: 0.05,
+            channel_width: GeometryConfig::default().channel_width,
+            channel_height: GeometryConfig::default().channel_height,
+            curve_segments: 16,
+        }
+    }
+}
+
+impl ImportOptions {
+    /// Whether a shape on `layers` belongs to the outline
+    fn on_outline_layer(&self, layers: &[String]) -> bool {
+        layers.iter().any(|layer| layer.eq_ignore_ascii_case(&self.outline_layer))
+    }
+
+    /// Whether a stroke on `layers`, and not on the outline layer, becomes a channel
+    fn on_channel_layer(&self, layers: &[String]) -> bool {
+        self.channel_layers.is_empty()
+            || layers.iter().any(|layer| self.channel_layers.iter().any(|name| layer.eq_ignore_ascii_case(name)))
+    }
+}
+
+/// Result of importing a drawing
+#[derive(Debug, Clone)]
+pub struct Imported {
+    /// The validated channel system
+    pub system: ChannelSystem,
+    /// Number of shapes left out of the system, by kind, such as `circle`
+    /// for SVG elements or `TEXT` for DXF entities
+    pub skipped: BTreeMap<String, usize>,
+}
+
+/// Channel centerline read from a drawing, in drawing coordinates (mm)
+#[derive(Debug, Clone)]
+pub(crate) struct Stroke {
+    /// Points along the centerline, curves already flattened
+    pub points: Vec<Point2D>,
+    /// Width of the stroke (mm)
+    pub width: f64,
+    /// Whether the stroke contains curves
+    pub curved: bool,
+}
+
+/// Build and validate a channel system from an outline and channel strokes
+///
+/// `y_down` flips drawings whose y axis points down, such as SVG.
+pub(crate) fn assemble(
+    outline: &[(Point2D, Point2D)],
+    strokes: Vec<Stroke>,
+    mut skipped: BTreeMap<String, usize>,
+    options: &ImportOptions,
+    y_down: bool,
+) -> ImportResult<Imported> {
+    if outline.is_empty() {
+        return Err(ImportError::MissingOutline { layer: options.outline_layer.clone() });
+    }
+    let (min, max) = outline.iter().flat_map(|&segment| <[Point2D; 2]>::from(segment)).fold(
+        ((f64::INFINITY, f64::INFINITY), (f64::NEG_INFINITY, f64::NEG_INFINITY)),
+        |(min, max), point| ((min.0.min(point.0), min.1.min(point.1)), (max.0.max(point.0), max.1.max(point.1))),
+    );
+    let place = |point: Point2D| (point.0 - min.0, if y_down { max.1 - point.1 } else { point.1 - min.1 });
+
+    let mut nodes: Vec<Node> = Vec::new();
+    let mut channels = Vec::new();
+    for stroke in strokes {
+        let mut path: Vec<Point2D> = stroke.points.into_iter().map(place).collect();
+        path.dedup();
+        let start = path[0];
+        if path.iter().all(|point| distance(*point, start) <= options.merge_tolerance) {
+            *skipped.entry("zero-length stroke".to_string()).or_default() += 1;
+            continue;
+        }
+
+        let from_node = node_at(&mut nodes, start, options.merge_tolerance);
+        let to_node = node_at(&mut nodes, path[path.len() - 1], options.merge_tolerance);
+        let last = path.len() - 1;
+        path[0] = nodes[from_node].point;
+        path[last] = nodes[to_node].point;
+
+        let channel_type = if stroke.curved {
+            ChannelType::Arc { path }
+        } else if path.len() == 2 {
+            ChannelType::Straight
+        } else {
+            ChannelType::SmoothStraight { path }
+        };
+        channels.push(Channel {
+            id: channels.len(),
+            from_node,
+            to_node,
+            width: stroke.width,
+            height: options.channel_height,
+            channel_type,
+            metadata: None,
+        });
+    }
+    if channels.is_empty() {
+        return Err(ImportError::NoChannels);
+    }
+
+    let system = ChannelSystem {
+        box_dims: (max.0 - min.0, max.1 - min.1),
+        nodes,
+        channels,
+        box_outline: outline.iter().map(|&(start, end)| (place(start), place(end))).collect(),
+    };
+    system.validate()?;
+    Ok(Imported { system, skipped })
+}
+
+/// Index of the node nearest `point` within `tolerance`, adding one if none is
+fn node_at(nodes: &mut Vec<Node>, point: Point2D, tolerance: f64) -> usize {
+    nodes
+        .iter()
+        .map(|node| (node.id, distance(node.point, point)))
+        .filter(|&(_, distance)| distance <= tolerance)
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map_or_else(
+            || {
+                nodes.push(Node { id: nodes.len(), point, metadata: None });
+                nodes.len() - 1
+            },
+            |(id, _)| id,
+        )
+}
+
+fn distance(a: Point2D, b: Point2D) -> f64 {
+    (a.0 - b.0).hypot(a.1 - b.1)
+}
@@ -0,0 +1,702 @@
+//! import/svg.rs - SVG Import
+//!
+//! Reads `path`, `line`, `polyline` and `polygon` elements of an SVG drawing
+//! as channel strokes, and the shapes on the outline layer, including
+//! `rect`, as the chip outline. A layer is a group (`g`) whose `id` or
+//! Inkscape layer name matches; shapes belong to every layer they are nested
+//! in.
+//!
+//! Group and element `transform`s are applied, and each channel's width is
+//! its `stroke-width` (from the attribute or `style`, inherited from
+//! enclosing groups, 1 by default) scaled by the element's transform.
+//! Cubic, quadratic and elliptical arc path segments are flattened into
+//! [`ImportOptions::curve_segments`] straight segments each.
+//!
+//! Drawings whose root `width` is given in physical units (`mm`, `cm`, `in`,
+//! `pt` or `pc`) together with a `viewBox` are scaled to millimetres;
+//! otherwise one user unit is taken as one millimetre. Coordinates and
+//! widths given in physical units are converted with the same scale, so
+//! `1mm` is one millimetre in either case. Hidden elements
+//! (`display: none`), definitions and elements from other namespaces are
+//! ignored, and other shapes on the outline or channel layers, such as
+//! circles and text, are counted in [`Imported::skipped`].
+//!
+//! # Examples
+//!
+//! ```rust
+//! use scheme::geometry::ChannelType;
+//! use scheme::import::{svg, ImportOptions};
+//!
+//! let drawing = r#"
+//!     <svg xmlns="http://www.w3.org/2000/svg" width="60mm" height="30mm" viewBox="0 0 60 30">
+//!         <g id="outline"><rect width="60" height="30"/></g>
+//!         <g id="channels" stroke-width="1.5">
+//!             <line x1="0" y1="15" x2="20" y2="15"/>
+//!             <path d="M 20 15 Q 30 5 40 15"/>
+//!             <polyline points="20.02,15 30,25 40,15 60,15"/>
+//!         </g>
+//!     </svg>"#;
+//!
+//! let imported = svg::import(drawing, &ImportOptions::default())?;
+//! let system = imported.system;
+//! assert_eq!(system.box_dims, (60.0, 30.0));
+//! assert_eq!((system.nodes.len(), system.channels.len()), (4, 3));
+//! assert!(matches!(system.channels[1].channel_type, ChannelType::Arc { .. }));
+//! assert_eq!(system.channels[2].width, 1.5);
+//! # Ok::<(), scheme::error::ImportError>(())
+//! ```
+
+use super::{assemble, ImportOptions, Imported, Stroke};
+use crate::error::{ImportError, ImportResult};
+use crate::geometry::Point2D;
+use roxmltree::{Document, Node};
+use std::collections::BTreeMap;
+use std::f64::consts::TAU;
+use std::path::Path;
+
+/// Name of the format for messages
+const FORMAT: &str = "SVG";
+
+/// Namespace of SVG elements
+const SVG_NAMESPACE: &str = "http://www.w3.org/2000/svg";
+
+/// Namespace of Inkscape's layer names
+const INKSCAPE_NAMESPACE: &str = "http://www.inkscape.org/namespaces/inkscape";
+
+/// Elements that are never drawn directly
+const UNDRAWN: [&str; 14] = [
+    "defs",
+    "symbol",
+    "clipPath",
+    "mask",
+    "marker",
+    "pattern",
+    "linearGradient",
+    "radialGradient",
+    "filter",
+    "metadata",
+    "title",
+    "desc",
+    "style",
+    "script",
+];
+
+/// Import a channel system from SVG text
+///
+/// # Errors
+///
+/// Returns [`ImportError::ParseError`] if the text is not an SVG drawing or
+/// contains invalid path data, transforms or coordinates,
+/// [`ImportError::MissingOutline`] or [`ImportError::NoChannels`] if the
+/// outline or channel layers are empty, and [`ImportError::InvalidSystem`]
+/// if the channels do not form a valid system, for example because they
+/// extend beyond the outline.
+pub fn import(text: &str, options: &ImportOptions) -> ImportResult<Imported> {
+    let document = Document::parse(text).map_err(|error| ImportError::parse_error(FORMAT, &error.to_string()))?;
+    let root = document.root_element();
+    if root.tag_name().name() != "svg" {
+        return Err(ImportError::parse_error(
+            FORMAT,
+            &format!("the root element is <{}>, not <svg>", root.tag_name().name()),
+        ));
+    }
+
+    let mut reader = Reader {
+        options,
+        outline: Vec::new(),
+        strokes: Vec::new(),
+        skipped: BTreeMap::new(),
+        scale: document_scale(root),
+    };
+    let scale = reader.scale;
+    let style = Style {
+        transform: Affine([scale, 0.0, 0.0, scale, 0.0, 0.0]),
+        stroke_width: 1.0,
+        layers: Vec::new(),
+    };
+    reader.read_children(root, &style)?;
+    assemble(&reader.outline, reader.strokes, reader.skipped, options, true)
+}
+
+/// Import a channel system from an SVG file
+///
+/// # Errors
+///
+/// Returns [`ImportError::FileError`] if the file cannot be read, and the
+/// errors of [`import`] otherwise.
+pub fn import_file(path: impl AsRef<Path>, options: &ImportOptions) -> ImportResult<Imported> {
+    let path = path.as_ref();
+    let text = std::fs::read_to_string(path)
+        .map_err(|error| ImportError::file_error(&format!("cannot read '{}': {error}", path.display())))?;
+    import(&text, options)
+}
+
+/// Affine map `(x, y) -> (a x + c y + e, b x + d y + f)` stored as SVG's
+/// `[a, b, c, d, e, f]`
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Affine([f64; 6]);
+
+impl Affine {
+    const IDENTITY: Self = Self([1.0, 0.0, 0.0, 1.0, 0.0, 0.0]);
+
+    /// Map that applies `inner` first and `self` second
+    #[allow(clippy::many_single_char_names)]
+    fn compose(self, inner: Self) -> Self {
+        let [a, b, c, d, e, f] = self.0;
+        let [ia, ib, ic, id, ie, if_] = inner.0;
+        let (e, f) = (a.mul_add(ie, c.mul_add(if_, e)), b.mul_add(ie, d.mul_add(if_, f)));
+        Self([
+            a.mul_add(ia, c * ib),
+            b.mul_add(ia, d * ib),
+            a.mul_add(ic, c * id),
+            b.mul_add(ic, d * id),
+            e,
+            f,
+        ])
+    }
+
+    #[allow(clippy::many_single_char_names)]
+    const fn apply(self, point: Point2D) -> Point2D {
+        let [a, b, c, d, e, f] = self.0;
+        (a.mul_add(point.0, c.mul_add(point.1, e)), b.mul_add(point.0, d.mul_add(point.1, f)))
+    }
+
+    /// Factor by which the map scales areas' square roots, used for widths
+    fn scale_factor(self) -> f64 {
+        let [a, b, c, d, ..] = self.0;
+        a.mul_add(d, -b * c).abs().sqrt()
+    }
+}
+
+/// Inherited state of an element
+#[derive(Debug, Clone)]
+struct Style {
+    /// Map from the element's user space to millimetres
+    transform: Affine,
+    /// Stroke width in the element's user units
+    stroke_width: f64,
+    /// Names of the groups the element is nested in
+    layers: Vec<String>,
+}
+
+/// Subpath of a shape in the shape's user space
+#[derive(Debug, Clone)]
+struct Subpath {
+    points: Vec<Point2D>,
+    closed: bool,
+    curved: bool,
+}
+
+impl Subpath {
+    fn at(point: Point2D) -> Self {
+        Self { points: vec![point], closed: false, curved: false }
+    }
+}
+
+/// Shapes collected while walking the document
+struct Reader<'a> {
+    options: &'a ImportOptions,
+    outline: Vec<(Point2D, Point2D)>,
+    strokes: Vec<Stroke>,
+    skipped: BTreeMap<String, usize>,
+    /// Millimetres per user unit of the document
+    scale: f64,
+}
+
+impl Reader<'_> {
+    fn read_children(&mut self, parent: Node, style: &Style) -> ImportResult<()> {
+        for element in parent.children().filter(Node::is_element) {
+            let drawn = element.tag_name().namespace().is_none_or(|namespace| namespace == SVG_NAMESPACE)
+                && property(element, "display") != Some("none")
+                && !UNDRAWN.contains(&element.tag_name().name());
+            if drawn {
+                self.read_element(element, style)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn read_element(&mut self, element: Node, inherited: &Style) -> ImportResult<()> {
+        let fail = |message: String| parse_error(element, &message);
+        let mut style = inherited.clone();
+        if let Some(transform) = element.attribute("transform") {
+            style.transform = style.transform.compose(parse_transform(transform).map_err(fail)?);
+        }
+        if let Some(width) = property(element, "stroke-width") {
+            style.stroke_width = parse_length(width, self.scale).ok_or_else(|| fail(format!("invalid stroke-width '{width}'")))?;
+        }
+
+        let name = element.tag_name().name();
+        if matches!(name, "g" | "svg" | "a" | "switch") {
+            let labels = [element.attribute((INKSCAPE_NAMESPACE, "label")), element.attribute("id")];
+            style.layers.extend(labels.into_iter().flatten().map(str::to_string));
+            return self.read_children(element, &style);
+        }
+
+        let outline = self.options.on_outline_layer(&style.layers);
+        if !outline && !self.options.on_channel_layer(&style.layers) {
+            return Ok(());
+        }
+        let subpaths = match name {
+            "path" => parse_path(element.attribute("d").unwrap_or_default(), self.options.curve_segments).map_err(fail)?,
+            "line" => {
+                let [x1, y1, x2, y2] = ["x1", "y1", "x2", "y2"].map(|name| length_attribute(element, name, self.scale));
+                let mut subpath = Subpath::at((x1.map_err(fail)?, y1.map_err(fail)?));
+                subpath.points.push((x2.map_err(fail)?, y2.map_err(fail)?));
+                vec![subpath]
+            }
+            "polyline" | "polygon" => {
+                let points = parse_points(element.attribute("points").unwrap_or_default()).map_err(fail)?;
+                vec![Subpath { points, closed: name == "polygon", curved: false }]
+            }
+            "rect" if outline => {
+                let [x, y, width, height] = ["x", "y", "width", "height"].map(|name| length_attribute(element, name, self.scale));
+                let (x, y, width, height) = (x.map_err(fail)?, y.map_err(fail)?, width.map_err(fail)?, height.map_err(fail)?);
+                let points = vec![(x, y), (x + width, y), (x + width, y + height), (x, y + height)];
+                vec![Subpath { points, closed: true, curved: false }]
+            }
+            _ => {
+                *self.skipped.entry(name.to_string()).or_default() += 1;
+                return Ok(());
+            }
+        };
+
+        for subpath in subpaths.into_iter().filter(|subpath| subpath.points.len() >= 2) {
+            let mut points: Vec<Point2D> = subpath.points.iter().map(|&point| style.transform.apply(point)).collect();
+            if subpath.closed && points.first() != points.last() {
+                points.push(points[0]);
+            }
+            if outline {
+                self.outline.extend(points.windows(2).map(|pair| (pair[0], pair[1])));
+            } else {
+                self.strokes.push(Stroke {
+                    points,
+                    width: style.stroke_width * style.transform.scale_factor(),
+                    curved: subpath.curved,
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Parse error naming the element and its line
+fn parse_error(element: Node, message: &str) -> ImportError {
+    let line = element.document().text_pos_at(element.range().start).row;
+    let id = element.attribute("id").map(|id| format!(" id=\"{id}\"")).unwrap_or_default();
+    ImportError::parse_error(FORMAT, &format!("<{}{id}> on line {line}: {message}", element.tag_name().name()))
+}
+
+/// Value of a presentation property, from `style` or the attribute
+fn property<'a>(element: Node<'a, '_>, name: &str) -> Option<&'a str> {
+    element
+        .attribute("style")
+        .and_then(|style| {
+            style
+                .split(';')
+                .filter_map(|declaration| declaration.split_once(':'))
+                .rfind(|(property, _)| property.trim() == name)
+                .map(|(_, value)| value.trim())
+        })
+        .or_else(|| element.attribute(name).map(str::trim))
+}
+
+/// Coordinate or length attribute in user units, `0` if absent
+fn length_attribute(element: Node, name: &str, scale: f64) -> Result<f64, String> {
+    element.attribute(name).map_or(Ok(0.0), |value| {
+        parse_length(value, scale).ok_or_else(|| format!("invalid {name} '{value}'"))
+    })
+}
+
+/// Millimetres per unit of a physical length unit, `None` for other units
+fn unit_in_mm(unit: &str) -> Option<f64> {
+    match unit {
+        "mm" => Some(1.0),
+        "cm" => Some(10.0),
+        "in" => Some(25.4),
+        "pt" => Some(25.4 / 72.0),
+        "pc" => Some(25.4 / 6.0),
+        _ => None,
+    }
+}
+
+/// Split a length into its number and unit
+fn split_length(text: &str) -> Option<(f64, &str)> {
+    let text = text.trim();
+    let end = text.find(|character: char| character.is_ascii_alphabetic() || character == '%').unwrap_or(text.len());
+    let value = text[..end].trim().parse::<f64>().ok().filter(|value| value.is_finite())?;
+    Some((value, &text[end..]))
+}
+
+/// Length in user units, converting physical units with `scale`, the
+/// document's millimetres per user unit
+fn parse_length(text: &str, scale: f64) -> Option<f64> {
+    let (value, unit) = split_length(text)?;
+    match unit {
+        "" | "px" => Some(value),
+        unit => unit_in_mm(unit).map(|mm| value * mm / scale),
+    }
+}
+
+/// Millimetres per user unit of the document
+fn document_scale(root: Node) -> f64 {
+    let width_mm = root
+        .attribute("width")
+        .and_then(split_length)
+        .and_then(|(value, unit)| unit_in_mm(unit).map(|mm| value * mm));
+    let view_width = root
+        .attribute("viewBox")
+        .and_then(|view_box| view_box.split(|character: char| character.is_whitespace() || character == ',').filter(|part| !part.is_empty()).nth(2))
+        .and_then(|width| width.parse::<f64>().ok());
+    match (width_mm, view_width) {
+        (Some(width_mm), Some(view_width)) if width_mm > 0.0 && view_width > 0.0 => width_mm / view_width,
+        _ => 1.0,
+    }
+}
+
+/// Cursor over the numbers and letters of path data, point lists and transforms
+struct Scanner<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Scanner<'a> {
+    const fn new(text: &'a str) -> Self {
+        Self { bytes: text.as_bytes(), position: 0 }
+    }
+
+    fn skip_separators(&mut self) {
+        while self.bytes.get(self.position).is_some_and(|byte| byte.is_ascii_whitespace() || *byte == b',') {
+            self.position += 1;
+        }
+    }
+
+    /// Next character after any separators
+    fn peek(&mut self) -> Option<u8> {
+        self.skip_separators();
+        self.bytes.get(self.position).copied()
+    }
+
+    fn digits(&mut self) -> usize {
+        let start = self.position;
+        while self.bytes.get(self.position).is_some_and(u8::is_ascii_digit) {
+            self.position += 1;
+        }
+        self.position - start
+    }
+
+    fn number(&mut self) -> Result<f64, String> {
+        self.skip_separators();
+        let start = self.position;
+        if matches!(self.bytes.get(self.position), Some(b'+' | b'-')) {
+            self.position += 1;
+        }
+        let mut digits = self.digits();
+        if self.bytes.get(self.position) == Some(&b'.') {
+            self.position += 1;
+            digits += self.digits();
+        }
+        if digits == 0 {
+            self.position = start;
+            return Err(self.unexpected("a number"));
+        }
+        if matches!(self.bytes.get(self.position), Some(b'e' | b'E')) {
+            let mantissa_end = self.position;
+            self.position += 1;
+            if matches!(self.bytes.get(self.position), Some(b'+' | b'-')) {
+                self.position += 1;
+            }
+            if self.digits() == 0 {
+                self.position = mantissa_end;
+            }
+        }
+        std::str::from_utf8(&self.bytes[start..self.position])
+            .ok()
+            .and_then(|text| text.parse().ok())
+            .ok_or_else(|| self.unexpected("a number"))
+    }
+
+    /// Arc flag, which may be written without a separator after it
+    fn flag(&mut self) -> Result<bool, String> {
+        match self.peek() {
+            Some(b'0') => {
+                self.position += 1;
+                Ok(false)
+            }
+            Some(b'1') => {
+                self.position += 1;
+                Ok(true)
+            }
+            _ => Err(self.unexpected("an arc flag")),
+        }
+    }
+
+    /// Point relative to `origin`
+    fn point(&mut self, origin: Point2D) -> Result<Point2D, String> {
+        Ok((self.number()? + origin.0, self.number()? + origin.1))
+    }
+
+    fn unexpected(&self, expected: &str) -> String {
+        self.bytes.get(self.position).map_or_else(
+            || format!("expected {expected} at the end"),
+            |byte| format!("expected {expected} at '{}'", char::from(*byte)),
+        )
+    }
+}
+
+/// Parse a `points` attribute
+fn parse_points(text: &str) -> Result<Vec<Point2D>, String> {
+    let mut scanner = Scanner::new(text);
+    let mut points = Vec::new();
+    while scanner.peek().is_some() {
+        points.push(scanner.point((0.0, 0.0))?);
+    }
+    Ok(points)
+}
+
+/// Parse a `transform` attribute
+#[allow(clippy::many_single_char_names)]
+fn parse_transform(text: &str) -> Result<Affine, String> {
+    let mut scanner = Scanner::new(text);
+    let mut transform = Affine::IDENTITY;
+    while scanner.peek().is_some() {
+        let start = scanner.position;
+        while scanner.bytes.get(scanner.position).is_some_and(u8::is_ascii_alphabetic) {
+            scanner.position += 1;
+        }
+        let name = String::from_utf8_lossy(&scanner.bytes[start..scanner.position]).into_owned();
+        if scanner.peek() != Some(b'(') {
+            return Err(scanner.unexpected("'('"));
+        }
+        scanner.position += 1;
+        let mut arguments = Vec::new();
+        while scanner.peek() != Some(b')') {
+            arguments.push(scanner.number()?);
+        }
+        scanner.position += 1;
+
+        let step = match (name.as_str(), arguments.as_slice()) {
+            ("matrix", &[a, b, c, d, e, f]) => Affine([a, b, c, d, e, f]),
+            ("translate", &[x]) => Affine([1.0, 0.0, 0.0, 1.0, x, 0.0]),
+            ("translate", &[x, y]) => Affine([1.0, 0.0, 0.0, 1.0, x, y]),
+            ("scale", &[s]) => Affine([s, 0.0, 0.0, s, 0.0, 0.0]),
+            ("scale", &[x, y]) => Affine([x, 0.0, 0.0, y, 0.0, 0.0]),
+            ("rotate", &[angle]) => rotation(angle),
+            ("rotate", &[angle, x, y]) => Affine([1.0, 0.0, 0.0, 1.0, x, y])
+                .compose(rotation(angle))
+                .compose(Affine([1.0, 0.0, 0.0, 1.0, -x, -y])),
+            ("skewX", &[angle]) => Affine([1.0, 0.0, angle.to_radians().tan(), 1.0, 0.0, 0.0]),
+            ("skewY", &[angle]) => Affine([1.0, angle.to_radians().tan(), 0.0, 1.0, 0.0, 0.0]),
+            _ => return Err(format!("invalid transform {name}({} arguments)", arguments.len())),
+        };
+        transform = transform.compose(step);
+    }
+    Ok(transform)
+}
+
+/// Rotation by `angle` degrees, clockwise on screen as in SVG
+fn rotation(angle: f64) -> Affine {
+    let (sin, cos) = angle.to_radians().sin_cos();
+    Affine([cos, sin, -sin, cos, 0.0, 0.0])
+}
+
+/// Point a fraction `t` of the way from `a` to `b`
+fn lerp(a: Point2D, b: Point2D, t: f64) -> Point2D {
+    ((b.0 - a.0).mul_add(t, a.0), (b.1 - a.1).mul_add(t, a.1))
+}
+
+/// Subpaths of path data being parsed, with curves flattened
+struct PathBuilder {
+    subpaths: Vec<Subpath>,
+    current: Option<Subpath>,
+    point: Point2D,
+    start: Point2D,
+    cubic_control: Option<Point2D>,
+    quadratic_control: Option<Point2D>,
+    segments: usize,
+}
+
+impl PathBuilder {
+    fn move_to(&mut self, point: Point2D) {
+        self.finish();
+        self.current = Some(Subpath::at(point));
+        self.point = point;
+        self.start = point;
+        self.cubic_control = None;
+        self.quadratic_control = None;
+    }
+
+    fn close(&mut self) {
+        if let Some(mut subpath) = self.current.take() {
+            subpath.closed = true;
+            self.subpaths.push(subpath);
+        }
+        self.point = self.start;
+        self.cubic_control = None;
+        self.quadratic_control = None;
+    }
+
+    fn finish(&mut self) {
+        self.subpaths.extend(self.current.take());
+    }
+
+    /// Append points to the current subpath, starting one if needed
+    fn extend(&mut self, points: impl IntoIterator<Item = Point2D>, curved: bool) {
+        let start = self.start;
+        let subpath = self.current.get_or_insert_with(|| Subpath::at(start));
+        subpath.points.extend(points);
+        subpath.curved |= curved;
+        self.point = subpath.points[subpath.points.len() - 1];
+        self.cubic_control = None;
+        self.quadratic_control = None;
+    }
+
+    fn line_to(&mut self, point: Point2D) {
+        self.extend([point], false);
+    }
+
+    /// Parameters `t` of the flattened curve's points after its start
+    #[allow(clippy::cast_precision_loss)]
+    fn steps(&self) -> impl Iterator<Item = f64> {
+        let segments = self.segments.max(1);
+        (1..=segments).map(move |step| step as f64 / segments as f64)
+    }
+
+    fn cubic_to(&mut self, first: Point2D, second: Point2D, end: Point2D) {
+        let start = self.point;
+        let points: Vec<Point2D> = self
+            .steps()
+            .map(|t| {
+                let (a, b, c) = (lerp(start, first, t), lerp(first, second, t), lerp(second, end, t));
+                lerp(lerp(a, b, t), lerp(b, c, t), t)
+            })
+            .collect();
+        self.extend(points, true);
+        self.cubic_control = Some(second);
+    }
+
+    fn quadratic_to(&mut self, control: Point2D, end: Point2D) {
+        let start = self.point;
+        let points: Vec<Point2D> = self.steps().map(|t| lerp(lerp(start, control, t), lerp(control, end, t), t)).collect();
+        self.extend(points, true);
+        self.quadratic_control = Some(control);
+    }
+
+    /// Elliptical arc, converted from SVG's endpoint form to its center
+    #[allow(clippy::similar_names)]
+    fn arc_to(&mut self, radii: Point2D, rotation: f64, large_arc: bool, sweep: bool, end: Point2D) {
+        let start = self.point;
+        let (mut rx, mut ry) = (radii.0.abs(), radii.1.abs());
+        if rx == 0.0 || ry == 0.0 {
+            self.line_to(end);
+            return;
+        }
+        if start == end {
+            return;
+        }
+        let (sin, cos) = rotation.to_radians().sin_cos();
+        let (dx, dy) = ((start.0 - end.0) / 2.0, (start.1 - end.1) / 2.0);
+        let (x1, y1) = (cos.mul_add(dx, sin * dy), cos.mul_add(dy, -sin * dx));
+        let (u, v) = (x1 / rx, y1 / ry);
+        let lambda = u.mul_add(u, v * v);
+        if lambda > 1.0 {
+            rx *= lambda.sqrt();
+            ry *= lambda.sqrt();
+        }
+        let (rx2, ry2) = (rx * rx, ry * ry);
+        let denominator = rx2.mul_add(y1 * y1, ry2 * x1 * x1);
+        let sign = if large_arc == sweep { -1.0 } else { 1.0 };
+        let coefficient = sign * ((rx2.mul_add(ry2, -denominator)) / denominator).max(0.0).sqrt();
+        let (cx1, cy1) = (coefficient * rx * y1 / ry, -coefficient * ry * x1 / rx);
+        let center = (
+            cos.mul_add(cx1, -sin * cy1) + f64::midpoint(start.0, end.0),
+            sin.mul_add(cx1, cos * cy1) + f64::midpoint(start.1, end.1),
+        );
+
+        let theta = ((y1 - cy1) / ry).atan2((x1 - cx1) / rx);
+        let mut delta = ((-y1 - cy1) / ry).atan2((-x1 - cx1) / rx) - theta;
+        if sweep && delta < 0.0 {
+            delta += TAU;
+        } else if !sweep && delta > 0.0 {
+            delta -= TAU;
+        }
+        let mut points: Vec<Point2D> = self
+            .steps()
+            .map(|t| {
+                let (sin_angle, cos_angle) = delta.mul_add(t, theta).sin_cos();
+                let (x, y) = (rx * cos_angle, ry * sin_angle);
+                (cos.mul_add(x, -sin * y) + center.0, sin.mul_add(x, cos * y) + center.1)
+            })
+            .collect();
+        let last = points.len() - 1;
+        points[last] = end;
+        self.extend(points, true);
+    }
+}
+
+/// Parse path data into subpaths with curves flattened into `segments` steps
+fn parse_path(data: &str, segments: usize) -> Result<Vec<Subpath>, String> {
+    let mut scanner = Scanner::new(data);
+    let mut path = PathBuilder {
+        subpaths: Vec::new(),
+        current: None,
+        point: (0.0, 0.0),
+        start: (0.0, 0.0),
+        cubic_control: None,
+        quadratic_control: None,
+        segments,
+    };
+    let mut command = None;
+    while let Some(next) = scanner.peek() {
+        if next.is_ascii_alphabetic() {
+            command = Some(next);
+            scanner.position += 1;
+        }
+        let Some(letter) = command else {
+            return Err(scanner.unexpected("a path command"));
+        };
+        let origin = if letter.is_ascii_lowercase() { path.point } else { (0.0, 0.0) };
+        let reflect = |control: Option<Point2D>, point: Point2D| {
+            control.map_or(point, |control| (2.0f64.mul_add(point.0, -control.0), 2.0f64.mul_add(point.1, -control.1)))
+        };
+        match letter.to_ascii_uppercase() {
+            b'M' => {
+                path.move_to(scanner.point(origin)?);
+                command = Some(if letter == b'm' { b'l' } else { b'L' });
+            }
+            b'Z' => {
+                path.close();
+                command = None;
+            }
+            b'L' => path.line_to(scanner.point(origin)?),
+            b'H' => path.line_to((scanner.number()? + origin.0, path.point.1)),
+            b'V' => path.line_to((path.point.0, scanner.number()? + origin.1)),
+            b'C' => {
+                let (first, second, end) = (scanner.point(origin)?, scanner.point(origin)?, scanner.point(origin)?);
+                path.cubic_to(first, second, end);
+            }
+            b'S' => {
+                let first = reflect(path.cubic_control, path.point);
+                let (second, end) = (scanner.point(origin)?, scanner.point(origin)?);
+                path.cubic_to(first, second, end);
+            }
+            b'Q' => {
+                let (control, end) = (scanner.point(origin)?, scanner.point(origin)?);
+                path.quadratic_to(control, end);
+            }
+            b'T' => {
+                let control = reflect(path.quadratic_control, path.point);
+                path.quadratic_to(control, scanner.point(origin)?);
+            }
+            b'A' => {
+                let radii = (scanner.number()?, scanner.number()?);
+                let rotation = scanner.number()?;
+                let (large_arc, sweep) = (scanner.flag()?, scanner.flag()?);
+                path.arc_to(radii, rotation, large_arc, sweep, scanner.point(origin)?);
+            }
+            _ => return Err(format!("unknown path command '{}'", char::from(letter))),
+        }
+    }
+    path.finish();
+    Ok(path.subpaths)
+}
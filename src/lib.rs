@@ -14,6 +14,8 @@
 //!   - `strategies`: Channel type generation strategies (Strategy pattern)
 //!   - `generator`: Main geometry generation orchestration
 //! - **config**: Configuration types for geometry and channel generation
+//! - **design**: Versioned TOML/JSON design files capturing all generation inputs
+//! - **import**: Channel systems read from SVG and DXF drawings of existing layouts
 //! - **visualizations**: 2D schematic rendering and export
 //! - **error**: Domain-specific error types
 //!
@@ -81,11 +83,13 @@ pub mod geometry;
 pub mod visualizations;
 pub mod config;
 pub mod config_constants;
+pub mod design;
 pub mod error;
+pub mod import;
 pub mod state_management;
 
 pub use visualizations::schematic::plot_geometry;
-pub use error::{SchemeError, SchemeResult, GeometryError, ConfigurationError, VisualizationError, StrategyError};
+pub use error::{SchemeError, SchemeResult, GeometryError, ConfigurationError, VisualizationError, StrategyError, DesignError, ImportError};
 pub use state_management::{
     ParameterRegistry, ParameterManager, ConfigurableParameter, ParameterConstraints,
     StateManagementError, ParameterError, StateManagementResult, ConstraintError,
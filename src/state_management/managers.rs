@@ -201,6 +201,8 @@ impl SerpentineParameterManager {
                 OptimizationProfile::Fast,
                 OptimizationProfile::Balanced,
                 OptimizationProfile::Thorough,
+                OptimizationProfile::SimulatedAnnealing,
+                OptimizationProfile::ParticleSwarm,
             ]),
             ParameterMetadata::new(
                 "optimization_profile",
@@ -15,6 +15,7 @@
 //! - `traits`: Abstract interfaces for visualization operations
 //! - `plotters_backend`: Concrete implementation using the plotters library
 //! - `schematic`: High-level schematic rendering functions
+//! - `pareto`: Plots of multi-objective optimization fronts
 //! - `shared_utilities`: Common utilities for visualization operations
 
 /// High-level schematic rendering functions
@@ -23,7 +24,10 @@ pub mod schematic;
 pub mod shared_utilities;
 pub mod traits;
 pub mod plotters_backend;
+/// Plots of multi-objective optimization fronts
+pub mod pareto;
 
 pub use schematic::plot_geometry;
 pub use traits::{SchematicRenderer, RenderConfig, OutputFormat, Color, LineStyle, TextStyle, ChannelTypeStyles};
-pub use plotters_backend::{PlottersRenderer, create_plotters_renderer, plot_geometry_with_plotters};
\ No newline at end of file
+pub use plotters_backend::{PlottersRenderer, create_plotters_renderer, plot_geometry_with_plotters};
+pub use pareto::{plot_pareto_front, plot_pareto_front_with_config};
\ No newline at end of file
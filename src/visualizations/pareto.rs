@@ -0,0 +1,217 @@
+//! visualizations/pareto.rs - Pareto Front Plots
+//!
+//! This module renders the trade-off between two objectives of a
+//! [`ParetoFront`] as a scatter plot, highlighting the knee design.
+
+use crate::error::{VisualizationError, VisualizationResult};
+use crate::geometry::pareto::ParetoFront;
+use crate::visualizations::traits::{Color, OutputFormat, RenderConfig};
+use crate::config_constants::ConstantsRegistry;
+use plotters::coord::Shift;
+use plotters::prelude::*;
+use plotters::style::Color as PlottersColor;
+use std::path::Path;
+
+/// Pareto plot constants
+mod constants {
+    /// Radius of the marker for each design (pixels)
+    pub const POINT_RADIUS: i32 = 4;
+
+    /// Radius of the marker highlighting the knee design (pixels)
+    pub const KNEE_RADIUS: i32 = 7;
+
+    /// Padding added around the data range, as a fraction of the range
+    pub const RANGE_PADDING_FRACTION: f64 = 0.05;
+
+    /// Padding used when all designs share the same value
+    pub const DEGENERATE_RANGE_PADDING: f64 = 1.0;
+}
+
+/// Plot two objectives of a Pareto front using the default configuration
+///
+/// The output format is chosen from the file extension (PNG, JPEG or SVG).
+///
+/// # Arguments
+/// * `front` - Pareto front to plot
+/// * `x_objective` - Index of the objective on the horizontal axis
+/// * `y_objective` - Index of the objective on the vertical axis
+/// * `output_path` - Path of the image to write
+///
+/// # Errors
+///
+/// Returns an error if the front is empty, an objective index is out of
+/// range, the output format is unsupported, or rendering fails.
+pub fn plot_pareto_front(
+    front: &ParetoFront,
+    x_objective: usize,
+    y_objective: usize,
+    output_path: &str,
+) -> VisualizationResult<()> {
+    let config = RenderConfig {
+        title: "Pareto Front".to_string(),
+        ..RenderConfig::default()
+    };
+    plot_pareto_front_with_config(front, x_objective, y_objective, output_path, &config)
+}
+
+/// Plot two objectives of a Pareto front with a custom configuration
+///
+/// Designs are drawn with the channel style color, connected in order of the
+/// horizontal objective, and the knee design is circled in red. Designs that
+/// violate the clearance constraints are drawn hollow.
+///
+/// # Errors
+///
+/// Returns an error if the front is empty, an objective index is out of
+/// range, the output format is unsupported, or rendering fails.
+pub fn plot_pareto_front_with_config(
+    front: &ParetoFront,
+    x_objective: usize,
+    y_objective: usize,
+    output_path: &str,
+    config: &RenderConfig,
+) -> VisualizationResult<()> {
+    if front.is_empty() {
+        return Err(VisualizationError::InvalidParameters {
+            parameter: "front".to_string(),
+            value: "0 solutions".to_string(),
+            constraint: "Pareto front must contain at least one solution".to_string(),
+        });
+    }
+    for index in [x_objective, y_objective] {
+        if index >= front.objectives.len() {
+            return Err(VisualizationError::InvalidParameters {
+                parameter: "objective".to_string(),
+                value: index.to_string(),
+                constraint: format!("Must be less than {}", front.objectives.len()),
+            });
+        }
+    }
+
+    match detect_output_format(output_path)? {
+        OutputFormat::PNG | OutputFormat::JPEG => {
+            let root = BitMapBackend::new(output_path, (config.width, config.height)).into_drawing_area();
+            draw_front(front, x_objective, y_objective, config, &root)
+        }
+        OutputFormat::SVG => {
+            let root = SVGBackend::new(output_path, (config.width, config.height)).into_drawing_area();
+            draw_front(front, x_objective, y_objective, config, &root)
+        }
+        OutputFormat::PDF => Err(VisualizationError::unsupported_format(
+            "PDF",
+            "PDF output is not yet implemented",
+        )),
+    }
+}
+
+/// Detect output format from file extension
+fn detect_output_format(output_path: &str) -> VisualizationResult<OutputFormat> {
+    let extension = Path::new(output_path)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(str::to_lowercase)
+        .ok_or_else(|| VisualizationError::invalid_output_path(output_path, "File must have a valid extension"))?;
+
+    match extension.as_str() {
+        "png" => Ok(OutputFormat::PNG),
+        "jpg" | "jpeg" => Ok(OutputFormat::JPEG),
+        "svg" => Ok(OutputFormat::SVG),
+        "pdf" => Ok(OutputFormat::PDF),
+        _ => Err(VisualizationError::invalid_output_path(
+            output_path,
+            &format!("Unsupported file extension: .{extension}"),
+        )),
+    }
+}
+
+/// Padded axis range covering `values`
+fn padded_range(values: impl Iterator<Item = f64>) -> std::ops::Range<f64> {
+    let (low, high) = values
+        .filter(|value| value.is_finite())
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(low, high), value| (low.min(value), high.max(value)));
+    if !low.is_finite() {
+        return -constants::DEGENERATE_RANGE_PADDING..constants::DEGENERATE_RANGE_PADDING;
+    }
+
+    let padding = if high > low {
+        (high - low) * constants::RANGE_PADDING_FRACTION
+    } else {
+        (low.abs() * constants::RANGE_PADDING_FRACTION).max(constants::DEGENERATE_RANGE_PADDING)
+    };
+    (low - padding)..(high + padding)
+}
+
+/// Draw the front onto a drawing area
+fn draw_front<DB: DrawingBackend>(
+    front: &ParetoFront,
+    x_objective: usize,
+    y_objective: usize,
+    config: &RenderConfig,
+    root: &DrawingArea<DB, Shift>,
+) -> VisualizationResult<()> {
+    root.fill(&convert_color(&config.background_color))
+        .map_err(|e| VisualizationError::rendering_error(&e.to_string()))?;
+
+    let mut points: Vec<(f64, f64, bool)> = front.solutions.iter()
+        .map(|solution| (solution.values[x_objective], solution.values[y_objective], solution.feasible))
+        .collect();
+    points.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    let constants_registry = ConstantsRegistry::new();
+    #[allow(clippy::cast_possible_truncation)]
+    let title_size = config.title_style.font_size as i32;
+    let mut chart = ChartBuilder::on(root)
+        .caption(&config.title, (config.title_style.font_family.as_str(), title_size))
+        .margin(constants_registry.get_default_chart_margin())
+        .margin_right(constants_registry.get_default_chart_right_margin())
+        .x_label_area_size(constants_registry.get_default_x_label_area_size())
+        .y_label_area_size(constants_registry.get_default_y_label_area_size())
+        .build_cartesian_2d(
+            padded_range(points.iter().map(|point| point.0)),
+            padded_range(points.iter().map(|point| point.1)),
+        )
+        .map_err(|e| VisualizationError::rendering_error(&e.to_string()))?;
+
+    if config.show_axes {
+        chart
+            .configure_mesh()
+            .x_desc(front.objectives[x_objective].label())
+            .y_desc(front.objectives[y_objective].label())
+            .draw()
+            .map_err(|e| VisualizationError::rendering_error(&e.to_string()))?;
+    }
+
+    let color = convert_color(&config.channel_style.color);
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let line_width = config.channel_style.width.max(1.0) as u32;
+    chart.draw_series(std::iter::once(PathElement::new(
+        points.iter().map(|&(x, y, _)| (x, y)).collect::<Vec<_>>(),
+        color.stroke_width(line_width),
+    )))
+    .map_err(|e| VisualizationError::rendering_error(&e.to_string()))?;
+
+    chart.draw_series(points.iter().map(|&(x, y, feasible)| {
+        let style = if feasible { color.filled() } else { color.stroke_width(1) };
+        Circle::new((x, y), constants::POINT_RADIUS, style)
+    }))
+    .map_err(|e| VisualizationError::rendering_error(&e.to_string()))?;
+
+    if let Some(knee) = front.select_knee() {
+        let knee_color = convert_color(&Color::RED);
+        chart.draw_series(std::iter::once(Circle::new(
+            (knee.values[x_objective], knee.values[y_objective]),
+            constants::KNEE_RADIUS,
+            knee_color.stroke_width(2),
+        )))
+        .map_err(|e| VisualizationError::rendering_error(&e.to_string()))?;
+    }
+
+    root.present()
+        .map_err(|e| VisualizationError::rendering_error(&e.to_string()))?;
+    Ok(())
+}
+
+/// Convert our Color type to plotters `RGBColor`
+const fn convert_color(color: &Color) -> RGBColor {
+    RGBColor(color.r, color.g, color.b)
+}
@@ -124,7 +124,8 @@ impl PlottersRenderer {
         output_path: &str,
     ) -> VisualizationResult<()> {
         // Set up coordinate system
-        let (length, width) = system.box_dims;
+        let ((min_x, min_y), (max_x, max_y)) = system.bounds();
+        let (length, width) = (max_x - min_x, max_y - min_y);
         let x_buffer = length * config.margin_fraction;
         let y_buffer = width * config.margin_fraction;
 
@@ -136,8 +137,8 @@ impl PlottersRenderer {
             .x_label_area_size(constants.get_default_x_label_area_size())
             .y_label_area_size(constants.get_default_y_label_area_size())
             .build_cartesian_2d(
-                -x_buffer..length + x_buffer,
-                -y_buffer..width + y_buffer,
+                min_x - x_buffer..max_x + x_buffer,
+                min_y - y_buffer..max_y + y_buffer,
             )
             .map_err(|e| VisualizationError::rendering_error(&e.to_string()))?;
 
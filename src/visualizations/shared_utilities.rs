@@ -52,7 +52,8 @@ pub fn visualize<'a, 'b>(
     root.fill(&WHITE)
         .map_err(|e| VisualizationError::rendering_error(&e.to_string()))?;
 
-    let (length, width) = system.box_dims;
+    let ((min_x, min_y), (max_x, max_y)) = system.bounds();
+    let (length, width) = (max_x - min_x, max_y - min_y);
     let x_buffer = length * 0.05;
     let y_buffer = width * 0.05;
 
@@ -63,8 +64,8 @@ pub fn visualize<'a, 'b>(
         .x_label_area_size(40)
         .y_label_area_size(40)
         .build_cartesian_2d(
-            -x_buffer..length + x_buffer,
-            -y_buffer..width + y_buffer,
+            min_x - x_buffer..max_x + x_buffer,
+            min_y - y_buffer..max_y + y_buffer,
         ).map_err(|e| VisualizationError::rendering_error(&e.to_string()))?;
 
     chart
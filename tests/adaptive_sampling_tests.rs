@@ -0,0 +1,196 @@
+//! Adaptive Sampling Tests
+//!
+//! Tests for curvature-based path sampling: tolerance guarantees of the
+//! samplers, exact endpoints and the use of adaptive sampling by the
+//! path-producing channel strategies.
+
+use scheme::{
+    config::{
+        AdaptiveSamplingConfig, ArcConfig, FrustumConfig, GeometryConfig, GeometryGenerationConfig, SerpentineConfig,
+    },
+    geometry::{
+        optimization::calculate_path_length,
+        sampling::{sample_curve, simplify_path},
+        spatial_index::closest_point_on_segment,
+        strategies::{
+            ArcChannelStrategy, ChannelTypeStrategy, FrustumChannelStrategy, SerpentineChannelStrategy,
+            SmoothStraightChannelStrategy, SmoothTransitionConfig,
+        },
+        ChannelType, Point2D,
+    },
+};
+use std::f64::consts::PI;
+
+const BOX_DIMS: (f64, f64) = (200.0, 100.0);
+const P1: Point2D = (0.0, 50.0);
+const P2: Point2D = (200.0, 50.0);
+
+/// Geometry configuration sampling paths with the given tolerances
+fn adaptive_geometry(max_chord_error: f64, max_turn_angle: f64) -> GeometryConfig {
+    let sampling = AdaptiveSamplingConfig::new(max_chord_error, max_turn_angle).unwrap();
+    GeometryConfig {
+        generation: GeometryGenerationConfig::default().with_adaptive_sampling(sampling),
+        ..GeometryConfig::default()
+    }
+}
+
+/// Path of a channel that has one
+fn path_of(channel_type: ChannelType) -> Vec<Point2D> {
+    match channel_type {
+        ChannelType::SmoothStraight { path }
+        | ChannelType::Serpentine { path }
+        | ChannelType::Arc { path }
+        | ChannelType::Frustum { path, .. } => path,
+        ChannelType::Straight => panic!("Expected a path channel"),
+    }
+}
+
+/// Largest distance from any `reference` point to the sampled `path`
+fn max_deviation(reference: &[Point2D], path: &[Point2D]) -> f64 {
+    reference
+        .iter()
+        .map(|&point| {
+            path.windows(2)
+                .map(|segment| {
+                    let closest = closest_point_on_segment(point, segment[0], segment[1]);
+                    (point.0 - closest.0).hypot(point.1 - closest.1)
+                })
+                .fold(f64::INFINITY, f64::min)
+        })
+        .fold(0.0, f64::max)
+}
+
+/// Test that a circular arc is sampled within both tolerances
+#[test]
+fn test_circle_within_tolerances() {
+    let radius = 5.0;
+    let circle = |t: f64| (radius * (PI * t).cos(), radius * (PI * t).sin());
+    let config = AdaptiveSamplingConfig::new(0.001, 0.1).unwrap();
+    let path = sample_curve(circle, 1, &config);
+
+    assert_eq!(path.first(), Some(&circle(0.0)));
+    assert_eq!(path.last(), Some(&circle(1.0)));
+    for segment in path.windows(2) {
+        let chord = (segment[1].0 - segment[0].0).hypot(segment[1].1 - segment[0].1);
+        let subtended = 2.0 * (chord / (2.0 * radius)).asin();
+        let sagitta = radius * (1.0 - (subtended / 2.0).cos());
+        assert!(sagitta <= config.max_chord_error + 1e-12);
+        assert!(subtended <= config.max_turn_angle + 1e-12);
+    }
+}
+
+/// Test that straight stretches need no interior points
+#[test]
+fn test_straight_curve_sampled_sparsely() {
+    let line = |t: f64| (10.0 * t, 3.0 * t);
+    assert_eq!(sample_curve(line, 1, &AdaptiveSamplingConfig::default()), vec![(0.0, 0.0), (10.0, 3.0)]);
+}
+
+/// Test that simplification keeps corners and endpoints and drops collinear points
+#[test]
+fn test_simplify_path() {
+    let dense: Vec<Point2D> = (0..=20)
+        .map(|step| (f64::from(step), 0.0))
+        .chain((1..=20).map(|step| (20.0, f64::from(step))))
+        .collect();
+    let simplified = simplify_path(&dense, &AdaptiveSamplingConfig::default());
+    assert_eq!(simplified, vec![(0.0, 0.0), (20.0, 0.0), (20.0, 20.0)]);
+
+    // Points of a smooth curve are only dropped within the chord error
+    let config = AdaptiveSamplingConfig::new(0.01, 0.2).unwrap();
+    let arc: Vec<Point2D> = (0..=200)
+        .map(|step| {
+            let angle = PI * f64::from(step) / 200.0;
+            (10.0 * angle.cos(), 10.0 * angle.sin())
+        })
+        .collect();
+    let simplified = simplify_path(&arc, &config);
+    assert!(simplified.len() < arc.len() / 2);
+    assert!(max_deviation(&arc, &simplified) <= config.max_chord_error);
+    assert_eq!(simplified.first(), arc.first());
+    assert_eq!(simplified.last(), arc.last());
+}
+
+/// Test that adaptive serpentines beat uniform sampling with the same point count
+#[test]
+fn test_adaptive_serpentine_accuracy() {
+    let strategy = SerpentineChannelStrategy::new(SerpentineConfig {
+        wavelength_factor: 10.0,
+        wave_density_factor: 0.5,
+        ..SerpentineConfig::default()
+    });
+    let serpentine = |geometry_config: &GeometryConfig| {
+        let ChannelType::Serpentine { path } = strategy.create_channel(P1, P2, geometry_config, BOX_DIMS, 1, None) else {
+            panic!("Expected a serpentine channel");
+        };
+        path
+    };
+    let uniform = |serpentine_points| {
+        serpentine(&GeometryConfig {
+            generation: GeometryGenerationConfig {
+                serpentine_points,
+                ..GeometryGenerationConfig::default()
+            },
+            ..GeometryConfig::default()
+        })
+    };
+
+    let reference = uniform(1000);
+    let adaptive = serpentine(&adaptive_geometry(0.05, PI / 4.0));
+    assert_eq!(adaptive.first(), Some(&P1));
+    assert_eq!(adaptive.last(), Some(&P2));
+
+    let adaptive_deviation = max_deviation(&reference, &adaptive);
+    let uniform_deviation = max_deviation(&reference, &uniform(adaptive.len()));
+    assert!(adaptive.len() < reference.len() / 2);
+    assert!(adaptive_deviation <= 0.05);
+    assert!(uniform_deviation > 2.0 * adaptive_deviation);
+
+    let reference_length = calculate_path_length(&reference);
+    assert!((calculate_path_length(&adaptive) - reference_length).abs() / reference_length < 1e-3);
+}
+
+/// Test that arc, smooth straight and frustum strategies sample adaptively
+#[test]
+fn test_strategies_sample_adaptively() {
+    let coarse = adaptive_geometry(0.1, 0.5);
+    let fine = adaptive_geometry(0.001, 0.05);
+
+    let arc = ArcChannelStrategy::new(ArcConfig::default());
+    let smooth = SmoothStraightChannelStrategy::new(SmoothTransitionConfig::default());
+    let to = (100.0, 80.0);
+    for strategy in [&arc as &dyn ChannelTypeStrategy, &smooth] {
+        let coarse_path = path_of(strategy.create_channel(P1, to, &coarse, BOX_DIMS, 1, None));
+        let fine_path = path_of(strategy.create_channel(P1, to, &fine, BOX_DIMS, 1, None));
+        assert_eq!((coarse_path.first(), coarse_path.last()), (Some(&P1), Some(&to)));
+        assert_eq!((fine_path.first(), fine_path.last()), (Some(&P1), Some(&to)));
+        assert!(fine_path.len() > coarse_path.len());
+    }
+
+    let frustum = FrustumChannelStrategy::new(FrustumConfig::default());
+    let ChannelType::Frustum { path, widths, .. } = frustum.create_channel(P1, P2, &fine, BOX_DIMS, 1, None) else {
+        panic!("Expected a frustum channel");
+    };
+    assert_eq!(path.len(), widths.len());
+    assert_eq!((path.first(), path.last()), (Some(&P1), Some(&P2)));
+    let config = FrustumConfig::default();
+    assert!((widths[0] - config.inlet_width).abs() < 1e-9);
+    assert!((widths[widths.len() - 1] - config.outlet_width).abs() < 1e-9);
+}
+
+/// Test that out-of-range tolerances are rejected
+#[test]
+fn test_invalid_tolerances_rejected() {
+    assert!(AdaptiveSamplingConfig::new(0.0, 0.1).is_err());
+    assert!(AdaptiveSamplingConfig::new(0.01, PI).is_err());
+
+    let generation = GeometryGenerationConfig {
+        adaptive_sampling: Some(AdaptiveSamplingConfig {
+            max_chord_error: -1.0,
+            max_turn_angle: 0.1,
+        }),
+        ..GeometryGenerationConfig::default()
+    };
+    assert!(generation.validate().is_err());
+    assert!(GeometryGenerationConfig::default().validate().is_ok());
+}
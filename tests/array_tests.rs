@@ -0,0 +1,134 @@
+//! Array Tests
+//!
+//! Tests for step-and-repeat arrays: grid placement on plates and wafers,
+//! dicing lanes, keep-out margins, labels, parameter sweeps and rejected
+//! layouts.
+
+use scheme::{
+    config::{ChannelTypeConfig, GeometryConfig, SerpentineConfig},
+    error::GeometryError,
+    geometry::{
+        array::{ArrayGenerator, ParameterSweep, SweepAxis, WaferOutline},
+        generator::create_geometry,
+        ChannelSystem, ChannelType, SplitType,
+    },
+};
+
+/// Straight bifurcation of the given size
+fn device(box_dims: (f64, f64)) -> ChannelSystem {
+    create_geometry(box_dims, &[SplitType::Bifurcation], &GeometryConfig::default(), &ChannelTypeConfig::AllStraight)
+}
+
+/// Test that a fixed grid on a plate is centered and spaced by the pitch
+#[test]
+fn test_plate_grid_placement() {
+    let generator = ArrayGenerator::new(WaferOutline::Plate { width: 75.0, height: 25.0 }, (24.0, 12.0))
+        .with_dicing_lane(2.0)
+        .with_grid(2, 3);
+    let template = device((20.0, 8.0));
+    let layout = generator.build(|_| template.clone()).unwrap();
+
+    assert_eq!(layout.dies.len(), 6);
+    assert_eq!(layout.system.channels.len(), 6 * template.channels.len());
+    assert_eq!(layout.system.box_dims, (75.0, 25.0));
+    assert_eq!(layout.system.box_outline.len(), 4 + 6 * 4);
+
+    let labels: Vec<_> = layout.dies.iter().map(|die| die.site.label.as_str()).collect();
+    assert_eq!(labels, ["A1", "A2", "A3", "B1", "B2", "B3"]);
+    assert_eq!(layout.dies[0].bounds, ((-35.0, 1.0), (-13.0, 11.0)));
+    assert_eq!(layout.dies[4].bounds, ((-11.0, -11.0), (11.0, -1.0)));
+    assert_eq!(layout.dies[0].label_position, (-34.0, 10.0));
+
+    for die in &layout.dies {
+        let ((min_x, min_y), (max_x, max_y)) = die.bounds;
+        assert!((max_x - min_x - 22.0).abs() < 1e-9 && (max_y - min_y - 10.0).abs() < 1e-9);
+        let center = (f64::midpoint(min_x, max_x), f64::midpoint(min_y, max_y));
+        for (index, node) in template.nodes.iter().enumerate() {
+            let placed = layout.system.nodes[die.node_offset + index].point;
+            assert!((placed.0 - (node.point.0 - 10.0 + center.0)).abs() < 1e-9);
+            assert!((placed.1 - (node.point.1 - 4.0 + center.1)).abs() < 1e-9);
+        }
+    }
+}
+
+/// Test that only dies inside the wafer's keep-out margin are placed
+#[test]
+fn test_wafer_keep_out() {
+    let outline = WaferOutline::Wafer { diameter: 100.0 };
+    let generator = ArrayGenerator::new(outline, (25.0, 20.0)).with_dicing_lane(2.0).with_grid(5, 4);
+    let margined = generator.clone().with_keep_out(5.0);
+
+    let full = generator.build(|_| device((20.0, 15.0))).unwrap();
+    let reduced = margined.build(|_| device((20.0, 15.0))).unwrap();
+    assert!(reduced.dies.len() < full.dies.len());
+    for die in &reduced.dies {
+        assert!(outline.contains(die.bounds.0, die.bounds.1, 5.0));
+    }
+    for node in &full.system.nodes {
+        assert!(node.point.0.hypot(node.point.1) <= 50.0);
+    }
+}
+
+/// Test that a sweep assigns one fill factor per column and reaches the designs
+#[test]
+fn test_fill_factor_sweep_across_columns() {
+    let generator = ArrayGenerator::new(WaferOutline::Plate { width: 130.0, height: 70.0 }, (32.0, 32.0))
+        .with_dicing_lane(2.0)
+        .with_label_prefix("FF-")
+        .with_sweep(ParameterSweep::linear(SweepAxis::Columns, 0.2, 0.8, 4));
+    let layout = generator
+        .build(|site| {
+            let serpentine = SerpentineConfig {
+                fill_factor: site.parameter.unwrap(),
+                ..SerpentineConfig::default()
+            };
+            create_geometry((30.0, 30.0), &[], &GeometryConfig::default(), &ChannelTypeConfig::AllSerpentine(serpentine))
+        })
+        .unwrap();
+
+    assert_eq!(layout.dies.len(), 8);
+    assert_eq!(layout.dies[5].site.label, "FF-B2");
+    let amplitude = |index: usize| {
+        let channel = &layout.system.channels[layout.dies[index].channel_offset];
+        let ChannelType::Serpentine { path } = &channel.channel_type else {
+            panic!("Expected a serpentine channel");
+        };
+        let (low, high) = path.iter().fold((f64::MAX, f64::MIN), |(low, high), point| (low.min(point.1), high.max(point.1)));
+        high - low
+    };
+    for (index, die) in layout.dies.iter().enumerate() {
+        let expected = [0.2, 0.4, 0.6, 0.8][die.site.column];
+        assert!((die.site.parameter.unwrap() - expected).abs() < 1e-12);
+        if die.site.column > 0 {
+            assert!(amplitude(index) > amplitude(index - 1));
+        }
+    }
+}
+
+/// Test that sweeps repeat when the grid is larger than the study
+#[test]
+fn test_sweep_repeats() {
+    let sweep = ParameterSweep::new(SweepAxis::Rows, vec![1.0, 2.0]);
+    assert_eq!(sweep.value(0, 5), Some(1.0));
+    assert_eq!(sweep.value(3, 0), Some(2.0));
+    assert_eq!(ParameterSweep::new(SweepAxis::Rows, Vec::new()).value(0, 0), None);
+}
+
+/// Test that invalid layouts and oversized designs are rejected
+#[test]
+fn test_invalid_arrays_rejected() {
+    let plate = WaferOutline::Plate { width: 50.0, height: 50.0 };
+    let invalid = [
+        ArrayGenerator::new(plate, (10.0, 10.0)).with_dicing_lane(10.0),
+        ArrayGenerator::new(plate, (10.0, 10.0)).with_keep_out(-1.0),
+        ArrayGenerator::new(plate, (60.0, 10.0)),
+        ArrayGenerator::new(plate, (20.0, 20.0)).with_keep_out(20.0),
+    ];
+    for generator in invalid {
+        assert!(matches!(generator.build(|_| device((5.0, 5.0))), Err(GeometryError::InvalidArray { .. })));
+    }
+
+    let generator = ArrayGenerator::new(plate, (20.0, 20.0)).with_dicing_lane(2.0);
+    assert!(matches!(generator.build(|_| device((19.0, 10.0))), Err(GeometryError::InvalidArray { .. })));
+    assert!(generator.build(|_| device((18.0, 18.0))).is_ok());
+}
@@ -0,0 +1,162 @@
+//! Command-Line Tool Tests
+//!
+//! Tests for the `scheme` binary: generating systems from design files,
+//! rendering, validation, statistics, export and the exit code reported for
+//! each kind of failure.
+
+use std::{
+    path::{Path, PathBuf},
+    process::{Command, Output},
+};
+
+use scheme::{
+    config::{ArcConfig, ChannelTypeConfig, GeometryConfig},
+    design::DesignSpec,
+    geometry::{generator::create_geometry, ChannelSystem, SplitType},
+};
+
+/// Run the binary with the given arguments
+fn scheme(args: &[&Path]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_scheme")).args(args).output().unwrap()
+}
+
+/// Fresh scratch directory for one test
+fn scratch(name: &str) -> PathBuf {
+    let directory = std::env::temp_dir().join(format!("scheme_cli_tests_{}_{name}", std::process::id()));
+    std::fs::create_dir_all(&directory).unwrap();
+    directory
+}
+
+/// Arc bifurcation design saved as TOML
+fn design_file(directory: &Path) -> (DesignSpec, PathBuf) {
+    let spec = DesignSpec::new(
+        (200.0, 100.0),
+        &[SplitType::Bifurcation],
+        GeometryConfig::default(),
+        ChannelTypeConfig::AllArcs(ArcConfig::default()),
+    );
+    let path = directory.join("design.toml");
+    spec.save(&path).unwrap();
+    (spec, path)
+}
+
+/// Test that generate writes the system described by the design
+#[test]
+fn test_generate_from_design() {
+    let directory = scratch("generate");
+    let (spec, design) = design_file(&directory);
+    let output = directory.join("system.json");
+
+    let result = scheme(&[Path::new("generate"), &design, Path::new("-o"), &output]);
+    assert!(result.status.success(), "{}", String::from_utf8_lossy(&result.stderr));
+    let system = ChannelSystem::from_json(&std::fs::read_to_string(&output).unwrap()).unwrap();
+    let expected = spec.generate().unwrap();
+    assert_eq!(system.nodes.len(), expected.nodes.len());
+    assert_eq!(system.channels.len(), expected.channels.len());
+
+    let stdout = scheme(&[Path::new("generate"), &design]);
+    assert!(ChannelSystem::from_json(&String::from_utf8(stdout.stdout).unwrap()).is_ok());
+
+    std::fs::remove_dir_all(&directory).ok();
+}
+
+/// Test that render and export write images and files by extension
+#[test]
+fn test_render_and_export() {
+    let directory = scratch("render");
+    let (_, design) = design_file(&directory);
+
+    for name in ["schematic.png", "schematic.svg"] {
+        let output = directory.join(name);
+        let result = scheme(&[Path::new("render"), &design, Path::new("-o"), &output, Path::new("--width"), Path::new("400")]);
+        assert!(result.status.success(), "{}", String::from_utf8_lossy(&result.stderr));
+        assert!(std::fs::metadata(&output).unwrap().len() > 0);
+    }
+
+    let exported = directory.join("exported.toml");
+    assert!(scheme(&[Path::new("export"), &design, Path::new("-o"), &exported]).status.success());
+    assert!(DesignSpec::load(&exported).is_ok());
+
+    let system = directory.join("system.json");
+    assert!(scheme(&[Path::new("export"), &design, Path::new("-o"), &system]).status.success());
+    let result = scheme(&[Path::new("export"), &system, Path::new("-o"), &directory.join("system.toml")]);
+    assert_eq!(result.status.code(), Some(5));
+    let result = scheme(&[Path::new("export"), &system, Path::new("-o"), &directory.join("system.pdf")]);
+    assert_eq!(result.status.code(), Some(5));
+
+    std::fs::remove_dir_all(&directory).ok();
+}
+
+/// Test that stats reports counts, ports and total length
+#[test]
+fn test_stats_json() {
+    let directory = scratch("stats");
+    let system = create_geometry((200.0, 100.0), &[SplitType::Bifurcation], &GeometryConfig::default(), &ChannelTypeConfig::AllStraight);
+    let input = directory.join("system.json");
+    std::fs::write(&input, system.to_json().unwrap()).unwrap();
+
+    let result = scheme(&[Path::new("stats"), &input, Path::new("--json")]);
+    assert!(result.status.success());
+    let stats: serde_json::Value = serde_json::from_slice(&result.stdout).unwrap();
+    assert_eq!(stats["nodes"], system.nodes.len());
+    assert_eq!(stats["channels"], system.channels.len());
+    assert_eq!(stats["channel_types"]["Straight"], system.channels.len());
+    assert_eq!((stats["inlets"].as_u64(), stats["outlets"].as_u64()), (Some(1), Some(1)));
+
+    let expected: f64 = system
+        .channels
+        .iter()
+        .map(|channel| {
+            let (from, to) = (system.nodes[channel.from_node].point, system.nodes[channel.to_node].point);
+            (to.0 - from.0).hypot(to.1 - from.1)
+        })
+        .sum();
+    assert!((stats["total_length"].as_f64().unwrap() - expected).abs() < 1e-9);
+
+    let text = scheme(&[Path::new("stats"), &input]);
+    assert!(String::from_utf8(text.stdout).unwrap().contains("channels:"));
+
+    std::fs::remove_dir_all(&directory).ok();
+}
+
+/// Test that validation failures map to the exit code of their error type
+#[test]
+fn test_exit_codes() {
+    let directory = scratch("exit_codes");
+    let system = create_geometry((200.0, 100.0), &[SplitType::Bifurcation], &GeometryConfig::default(), &ChannelTypeConfig::AllStraight);
+    let valid = directory.join("valid.json");
+    std::fs::write(&valid, system.to_json().unwrap()).unwrap();
+    assert_eq!(scheme(&[Path::new("validate"), &valid]).status.code(), Some(0));
+
+    let mut broken = system;
+    broken.channels[0].to_node = 99;
+    let dangling = directory.join("dangling.json");
+    std::fs::write(&dangling, broken.to_json().unwrap()).unwrap();
+    let result = scheme(&[Path::new("validate"), &dangling]);
+    assert_eq!(result.status.code(), Some(3));
+    assert!(String::from_utf8_lossy(&result.stderr).starts_with("error: Geometry error"));
+
+    let malformed = directory.join("malformed.json");
+    std::fs::write(&malformed, "{ not json").unwrap();
+    assert_eq!(scheme(&[Path::new("validate"), &malformed]).status.code(), Some(1));
+    assert_eq!(scheme(&[Path::new("validate"), &directory.join("missing.json")]).status.code(), Some(1));
+
+    let invalid_design = directory.join("invalid.toml");
+    std::fs::write(&invalid_design, "version = 1\nbox_dims = [100.0, -5.0]\n").unwrap();
+    assert_eq!(scheme(&[Path::new("validate"), &invalid_design]).status.code(), Some(8));
+
+    let drawing = directory.join("drawing.svg");
+    std::fs::write(&drawing, r#"<svg><g id="outline"><rect width="50" height="20"/></g><line x1="0" y1="10" x2="50" y2="10"/></svg>"#).unwrap();
+    assert_eq!(scheme(&[Path::new("validate"), &drawing]).status.code(), Some(0));
+    std::fs::write(&drawing, r#"<svg><line x1="0" y1="10" x2="50" y2="10"/></svg>"#).unwrap();
+    assert_eq!(scheme(&[Path::new("validate"), &drawing]).status.code(), Some(9));
+    let dxf = directory.join("drawing.dxf");
+    let entities = "0 SECTION 2 ENTITIES 0 LWPOLYLINE 8 outline 90 4 70 1 10 0 20 0 10 50 20 0 10 50 20 20 10 0 20 20 \
+        0 LINE 8 channels 10 0 20 10 11 50 21 10 0 ENDSEC 0 EOF";
+    std::fs::write(&dxf, entities.split_whitespace().collect::<Vec<_>>().join("\n")).unwrap();
+    assert_eq!(scheme(&[Path::new("validate"), &dxf]).status.code(), Some(0));
+
+    assert_eq!(scheme(&[Path::new("frobnicate")]).status.code(), Some(2));
+
+    std::fs::remove_dir_all(&directory).ok();
+}
@@ -0,0 +1,216 @@
+//! Collision Avoidance Tests
+//!
+//! Tests for the post-generation pass that shrinks curved channels until the
+//! finished channel paths no longer collide with each other or the walls.
+
+use scheme::{
+    config::{ArcConfig, ChannelTypeConfig, GeometryConfig, SerpentineConfig},
+    geometry::{
+        builders::{ChannelBuilder, ChannelExt, NodeBuilder},
+        collision_detection::{CollisionDetectionSystem, SystemAvoidanceConfig},
+        generator::{create_geometry, create_geometry_with_collision_avoidance, try_create_geometry},
+        metadata::CollisionAvoidanceMetadata,
+        spatial_index::ChannelSpatialIndex,
+        ChannelSystem, ChannelType, Point2D, SplitType,
+    },
+};
+
+const BOX_DIMS: (f64, f64) = (100.0, 50.0);
+
+/// Sine-shaped path from `(0, y)` to `(100, y)`
+fn wave(y: f64, amplitude: f64) -> Vec<Point2D> {
+    (0..=200)
+        .map(|step| {
+            let x = f64::from(step) * 0.5;
+            (x, amplitude.mul_add((x * std::f64::consts::PI / 10.0).sin(), y))
+        })
+        .collect()
+}
+
+/// Build a system of unconnected channels, each with its own pair of nodes
+fn system(channel_types: Vec<(Point2D, Point2D, ChannelType)>) -> ChannelSystem {
+    let nodes = channel_types.iter()
+        .flat_map(|(from, to, _)| [*from, *to])
+        .enumerate()
+        .map(|(id, point)| NodeBuilder::new(id, point).build())
+        .collect();
+    let channels = channel_types.into_iter()
+        .enumerate()
+        .map(|(id, (_, _, channel_type))| ChannelBuilder::new(id, 2 * id, 2 * id + 1, 1.0, 0.5, channel_type).build())
+        .collect();
+    ChannelSystem { box_dims: BOX_DIMS, nodes, channels, box_outline: Vec::new() }
+}
+
+fn path(channel_type: &ChannelType) -> &[Point2D] {
+    match channel_type {
+        ChannelType::Serpentine { path } | ChannelType::Arc { path } => path,
+        _ => panic!("Expected a curved channel"),
+    }
+}
+
+/// Test that neighboring serpentines are shrunk until they clear each other
+#[test]
+fn test_overlapping_serpentines_are_resolved() {
+    let mut layout = system(vec![
+        ((0.0, 20.0), (100.0, 20.0), ChannelType::Serpentine { path: wave(20.0, 4.0) }),
+        ((0.0, 28.0), (100.0, 28.0), ChannelType::Serpentine { path: wave(28.0, -4.0) }),
+    ]);
+    let original = layout.clone();
+    let config = SystemAvoidanceConfig { min_channel_spacing: Some(1.0), ..SystemAvoidanceConfig::default() };
+
+    let report = CollisionDetectionSystem::default()
+        .resolve_system_collisions(&mut layout, &GeometryConfig::default(), &config)
+        .unwrap();
+
+    assert_eq!(report.initial_collisions, 1);
+    assert!(report.is_clean());
+    assert!(report.iterations > 0);
+    assert_eq!(report.adjustments.len(), 2);
+    for adjustment in &report.adjustments {
+        assert!(adjustment.amplitude_scale < 1.0 && adjustment.amplitude_scale > 0.0);
+        assert!(adjustment.final_length < adjustment.initial_length);
+        assert_eq!(adjustment.rounds, report.iterations);
+    }
+
+    // Endpoints stay on their nodes and the gap meets the required spacing
+    for (before, after) in original.channels.iter().zip(&layout.channels) {
+        let (before, after) = (path(&before.channel_type), path(&after.channel_type));
+        assert_eq!(before.first(), after.first());
+        assert_eq!(before.last(), after.last());
+    }
+    let gap = ChannelSpatialIndex::new(&layout).channel_distance(0, 1).unwrap();
+    assert!(gap.spacing >= 1.0);
+}
+
+/// Test that curves bulging into a wall are flattened
+#[test]
+fn test_wall_violations_are_resolved() {
+    let bulge = vec![(0.0, 25.0), (25.0, 40.0), (50.0, 49.5), (75.0, 40.0), (100.0, 25.0)];
+    let mut layout = system(vec![((0.0, 25.0), (100.0, 25.0), ChannelType::Arc { path: bulge })]);
+
+    let report = CollisionDetectionSystem::default()
+        .resolve_system_collisions(&mut layout, &GeometryConfig::default(), &SystemAvoidanceConfig::default())
+        .unwrap();
+    assert_eq!(report.initial_collisions, 1);
+    assert!(report.is_clean());
+    assert!(path(&layout.channels[0].channel_type).iter().all(|point| point.1 <= 49.0 + 1e-9));
+
+    let mut ignored = system(vec![((0.0, 44.0), (100.0, 44.0), ChannelType::Arc { path: wave(44.0, 5.5) })]);
+    let config = SystemAvoidanceConfig { include_walls: false, ..SystemAvoidanceConfig::default() };
+    let report = CollisionDetectionSystem::default()
+        .resolve_system_collisions(&mut ignored, &GeometryConfig::default(), &config)
+        .unwrap();
+    assert_eq!(report.initial_collisions, 0);
+    assert!(!report.changed());
+}
+
+/// Test that wall clearance is measured against the outline of translated systems
+#[test]
+fn test_translated_system_uses_outline() {
+    let bulge = vec![(0.0, 25.0), (25.0, 35.0), (50.0, 45.0), (75.0, 35.0), (100.0, 25.0)];
+    let mut layout = system(vec![((0.0, 25.0), (100.0, 25.0), ChannelType::Arc { path: bulge.clone() })]);
+    let (length, width) = BOX_DIMS;
+    let corners = [(0.0, 0.0), (length, 0.0), (length, width), (0.0, width)];
+    layout.box_outline = (0..4).map(|index| (corners[index], corners[(index + 1) % 4])).collect();
+    layout.translate(0.0, 20.0);
+
+    let report = CollisionDetectionSystem::default()
+        .resolve_system_collisions(&mut layout, &GeometryConfig::default(), &SystemAvoidanceConfig::default())
+        .unwrap();
+    assert_eq!(report.initial_collisions, 0);
+    assert!(!report.changed());
+    let shifted: Vec<Point2D> = bulge.iter().map(|&(x, y)| (x, y + 20.0)).collect();
+    assert_eq!(path(&layout.channels[0].channel_type), shifted.as_slice());
+}
+
+/// Test that collisions between straight channels are reported, not modified
+#[test]
+fn test_straight_collisions_remain() {
+    let mut layout = system(vec![
+        ((0.0, 10.0), (100.0, 40.0), ChannelType::Straight),
+        ((0.0, 40.0), (100.0, 10.0), ChannelType::Straight),
+    ]);
+
+    let report = CollisionDetectionSystem::default()
+        .resolve_system_collisions(&mut layout, &GeometryConfig::default(), &SystemAvoidanceConfig::default())
+        .unwrap();
+
+    assert_eq!(report.initial_collisions, 1);
+    assert_eq!(report.remaining_collisions, 1);
+    assert_eq!(report.iterations, 0);
+    assert_eq!(report.unresolved_channels, [0, 1]);
+    assert!(!report.is_clean());
+    assert!(!report.changed());
+}
+
+/// Test the generator entry point on a branching serpentine design
+#[test]
+fn test_generated_design_is_clean() {
+    let config = GeometryConfig::default();
+    let (system, report) = create_geometry_with_collision_avoidance(
+        (200.0, 100.0),
+        &[SplitType::Bifurcation, SplitType::Trifurcation],
+        &config,
+        &ChannelTypeConfig::AllSerpentine(SerpentineConfig::default()),
+        &SystemAvoidanceConfig::default(),
+    )
+    .unwrap();
+
+    assert!(report.is_clean(), "Remaining collisions: {report:?}");
+    assert!((report.required_spacing - config.wall_clearance).abs() < f64::EPSILON);
+    for channel in &system.channels {
+        let path = path(&channel.channel_type);
+        assert_eq!(path.first().copied(), Some(system.nodes[channel.from_node].point));
+        assert_eq!(path.last().copied(), Some(system.nodes[channel.to_node].point));
+    }
+}
+
+/// Test that `create_geometry` runs the pass when the configuration opts in
+#[test]
+fn test_opt_in_through_geometry_config() {
+    let avoidance = SystemAvoidanceConfig::default();
+    let plain = GeometryConfig::default();
+    let config = GeometryConfig { generation: plain.generation.with_collision_avoidance(avoidance), ..plain };
+    let splits = [SplitType::Bifurcation, SplitType::Trifurcation];
+    let arcs = ArcConfig { curvature_factor: 2.0, enable_collision_prevention: false, ..ArcConfig::default() };
+    let channel_types = ChannelTypeConfig::AllArcs(arcs);
+
+    let (expected, report) = create_geometry_with_collision_avoidance((200.0, 100.0), &splits, &plain, &channel_types, &avoidance).unwrap();
+    assert!(report.changed());
+    let system = create_geometry((200.0, 100.0), &splits, &config, &channel_types);
+    let unchanged = create_geometry((200.0, 100.0), &splits, &plain, &channel_types);
+    for (channel, expected) in system.channels.iter().zip(&expected.channels) {
+        assert_eq!(path(&channel.channel_type), path(&expected.channel_type));
+    }
+    for adjustment in &report.adjustments {
+        let metadata = system.channels[adjustment.channel_id].get_metadata::<CollisionAvoidanceMetadata>().unwrap();
+        assert!((metadata.amplitude_scale - adjustment.amplitude_scale).abs() < 1e-12);
+        assert_eq!(metadata.rounds, adjustment.rounds);
+        assert!(metadata.length_change < 0.0);
+    }
+    let recorded = system.channels.iter().filter(|channel| channel.has_metadata::<CollisionAvoidanceMetadata>()).count();
+    assert_eq!(recorded, report.adjustments.len() + report.unresolved_channels.len());
+    assert!(report.unresolved_channels.is_empty());
+    assert!(system.channels.iter().zip(&unchanged.channels).any(|(a, b)| path(&a.channel_type) != path(&b.channel_type)));
+
+    let invalid = SystemAvoidanceConfig { max_iterations: 0, ..avoidance };
+    let config = GeometryConfig { generation: plain.generation.with_collision_avoidance(invalid), ..plain };
+    assert!(try_create_geometry((200.0, 100.0), &splits, &config, &channel_types).is_err());
+}
+
+/// Test that invalid avoidance settings are rejected
+#[test]
+fn test_config_validation() {
+    assert!(SystemAvoidanceConfig::default().validate().is_ok());
+    for invalid in [
+        SystemAvoidanceConfig { min_channel_spacing: Some(-1.0), ..SystemAvoidanceConfig::default() },
+        SystemAvoidanceConfig { max_iterations: 0, ..SystemAvoidanceConfig::default() },
+        SystemAvoidanceConfig { min_reduction_step: 0.0, ..SystemAvoidanceConfig::default() },
+        SystemAvoidanceConfig { min_reduction_step: 1.5, ..SystemAvoidanceConfig::default() },
+    ] {
+        let mut layout = system(vec![((0.0, 25.0), (100.0, 25.0), ChannelType::Straight)]);
+        let result = CollisionDetectionSystem::default()
+            .resolve_system_collisions(&mut layout, &GeometryConfig::default(), &invalid);
+        assert!(result.is_err(), "{invalid:?} should be rejected");
+    }
+}
@@ -0,0 +1,180 @@
+//! Composition Tests
+//!
+//! Tests for ports of channel systems and for composing several systems into
+//! one chip: placement, ID renumbering, connector stitching, the chip outline
+//! and rejected connections.
+
+use scheme::{
+    config::{ChannelTypeConfig, GeometryConfig, SerpentineConfig},
+    error::GeometryError,
+    geometry::{
+        composition::{ChipComposer, PortKind},
+        drc::{check_design_rules, DesignRule, RuleDeck, Severity},
+        generator::create_geometry,
+        ChannelSystem, ChannelType, SplitType,
+    },
+};
+
+/// Straight splitter tree of the given size
+fn splitter(box_dims: (f64, f64), splits: &[SplitType]) -> ChannelSystem {
+    create_geometry(box_dims, splits, &GeometryConfig::default(), &ChannelTypeConfig::AllStraight)
+}
+
+/// Chip of a mixer, a splitter tree and a delay line connected in series
+fn three_module_chip(composer: ChipComposer) -> (ChipComposer, [ChannelSystem; 3]) {
+    let mixer = splitter((80.0, 40.0), &[SplitType::Trifurcation]);
+    let tree = splitter((120.0, 60.0), &[SplitType::Bifurcation, SplitType::Bifurcation]);
+    let delay = create_geometry(
+        (150.0, 40.0),
+        &[],
+        &GeometryConfig::default(),
+        &ChannelTypeConfig::AllSerpentine(SerpentineConfig::default()),
+    );
+
+    let mut composer = composer;
+    let first = composer.add_module(mixer.clone(), (0.0, 10.0));
+    let second = composer.add_module(tree.clone(), (100.0, 0.0));
+    let third = composer.add_module(delay.clone(), (250.0, 10.0));
+    composer.connect(first.outlet(0), second.inlet(0)).unwrap();
+    composer.connect(second.outlet(0), third.inlet(0)).unwrap();
+    (composer, [mixer, tree, delay])
+}
+
+/// Test that generated systems expose their inlet and outlet as ports
+#[test]
+fn test_generated_system_ports() {
+    let system = splitter((200.0, 100.0), &[SplitType::Trifurcation, SplitType::Bifurcation]);
+    let ports = system.ports();
+    assert_eq!(ports.len(), 2);
+
+    let inlet = ports.iter().find(|port| port.kind == PortKind::Inlet).unwrap();
+    let outlet = ports.iter().find(|port| port.kind == PortKind::Outlet).unwrap();
+    assert_eq!((inlet.point, inlet.direction), ((0.0, 50.0), (-1.0, 0.0)));
+    assert_eq!((outlet.point, outlet.direction), ((200.0, 50.0), (1.0, 0.0)));
+    assert_eq!(system.nodes[inlet.node].point, inlet.point);
+    assert!((inlet.width - GeometryConfig::default().channel_width).abs() < f64::EPSILON);
+
+    // Channels referring to missing nodes are ignored rather than panicking
+    let mut dangling = system;
+    dangling.channels[0].to_node = dangling.nodes.len();
+    let inlet_node = dangling.channels[0].from_node;
+    assert!(dangling.ports().iter().all(|port| port.node != inlet_node));
+}
+
+/// Test that modules are placed and their IDs renumbered without collisions
+#[test]
+fn test_modules_placed_and_renumbered() {
+    let (composer, modules) = three_module_chip(ChipComposer::new());
+    let chip = composer.build();
+
+    let node_count: usize = modules.iter().map(|module| module.nodes.len()).sum();
+    let channel_count: usize = modules.iter().map(|module| module.channels.len()).sum();
+    assert_eq!(chip.system.nodes.len(), node_count);
+    assert_eq!(chip.system.channels.len(), channel_count + 2);
+    for (index, node) in chip.system.nodes.iter().enumerate() {
+        assert_eq!(node.id, index);
+    }
+    for (index, channel) in chip.system.channels.iter().enumerate() {
+        assert_eq!(channel.id, index);
+        assert!(channel.from_node < node_count && channel.to_node < node_count);
+    }
+
+    let placements = [(0.0, 10.0), (100.0, 0.0), (250.0, 10.0)];
+    for ((layout, module), position) in chip.modules.iter().zip(&modules).zip(placements) {
+        assert_eq!(layout.bounds.0, position);
+        assert_eq!(layout.node_count, module.nodes.len());
+        for (index, node) in module.nodes.iter().enumerate() {
+            let placed = chip.system.nodes[layout.node_offset + index].point;
+            assert!((placed.0 - node.point.0 - position.0).abs() < 1e-9);
+            assert!((placed.1 - node.point.1 - position.1).abs() < 1e-9);
+        }
+    }
+}
+
+/// Test that connectors join the chosen outlet and inlet and leave the rest as chip ports
+#[test]
+fn test_connectors_stitch_ports() {
+    let (composer, _) = three_module_chip(ChipComposer::new());
+    let chip = composer.build();
+    assert_eq!(chip.connectors.len(), 2);
+
+    let first = &chip.system.channels[chip.connectors[0]];
+    assert!(matches!(first.channel_type, ChannelType::Straight));
+    assert_eq!(chip.system.nodes[first.from_node].point, (80.0, 30.0));
+    assert_eq!(chip.system.nodes[first.to_node].point, (100.0, 30.0));
+
+    let ports = chip.system.ports();
+    assert_eq!(ports.len(), 2);
+    assert_eq!((ports[0].kind, ports[0].point), (PortKind::Inlet, (0.0, 30.0)));
+    assert_eq!((ports[1].kind, ports[1].point), (PortKind::Outlet, (400.0, 30.0)));
+}
+
+/// Test that generated connectors follow the configured channel type
+#[test]
+fn test_connector_channel_type() {
+    let composer = ChipComposer::new().with_connector_type(ChannelTypeConfig::AllSerpentine(SerpentineConfig::default()));
+    let (composer, _) = three_module_chip(composer);
+    let chip = composer.build();
+
+    for &index in &chip.connectors {
+        let connector = &chip.system.channels[index];
+        let ChannelType::Serpentine { path } = &connector.channel_type else {
+            panic!("Expected a serpentine connector");
+        };
+        assert_eq!(path.first(), Some(&chip.system.nodes[connector.from_node].point));
+        assert_eq!(path.last(), Some(&chip.system.nodes[connector.to_node].point));
+    }
+}
+
+/// Test that the module boxes are replaced by one outline around the chip
+#[test]
+fn test_single_chip_outline() {
+    let (composer, _) = three_module_chip(ChipComposer::new().with_margin(5.0));
+    let chip = composer.build();
+
+    assert_eq!(chip.system.box_outline.len(), 4);
+    assert_eq!(chip.system.bounds(), ((-5.0, -5.0), (405.0, 65.0)));
+    assert_eq!(chip.system.box_dims, (410.0, 70.0));
+}
+
+/// Test that invalid connections are rejected
+#[test]
+fn test_invalid_connections_rejected() {
+    let mut composer = ChipComposer::new();
+    let first = composer.add_module(splitter((100.0, 50.0), &[SplitType::Bifurcation]), (0.0, 0.0));
+    let second = composer.add_module(splitter((100.0, 50.0), &[SplitType::Bifurcation]), (120.0, 0.0));
+
+    let invalid = [
+        (first.inlet(0), second.inlet(0)),
+        (first.outlet(0), first.inlet(0)),
+        (first.outlet(1), second.inlet(0)),
+    ];
+    for (outlet, inlet) in invalid {
+        assert!(matches!(composer.connect(outlet, inlet), Err(GeometryError::InvalidComposition { .. })));
+    }
+
+    composer.connect(first.outlet(0), second.inlet(0)).unwrap();
+    assert!(composer.connect(first.outlet(0), second.inlet(0)).is_err());
+    assert_eq!(composer.build().connectors.len(), 1);
+}
+
+/// Test that wall distances are measured against placed boxes
+#[test]
+fn test_design_rules_follow_placement() {
+    let deck = RuleDeck::new().with_rule(DesignRule::MinWallDistance { min_distance: 20.0 }, Severity::Error);
+    let mut system = create_geometry(
+        (100.0, 50.0),
+        &[SplitType::Bifurcation],
+        &GeometryConfig::default(),
+        &ChannelTypeConfig::AllSerpentine(SerpentineConfig::default()),
+    );
+    let original = check_design_rules(&system, &deck).unwrap();
+    assert!(!original.violations.is_empty());
+
+    system.translate(300.0, -80.0);
+    let placed = check_design_rules(&system, &deck).unwrap();
+    assert_eq!(placed.violations.len(), original.violations.len());
+    for (before, after) in original.violations.iter().zip(&placed.violations) {
+        assert!((before.measured - after.measured).abs() < 1e-9);
+    }
+}
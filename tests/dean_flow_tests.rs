@@ -0,0 +1,129 @@
+//! Dean Flow Tests
+//!
+//! Tests for Reynolds and Dean numbers along channel paths, computed from path
+//! curvature, channel cross-sections, flow rates and fluid properties.
+
+use scheme::{
+    config::FluidProperties,
+    geometry::{
+        builders::{ChannelBuilder, ChannelExt, NodeBuilder},
+        dean_flow::{analyze_system_dean_numbers, attach_dean_metadata, hydraulic_diameter, reynolds_number},
+        metadata::{DeanMetadata, FlowMetadata},
+        ChannelSystem, ChannelType, Point2D,
+    },
+};
+use std::f64::consts::PI;
+
+/// Half circle of radius 10 mm from `(10, 0)` to `(-10, 0)`
+fn half_circle() -> Vec<Point2D> {
+    (0..=180)
+        .map(|step| {
+            let angle = f64::from(step).to_radians();
+            (10.0 * angle.cos(), 10.0 * angle.sin())
+        })
+        .collect()
+}
+
+/// Build a system of unconnected channels of width 1 mm and height 0.5 mm
+fn system(channel_types: Vec<ChannelType>, endpoints: &[(Point2D, Point2D)]) -> ChannelSystem {
+    let nodes = endpoints.iter()
+        .flat_map(|&endpoints| <[Point2D; 2]>::from(endpoints))
+        .enumerate()
+        .map(|(id, point)| NodeBuilder::new(id, point).build())
+        .collect();
+    let channels = channel_types.into_iter()
+        .enumerate()
+        .map(|(id, channel_type)| ChannelBuilder::new(id, 2 * id, 2 * id + 1, 1.0, 0.5, channel_type).build())
+        .collect();
+    ChannelSystem { box_dims: (100.0, 50.0), nodes, channels, box_outline: Vec::new() }
+}
+
+/// Test Reynolds numbers and hydraulic diameters against hand calculations
+#[test]
+fn test_reynolds_number() {
+    let water = FluidProperties::water();
+    assert!((hydraulic_diameter(1.0, 0.5) - 2.0 / 3.0).abs() < 1e-12);
+
+    // 60 µL/min through 1 mm × 0.5 mm is a mean velocity of 2 mm/s
+    let expected = 2.0e-3 * (2.0 / 3.0) * 1.0e-3 * water.density / water.viscosity;
+    assert!((reynolds_number(60.0, 1.0, 0.5, &water) - expected).abs() < 1e-9);
+    assert!((reynolds_number(-60.0, 1.0, 0.5, &water) - expected).abs() < 1e-9);
+}
+
+/// Test the Dean number along an arc of constant radius and a straight channel
+#[test]
+fn test_arc_and_straight_profiles() {
+    let layout = system(
+        vec![ChannelType::Arc { path: half_circle() }, ChannelType::Straight],
+        &[((10.0, 0.0), (-10.0, 0.0)), ((0.0, 40.0), (100.0, 40.0))],
+    );
+    let profiles = analyze_system_dean_numbers(&layout, &[600.0, 600.0], &FluidProperties::water()).unwrap();
+
+    let arc = &profiles[0];
+    let expected = arc.reynolds_number * (hydraulic_diameter(1.0, 0.5) / 20.0).sqrt();
+    assert!((arc.max_dean_number - expected).abs() < 1e-9);
+    assert!(arc.samples[1..180].iter().all(|sample| (sample.dean_number - expected).abs() < 1e-9));
+    assert!((arc.samples[90].curvature_ratio - hydraulic_diameter(1.0, 0.5) / 20.0).abs() < 1e-9);
+    assert!(arc.mean_dean_number < expected && arc.mean_dean_number > 0.99 * expected);
+    assert!(arc.max_location.is_some());
+
+    let straight = &profiles[1];
+    assert_eq!(straight.channel_id, 1);
+    assert!(straight.max_dean_number.abs() < f64::EPSILON);
+    assert_eq!(straight.max_location, None);
+    assert!((straight.reynolds_number - arc.reynolds_number).abs() < 1e-12);
+}
+
+/// Test that frustum channels use their local width
+#[test]
+fn test_frustum_local_width() {
+    let path: Vec<Point2D> = (0..=100).map(|step| {
+        let x = f64::from(step);
+        (x, 5.0f64.mul_add((x * PI / 100.0).sin(), 25.0))
+    }).collect();
+    let widths: Vec<f64> = (0..=100).map(|step| 0.5f64.mul_add(-(f64::from(step) * PI / 100.0).sin(), 1.0)).collect();
+    let frustum = ChannelType::Frustum { path, widths, inlet_width: 1.0, throat_width: 0.5, outlet_width: 1.0 };
+    let layout = system(vec![frustum], &[((0.0, 25.0), (100.0, 25.0))]);
+
+    let profile = &analyze_system_dean_numbers(&layout, &[100.0], &FluidProperties::water()).unwrap()[0];
+
+    let water = FluidProperties::water();
+    assert!((profile.samples[50].reynolds_number - reynolds_number(100.0, 0.5, 0.5, &water)).abs() < 1e-9);
+    assert!(profile.samples[50].reynolds_number > profile.samples[0].reynolds_number);
+}
+
+/// Test input validation
+#[test]
+fn test_invalid_inputs() {
+    let layout = system(vec![ChannelType::Straight], &[((0.0, 25.0), (100.0, 25.0))]);
+    let water = FluidProperties::water();
+
+    assert!(analyze_system_dean_numbers(&layout, &[], &water).is_err());
+    assert!(analyze_system_dean_numbers(&layout, &[f64::NAN], &water).is_err());
+    assert!(analyze_system_dean_numbers(&layout, &[10.0], &FluidProperties::new(0.0, 1e-3)).is_err());
+    assert!(analyze_system_dean_numbers(&layout, &[10.0], &FluidProperties::new(1000.0, -1.0)).is_err());
+}
+
+/// Test that Dean metadata is attached to channels with flow metadata only
+#[test]
+fn test_dean_metadata() {
+    let mut layout = system(
+        vec![ChannelType::Arc { path: half_circle() }, ChannelType::Arc { path: half_circle() }],
+        &[((10.0, 0.0), (-10.0, 0.0)), ((10.0, 0.0), (-10.0, 0.0))],
+    );
+    layout.channels[0].add_metadata(FlowMetadata {
+        flow_rate: 600.0,
+        pressure_drop: 0.0,
+        reynolds_number: 0.0,
+        velocity: 0.0,
+    });
+
+    let annotated = attach_dean_metadata(&mut layout, &FluidProperties::water()).unwrap();
+
+    assert_eq!(annotated, 1);
+    let metadata = layout.channels[0].get_metadata::<DeanMetadata>().unwrap();
+    assert!((metadata.flow_rate - 600.0).abs() < f64::EPSILON);
+    assert!(metadata.max_dean_number > 0.0);
+    assert_eq!(metadata.profile.len(), 181);
+    assert!(!layout.channels[1].has_metadata::<DeanMetadata>());
+}
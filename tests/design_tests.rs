@@ -0,0 +1,259 @@
+//! Design File Tests
+//!
+//! Tests for declarative design files: TOML and JSON round trips of every
+//! channel type configuration, defaults for omitted fields, field-level
+//! validation errors, version checks and loading designs from disk.
+
+use scheme::{
+    config::{
+        ArcConfig, ChannelTypeConfig, FrustumConfig, GeometryConfig, GeometryGenerationConfig, OptimizationTarget,
+        SerpentineConfig, WaveShape,
+    },
+    design::{DesignFormat, DesignSpec, DESIGN_FORMAT_VERSION},
+    error::{DesignError, GeometryError, SchemeError},
+    geometry::{
+        objectives::{ObjectiveSpec, ObjectiveTerm, WeightedTerm},
+        strategies::SmoothTransitionConfig,
+        ChannelSystem, ChannelType, SplitType,
+    },
+};
+
+/// Node positions and channel kinds of a generated system
+fn fingerprint(system: &ChannelSystem) -> (Vec<(f64, f64)>, Vec<String>) {
+    let points = system.nodes.iter().map(|node| node.point).collect();
+    let kinds = system
+        .channels
+        .iter()
+        .map(|channel| format!("{:?}", std::mem::discriminant(&channel.channel_type)))
+        .collect();
+    (points, kinds)
+}
+
+/// Test that every channel type configuration survives TOML and JSON round trips
+#[test]
+fn test_round_trip_all_channel_types() {
+    let serpentine = SerpentineConfig::default().with_square_wave();
+    let channel_types = [
+        ChannelTypeConfig::AllStraight,
+        ChannelTypeConfig::AllSmoothStraight(SmoothTransitionConfig::default()),
+        ChannelTypeConfig::AllSerpentine(serpentine),
+        ChannelTypeConfig::AllArcs(ArcConfig::default()),
+        ChannelTypeConfig::AllFrustum(FrustumConfig::default()),
+        ChannelTypeConfig::MixedByPosition {
+            middle_zone_fraction: 0.4,
+            serpentine_config: serpentine,
+            arc_config: ArcConfig::default(),
+        },
+        ChannelTypeConfig::default(),
+        ChannelTypeConfig::SmoothSerpentineWithTransitions {
+            serpentine_config: serpentine,
+            smooth_straight_config: SmoothTransitionConfig::default(),
+        },
+    ];
+
+    for channel_type in channel_types {
+        let spec = DesignSpec::new((200.0, 100.0), &[SplitType::Bifurcation], GeometryConfig::default(), channel_type)
+            .with_name("round trip");
+        let expected = fingerprint(&spec.generate().unwrap());
+        for format in [DesignFormat::Toml, DesignFormat::Json] {
+            let text = spec.to_text(format).unwrap();
+            let reloaded = DesignSpec::parse(&text, format).unwrap();
+            assert_eq!(reloaded.name.as_deref(), Some("round trip"));
+            assert_eq!(reloaded.to_text(format).unwrap(), text);
+            assert_eq!(fingerprint(&reloaded.generate().unwrap()), expected);
+        }
+    }
+}
+
+/// Test that optimization settings, including targets and objectives, are saved
+#[test]
+fn test_optimization_settings_round_trip() {
+    let serpentine = SerpentineConfig::default()
+        .with_optimization_target(OptimizationTarget::length(150.0, 0.02))
+        .with_objective(ObjectiveSpec::from_terms(vec![
+            WeightedTerm::new(ObjectiveTerm::PathLength, 1.0),
+            WeightedTerm::new(ObjectiveTerm::MinRadiusShortfall { min_radius: 2.0 }, -500.0),
+        ]));
+    let spec = DesignSpec::new((150.0, 60.0), &[], GeometryConfig::default(), ChannelTypeConfig::AllSerpentine(serpentine));
+
+    let reloaded = DesignSpec::from_toml(&spec.to_toml().unwrap()).unwrap();
+    let ChannelTypeConfig::AllSerpentine(config) = reloaded.channel_type else {
+        panic!("Expected a serpentine configuration");
+    };
+    assert!(config.optimization_enabled);
+    assert_eq!(config.optimization_target, Some(OptimizationTarget::length(150.0, 0.02)));
+    let objective = config.objective.unwrap();
+    let terms = objective.terms();
+    assert_eq!(terms.len(), 2);
+    assert!(matches!(terms[1].term, ObjectiveTerm::MinRadiusShortfall { min_radius } if (min_radius - 2.0).abs() < 1e-12));
+    assert!((terms[1].weight + 500.0).abs() < 1e-12);
+}
+
+/// Test that omitted fields take their defaults
+#[test]
+fn test_omitted_fields_use_defaults() {
+    let spec = DesignSpec::from_toml(
+        r#"
+        version = 1
+        box_dims = [120.0, 60.0]
+
+        [geometry.generation]
+        serpentine_points = 80
+
+        [channel_type.AllSerpentine]
+        wave_shape = "Square"
+        "#,
+    )
+    .unwrap();
+
+    let defaults = GeometryConfig::default();
+    assert!(spec.splits.is_empty());
+    assert!((spec.geometry.channel_width - defaults.channel_width).abs() < f64::EPSILON);
+    assert_eq!(spec.geometry.generation.serpentine_points, 80);
+    assert_eq!(
+        spec.geometry.generation.optimization_points,
+        GeometryGenerationConfig::default().optimization_points
+    );
+    let ChannelTypeConfig::AllSerpentine(config) = spec.channel_type else {
+        panic!("Expected a serpentine configuration");
+    };
+    assert_eq!(config.wave_shape, WaveShape::Square);
+    assert!((config.fill_factor - SerpentineConfig::default().fill_factor).abs() < f64::EPSILON);
+
+    let system = spec.generate().unwrap();
+    assert!(matches!(system.channels[0].channel_type, ChannelType::Serpentine { .. }));
+}
+
+/// Test that constraint violations name the offending fields
+#[test]
+fn test_field_level_validation_errors() {
+    let spec = DesignSpec::from_json(
+        r#"{
+            "version": 1,
+            "box_dims": [100.0, -5.0],
+            "geometry": { "channel_width": 0.0001, "generation": { "serpentine_points": 2 } },
+            "channel_type": { "MixedByPosition": {
+                "middle_zone_fraction": 1.5,
+                "serpentine_config": { "fill_factor": 2.0 },
+                "arc_config": {}
+            } }
+        }"#,
+    )
+    .unwrap();
+
+    let Err(SchemeError::Design(DesignError::InvalidFields { errors })) = spec.generate() else {
+        panic!("Expected field errors");
+    };
+    let fields: Vec<_> = errors.iter().map(|error| error.field.as_str()).collect();
+    assert_eq!(
+        fields,
+        [
+            "box_dims",
+            "geometry.channel_width",
+            "channel_type.MixedByPosition.middle_zone_fraction",
+            "channel_type.MixedByPosition.serpentine_config.fill_factor",
+        ]
+    );
+
+    let mut spec = DesignSpec::new((100.0, 50.0), &[], GeometryConfig::default(), ChannelTypeConfig::AllStraight);
+    spec.geometry.generation.serpentine_points = 2;
+    let Err(DesignError::InvalidFields { errors }) = spec.validate() else {
+        panic!("Expected field errors");
+    };
+    assert_eq!(errors[0].field, "geometry.generation.serpentine_points");
+}
+
+/// Test that valid designs which cannot be laid out fail to generate
+#[test]
+fn test_infeasible_design_not_generated() {
+    let spec = DesignSpec::new(
+        (200.0, 10.0),
+        &[SplitType::Trifurcation; 3],
+        GeometryConfig::default(),
+        ChannelTypeConfig::AllStraight,
+    );
+    assert!(spec.validate().is_ok());
+    assert!(matches!(
+        spec.generate(),
+        Err(SchemeError::Geometry(GeometryError::InvalidSplitPattern { .. }))
+    ));
+}
+
+/// Test that malformed designs, unknown fields and other versions are rejected
+#[test]
+fn test_parse_errors() {
+    let unknown_field = DesignSpec::from_toml("version = 1\nbox_dims = [10.0, 10.0]\n[geometry]\nchanel_width = 1.0\n");
+    assert!(matches!(unknown_field, Err(DesignError::ParseError { ref message, .. }) if message.contains("chanel_width")));
+
+    // Configurations that were serializable before design files keep their rules
+    let frustum = serde_json::to_value(FrustumConfig::default()).unwrap();
+    let mut extended = frustum.clone();
+    extended["comment"] = "ignored".into();
+    assert!(serde_json::from_value::<FrustumConfig>(extended).is_ok());
+    let mut partial = frustum;
+    partial.as_object_mut().unwrap().remove("throat_position");
+    assert!(serde_json::from_value::<FrustumConfig>(partial).is_err());
+
+    let missing_box = DesignSpec::from_json(r#"{ "version": 1 }"#);
+    assert!(matches!(missing_box, Err(DesignError::ParseError { ref message, .. }) if message.contains("box_dims")));
+
+    assert!(matches!(DesignSpec::from_json(r#"{ "box_dims": [1.0, 1.0] }"#), Err(DesignError::ParseError { .. })));
+    assert!(matches!(DesignSpec::from_toml("version = [1"), Err(DesignError::ParseError { .. })));
+
+    let future = DesignSpec::from_toml("version = 99\nbox_dims = [10.0, 10.0]\nsomething_new = true\n");
+    assert!(matches!(
+        future,
+        Err(DesignError::UnsupportedVersion { found: 99, supported }) if supported == DESIGN_FORMAT_VERSION
+    ));
+}
+
+/// Test that designs with custom channel types or objective terms cannot be saved
+#[test]
+fn test_custom_channel_type_not_serializable() {
+    let spec = DesignSpec::new(
+        (100.0, 50.0),
+        &[],
+        GeometryConfig::default(),
+        ChannelTypeConfig::Custom(|_, _, _| ChannelType::Straight),
+    );
+    assert!(matches!(spec.to_toml(), Err(DesignError::SerializeError { .. })));
+    assert!(matches!(spec.to_json(), Err(DesignError::SerializeError { .. })));
+    assert!(spec.generate().is_ok());
+
+    let objective = ObjectiveSpec::new(&[
+        WeightedTerm::new(ObjectiveTerm::PathLength, 1.0),
+        WeightedTerm::new(ObjectiveTerm::Custom(|metrics, _| -metrics.footprint_area), 0.1),
+    ]);
+    let spec = DesignSpec::new(
+        (100.0, 50.0),
+        &[],
+        GeometryConfig::default(),
+        ChannelTypeConfig::AllSerpentine(SerpentineConfig::default().with_objective(objective)),
+    );
+    assert!(matches!(spec.to_toml(), Err(DesignError::SerializeError { .. })));
+    assert!(matches!(spec.to_json(), Err(DesignError::SerializeError { .. })));
+}
+
+/// Test that designs are saved and loaded by file extension
+#[test]
+fn test_save_and_load_files() {
+    let spec = DesignSpec::new(
+        (200.0, 100.0),
+        &[SplitType::Trifurcation],
+        GeometryConfig::default(),
+        ChannelTypeConfig::AllArcs(ArcConfig::default()),
+    );
+    let directory = std::env::temp_dir().join(format!("scheme_design_tests_{}", std::process::id()));
+    std::fs::create_dir_all(&directory).unwrap();
+
+    for name in ["design.toml", "design.json"] {
+        let path = directory.join(name);
+        spec.save(&path).unwrap();
+        let loaded = DesignSpec::load(&path).unwrap();
+        assert_eq!(fingerprint(&loaded.generate().unwrap()), fingerprint(&spec.generate().unwrap()));
+    }
+    assert!(matches!(spec.save(directory.join("design.yaml")), Err(DesignError::FileError { .. })));
+    assert!(matches!(DesignSpec::load(directory.join("missing.toml")), Err(DesignError::FileError { .. })));
+
+    std::fs::remove_dir_all(&directory).ok();
+}
@@ -0,0 +1,243 @@
+//! Design Rule Checking Tests
+//!
+//! Tests for checking channel systems against fabrication rule decks and for
+//! the located violations they produce.
+
+use scheme::{
+    config::{ChannelTypeConfig, GeometryConfig},
+    error::{GeometryError, SchemeError},
+    geometry::{
+        builders::{ChannelBuilder, NodeBuilder},
+        drc::{check_design_rules, DesignRule, RuleDeck, Severity},
+        generator::create_geometry,
+        ChannelSystem, ChannelType, Point2D, SplitType,
+    },
+};
+
+const BOX_DIMS: (f64, f64) = (100.0, 50.0);
+
+/// Build a system from node positions and `(from, to, width, type)` channels
+fn system(points: &[Point2D], channels: Vec<(usize, usize, f64, ChannelType)>) -> ChannelSystem {
+    ChannelSystem {
+        box_dims: BOX_DIMS,
+        nodes: points.iter().enumerate().map(|(id, &point)| NodeBuilder::new(id, point).build()).collect(),
+        channels: channels.into_iter()
+            .enumerate()
+            .map(|(id, (from, to, width, channel_type))| {
+                ChannelBuilder::new(id, from, to, width, 0.5, channel_type).build()
+            })
+            .collect(),
+        box_outline: Vec::new(),
+    }
+}
+
+fn deck(rule: DesignRule) -> RuleDeck {
+    RuleDeck::new().with_rule(rule, Severity::Error)
+}
+
+/// Test that a generated straight bifurcation passes its own default rules
+#[test]
+fn test_generated_system_passes_default_deck() {
+    let geometry_config = GeometryConfig::default();
+    let generated = create_geometry(
+        (200.0, 100.0),
+        &[SplitType::Bifurcation, SplitType::Bifurcation],
+        &geometry_config,
+        &ChannelTypeConfig::AllStraight,
+    );
+
+    let report = check_design_rules(&generated, &RuleDeck::from_geometry_config(&geometry_config)).unwrap();
+
+    assert!(report.is_clean(), "Unexpected violations: {:?}", report.violations);
+    assert_eq!(report.rules_checked, 5);
+    assert_eq!(report.channels_checked, generated.channels.len());
+}
+
+/// Test that narrow channels and frustum throats are located
+#[test]
+fn test_min_feature_width() {
+    let frustum = ChannelType::Frustum {
+        path: vec![(0.0, 40.0), (50.0, 40.0), (100.0, 40.0)],
+        widths: vec![1.0, 0.2, 1.0],
+        inlet_width: 1.0,
+        throat_width: 0.2,
+        outlet_width: 1.0,
+    };
+    let layout = system(
+        &[(0.0, 10.0), (100.0, 10.0), (0.0, 40.0), (100.0, 40.0)],
+        vec![(0, 1, 0.3, ChannelType::Straight), (2, 3, 1.0, frustum)],
+    );
+
+    let report = check_design_rules(&layout, &deck(DesignRule::MinFeatureWidth { min_width: 0.5 })).unwrap();
+
+    assert_eq!(report.violations.len(), 2);
+    let straight = report.for_channel(0).next().unwrap();
+    assert!((straight.measured - 0.3).abs() < 1e-12);
+    assert!((straight.location.0 - 50.0).abs() < 1e-9);
+    let throat = report.for_channel(1).next().unwrap();
+    assert!((throat.measured - 0.2).abs() < 1e-12);
+    assert_eq!(throat.location, (50.0, 40.0));
+    assert!((throat.required - 0.5).abs() < 1e-12);
+}
+
+/// Test spacing between unconnected channels, and crossing channels as overlaps
+#[test]
+fn test_min_channel_spacing() {
+    let layout = system(
+        &[(0.0, 20.0), (100.0, 20.0), (0.0, 22.0), (100.0, 22.0), (30.0, 0.0), (30.0, 50.0)],
+        vec![
+            (0, 1, 1.0, ChannelType::Straight),
+            (2, 3, 1.0, ChannelType::Straight),
+            (4, 5, 1.0, ChannelType::Straight),
+        ],
+    );
+
+    let report = check_design_rules(&layout, &deck(DesignRule::MinChannelSpacing { min_spacing: 1.5 })).unwrap();
+
+    // Parallel channels are 1 mm apart edge to edge; the vertical one crosses both
+    assert_eq!(report.violations.len(), 3);
+    let parallel = report.violations.iter().find(|v| v.channel_ids == [0, 1]).unwrap();
+    assert!((parallel.measured - 1.0).abs() < 1e-9);
+    let (first, second) = parallel.extent.unwrap();
+    assert!((first.1 - 20.0).abs() < 1e-9 && (second.1 - 22.0).abs() < 1e-9);
+    assert!(parallel.to_geometry_error().is_none());
+
+    let overlaps = report.overlaps();
+    assert_eq!(overlaps.len(), 2);
+    assert!(overlaps.iter().any(|error| matches!(
+        error,
+        GeometryError::OverlappingChannels { x1, y1, .. } if (x1 - 30.0).abs() < 1e-9 && (y1 - 20.0).abs() < 1e-9
+    )));
+}
+
+/// Test that channels meeting at a junction are not reported as too close
+#[test]
+fn test_spacing_ignores_shared_junctions() {
+    let layout = system(
+        &[(0.0, 25.0), (50.0, 25.0), (100.0, 10.0), (100.0, 40.0)],
+        vec![
+            (0, 1, 1.0, ChannelType::Straight),
+            (1, 2, 1.0, ChannelType::Straight),
+            (1, 3, 1.0, ChannelType::Straight),
+        ],
+    );
+
+    let report = check_design_rules(&layout, &deck(DesignRule::MinChannelSpacing { min_spacing: 1.0 })).unwrap();
+    assert!(report.is_clean(), "Unexpected violations: {:?}", report.violations);
+}
+
+/// Test wall distance, with ports on the box boundary exempt
+#[test]
+fn test_min_wall_distance() {
+    let hugging = ChannelType::SmoothStraight { path: vec![(0.0, 25.0), (20.0, 49.2), (80.0, 49.2), (100.0, 25.0)] };
+    let layout = system(
+        &[(0.0, 25.0), (100.0, 25.0)],
+        vec![(0, 1, 1.0, ChannelType::Straight), (0, 1, 1.0, hugging)],
+    );
+
+    let report = check_design_rules(&layout, &deck(DesignRule::MinWallDistance { min_distance: 0.5 })).unwrap();
+
+    assert_eq!(report.violations.len(), 1);
+    let violation = &report.violations[0];
+    assert_eq!(violation.channel_ids, [1]);
+    assert!((violation.measured - 0.3).abs() < 1e-9);
+    assert!((violation.location.1 - 49.2).abs() < 1e-9);
+}
+
+/// Test that sharp corners are reported at the tightest bend
+#[test]
+fn test_min_bend_radius() {
+    let corner = ChannelType::Arc { path: vec![(0.0, 10.0), (50.0, 10.0), (51.0, 11.0), (51.0, 50.0)] };
+    let gentle = ChannelType::Arc { path: vec![(0.0, 30.0), (50.0, 31.0), (100.0, 30.0)] };
+    let layout = system(
+        &[(0.0, 10.0), (51.0, 50.0), (0.0, 30.0), (100.0, 30.0)],
+        vec![(0, 1, 1.0, corner), (2, 3, 1.0, gentle)],
+    );
+
+    let report = check_design_rules(&layout, &deck(DesignRule::MinBendRadius { min_radius: 5.0 })).unwrap();
+
+    assert_eq!(report.violations.len(), 1);
+    let violation = &report.violations[0];
+    assert_eq!(violation.channel_ids, [0]);
+    assert!(violation.measured < 5.0);
+    assert_eq!(violation.location, (50.0, 10.0));
+}
+
+/// Test that acute junctions are reported at the junction node
+#[test]
+fn test_min_junction_angle() {
+    let layout = system(
+        &[(0.0, 25.0), (50.0, 25.0), (100.0, 30.0), (100.0, 45.0)],
+        vec![
+            (0, 1, 1.0, ChannelType::Straight),
+            (1, 2, 1.0, ChannelType::Straight),
+            (1, 3, 1.0, ChannelType::Straight),
+        ],
+    );
+
+    let report = check_design_rules(&layout, &deck(DesignRule::MinJunctionAngle { min_angle_degrees: 30.0 })).unwrap();
+
+    // Branches leave at 5.7° and 21.8°, so they are only 16.1° apart
+    assert_eq!(report.violations.len(), 1);
+    let violation = &report.violations[0];
+    assert_eq!(violation.node_id, Some(1));
+    assert_eq!(violation.channel_ids, [1, 2]);
+    assert_eq!(violation.location, (50.0, 25.0));
+    assert!((violation.measured - 16.1).abs() < 0.1);
+
+    // Channels refer to nodes by index, so node IDs that differ from it are rejected
+    let mut renumbered = layout;
+    for node in &mut renumbered.nodes {
+        node.id += 100;
+    }
+    assert!(matches!(
+        check_design_rules(&renumbered, &deck(DesignRule::MinJunctionAngle { min_angle_degrees: 30.0 })),
+        Err(SchemeError::Geometry(GeometryError::NodeIdMismatch { index: 0, id: 100 }))
+    ));
+}
+
+/// Test severity ordering, report queries, and deck validation
+#[test]
+fn test_severities_and_deck_validation() {
+    let layout = system(
+        &[(0.0, 25.0), (100.0, 25.0)],
+        vec![(0, 1, 0.3, ChannelType::Straight)],
+    );
+    let deck = RuleDeck::new()
+        .with_rule(DesignRule::MinFeatureWidth { min_width: 0.5 }, Severity::Warning)
+        .with_rule(DesignRule::MinFeatureWidth { min_width: 0.1 }, Severity::Error)
+        .with_rule(DesignRule::MinFeatureWidth { min_width: 1.0 }, Severity::Info);
+
+    let report = check_design_rules(&layout, &deck).unwrap();
+    assert_eq!(report.violations.len(), 2);
+    assert_eq!(report.violations[0].severity, Severity::Warning);
+    assert_eq!(report.worst_severity(), Some(Severity::Warning));
+    assert!(report.passes());
+    assert_eq!(report.count(Severity::Info), 1);
+    assert!(report.violations[0].to_string().starts_with("[warning] min_feature_width"));
+
+    for invalid in [
+        DesignRule::MinFeatureWidth { min_width: 0.0 },
+        DesignRule::MinChannelSpacing { min_spacing: -1.0 },
+        DesignRule::MinBendRadius { min_radius: f64::NAN },
+        DesignRule::MinJunctionAngle { min_angle_degrees: 200.0 },
+    ] {
+        let deck = RuleDeck::new().with_rule(invalid, Severity::Error);
+        assert!(check_design_rules(&layout, &deck).is_err(), "{invalid:?} should be rejected");
+    }
+}
+
+/// Test that systems with channels referring to missing nodes are rejected
+/// instead of panicking
+#[test]
+fn test_dangling_node_reference_rejected() {
+    let mut system = create_geometry((200.0, 100.0), &[SplitType::Bifurcation], &GeometryConfig::default(), &ChannelTypeConfig::AllStraight);
+    system.channels[0].to_node = 99;
+    let loaded = ChannelSystem::from_json(&system.to_json().unwrap()).unwrap();
+
+    let result = check_design_rules(&loaded, &RuleDeck::default());
+    assert!(matches!(
+        result,
+        Err(SchemeError::Geometry(GeometryError::DanglingNodeReference { node_id: 99, .. }))
+    ));
+}
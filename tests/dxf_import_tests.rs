@@ -0,0 +1,188 @@
+//! DXF Import Tests
+//!
+//! Tests for importing channel layouts from DXF drawings: layers and units,
+//! joined endpoints, polyline widths, arcs and bulges, spline evaluation,
+//! reported unsupported entities and the errors for unusable drawings.
+
+use scheme::{
+    error::ImportError,
+    geometry::{ChannelSystem, ChannelType, Point2D},
+    import::{dxf, ImportOptions},
+};
+
+/// 100 mm square outline drawn with lines
+const SQUARE: &str = "0 LINE 8 outline 10 0 20 0 11 100 21 0 \
+    0 LINE 8 outline 10 100 20 0 11 100 21 100 \
+    0 LINE 8 outline 10 100 20 100 11 0 21 100 \
+    0 LINE 8 outline 10 0 20 100 11 0 21 0";
+
+/// DXF text with the given header variables and entities, written as
+/// space-separated group code and value pairs
+fn drawing(header: &str, entities: &str) -> String {
+    let text = format!("0 SECTION 2 HEADER {header} 0 ENDSEC 0 SECTION 2 ENTITIES {entities} 0 ENDSEC 0 EOF");
+    text.split_whitespace().collect::<Vec<_>>().join("\n")
+}
+
+/// Path points of a curved or polyline channel
+fn path(system: &ChannelSystem, channel: usize) -> &[Point2D] {
+    match &system.channels[channel].channel_type {
+        ChannelType::SmoothStraight { path } | ChannelType::Arc { path } => path,
+        other => panic!("channel {channel} has no path: {other:?}"),
+    }
+}
+
+/// Assert that two points agree to within 1e-9
+fn assert_close(actual: Point2D, expected: Point2D) {
+    assert!((actual.0 - expected.0).abs() < 1e-9 && (actual.1 - expected.1).abs() < 1e-9, "{actual:?} != {expected:?}");
+}
+
+/// Test that layers, units, joined endpoints and polyline widths are imported
+#[test]
+fn test_layers_units_and_joined_endpoints() {
+    let text = drawing(
+        "9 $INSUNITS 70 5",
+        "0 LWPOLYLINE 8 Outline 90 4 70 1 10 0 20 0 10 10 20 0 10 10 20 5 10 0 20 5 \
+         0 LINE 8 CHANNELS 10 0 20 2.5 11 4 21 2.5 \
+         0 LINE 8 CHANNELS 10 4.003 20 2.5 11 10 21 4 \
+         0 LWPOLYLINE 8 CHANNELS 90 3 43 0.02 10 4 20 2.5 10 7 20 1 10 10 20 1 \
+         0 LINE 8 DIMENSIONS 10 0 20 0 11 10 21 0 \
+         0 TEXT 8 DIMENSIONS 10 1 20 1 1 100mm",
+    );
+    let options = ImportOptions { channel_layers: vec!["channels".to_string()], ..ImportOptions::default() };
+    let imported = dxf::import(&text, &options).unwrap();
+    let system = &imported.system;
+
+    assert_eq!(system.box_dims, (100.0, 50.0));
+    assert!(imported.skipped.is_empty());
+    let points: Vec<Point2D> = system.nodes.iter().map(|node| node.point).collect();
+    assert_eq!(points, [(0.0, 25.0), (40.0, 25.0), (100.0, 40.0), (100.0, 10.0)]);
+    let connections: Vec<(usize, usize)> = system.channels.iter().map(|channel| (channel.from_node, channel.to_node)).collect();
+    assert_eq!(connections, [(0, 1), (1, 2), (1, 3)]);
+
+    assert!(matches!(system.channels[0].channel_type, ChannelType::Straight));
+    assert!(matches!(system.channels[2].channel_type, ChannelType::SmoothStraight { .. }));
+    assert!((system.channels[0].width - options.channel_width).abs() < 1e-12);
+    assert!((system.channels[2].width - 0.2).abs() < 1e-12);
+}
+
+/// Test that arcs and bulged polyline segments become arc channels
+#[test]
+fn test_arcs_and_bulges() {
+    let text = drawing(
+        "",
+        &format!(
+            "{SQUARE} \
+             0 ARC 8 channels 10 50 20 50 40 20 50 0 51 90 \
+             0 LWPOLYLINE 8 channels 90 2 10 10 20 10 42 -1 10 30 20 10 \
+             0 ARC 8 channels 10 -80 20 50 40 10 50 0 51 90 210 0 220 0 230 -1"
+        ),
+    );
+    let system = dxf::import(&text, &ImportOptions::default()).unwrap().system;
+    assert!(system.channels.iter().all(|channel| matches!(channel.channel_type, ChannelType::Arc { .. })));
+
+    let arc = path(&system, 0);
+    assert_eq!(arc.len(), 17);
+    assert!(arc.iter().all(|point| ((point.0 - 50.0).hypot(point.1 - 50.0) - 20.0).abs() < 1e-9));
+    assert_close(arc[0], (70.0, 50.0));
+    assert_close(arc[16], (50.0, 70.0));
+
+    // A negative bulge turns clockwise, above the chord
+    let bulge = path(&system, 1);
+    assert!(bulge.iter().all(|point| ((point.0 - 20.0).hypot(point.1 - 10.0) - 10.0).abs() < 1e-9));
+    assert_close(bulge[8], (20.0, 20.0));
+    assert_eq!(bulge[16], (30.0, 10.0));
+
+    // Mirrored by its downward extrusion, the arc starts where the first one does
+    let mirrored = path(&system, 2);
+    assert_eq!(system.channels[2].from_node, system.channels[0].from_node);
+    assert_close(mirrored[16], (80.0, 60.0));
+}
+
+/// Test that splines are evaluated from control points, knots and weights
+#[test]
+fn test_splines() {
+    let text = drawing(
+        "",
+        &format!(
+            "{SQUARE} \
+             0 SPLINE 8 channels 71 2 72 6 73 3 40 0 40 0 40 0 40 1 40 1 40 1 \
+             41 1 41 0.7071067811865476 41 1 10 60 20 10 10 60 20 60 10 10 20 60 \
+             0 SPLINE 8 channels 71 1 40 0 40 0 40 1 40 2 40 2 10 20 20 20 10 40 20 30 10 60 20 20 \
+             0 SPLINE 8 channels 74 3 11 5 21 90 11 50 21 95 11 95 21 90 \
+             0 SPLINE 8 channels 71 1 40 0 40 0 40 1 40 2 40 2 10 -20 20 70 10 -40 20 80 10 -60 20 70 \
+             210 0 220 0 230 -1 \
+             0 SPLINE 8 channels 74 2 11 -5 21 40 11 -95 21 40 210 0 220 0 230 -1"
+        ),
+    );
+    let system = dxf::import(&text, &ImportOptions::default()).unwrap().system;
+
+    // A rational quadratic with these weights is an exact quarter circle
+    let quarter = path(&system, 0);
+    assert_eq!(quarter.len(), 17);
+    assert!(quarter.iter().all(|point| ((point.0 - 10.0).hypot(point.1 - 10.0) - 50.0).abs() < 1e-9));
+    assert_close(quarter[16], (10.0, 60.0));
+
+    // A linear spline follows its control polygon, 16 steps per span
+    let linear = path(&system, 1);
+    assert_eq!(linear.len(), 33);
+    assert_close(linear[8], (30.0, 25.0));
+    assert_close(linear[16], (40.0, 30.0));
+
+    assert_eq!(path(&system, 2), [(5.0, 90.0), (50.0, 95.0), (95.0, 90.0)]);
+
+    // Mirrored by their downward extrusion, control and fit points flip back into the box
+    let mirrored = path(&system, 3);
+    assert_eq!(mirrored.len(), 33);
+    assert_close(mirrored[0], (20.0, 70.0));
+    assert_close(mirrored[16], (40.0, 80.0));
+    assert_close(mirrored[32], (60.0, 70.0));
+    assert_eq!(path(&system, 4), [(5.0, 40.0), (95.0, 40.0)]);
+}
+
+/// Test that unsupported entities on imported layers are reported
+#[test]
+fn test_unsupported_entities_reported() {
+    let text = drawing(
+        "",
+        &format!(
+            "{SQUARE} \
+             0 HATCH 8 outline \
+             0 LINE 8 channels 10 0 20 50 11 100 21 50 \
+             0 CIRCLE 8 channels 10 50 20 50 40 5 \
+             0 POLYLINE 8 channels 66 1 0 VERTEX 8 channels 10 0 20 0 0 VERTEX 8 channels 10 5 20 5 0 SEQEND \
+             0 TEXT 8 0 10 5 20 5 1 inlet \
+             0 INSERT 8 0 2 PORT 10 0 20 50"
+        ),
+    );
+    let all = dxf::import(&text, &ImportOptions::default()).unwrap();
+    assert_eq!(all.system.channels.len(), 1);
+    let skipped: Vec<(&str, usize)> = all.skipped.iter().map(|(kind, count)| (kind.as_str(), *count)).collect();
+    assert_eq!(skipped, [("CIRCLE", 1), ("HATCH", 1), ("INSERT", 1), ("POLYLINE", 1), ("TEXT", 1)]);
+
+    let options = ImportOptions { channel_layers: vec!["channels".to_string()], ..ImportOptions::default() };
+    let channels_only = dxf::import(&text, &options).unwrap();
+    assert_eq!(channels_only.skipped.keys().collect::<Vec<_>>(), ["CIRCLE", "HATCH", "POLYLINE"]);
+}
+
+/// Test that unusable drawings are reported with the reason
+#[test]
+fn test_import_errors() {
+    let options = ImportOptions::default();
+    let message = |text: &str| dxf::import(text, &options).unwrap_err().to_string();
+
+    assert!(message("AutoCAD Binary DXF\r\n\u{1a}\0").contains("binary DXF is not supported"));
+    assert!(message("0\nSECTION\nx\nENTITIES\n").contains("line 3: expected a group code, found 'x'"));
+    assert!(message("0\nSECTION\n2").contains("line 3: group code 2 has no value"));
+
+    let missing = drawing("", &format!("{SQUARE} 0 LINE 8 channels 10 0 20 0 11 5"));
+    assert!(message(&missing).contains("LINE on line 59: missing group code 21"));
+    let invalid = drawing("", &format!("{SQUARE} 0 ARC 8 channels 10 abc 20 0 40 5"));
+    assert!(message(&invalid).contains("invalid value 'abc' for group code 10"));
+    let spline = drawing("", &format!("{SQUARE} 0 SPLINE 8 channels 71 3 10 0 20 0 10 5 20 5"));
+    assert!(message(&spline).contains("2 control points and 0 knots"));
+
+    let no_outline = drawing("", "0 LINE 8 channels 10 0 20 0 11 5 21 0");
+    assert!(matches!(dxf::import(&no_outline, &options), Err(ImportError::MissingOutline { .. })));
+    assert!(matches!(dxf::import(&drawing("", SQUARE), &options), Err(ImportError::NoChannels)));
+    assert!(matches!(dxf::import_file("missing.dxf", &options), Err(ImportError::FileError { .. })));
+}
@@ -0,0 +1,112 @@
+//! Format Version Tests
+//!
+//! Tests for versioned channel system JSON: the version written on save,
+//! migration of unversioned documents, versioned load errors and the
+//! published JSON Schema.
+
+use scheme::{
+    config::{ChannelTypeConfig, FrustumConfig, GeometryConfig},
+    error::GeometryError,
+    geometry::{
+        format::{document_version, json_schema, migrate, SYSTEM_FORMAT_VERSION},
+        generator::create_geometry,
+        ChannelSystem, SplitType,
+    },
+};
+use serde_json::{json, Value};
+
+/// Straight bifurcation as a JSON value
+fn document() -> Value {
+    let system = create_geometry((200.0, 100.0), &[SplitType::Bifurcation], &GeometryConfig::default(), &ChannelTypeConfig::AllStraight);
+    serde_json::from_str(&system.to_json().unwrap()).unwrap()
+}
+
+/// Test that saved systems record the current version and load back
+#[test]
+fn test_saved_version_round_trip() {
+    let document = document();
+    assert_eq!(document["format_version"], SYSTEM_FORMAT_VERSION);
+    assert_eq!(document_version(&document).unwrap(), SYSTEM_FORMAT_VERSION);
+
+    let system = ChannelSystem::from_json(&document.to_string()).unwrap();
+    assert_eq!(system.channels.len(), document["channels"].as_array().unwrap().len());
+    assert_eq!(serde_json::from_str::<Value>(&system.to_json().unwrap()).unwrap(), document);
+}
+
+/// Test that unversioned documents are migrated from version 1
+#[test]
+fn test_unversioned_documents_migrate() {
+    let mut legacy = document();
+    legacy.as_object_mut().unwrap().remove("format_version");
+    legacy["channels"][0]["metadata"] = json!("ignored by version 1");
+    assert_eq!(document_version(&legacy).unwrap(), 1);
+
+    let migrated = migrate(legacy.clone()).unwrap();
+    assert_eq!(migrated["format_version"], SYSTEM_FORMAT_VERSION);
+    assert!(migrated["channels"][0].get("metadata").is_none());
+
+    let system = ChannelSystem::from_json(&legacy.to_string()).unwrap();
+    assert!(system.channels[0].metadata.is_none());
+}
+
+/// Test that newer and malformed documents fail with versioned errors
+#[test]
+fn test_versioned_load_errors() {
+    let mut future = document();
+    future["format_version"] = json!(SYSTEM_FORMAT_VERSION + 1);
+    let error = ChannelSystem::from_json(&future.to_string()).unwrap_err();
+    assert!(matches!(
+        error,
+        GeometryError::UnsupportedFormatVersion { found, supported }
+            if found == SYSTEM_FORMAT_VERSION + 1 && supported == SYSTEM_FORMAT_VERSION
+    ));
+
+    let mut missing_width = document();
+    missing_width["channels"][0].as_object_mut().unwrap().remove("width");
+    let message = ChannelSystem::from_json(&missing_width.to_string()).unwrap_err().to_string();
+    assert!(message.contains(&format!("format version {SYSTEM_FORMAT_VERSION}")), "{message}");
+    assert!(message.contains("width"), "{message}");
+
+    let mut bad_version = document();
+    bad_version["format_version"] = json!("two");
+    for json in [bad_version.to_string(), "[1, 2]".to_string(), "{ not json".to_string()] {
+        assert!(matches!(ChannelSystem::from_json(&json), Err(GeometryError::InvalidDocument { .. })));
+    }
+}
+
+/// Test that the published schema matches the format written on save
+#[test]
+fn test_json_schema() {
+    let schema = json_schema();
+    let published: Value =
+        serde_json::from_str(&std::fs::read_to_string(concat!(env!("CARGO_MANIFEST_DIR"), "/schemas/channel_system.schema.json")).unwrap())
+            .unwrap();
+    assert_eq!(published, schema);
+    assert_eq!(schema["properties"]["format_version"]["const"], SYSTEM_FORMAT_VERSION);
+
+    let frustum = create_geometry(
+        (200.0, 100.0),
+        &[SplitType::Trifurcation],
+        &GeometryConfig::default(),
+        &ChannelTypeConfig::AllFrustum(FrustumConfig::default()),
+    );
+    let documents = [document(), serde_json::from_str(&frustum.to_json().unwrap()).unwrap()];
+    let variants: Vec<&Value> = schema["$defs"]["channel_type"]["oneOf"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|variant| variant.get("const").unwrap_or_else(|| &variant["required"][0]))
+        .collect();
+    for document in &documents {
+        for field in schema["required"].as_array().unwrap() {
+            assert!(document.get(field.as_str().unwrap()).is_some(), "missing {field}");
+        }
+        for channel in document["channels"].as_array().unwrap() {
+            let variant = match &channel["channel_type"] {
+                Value::Object(object) => json!(object.keys().next().unwrap()),
+                other => other.clone(),
+            };
+            assert!(variants.contains(&&variant), "{variant} not in schema");
+        }
+    }
+}
@@ -0,0 +1,154 @@
+//! Hierarchy Tests
+//!
+//! Tests for cell libraries: flattening of transformed and nested instances,
+//! parameter overrides, rejected hierarchies and hierarchy-preserving JSON.
+
+use scheme::{
+    config::{ChannelTypeConfig, GeometryConfig},
+    error::GeometryError,
+    geometry::{
+        generator::create_geometry,
+        hierarchy::{Cell, CellLibrary, Instance},
+        transform::Transform,
+        ChannelSystem, SplitType,
+    },
+};
+use std::f64::consts::FRAC_PI_2;
+
+/// Straight bifurcation used as a reusable cell
+fn splitter() -> ChannelSystem {
+    create_geometry((100.0, 50.0), &[SplitType::Bifurcation], &GeometryConfig::default(), &ChannelTypeConfig::AllStraight)
+}
+
+/// Library with a row of two splitters placed twice on a chip
+fn nested_library() -> CellLibrary {
+    CellLibrary::new("chip")
+        .with_cell(Cell::new("splitter", splitter()))
+        .with_cell(
+            Cell::empty("row", (220.0, 50.0))
+                .with_instance(Instance::new("splitter", Transform::identity()))
+                .with_instance(Instance::new("splitter", Transform::translation(120.0, 0.0))),
+        )
+        .with_cell(
+            Cell::empty("chip", (220.0, 120.0))
+                .with_instance(Instance::new("row", Transform::identity()))
+                .with_instance(Instance::new("row", Transform::translation(0.0, 70.0))),
+        )
+}
+
+/// Test that instances are transformed into the containing cell and renumbered
+#[test]
+fn test_flatten_transformed_instances() {
+    let cell = splitter();
+    let placement = Transform::rotation(FRAC_PI_2, (0.0, 0.0)).then(&Transform::translation(200.0, 0.0));
+    let library = CellLibrary::new("chip").with_cell(Cell::new("splitter", cell.clone())).with_cell(
+        Cell::empty("chip", (200.0, 200.0))
+            .with_instance(Instance::new("splitter", Transform::translation(0.0, 100.0)))
+            .with_instance(Instance::new("splitter", placement)),
+    );
+
+    let chip = library.flatten().unwrap();
+    assert_eq!(chip.box_dims, (200.0, 200.0));
+    assert_eq!(chip.box_outline.len(), 4);
+    assert_eq!(chip.nodes.len(), 2 * cell.nodes.len());
+    assert_eq!(chip.channels.len(), 2 * cell.channels.len());
+    for (index, channel) in chip.channels.iter().enumerate() {
+        assert_eq!(channel.id, index);
+    }
+
+    let offset = cell.nodes.len();
+    for (index, node) in cell.nodes.iter().enumerate() {
+        assert_eq!(chip.nodes[index].point, (node.point.0, node.point.1 + 100.0));
+        let expected = placement.apply(node.point);
+        let placed = chip.nodes[offset + index].point;
+        assert!((placed.0 - expected.0).abs() < 1e-9 && (placed.1 - expected.1).abs() < 1e-9);
+    }
+}
+
+/// Test that nested instances compose their transforms
+#[test]
+fn test_flatten_nested_instances() {
+    let library = nested_library();
+    let cell = splitter();
+    let chip = library.flatten().unwrap();
+    assert_eq!(chip.channels.len(), 4 * cell.channels.len());
+
+    let offsets = [(0.0, 0.0), (120.0, 0.0), (0.0, 70.0), (120.0, 70.0)];
+    for (copy, offset) in offsets.iter().enumerate() {
+        for (index, node) in cell.nodes.iter().enumerate() {
+            let placed = chip.nodes[copy * cell.nodes.len() + index].point;
+            assert_eq!(placed, (node.point.0 + offset.0, node.point.1 + offset.1));
+        }
+    }
+
+    let row = library.flatten_cell("row").unwrap();
+    assert_eq!(row.box_dims, (220.0, 50.0));
+    assert_eq!(row.channels.len(), 2 * cell.channels.len());
+}
+
+/// Test that overrides apply before the transform and outer overrides win
+#[test]
+fn test_instance_overrides() {
+    let library = CellLibrary::new("chip")
+        .with_cell(Cell::new("splitter", splitter()))
+        .with_cell(
+            Cell::empty("pair", (100.0, 120.0))
+                .with_instance(Instance::new("splitter", Transform::identity()).with_channel_width(2.0))
+                .with_instance(Instance::new("splitter", Transform::translation(0.0, 70.0)).with_channel_height(0.2)),
+        )
+        .with_cell(
+            Cell::empty("chip", (200.0, 240.0))
+                .with_instance(Instance::new("pair", Transform::identity()))
+                .with_instance(Instance::new("pair", Transform::scaling(2.0, (0.0, 0.0))).with_channel_width(0.5)),
+        );
+
+    let chip = library.flatten().unwrap();
+    let defaults = GeometryConfig::default();
+    let quarter = chip.channels.len() / 4;
+    let expected = [(2.0, defaults.channel_height), (defaults.channel_width, 0.2), (1.0, defaults.channel_height), (1.0, 0.2)];
+    for (copy, (width, height)) in expected.into_iter().enumerate() {
+        for channel in &chip.channels[copy * quarter..(copy + 1) * quarter] {
+            assert!((channel.width - width).abs() < 1e-9);
+            assert!((channel.height - height).abs() < 1e-9);
+        }
+    }
+}
+
+/// Test that unknown cells, cycles and duplicate names are rejected
+#[test]
+fn test_invalid_hierarchies_rejected() {
+    let missing_top = CellLibrary::new("chip").with_cell(Cell::new("splitter", splitter()));
+    let unknown = CellLibrary::new("chip")
+        .with_cell(Cell::empty("chip", (100.0, 100.0)).with_instance(Instance::new("mixer", Transform::identity())));
+    let cycle = CellLibrary::new("chip")
+        .with_cell(Cell::empty("chip", (100.0, 100.0)).with_instance(Instance::new("a", Transform::identity())))
+        .with_cell(Cell::empty("a", (10.0, 10.0)).with_instance(Instance::new("b", Transform::identity())))
+        .with_cell(Cell::empty("b", (10.0, 10.0)).with_instance(Instance::new("a", Transform::identity())));
+    let duplicate = nested_library().with_cell(Cell::new("splitter", splitter()));
+
+    for library in [missing_top, unknown, cycle, duplicate] {
+        assert!(matches!(library.validate(), Err(GeometryError::InvalidHierarchy { .. })));
+        assert!(matches!(library.flatten(), Err(GeometryError::InvalidHierarchy { .. })));
+    }
+    assert!(nested_library().validate().is_ok());
+}
+
+/// Test that JSON export keeps cells and instances instead of flattening them
+#[test]
+fn test_json_round_trip_preserves_hierarchy() {
+    let library = nested_library();
+    let json = library.to_json().unwrap();
+    let restored = CellLibrary::from_json(&json).unwrap();
+
+    assert_eq!(restored.top, "chip");
+    assert_eq!(restored.cells.len(), 3);
+    assert_eq!(restored.cell("chip").unwrap().instances, library.cell("chip").unwrap().instances);
+    assert!(restored.cell("chip").unwrap().system.channels.is_empty());
+
+    let original = library.flatten().unwrap();
+    let flattened = restored.flatten().unwrap();
+    assert_eq!(flattened.nodes.len(), original.nodes.len());
+    for (a, b) in flattened.nodes.iter().zip(&original.nodes) {
+        assert_eq!(a.point, b.point);
+    }
+}
@@ -0,0 +1,172 @@
+//! Metadata Serialization Tests
+//!
+//! Tests for saving node and channel metadata with channel systems: built-in
+//! types, registered user-defined types, opaque entries of unknown types and
+//! compatibility with JSON written without metadata.
+
+use scheme::{
+    config::{ChannelTypeConfig, GeometryConfig},
+    geometry::{
+        builders::{ChannelExt, NodeExt},
+        generator::create_geometry,
+        metadata::{
+            register_metadata, CurvatureMetadata, DeanMetadata, FlowMetadata, ManufacturingMetadata, Metadata,
+            MetadataContainer, MetadataRegistry, OptimizationMetadata, ParametricPathMetadata, PerformanceMetadata,
+            ThermalMetadata,
+        },
+        parametric::ParametricPath,
+        path_analysis::CurvatureHistogram,
+        ChannelSystem, SplitType,
+    },
+    impl_metadata,
+};
+use serde::{Deserialize, Serialize};
+use std::any::Any;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct BatchMetadata {
+    batch: String,
+    wafer: u32,
+}
+
+impl_metadata!(BatchMetadata, "BatchMetadata");
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct CoatingMetadata {
+    material: String,
+}
+
+impl_metadata!(CoatingMetadata, "CoatingMetadata");
+
+/// Straight bifurcation without metadata
+fn system() -> ChannelSystem {
+    create_geometry((200.0, 100.0), &[SplitType::Bifurcation], &GeometryConfig::default(), &ChannelTypeConfig::AllStraight)
+}
+
+/// Serialize and load a system again
+fn round_trip(system: &ChannelSystem) -> ChannelSystem {
+    ChannelSystem::from_json(&system.to_json().unwrap()).unwrap()
+}
+
+/// Test that every built-in metadata type survives a JSON round trip
+#[test]
+fn test_builtin_metadata_round_trip() {
+    let flow = FlowMetadata { flow_rate: 10.0, pressure_drop: 1000.0, reynolds_number: 0.1, velocity: 0.001 };
+    let thermal = ThermalMetadata { temperature: 37.0, heat_transfer_coefficient: 100.0, thermal_conductivity: 0.6 };
+    let manufacturing = ManufacturingMetadata {
+        width_tolerance: 2.0,
+        height_tolerance: 1.0,
+        surface_roughness: 0.1,
+        manufacturing_method: "soft lithography".to_string(),
+    };
+    let optimization = OptimizationMetadata {
+        original_length: 100.0,
+        optimized_length: 120.0,
+        improvement_percentage: 20.0,
+        iterations: 42,
+        optimization_time_ms: 15,
+        optimization_profile: "Balanced".to_string(),
+    };
+    let performance = PerformanceMetadata { generation_time_us: 250, memory_usage_bytes: 4096, path_points_count: 2 };
+    let curvature = CurvatureMetadata {
+        min_radius: f64::INFINITY,
+        max_curvature: 0.0,
+        mean_curvature: 0.0,
+        total_turning_angle: 0.0,
+        turn_count: 0,
+        histogram: CurvatureHistogram { bin_edges: vec![0.0, 0.5], counts: vec![2], lengths: vec![50.0] },
+    };
+    let dean = DeanMetadata {
+        flow_rate: 10.0,
+        reynolds_number: 0.1,
+        max_dean_number: 0.0,
+        mean_dean_number: 0.0,
+        profile: vec![(0.0, 0.0), (50.0, 0.0)],
+    };
+    let path = ParametricPathMetadata { path: ParametricPath::Line { start: (0.0, 50.0), end: (50.0, 50.0) } };
+
+    let mut system = system();
+    system.nodes[0].add_metadata(thermal.clone());
+    let channel = &mut system.channels[0];
+    channel.add_metadata(flow.clone());
+    channel.add_metadata(manufacturing.clone());
+    channel.add_metadata(optimization.clone());
+    channel.add_metadata(performance.clone());
+    channel.add_metadata(curvature.clone());
+    channel.add_metadata(dean.clone());
+    channel.add_metadata(path.clone());
+
+    let loaded = round_trip(&system);
+    assert_eq!(loaded.nodes[0].get_metadata::<ThermalMetadata>(), Some(&thermal));
+    assert!(loaded.nodes[1].metadata.is_none());
+    let channel = &loaded.channels[0];
+    assert_eq!(channel.get_metadata::<FlowMetadata>(), Some(&flow));
+    assert_eq!(channel.get_metadata::<ManufacturingMetadata>(), Some(&manufacturing));
+    assert_eq!(channel.get_metadata::<OptimizationMetadata>(), Some(&optimization));
+    assert_eq!(channel.get_metadata::<PerformanceMetadata>(), Some(&performance));
+    assert_eq!(channel.get_metadata::<CurvatureMetadata>(), Some(&curvature));
+    assert_eq!(channel.get_metadata::<DeanMetadata>(), Some(&dean));
+    assert_eq!(channel.get_metadata::<ParametricPathMetadata>(), Some(&path));
+}
+
+/// Test that registered user-defined metadata is saved and restored
+#[test]
+fn test_registered_user_metadata_round_trip() {
+    register_metadata::<BatchMetadata>("BatchMetadata");
+    let batch = BatchMetadata { batch: "B-17".to_string(), wafer: 3 };
+
+    let mut system = system();
+    system.channels[1].add_metadata(batch.clone());
+    let json = system.to_json().unwrap();
+    assert!(json.contains("\"BatchMetadata\""));
+
+    let loaded = ChannelSystem::from_json(&json).unwrap();
+    assert_eq!(loaded.channels[1].get_metadata::<BatchMetadata>(), Some(&batch));
+}
+
+/// Test that metadata of unknown types is kept as opaque JSON
+#[test]
+fn test_unknown_metadata_kept_opaque() {
+    let mut value: serde_json::Value = serde_json::from_str(&system().to_json().unwrap()).unwrap();
+    value["channels"][0]["metadata"] = serde_json::json!({
+        "CoatingMetadata": { "material": "parylene" },
+        "FlowMetadata": { "flow_rate": 5.0, "pressure_drop": 10.0, "reynolds_number": 0.01, "velocity": 0.002 },
+    });
+
+    let loaded = ChannelSystem::from_json(&value.to_string()).unwrap();
+    let container = loaded.channels[0].metadata.as_ref().unwrap();
+    assert_eq!(container.len(), 2);
+    assert!(container.contains::<FlowMetadata>());
+    assert_eq!(container.opaque_types(), ["CoatingMetadata"]);
+    assert_eq!(container.get_opaque("CoatingMetadata").unwrap()["material"], "parylene");
+
+    let reloaded = round_trip(&loaded);
+    let container = reloaded.channels[0].metadata.as_ref().unwrap();
+    assert_eq!(container.get_opaque("CoatingMetadata").unwrap()["material"], "parylene");
+
+    let mut registry = MetadataRegistry::with_builtins();
+    registry.register::<CoatingMetadata>("CoatingMetadata");
+    let mut container = container.clone();
+    registry.resolve(&mut container).unwrap();
+    assert!(container.opaque_types().is_empty());
+    assert_eq!(container.get::<CoatingMetadata>(), Some(&CoatingMetadata { material: "parylene".to_string() }));
+}
+
+/// Test that systems without metadata keep their JSON and malformed entries are rejected
+#[test]
+fn test_compatibility_and_malformed_metadata() {
+    let json = system().to_json().unwrap();
+    assert!(!json.contains("metadata"));
+    assert!(ChannelSystem::from_json(&json).unwrap().channels.iter().all(|channel| channel.metadata.is_none()));
+
+    let mut value: serde_json::Value = serde_json::from_str(&json).unwrap();
+    value["nodes"][0]["metadata"] = serde_json::json!({ "ThermalMetadata": { "temperature": "warm" } });
+    assert!(ChannelSystem::from_json(&value.to_string()).is_err());
+    value["nodes"][0]["metadata"] = serde_json::json!([1, 2, 3]);
+    assert!(ChannelSystem::from_json(&value.to_string()).is_err());
+
+    let registry = MetadataRegistry::new();
+    let mut container = MetadataContainer::new();
+    container.insert(ThermalMetadata { temperature: 20.0, heat_transfer_coefficient: 1.0, thermal_conductivity: 1.0 });
+    assert_eq!(registry.to_value(&container).unwrap(), serde_json::json!({}));
+}
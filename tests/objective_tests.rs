@@ -0,0 +1,166 @@
+//! Objective Function Tests
+//!
+//! Tests for user-defined serpentine optimization objectives built from
+//! weighted terms and closures.
+
+use scheme::{
+    config::{ChannelTypeConfig, GeometryConfig, OptimizationProfile, SerpentineConfig},
+    geometry::{
+        generator::create_geometry,
+        objectives::{ObjectiveSpec, ObjectiveTerm, PathMetrics, SerpentineObjective, WeightedTerm},
+        optimization::{
+            measure_serpentine_candidate, optimize_serpentine_parameters_with,
+            optimize_serpentine_with_objective, optimizer_for_profile,
+        },
+        optimizers::OptimizationBudget,
+        strategies::ChannelGenerationContext,
+        ChannelType,
+    },
+};
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+const BOX_DIMS: (f64, f64) = (200.0, 100.0);
+const P1: (f64, f64) = (0.0, 50.0);
+const P2: (f64, f64) = (200.0, 50.0);
+
+/// Optimize with `spec` and measure the resulting path
+fn optimize_and_measure(spec: ObjectiveSpec) -> PathMetrics {
+    let geometry_config = GeometryConfig::default();
+    let context = ChannelGenerationContext::new(&geometry_config, BOX_DIMS, 1, None);
+    let serpentine_config = SerpentineConfig::default().with_objective(spec);
+    let optimizer = optimizer_for_profile(OptimizationProfile::Balanced);
+
+    let result = optimize_serpentine_parameters_with(
+        optimizer.as_ref(), P1, P2, &serpentine_config, &context, &OptimizationBudget::default(),
+    );
+    let params = [
+        result.params.wavelength_factor,
+        result.params.wave_density_factor,
+        result.params.fill_factor,
+    ];
+    measure_serpentine_candidate(params, P1, P2, &serpentine_config, &context).0
+}
+
+/// Test that the sign of a turn-count term steers the number of turns
+#[test]
+fn test_turn_count_objective() {
+    const MORE_TURNS: ObjectiveSpec = ObjectiveSpec::new(&[
+        WeightedTerm::new(ObjectiveTerm::TurnCount, 1.0),
+        WeightedTerm::new(ObjectiveTerm::ClearanceViolation, -1000.0),
+    ]);
+    const FEWER_TURNS: ObjectiveSpec = ObjectiveSpec::new(&[
+        WeightedTerm::new(ObjectiveTerm::TurnCount, -1.0),
+        WeightedTerm::new(ObjectiveTerm::ClearanceViolation, -1000.0),
+    ]);
+
+    let more = optimize_and_measure(MORE_TURNS);
+    let fewer = optimize_and_measure(FEWER_TURNS);
+
+    assert!(
+        more.turn_count > fewer.turn_count,
+        "Expected more turns ({}) than the minimizing objective ({})",
+        more.turn_count,
+        fewer.turn_count
+    );
+}
+
+/// Test that minimizing footprint yields a more compact channel than maximizing length
+#[test]
+fn test_footprint_objective() {
+    const COMPACT: ObjectiveSpec = ObjectiveSpec::new(&[
+        WeightedTerm::new(ObjectiveTerm::Footprint, -1.0),
+        WeightedTerm::new(ObjectiveTerm::ClearanceViolation, -1000.0),
+    ]);
+
+    let compact = optimize_and_measure(COMPACT);
+    let longest = optimize_and_measure(ObjectiveSpec::maximize_length());
+
+    assert!(compact.footprint_area < longest.footprint_area);
+    assert!(longest.path_length > compact.path_length);
+    assert!(longest.clearance_violation <= 0.0);
+}
+
+/// Test that capturing closures are evaluated during optimization
+#[test]
+fn test_closure_objective() {
+    let geometry_config = GeometryConfig::default();
+    let context = ChannelGenerationContext::new(&geometry_config, BOX_DIMS, 1, None);
+    let optimizer = optimizer_for_profile(OptimizationProfile::Balanced);
+
+    // Prefer paths close to 600 mm, counting how often the closure runs
+    let target_length = 600.0;
+    let calls = Arc::new(AtomicUsize::new(0));
+    let counter = Arc::clone(&calls);
+    let objective = SerpentineObjective::new()
+        .with_term(ObjectiveTerm::ClearanceViolation, -1000.0)
+        .with_closure(1.0, move |metrics, path| {
+            counter.fetch_add(1, Ordering::Relaxed);
+            assert!(!path.is_empty());
+            -(metrics.path_length - target_length).abs()
+        });
+
+    let result = optimize_serpentine_with_objective(
+        optimizer.as_ref(),
+        P1,
+        P2,
+        &SerpentineConfig::default(),
+        &context,
+        &objective,
+        &OptimizationBudget::default(),
+    );
+
+    assert!(calls.load(Ordering::Relaxed) > 0);
+    assert!(
+        (result.path_length - target_length).abs() < 0.1 * target_length,
+        "Expected length near {target_length}, got {}",
+        result.path_length
+    );
+}
+
+/// Test that invalid objectives are rejected by configuration validation
+#[test]
+fn test_invalid_objective_rejected() {
+    const EMPTY: ObjectiveSpec = ObjectiveSpec::new(&[]);
+    const BAD_RADIUS: ObjectiveSpec = ObjectiveSpec::new(&[
+        WeightedTerm::new(ObjectiveTerm::MinRadiusShortfall { min_radius: 0.0 }, -1.0),
+    ]);
+
+    assert!(SerpentineConfig::default().with_objective(EMPTY).validate().is_err());
+    assert!(SerpentineConfig::default().with_objective(BAD_RADIUS).validate().is_err());
+    assert!(SerpentineConfig::default().with_objective(ObjectiveSpec::maximize_length()).validate().is_ok());
+}
+
+/// Test that objectives stored in the configuration drive geometry generation
+#[test]
+fn test_objective_in_geometry_generation() {
+    const FEWER_TURNS: ObjectiveSpec = ObjectiveSpec::new(&[
+        WeightedTerm::new(ObjectiveTerm::TurnCount, -1.0),
+        WeightedTerm::new(ObjectiveTerm::ClearanceViolation, -1000.0),
+    ]);
+    let geometry_config = GeometryConfig::default();
+    let generate = |serpentine_config: SerpentineConfig| {
+        let system = create_geometry(
+            BOX_DIMS,
+            &[],
+            &geometry_config,
+            &ChannelTypeConfig::AllSerpentine(serpentine_config),
+        );
+        let ChannelType::Serpentine { path } = &system.channels[0].channel_type else {
+            panic!("Expected a serpentine channel");
+        };
+        PathMetrics::from_path(path, &geometry_config, BOX_DIMS, None)
+    };
+
+    let default_metrics = generate(SerpentineConfig::default());
+    let objective_metrics = generate(SerpentineConfig::default().with_objective(FEWER_TURNS));
+
+    assert!(
+        objective_metrics.turn_count < default_metrics.turn_count,
+        "Expected fewer turns ({}) than the default configuration ({})",
+        objective_metrics.turn_count,
+        default_metrics.turn_count
+    );
+}
@@ -278,3 +278,114 @@ fn test_optimization_multi_channel() {
         }
     }
 }
+
+/// Test that the global optimizer profiles generate valid serpentine geometry
+#[test]
+fn test_global_optimizer_profiles() {
+    let config = GeometryConfig::default();
+
+    for profile in [OptimizationProfile::SimulatedAnnealing, OptimizationProfile::ParticleSwarm] {
+        let serpentine_config = SerpentineConfig {
+            optimization_enabled: true,
+            optimization_profile: profile,
+            ..SerpentineConfig::default()
+        };
+        assert!(serpentine_config.validate().is_ok());
+
+        let system = create_geometry(
+            (200.0, 100.0),
+            &[],
+            &config,
+            &ChannelTypeConfig::AllSerpentine(serpentine_config),
+        );
+
+        for channel in &system.channels {
+            if let scheme::geometry::ChannelType::Serpentine { path } = &channel.channel_type {
+                assert!(calculate_path_length(path) > 0.0, "{profile:?} produced an empty path");
+            }
+        }
+    }
+}
+
+/// Test that each profile maps to its optimizer and that results stay within bounds
+#[test]
+fn test_profile_optimizers_respect_bounds() {
+    let config = GeometryConfig::default();
+    let bounds = serpentine_parameter_bounds();
+
+    for profile in [
+        OptimizationProfile::Fast,
+        OptimizationProfile::Balanced,
+        OptimizationProfile::Thorough,
+        OptimizationProfile::SimulatedAnnealing,
+        OptimizationProfile::ParticleSwarm,
+    ] {
+        let serpentine_config = SerpentineConfig {
+            wavelength_factor: 3.0,
+            optimization_profile: profile,
+            ..SerpentineConfig::default()
+        };
+        let result = optimize_serpentine_parameters((20.0, 50.0), (180.0, 50.0), &config, &serpentine_config, (200.0, 100.0), None);
+
+        let params = [result.params.wavelength_factor, result.params.wave_density_factor, result.params.fill_factor];
+        for (index, value) in params.iter().enumerate() {
+            assert!(
+                (bounds.lower[index]..=bounds.upper[index]).contains(value),
+                "{profile:?} returned out-of-bounds parameter {index}: {value}"
+            );
+        }
+        assert!(result.path_length > 0.0);
+    }
+
+    assert_eq!(optimizer_for_profile(OptimizationProfile::Fast).name(), "grid_search");
+    assert_eq!(optimizer_for_profile(OptimizationProfile::Balanced).name(), "nelder_mead");
+    assert_eq!(optimizer_for_profile(OptimizationProfile::ParticleSwarm).name(), "particle_swarm");
+}
+
+/// Test plugging a user-defined optimizer into serpentine optimization
+#[test]
+fn test_custom_optimizer_plugin() {
+    use scheme::geometry::optimizers::{OptimizationBudget, Optimizer, OptimizerOutcome, ParameterBounds};
+    use scheme::geometry::strategies::ChannelGenerationContext;
+
+    /// Evaluates only the upper corner of the bounds
+    #[derive(Debug)]
+    struct UpperCornerOptimizer;
+
+    impl Optimizer for UpperCornerOptimizer {
+        fn name(&self) -> &'static str {
+            "upper_corner"
+        }
+
+        fn optimize(
+            &self,
+            objective: &dyn Fn(&[f64]) -> f64,
+            bounds: &ParameterBounds,
+            _initial: &[f64],
+            _budget: &OptimizationBudget,
+        ) -> OptimizerOutcome {
+            OptimizerOutcome {
+                best_params: bounds.upper.clone(),
+                best_score: objective(&bounds.upper),
+                evaluations: 1,
+                iterations: 1,
+            }
+        }
+    }
+
+    let config = GeometryConfig::default();
+    let context = ChannelGenerationContext::new(&config, (200.0, 100.0), 1, None);
+    let result = optimize_serpentine_parameters_with(
+        &UpperCornerOptimizer,
+        (20.0, 50.0),
+        (180.0, 50.0),
+        &SerpentineConfig::default(),
+        &context,
+        &OptimizationBudget::default(),
+    );
+
+    let bounds = serpentine_parameter_bounds();
+    assert!((result.params.wavelength_factor - bounds.upper[0]).abs() < f64::EPSILON);
+    assert!((result.params.fill_factor - bounds.upper[2]).abs() < f64::EPSILON);
+    assert_eq!(result.iterations, 1);
+}
@@ -0,0 +1,189 @@
+//! Parametric Path Tests
+//!
+//! Tests for analytic channel paths: evaluators for position, tangent,
+//! curvature and arc length, on-demand discretization, and keeping the
+//! analytic paths of generated channels as metadata.
+
+use scheme::{
+    config::{
+        ArcConfig, ChannelTypeConfig, FrustumConfig, GeometryConfig, SerpentineConfig, WaveShape,
+    },
+    geometry::{
+        builders::ChannelExt,
+        generator::{create_geometry_with_metadata, MetadataConfig},
+        metadata::ParametricPathMetadata,
+        optimization::calculate_path_length,
+        parametric::{ParametricPath, WaveEnvelope},
+        strategies::{ChannelTypeStrategy, SerpentineChannelStrategy},
+        ChannelType, Point2D, SplitType,
+    },
+};
+
+const BOX_DIMS: (f64, f64) = (200.0, 100.0);
+const P1: Point2D = (0.0, 50.0);
+const P2: Point2D = (200.0, 50.0);
+
+/// Distance between two points
+fn distance(a: Point2D, b: Point2D) -> f64 {
+    (b.0 - a.0).hypot(b.1 - a.1)
+}
+
+/// Analytic and sampled path of a serpentine across the box
+fn serpentine(config: &SerpentineConfig) -> (ParametricPath, Vec<Point2D>) {
+    let geometry_config = GeometryConfig::default();
+    let (ChannelType::Serpentine { path }, Some(curve)) = SerpentineChannelStrategy::new(*config)
+        .create_parametric_channel(P1, P2, &geometry_config, BOX_DIMS, 1, None)
+    else {
+        panic!("Expected a serpentine channel with an analytic path");
+    };
+    (curve, path)
+}
+
+/// Test the evaluators of a line
+#[test]
+fn test_line_evaluators() {
+    let line = ParametricPath::Line { start: (1.0, 1.0), end: (4.0, 5.0) };
+    assert_eq!(line.position(0.0), (1.0, 1.0));
+    assert_eq!(line.position(1.0), (4.0, 5.0));
+    assert!(distance(line.position(0.5), (2.5, 3.0)) < 1e-12);
+    assert!(distance(line.tangent(0.3), (0.6, 0.8)) < 1e-12);
+    assert!(line.curvature(0.3).abs() < f64::EPSILON);
+    assert!((line.length() - 5.0).abs() < 1e-12);
+    assert!((line.arc_length(0.4) - 2.0).abs() < 1e-12);
+    assert_eq!(line.width_at(0.5), None);
+}
+
+/// Test the evaluators of a quadratic Bezier curve against closed forms
+#[test]
+fn test_bezier_evaluators() {
+    let arc = ParametricPath::Bezier { control_points: vec![(0.0, 0.0), (10.0, 10.0), (20.0, 0.0)] };
+    assert_eq!(arc.position(0.5), (10.0, 5.0));
+    assert!(distance(arc.derivative(0.5), (20.0, 0.0)) < 1e-12);
+    assert!(distance(arc.second_derivative(0.5), (0.0, -40.0)) < 1e-12);
+    assert!((arc.curvature(0.5) + 0.1).abs() < 1e-12);
+    assert!(distance(arc.normal(0.5), (0.0, 1.0)) < 1e-12);
+
+    // Closed-form length of the parabola y = x - x² / 20 over [0, 20]
+    let exact = 10.0f64.mul_add(2.0f64.sqrt(), 10.0 * 1.0f64.asinh());
+    assert!((arc.length() - exact).abs() < 1e-9, "{} vs {exact}", arc.length());
+
+    // Cubic curves use the general evaluator
+    let cubic = ParametricPath::Bezier { control_points: vec![(0.0, 0.0), (0.0, 10.0), (10.0, 10.0), (10.0, 0.0)] };
+    assert!(distance(cubic.position(0.5), (5.0, 7.5)) < 1e-12);
+    assert!(distance(cubic.tangent(0.5), (1.0, 0.0)) < 1e-12);
+}
+
+/// Test that wave derivatives and curvature agree with the wave position
+#[test]
+fn test_wave_derivatives_consistent() {
+    for shape in [WaveShape::Sine, WaveShape::Square] {
+        let (curve, _) = serpentine(&SerpentineConfig { wave_shape: shape, ..SerpentineConfig::default() });
+        let step = 1e-6;
+        for t in [0.2, 0.37, 0.5, 0.61] {
+            let before = curve.position(t - step);
+            let after = curve.position(t + step);
+            let numeric = ((after.0 - before.0) / (2.0 * step), (after.1 - before.1) / (2.0 * step));
+            let analytic = curve.derivative(t);
+            assert!(
+                distance(numeric, analytic) < 1e-4 * analytic.0.hypot(analytic.1),
+                "{shape:?} at {t}: {numeric:?} vs {analytic:?}"
+            );
+
+            // Curvature of the circle through three nearby points
+            let step = 1e-4;
+            let (a, b, c) = (curve.position(t - step), curve.position(t), curve.position(t + step));
+            let cross = (b.0 - a.0).mul_add(c.1 - a.1, -((b.1 - a.1) * (c.0 - a.0)));
+            let circle = 2.0 * cross / (distance(a, b) * distance(b, c) * distance(a, c));
+            assert!((curve.curvature(t) - circle).abs() < 1e-3 * circle.abs().max(1.0), "{shape:?} at {t}");
+        }
+    }
+}
+
+/// Test that strategies sample their analytic path and that it resamples on demand
+#[test]
+fn test_serpentine_path_is_discretized_curve() {
+    let (curve, path) = serpentine(&SerpentineConfig::default());
+    assert!(matches!(curve, ParametricPath::Wave { .. }));
+    assert_eq!(curve.discretize(path.len()), path);
+    assert_eq!((curve.start(), curve.end()), (P1, P2));
+}
+
+/// Test that the exact length bounds inscribed polylines and is their limit
+#[test]
+fn test_wave_length_is_polyline_limit() {
+    let curve = ParametricPath::Wave {
+        start: P1,
+        end: P2,
+        amplitude: 20.0,
+        half_periods: 6.0,
+        phase_offset: 0.0,
+        shape: WaveShape::Sine,
+        envelope: WaveEnvelope::Transition { transition_fraction: 0.25 },
+    };
+
+    let length = curve.length();
+    let coarse = calculate_path_length(&curve.discretize(100));
+    let fine = calculate_path_length(&curve.discretize(20_000));
+    assert!(coarse < fine && fine <= length + 1e-9);
+    assert!((length - fine) / length < 1e-6, "{length} vs {fine}");
+    assert!((curve.arc_length(0.5) - length / 2.0).abs() / length < 1e-6);
+}
+
+/// Test the width profile and wall offsets of a frustum path
+#[test]
+fn test_frustum_profile() {
+    let profile = FrustumConfig::default();
+    let frustum = ParametricPath::Frustum { start: (0.0, 0.0), end: (100.0, 0.0), profile };
+    assert_eq!(frustum.width_at(0.0), Some(profile.inlet_width));
+    assert_eq!(frustum.width_at(profile.throat_position), Some(profile.throat_width));
+    assert_eq!(frustum.width_at(1.0), Some(profile.outlet_width));
+
+    let wall = frustum.offset(0.25, frustum.width_at(0.25).unwrap() / 2.0);
+    assert!((wall.0 - 25.0).abs() < 1e-12);
+    assert!((wall.1 - profile.width_at_position(0.25) / 2.0).abs() < 1e-12);
+}
+
+/// Test that analytic paths survive a serialization round trip
+#[test]
+fn test_parametric_path_serialization() {
+    let (curve, _) = serpentine(&SerpentineConfig::default());
+    let json = serde_json::to_string(&curve).unwrap();
+    let restored: ParametricPath = serde_json::from_str(&json).unwrap();
+    assert_eq!(restored, curve);
+}
+
+/// Test that generation keeps analytic paths as metadata only when enabled
+#[test]
+fn test_generation_keeps_parametric_paths() {
+    let metadata_config = MetadataConfig { track_parametric_paths: true, ..MetadataConfig::default() };
+    let channel_type_configs = [
+        ChannelTypeConfig::AllStraight,
+        ChannelTypeConfig::AllSerpentine(SerpentineConfig::default()),
+        ChannelTypeConfig::AllArcs(ArcConfig::default()),
+        ChannelTypeConfig::AllFrustum(FrustumConfig::default()),
+    ];
+
+    for channel_type_config in &channel_type_configs {
+        let system = create_geometry_with_metadata(
+            BOX_DIMS,
+            &[SplitType::Bifurcation],
+            &GeometryConfig::default(),
+            channel_type_config,
+            &metadata_config,
+        );
+        for channel in &system.channels {
+            let curve = &channel.get_metadata::<ParametricPathMetadata>().expect("Analytic path should be kept").path;
+            assert_eq!(curve.start(), system.nodes[channel.from_node].point);
+            assert_eq!(curve.end(), system.nodes[channel.to_node].point);
+        }
+    }
+
+    let system = create_geometry_with_metadata(
+        BOX_DIMS,
+        &[SplitType::Bifurcation],
+        &GeometryConfig::default(),
+        &ChannelTypeConfig::AllSerpentine(SerpentineConfig::default()),
+        &MetadataConfig::default(),
+    );
+    assert!(system.channels.iter().all(|channel| !channel.has_metadata::<ParametricPathMetadata>()));
+}
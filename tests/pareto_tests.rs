@@ -0,0 +1,144 @@
+//! Pareto Optimization Tests
+//!
+//! Tests for multi-objective NSGA-II optimization of serpentine parameters,
+//! selection of designs from the resulting front, and front plotting.
+
+use scheme::{
+    config::{GeometryConfig, SerpentineConfig},
+    geometry::{
+        objectives::ObjectiveTerm,
+        pareto::{optimize_serpentine_pareto, Nsga2Config, ParetoFront, ParetoObjective},
+        strategies::ChannelGenerationContext,
+    },
+    visualizations::pareto::plot_pareto_front,
+};
+use std::{fs, path::Path};
+
+const BOX_DIMS: (f64, f64) = (200.0, 100.0);
+const P1: (f64, f64) = (0.0, 50.0);
+const P2: (f64, f64) = (200.0, 50.0);
+
+/// Channel length against footprint
+const OBJECTIVES: [ParetoObjective; 2] = [
+    ParetoObjective::maximize(ObjectiveTerm::PathLength),
+    ParetoObjective::minimize(ObjectiveTerm::Footprint),
+];
+
+fn small_config() -> Nsga2Config {
+    Nsga2Config { population_size: 12, generations: 6, ..Nsga2Config::default() }
+}
+
+fn compute_front(config: &Nsga2Config) -> ParetoFront {
+    let geometry_config = GeometryConfig::default();
+    let context = ChannelGenerationContext::new(&geometry_config, BOX_DIMS, 1, None);
+    optimize_serpentine_pareto(P1, P2, &SerpentineConfig::default(), &context, &OBJECTIVES, config)
+        .expect("valid Pareto configuration")
+}
+
+/// Test that the returned designs are feasible and mutually non-dominated
+#[test]
+fn test_front_is_non_dominated() {
+    let config = small_config();
+    let front = compute_front(&config);
+
+    assert!(front.len() >= 2, "Expected a trade-off, got {} designs", front.len());
+    assert_eq!(front.evaluations, config.evaluations());
+
+    for a in &front.solutions {
+        assert!(a.feasible);
+        for b in &front.solutions {
+            let dominates = a.values[0] >= b.values[0]
+                && a.values[1] <= b.values[1]
+                && (a.values[0] > b.values[0] || a.values[1] < b.values[1]);
+            assert!(!dominates, "Design {a:?} dominates {b:?}");
+        }
+    }
+
+    // Sorted by path length, so footprint must grow along the front
+    for pair in front.solutions.windows(2) {
+        assert!(pair[0].values[0] <= pair[1].values[0]);
+        assert!(pair[0].values[1] <= pair[1].values[1]);
+    }
+}
+
+/// Test that the same seed reproduces the same front
+#[test]
+fn test_front_is_reproducible() {
+    let first = compute_front(&small_config());
+    let second = compute_front(&small_config());
+
+    assert_eq!(first.len(), second.len());
+    for (a, b) in first.solutions.iter().zip(&second.solutions) {
+        assert!((a.values[0] - b.values[0]).abs() < 1e-9);
+        assert!((a.values[1] - b.values[1]).abs() < 1e-9);
+    }
+}
+
+/// Test the helpers that pick a design from the front
+#[test]
+fn test_design_selection() {
+    let front = compute_front(&small_config());
+
+    let longest = front.best_for(0).unwrap();
+    let smallest = front.best_for(1).unwrap();
+    assert!(front.solutions.iter().all(|solution| solution.values[0] <= longest.values[0]));
+    assert!(front.solutions.iter().all(|solution| solution.values[1] >= smallest.values[1]));
+
+    // Weighting one objective fully selects its extreme design
+    let by_length = front.select_weighted(&[1.0, 0.0]).unwrap();
+    assert!((by_length.values[0] - longest.values[0]).abs() < 1e-9);
+    assert!(front.select_weighted(&[1.0]).is_none());
+
+    // Longest design within a footprint budget
+    let budget = f64::midpoint(smallest.values[1], longest.values[1]);
+    let constrained = front.select_constrained(0, &[(1, budget)]).unwrap();
+    assert!(constrained.values[1] <= budget);
+    assert!(front.solutions.iter()
+        .filter(|solution| solution.values[1] <= budget)
+        .all(|solution| solution.values[0] <= constrained.values[0]));
+
+    let knee = front.select_knee().unwrap();
+    let chosen = knee.to_config(&SerpentineConfig::default());
+    assert!((chosen.fill_factor - knee.params.fill_factor).abs() < 1e-12);
+    assert!(!chosen.optimization_enabled);
+}
+
+/// Test that invalid settings are rejected
+#[test]
+fn test_invalid_pareto_configuration() {
+    let geometry_config = GeometryConfig::default();
+    let context = ChannelGenerationContext::new(&geometry_config, BOX_DIMS, 1, None);
+    let serpentine_config = SerpentineConfig::default();
+
+    let single = [ParetoObjective::maximize(ObjectiveTerm::PathLength)];
+    assert!(optimize_serpentine_pareto(P1, P2, &serpentine_config, &context, &single, &small_config()).is_err());
+
+    let tiny = Nsga2Config { population_size: 2, ..Nsga2Config::default() };
+    assert!(optimize_serpentine_pareto(P1, P2, &serpentine_config, &context, &OBJECTIVES, &tiny).is_err());
+
+    let invalid_term = [
+        ParetoObjective::maximize(ObjectiveTerm::PathLength),
+        ParetoObjective::minimize(ObjectiveTerm::HydraulicResistance { viscosity: -1.0 }),
+    ];
+    assert!(optimize_serpentine_pareto(P1, P2, &serpentine_config, &context, &invalid_term, &small_config()).is_err());
+}
+
+/// Test that the front can be rendered to PNG and SVG
+#[test]
+fn test_plot_pareto_front() {
+    let front = compute_front(&small_config());
+
+    let output_dir = "test_outputs_pareto";
+    fs::create_dir_all(output_dir).ok();
+
+    for extension in ["png", "svg"] {
+        let plot_path = format!("{output_dir}/pareto_front.{extension}");
+        let result = plot_pareto_front(&front, 0, 1, &plot_path);
+        assert!(result.is_ok(), "Plotting should succeed: {result:?}");
+        assert!(Path::new(&plot_path).exists(), "Plot file should be created");
+        fs::remove_file(&plot_path).ok();
+    }
+
+    assert!(plot_pareto_front(&front, 0, 5, &format!("{output_dir}/invalid.png")).is_err());
+    fs::remove_dir(output_dir).ok();
+}
@@ -0,0 +1,123 @@
+//! Path Analysis Tests
+//!
+//! Tests for local curvature, bend radius, turn counting and curvature
+//! histograms along channel paths, and for storing them as channel metadata.
+
+use scheme::{
+    config::{ChannelTypeConfig, GeometryConfig, SerpentineConfig},
+    geometry::{
+        builders::ChannelExt,
+        generator::create_geometry,
+        metadata::CurvatureMetadata,
+        path_analysis::{analyze_path_curvature, analyze_system_curvature, attach_curvature_metadata},
+        ChannelType, Point2D, SplitType,
+    },
+};
+use std::f64::consts::PI;
+
+/// Points on a circle of `radius` around the origin, counter-clockwise from `start` to `end` radians
+fn circle_arc(radius: f64, start: f64, end: f64, points: u32) -> Vec<Point2D> {
+    (0..=points)
+        .map(|step| {
+            let angle = (end - start).mul_add(f64::from(step) / f64::from(points), start);
+            (radius * angle.cos(), radius * angle.sin())
+        })
+        .collect()
+}
+
+/// Test that a sampled circular arc has the circle's radius everywhere
+#[test]
+fn test_circular_arc() {
+    let profile = analyze_path_curvature(&circle_arc(10.0, 0.0, PI, 90));
+
+    assert!((profile.min_radius - 10.0).abs() < 1e-9);
+    assert!((profile.max_curvature - 0.1).abs() < 1e-9);
+    assert!(profile.samples[1..90].iter().all(|sample| (sample.curvature - 0.1).abs() < 1e-9));
+    assert!((profile.length / PI - 10.0).abs() < 0.001);
+    // End points carry no curvature, so the mean is slightly below 1/r
+    assert!(profile.mean_curvature < 0.1 && profile.mean_curvature > 0.098);
+    assert!((profile.total_turning_angle - PI * 89.0 / 90.0).abs() < 1e-9);
+
+    assert_eq!(profile.turn_count(), 1);
+    assert!(profile.turns[0].angle > 0.0);
+    assert_eq!(profile.turns_tighter_than(10.5), 1);
+    assert_eq!(profile.turns_tighter_than(9.5), 0);
+
+    // Clockwise arcs have negative curvature
+    let mut clockwise = circle_arc(10.0, 0.0, PI, 90);
+    clockwise.reverse();
+    let profile = analyze_path_curvature(&clockwise);
+    assert!(profile.samples[45].curvature < 0.0);
+    assert!(profile.turns[0].angle < 0.0);
+}
+
+/// Test that straight and degenerate paths report no bends
+#[test]
+fn test_straight_path() {
+    let profile = analyze_path_curvature(&[(0.0, 0.0), (5.0, 5.0), (5.0, 5.0), (10.0, 10.0)]);
+    assert!(profile.min_radius.is_infinite());
+    assert_eq!(profile.min_radius_location, None);
+    assert!(profile.total_turning_angle.abs() < f64::EPSILON);
+    assert_eq!(profile.turn_count(), 0);
+
+    let histogram = profile.histogram(4);
+    assert_eq!(histogram.counts, [4, 0, 0, 0]);
+    assert!((histogram.lengths[0] - profile.length).abs() < 1e-9);
+
+    assert_eq!(analyze_path_curvature(&[]).samples.len(), 0);
+    assert_eq!(analyze_path_curvature(&[(1.0, 1.0)]).turn_count(), 0);
+}
+
+/// Test turn counting and histograms on a sine wave
+#[test]
+fn test_sine_wave_turns_and_histogram() {
+    // Four full periods: eight half-waves, alternating direction
+    let wave: Vec<Point2D> = (0..=400)
+        .map(|step| {
+            let x = f64::from(step) * 0.1;
+            (x, 2.0 * (x * PI / 5.0).sin())
+        })
+        .collect();
+    let profile = analyze_path_curvature(&wave);
+
+    // Tightest bend is at the crests: r = 1 / (A k²)
+    let expected_radius = 1.0 / (2.0 * (PI / 5.0).powi(2));
+    assert!((profile.min_radius - expected_radius).abs() < 0.01);
+    let location = profile.min_radius_location.unwrap();
+    assert!((location.1.abs() - 2.0).abs() < 1e-3);
+
+    assert_eq!(profile.turn_count(), 8);
+    assert!(profile.turns.windows(2).all(|pair| pair[0].angle.signum() != pair[1].angle.signum()));
+
+    let histogram = profile.histogram(8);
+    assert_eq!(histogram.bin_count(), 8);
+    assert_eq!(histogram.bin_edges.len(), 9);
+    assert!((histogram.bin_edges[8] - profile.max_curvature).abs() < 1e-12);
+    assert_eq!(histogram.counts.iter().sum::<usize>(), wave.len());
+    assert!((histogram.lengths.iter().sum::<f64>() - profile.length).abs() < 1e-9);
+}
+
+/// Test system-wide analysis and storage in channel metadata
+#[test]
+fn test_curvature_metadata() {
+    let mut system = create_geometry(
+        (200.0, 100.0),
+        &[SplitType::Bifurcation],
+        &GeometryConfig::default(),
+        &ChannelTypeConfig::AllSerpentine(SerpentineConfig::default()),
+    );
+    let profiles = analyze_system_curvature(&system);
+    assert_eq!(profiles.len(), system.channels.len());
+
+    attach_curvature_metadata(&mut system, 10);
+    for (channel, profile) in system.channels.iter().zip(&profiles) {
+        let metadata = channel.get_metadata::<CurvatureMetadata>().unwrap();
+        assert_eq!(metadata.histogram.bin_count(), 10);
+        assert_eq!(metadata.turn_count, profile.turn_count());
+        assert!((metadata.min_radius - profile.min_radius).abs() < f64::EPSILON || metadata.min_radius.is_infinite());
+        if matches!(channel.channel_type, ChannelType::Serpentine { .. }) {
+            assert!(metadata.min_radius.is_finite());
+            assert!(metadata.turn_count > 0);
+        }
+    }
+}
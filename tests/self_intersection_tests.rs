@@ -0,0 +1,152 @@
+//! Self-Intersection Tests
+//!
+//! Tests for detecting serpentine paths whose half-waves fold back onto each
+//! other, for correcting them during generation and for using the same check
+//! as an optimization constraint.
+
+use scheme::{
+    config::{GeometryConfig, OptimizationTarget, SerpentineConfig},
+    geometry::{
+        objectives::{ObjectiveSpec, PathMetrics},
+        optimization::{
+            calculate_min_self_spacing, optimize_serpentine_for_target, optimize_serpentine_parameters_with,
+            optimizer_for_profile,
+        },
+        optimizers::OptimizationBudget,
+        spatial_index::path_self_approach,
+        strategies::{ChannelGenerationContext, ChannelTypeStrategy, SerpentineChannelStrategy},
+        ChannelType, Point2D,
+    },
+};
+use std::f64::consts::PI;
+
+const BOX_DIMS: (f64, f64) = (200.0, 100.0);
+const P1: Point2D = (0.0, 50.0);
+const P2: Point2D = (200.0, 50.0);
+
+/// Path running right along `y = 0`, turning through a half circle of
+/// `radius` and running back left
+fn hairpin(radius: f64) -> Vec<Point2D> {
+    let out = (0..=20).map(|step| (f64::from(step), 0.0));
+    let turn = (1..20).map(|step| {
+        let angle = PI * f64::from(step) / 20.0;
+        (radius.mul_add(angle.sin(), 20.0), radius.mul_add(-angle.cos(), radius))
+    });
+    let back = (0..=20).rev().map(|step| (f64::from(step), 2.0 * radius));
+    out.chain(turn).chain(back).collect()
+}
+
+/// Serpentine tight enough for adjacent half-waves to overlap
+fn dense_config() -> SerpentineConfig {
+    SerpentineConfig {
+        wave_density_factor: 5.0,
+        fill_factor: 0.95,
+        ..SerpentineConfig::default()
+    }
+}
+
+/// Generate the serpentine path of one channel across the box
+fn generate(config: &SerpentineConfig, geometry_config: &GeometryConfig) -> Vec<Point2D> {
+    let ChannelType::Serpentine { path } = SerpentineChannelStrategy::new(*config)
+        .create_channel(P1, P2, geometry_config, BOX_DIMS, 1, None)
+    else {
+        panic!("Expected a serpentine channel");
+    };
+    path
+}
+
+/// Test that the legs of a tight hairpin are reported
+#[test]
+fn test_hairpin_detected() {
+    let approach = path_self_approach(&hairpin(0.5), 1.5).expect("Hairpin legs are 1 mm apart");
+    assert!((approach.centerline_distance - 1.0).abs() < 1e-9);
+    assert!(approach.arc_separation >= PI / 2.0 * 1.5);
+    assert!(approach.second_segment > approach.first_segment + 1);
+
+    // Channel edges of 1 mm wide legs touch
+    assert!(calculate_min_self_spacing(&hairpin(0.5), 1.0, 0.5).abs() < 1e-9);
+}
+
+/// Test that single bends, wide hairpins and straight paths are not reported
+#[test]
+fn test_clear_paths_not_reported() {
+    let straight: Vec<Point2D> = (0..=50).map(|step| (f64::from(step), 0.0)).collect();
+    let half_circle: Vec<Point2D> = (0..=40)
+        .map(|step| {
+            let angle = PI * f64::from(step) / 40.0;
+            (0.5 * angle.cos(), 0.5 * angle.sin())
+        })
+        .collect();
+
+    assert!(path_self_approach(&straight, 1.5).is_none());
+    assert!(path_self_approach(&half_circle, 1.5).is_none());
+    assert!(path_self_approach(&hairpin(1.5), 1.5).is_none());
+    assert!(calculate_min_self_spacing(&hairpin(1.5), 1.0, 0.5).is_infinite());
+}
+
+/// Test that overlapping half-waves are corrected during generation
+#[test]
+fn test_dense_serpentine_corrected() {
+    let geometry_config = GeometryConfig::default();
+    let clearance = geometry_config.wall_clearance;
+
+    let overlapping = generate(&dense_config(), &geometry_config);
+    let metrics = PathMetrics::from_path(&overlapping, &geometry_config, BOX_DIMS, None);
+    assert!(metrics.min_self_spacing < clearance);
+    assert!(metrics.self_spacing_violation > 0.0);
+
+    let corrected = generate(&dense_config().with_self_intersection_avoidance(), &geometry_config);
+    let metrics = PathMetrics::from_path(&corrected, &geometry_config, BOX_DIMS, None);
+    assert!(metrics.min_self_spacing >= clearance, "Self spacing {} below clearance", metrics.min_self_spacing);
+    assert!(metrics.self_spacing_violation.abs() < f64::EPSILON);
+    assert_eq!(corrected.first(), Some(&P1));
+    assert_eq!(corrected.last(), Some(&P2));
+}
+
+/// Test that paths that are already clear are left unchanged
+#[test]
+fn test_clear_serpentine_unchanged() {
+    let geometry_config = GeometryConfig::default();
+    let config = SerpentineConfig {
+        wavelength_factor: 10.0,
+        wave_density_factor: 0.5,
+        fill_factor: 0.3,
+        ..SerpentineConfig::default()
+    };
+
+    assert_eq!(
+        generate(&config, &geometry_config),
+        generate(&config.with_self_intersection_avoidance(), &geometry_config)
+    );
+}
+
+/// Test that the optimizers treat self-spacing as a constraint when enabled
+#[test]
+fn test_optimizers_respect_self_spacing() {
+    let geometry_config = GeometryConfig::default();
+    let clearance = geometry_config.wall_clearance;
+    let context = ChannelGenerationContext::new(&geometry_config, BOX_DIMS, 1, None);
+    let target = OptimizationTarget::length(400.0, 0.01);
+
+    // Without the constraint the closest design folds onto itself
+    let unconstrained = optimize_serpentine_for_target(P1, P2, &dense_config(), &context, target);
+    assert!(unconstrained.is_reached());
+    assert!(unconstrained.min_self_spacing < clearance);
+
+    let config = dense_config().with_self_intersection_avoidance();
+    let constrained = optimize_serpentine_for_target(P1, P2, &config, &context, target);
+    assert!(constrained.is_reached(), "Expected target to be reached, got {:?}", constrained.feasibility);
+    assert!(constrained.min_self_spacing >= clearance);
+
+    let config = config.with_objective(ObjectiveSpec::maximize_length());
+    let result = optimize_serpentine_parameters_with(
+        optimizer_for_profile(config.optimization_profile).as_ref(),
+        P1,
+        P2,
+        &config,
+        &context,
+        &OptimizationBudget::default(),
+    );
+    assert!(result.is_valid);
+    assert!(result.min_self_spacing >= clearance);
+}
@@ -0,0 +1,150 @@
+//! Spatial Index Tests
+//!
+//! Tests for exact polyline distance and intersection queries over whole
+//! channel systems, and for system-level collision detection built on them.
+
+use scheme::{
+    config::{ChannelTypeConfig, GeometryConfig, SerpentineConfig},
+    geometry::{
+        builders::{ChannelBuilder, NodeBuilder},
+        collision_detection::CollisionDetectionSystem,
+        generator::create_geometry,
+        spatial_index::{channel_paths, segment_closest_points, ChannelSpatialIndex},
+        ChannelSystem, ChannelType, Point2D, SplitType,
+    },
+};
+
+/// Build a system of unconnected channels, each with its own pair of nodes
+fn system(box_dims: (f64, f64), paths: Vec<Vec<Point2D>>) -> ChannelSystem {
+    let nodes = paths.iter()
+        .flat_map(|path| [path[0], path[path.len() - 1]])
+        .enumerate()
+        .map(|(id, point)| NodeBuilder::new(id, point).build())
+        .collect();
+    let channels = paths.into_iter()
+        .enumerate()
+        .map(|(id, path)| ChannelBuilder::new(id, 2 * id, 2 * id + 1, 1.0, 0.5, ChannelType::Arc { path }).build())
+        .collect();
+    ChannelSystem { box_dims, nodes, channels, box_outline: Vec::new() }
+}
+
+/// Test that indexed distances match a brute-force scan of every segment pair
+#[test]
+fn test_distances_match_brute_force() {
+    let angled = system(
+        (100.0, 100.0),
+        vec![
+            vec![(0.0, 0.0), (30.0, 40.0), (60.0, 20.0), (100.0, 90.0)],
+            vec![(0.0, 20.0), (40.0, 70.0), (80.0, 60.0)],
+            vec![(10.0, 100.0), (50.0, 55.0), (90.0, 99.0)],
+        ],
+    );
+    let index = ChannelSpatialIndex::new(&angled);
+    let paths = channel_paths(&angled);
+    assert_eq!(index.segment_count(), 7);
+
+    for first in 0..paths.len() {
+        for second in (first + 1)..paths.len() {
+            let brute_force = paths[first].windows(2)
+                .flat_map(|a| paths[second].windows(2).map(move |b| segment_closest_points(a[0], a[1], b[0], b[1]).0))
+                .fold(f64::INFINITY, f64::min);
+            let proximity = index.channel_distance(first, second).unwrap();
+            assert!((proximity.centerline_distance - brute_force).abs() < 1e-9);
+            assert!((proximity.spacing - (brute_force - 1.0)).abs() < 1e-9);
+        }
+    }
+
+    let all = index.proximities_within(f64::INFINITY);
+    assert_eq!(all.len(), 3);
+    for proximity in &all {
+        let direct = index.channel_distance(proximity.first_channel, proximity.second_channel).unwrap();
+        assert!((proximity.centerline_distance - direct.centerline_distance).abs() < 1e-9);
+    }
+}
+
+/// Test that crossings between non-horizontal channels are found
+#[test]
+fn test_crossing_channels() {
+    let crossing = system(
+        (100.0, 100.0),
+        vec![
+            vec![(0.0, 0.0), (50.0, 50.0), (100.0, 100.0)],
+            vec![(0.0, 100.0), (100.0, 0.0)],
+            vec![(60.0, 95.0), (90.0, 99.0)],
+        ],
+    );
+    let index = ChannelSpatialIndex::new(&crossing);
+
+    let intersections = index.intersections();
+    assert_eq!(intersections.len(), 1);
+    let intersection = intersections[0];
+    assert_eq!((intersection.first_channel, intersection.second_channel), (0, 1));
+    assert!((intersection.point.0 - 50.0).abs() < 1e-9 && (intersection.point.1 - 50.0).abs() < 1e-9);
+
+    let proximities = index.proximities_within(1.0);
+    assert_eq!(proximities.len(), 1);
+    assert!(proximities[0].spacing < 0.0);
+}
+
+/// Test that generated systems don't report their own junctions as collisions
+#[test]
+fn test_generated_system_has_no_crossings() {
+    let generated = create_geometry(
+        (200.0, 100.0),
+        &[SplitType::Bifurcation, SplitType::Trifurcation],
+        &GeometryConfig::default(),
+        &ChannelTypeConfig::AllSerpentine(SerpentineConfig::default()),
+    );
+    let index = ChannelSpatialIndex::new(&generated);
+
+    assert!(index.intersections().is_empty());
+    let overlaps = index.proximities_within(0.0);
+    assert!(overlaps.is_empty(), "Overlapping channels: {overlaps:?}");
+}
+
+/// Test a layout with thousands of segments
+#[test]
+fn test_many_segments() {
+    let channel_count: u32 = 100;
+    let segments_per_channel: u32 = 50;
+    let paths = (0..channel_count)
+        .map(|channel| {
+            (0..=segments_per_channel)
+                .map(|step| {
+                    let x = f64::from(step) * 2.0;
+                    let y = f64::from(channel).mul_add(5.0, 0.5 * (x / 10.0).sin());
+                    (x, y)
+                })
+                .collect()
+        })
+        .collect();
+    let layout = system((100.0, 500.0), paths);
+    let index = ChannelSpatialIndex::new(&layout);
+
+    assert_eq!(index.segment_count(), 5000);
+    assert!(index.intersections().is_empty());
+    assert!(index.proximities_within(2.0).is_empty());
+
+    // Only direct neighbors are within 4.5 mm of each other
+    let neighbors = index.proximities_within(4.5);
+    assert_eq!(neighbors.len(), 99);
+    assert!(neighbors.iter().all(|proximity| proximity.second_channel == proximity.first_channel + 1));
+}
+
+/// Test system-wide collision detection through the collision detection system
+#[test]
+fn test_detect_system_collisions() {
+    let detector = CollisionDetectionSystem::default();
+
+    let clear = system((100.0, 100.0), vec![vec![(0.0, 20.0), (100.0, 20.0)], vec![(0.0, 80.0), (100.0, 80.0)]]);
+    let result = detector.detect_system_collisions(&clear);
+    assert!(!result.has_collisions);
+    assert!(result.severity_score.abs() < f64::EPSILON);
+
+    let crossing = system((100.0, 100.0), vec![vec![(0.0, 20.0), (100.0, 80.0)], vec![(0.0, 80.0), (100.0, 20.0)]]);
+    let result = detector.detect_system_collisions(&crossing);
+    assert!(result.has_collisions);
+    assert_eq!(result.intersections.len(), 1);
+    assert_eq!(result.proximities.len(), 1);
+    assert!(result.severity_score >= 2.0);
+}
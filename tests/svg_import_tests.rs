@@ -0,0 +1,206 @@
+//! SVG Import Tests
+//!
+//! Tests for importing channel layouts from SVG drawings: units, layers and
+//! transforms, endpoint merging, stroke widths, curve flattening, skipped
+//! elements and the errors reported for unusable drawings.
+
+use scheme::{
+    error::{GeometryError, ImportError},
+    geometry::{ChannelSystem, ChannelType, Point2D},
+    import::{svg, ImportOptions},
+};
+
+/// Path points of a channel, or its endpoints for straight channels
+fn path(system: &ChannelSystem, channel: usize) -> Vec<Point2D> {
+    let channel = &system.channels[channel];
+    match &channel.channel_type {
+        ChannelType::Straight => vec![system.nodes[channel.from_node].point, system.nodes[channel.to_node].point],
+        ChannelType::SmoothStraight { path }
+        | ChannelType::Arc { path }
+        | ChannelType::Serpentine { path }
+        | ChannelType::Frustum { path, .. } => path.clone(),
+    }
+}
+
+/// Assert that two points agree to within 1e-9
+fn assert_close(actual: Point2D, expected: Point2D) {
+    assert!((actual.0 - expected.0).abs() < 1e-9 && (actual.1 - expected.1).abs() < 1e-9, "{actual:?} != {expected:?}");
+}
+
+/// Test that an Inkscape drawing in millimetres imports with merged endpoints
+#[test]
+fn test_inkscape_layers_units_and_merging() {
+    let drawing = r#"
+        <svg xmlns="http://www.w3.org/2000/svg" xmlns:inkscape="http://www.inkscape.org/namespaces/inkscape"
+             width="20mm" height="10mm" viewBox="0 0 200 100">
+            <g inkscape:groupmode="layer" id="layer1" inkscape:label="outline">
+                <rect width="200" height="100" style="fill:none;stroke:#000000"/>
+            </g>
+            <g inkscape:groupmode="layer" id="layer2" inkscape:label="channels" transform="translate(0,50)">
+                <g style="stroke:#000000;stroke-width:4">
+                    <path d="M 0,0 H 100"/>
+                    <path d="M 100.3,0 L 200,-40" style="stroke-width:2"/>
+                    <line x1="100" y1="0.2" x2="200" y2="40"/>
+                    <path d="m 0,0 h -50" transform="translate(200,-40) scale(0.5)"/>
+                </g>
+            </g>
+        </svg>"#;
+    let system = svg::import(drawing, &ImportOptions::default()).unwrap().system;
+
+    assert_eq!(system.box_dims, (20.0, 10.0));
+    let points: Vec<Point2D> = system.nodes.iter().map(|node| node.point).collect();
+    assert_eq!(points.len(), 5);
+    for (actual, expected) in points.into_iter().zip([(0.0, 5.0), (10.0, 5.0), (20.0, 9.0), (20.0, 1.0), (17.5, 9.0)]) {
+        assert_close(actual, expected);
+    }
+
+    let connections: Vec<(usize, usize)> = system.channels.iter().map(|channel| (channel.from_node, channel.to_node)).collect();
+    assert_eq!(connections, [(0, 1), (1, 2), (1, 3), (2, 4)]);
+    let widths: Vec<f64> = system.channels.iter().map(|channel| channel.width).collect();
+    for (width, expected) in widths.into_iter().zip([0.4, 0.2, 0.4, 0.2]) {
+        assert!((width - expected).abs() < 1e-12);
+    }
+    assert!(system.channels.iter().all(|channel| matches!(channel.channel_type, ChannelType::Straight)));
+}
+
+/// Test that arcs and Bézier curves are flattened into arc channels
+#[test]
+fn test_curves_become_arc_channels() {
+    let drawing = r#"
+        <svg xmlns="http://www.w3.org/2000/svg">
+            <g id="outline"><polygon points="0,0 100,0 100,100 0,100"/></g>
+            <path d="M 10 50 A 20 20 0 0 1 50 50"/>
+            <path d="M 50 50 C 60 40 70 40 80 50 S 90 60 95 50"/>
+            <path d="m 10 10 l 10 0 h 10 v 10 z"/>
+        </svg>"#;
+    let options = ImportOptions { curve_segments: 4, ..ImportOptions::default() };
+    let system = svg::import(drawing, &options).unwrap().system;
+    assert_eq!(system.channels.len(), 3);
+
+    // Clockwise on screen through the top of the circle, which is up after flipping y
+    let arc = path(&system, 0);
+    assert!(matches!(system.channels[0].channel_type, ChannelType::Arc { .. }));
+    assert_eq!(arc.len(), 5);
+    for point in &arc {
+        assert!(((point.0 - 30.0).hypot(point.1 - 50.0) - 20.0).abs() < 1e-9);
+    }
+    assert_close(arc[2], (30.0, 70.0));
+    assert_close(arc[4], (50.0, 50.0));
+
+    let curve = path(&system, 1);
+    assert!(matches!(system.channels[1].channel_type, ChannelType::Arc { .. }));
+    assert_eq!(curve.len(), 9);
+    assert_close(curve[2], (65.0, 57.5));
+    assert_close(curve[6], (89.375, 42.5));
+    assert_eq!(system.channels[1].from_node, system.channels[0].to_node);
+
+    let loop_channel = &system.channels[2];
+    assert!(matches!(loop_channel.channel_type, ChannelType::SmoothStraight { .. }));
+    assert_eq!(loop_channel.from_node, loop_channel.to_node);
+    assert_eq!(path(&system, 2), [(10.0, 90.0), (20.0, 90.0), (30.0, 90.0), (30.0, 80.0), (10.0, 90.0)]);
+}
+
+/// Test that channel layers select strokes and unsupported shapes are counted
+#[test]
+fn test_layers_and_skipped_elements() {
+    let drawing = r#"
+        <svg xmlns="http://www.w3.org/2000/svg" xmlns:sodipodi="http://sodipodi.sourceforge.net/DTD/sodipodi-0.dtd">
+            <sodipodi:namedview id="view"/>
+            <defs><path id="template" d="M 0 0 L 500 500"/></defs>
+            <g id="outline"><rect width="100" height="40"/><circle cx="5" cy="5" r="2"/></g>
+            <g id="flow">
+                <line x1="0" y1="20" x2="100" y2="20"/>
+                <circle cx="50" cy="20" r="3"/>
+                <text x="10" y="10">inlet</text>
+            </g>
+            <g id="notes"><line x1="10" y1="5" x2="90" y2="5"/></g>
+            <g id="hidden" style="display: none"><line x1="0" y1="0" x2="500" y2="0"/></g>
+        </svg>"#;
+    let all = svg::import(drawing, &ImportOptions::default()).unwrap();
+    assert_eq!(all.system.channels.len(), 2);
+    assert_eq!(all.skipped.get("circle"), Some(&2));
+    assert_eq!(all.skipped.get("text"), Some(&1));
+    assert_eq!(all.skipped.len(), 2);
+
+    let options = ImportOptions { channel_layers: vec!["flow".to_string()], ..ImportOptions::default() };
+    let flow = svg::import(drawing, &options).unwrap();
+    assert_eq!(flow.system.channels.len(), 1);
+    assert_eq!(flow.system.nodes[0].point, (0.0, 20.0));
+    assert_eq!(flow.skipped, all.skipped);
+}
+
+/// Test that unusable drawings are reported with the reason
+#[test]
+fn test_import_errors() {
+    let options = ImportOptions::default();
+    let error = svg::import(r#"<svg><line x1="0" y1="0" x2="10" y2="0"/></svg>"#, &options).unwrap_err();
+    assert!(matches!(error, ImportError::MissingOutline { ref layer } if layer == "outline"));
+
+    let error = svg::import(r#"<svg><g id="outline"><rect width="10" height="10"/></g></svg>"#, &options).unwrap_err();
+    assert!(matches!(error, ImportError::NoChannels));
+
+    let outside = r#"<svg><g id="outline"><rect width="10" height="10"/></g><line x1="5" y1="5" x2="15" y2="5"/></svg>"#;
+    let error = svg::import(outside, &options).unwrap_err();
+    assert!(matches!(error, ImportError::InvalidSystem(GeometryError::OutsideOutline { .. })));
+
+    let bad_path = "<svg>\n<g id=\"outline\"><rect width=\"10\" height=\"10\"/></g>\n<path id=\"inlet\" d=\"M 0 0 L 5 x\"/>\n</svg>";
+    let message = svg::import(bad_path, &options).unwrap_err().to_string();
+    assert!(message.contains("<path id=\"inlet\"> on line 3"), "{message}");
+    assert!(message.contains("expected a number at 'x'"), "{message}");
+
+    for text in ["<html/>", "<svg>", r#"<svg><g id="outline" transform="spin(3)"/></svg>"#] {
+        assert!(matches!(svg::import(text, &options), Err(ImportError::ParseError { .. })), "{text}");
+    }
+    assert!(matches!(svg::import_file("missing.svg", &options), Err(ImportError::FileError { .. })));
+}
+
+/// Test that lengths in physical units are converted with the document's scale
+#[test]
+fn test_lengths_with_physical_units() {
+    // Without a physical width one user unit is one millimetre
+    let unscaled = r#"
+        <svg xmlns="http://www.w3.org/2000/svg">
+            <g id="outline"><rect width="1cm" height="10"/></g>
+            <line x1="0" y1="5mm" x2="10mm" y2="5" stroke-width="1mm"/>
+            <line x1="10" y1="5" x2="10" y2="0.1in" stroke-width="0.02in"/>
+        </svg>"#;
+    let system = svg::import(unscaled, &ImportOptions::default()).unwrap().system;
+    assert_eq!(system.box_dims, (10.0, 10.0));
+    assert_eq!(system.nodes.len(), 3);
+    assert_close(system.nodes[1].point, (10.0, 5.0));
+    assert_close(system.nodes[2].point, (10.0, 7.46));
+    assert!((system.channels[0].width - 1.0).abs() < 1e-12);
+    assert!((system.channels[1].width - 0.508).abs() < 1e-12);
+
+    // With ten user units per millimetre, 1mm is still one millimetre
+    let scaled = r#"
+        <svg xmlns="http://www.w3.org/2000/svg" width="20mm" height="10mm" viewBox="0 0 200 100">
+            <g id="outline"><rect width="200" height="100"/></g>
+            <line x1="0" y1="50" x2="1.5cm" y2="50" stroke-width="1mm"/>
+        </svg>"#;
+    let system = svg::import(scaled, &ImportOptions::default()).unwrap().system;
+    assert_close(system.nodes[1].point, (15.0, 5.0));
+    assert!((system.channels[0].width - 1.0).abs() < 1e-12);
+}
+
+/// Test that imported systems save, reload and validate like generated ones
+#[test]
+fn test_imported_system_round_trip() {
+    let drawing = r#"
+        <svg xmlns="http://www.w3.org/2000/svg" width="4in" height="2in" viewBox="0 0 40 20">
+            <g id="outline"><rect width="40" height="20"/></g>
+            <g stroke-width="0.5">
+                <polyline points="0,10 10,10 15,5 40,5"/>
+                <path d="M 10 10 Q 25 20 40 15"/>
+            </g>
+        </svg>"#;
+    let system = svg::import(drawing, &ImportOptions::default()).unwrap().system;
+    assert_eq!(system.box_dims, (101.6, 50.8));
+    assert!((system.channels[0].width - 1.27).abs() < 1e-12);
+
+    let reloaded = ChannelSystem::from_json_validated(&system.to_json().unwrap()).unwrap();
+    assert_eq!(reloaded.channels.len(), 2);
+    for (reloaded, original) in reloaded.get_path_segments().iter().flatten().zip(system.get_path_segments().iter().flatten()) {
+        assert_close(*reloaded, *original);
+    }
+}
@@ -0,0 +1,108 @@
+//! System Optimization Tests
+//!
+//! Tests for joint optimization of all serpentine channels in a channel
+//! system, including pairwise clearance constraints and objectives.
+
+use scheme::{
+    config::{ChannelTypeConfig, GeometryConfig, SerpentineConfig},
+    geometry::{
+        generator::create_geometry,
+        optimization::calculate_path_length,
+        optimizers::OptimizationBudget,
+        system_optimization::{optimize_channel_system, SystemObjective, SystemOptimizationConfig},
+        ChannelSystem, ChannelType, SplitType,
+    },
+};
+
+const BOX_DIMS: (f64, f64) = (200.0, 100.0);
+
+fn bifurcation_system(channel_config: &ChannelTypeConfig) -> ChannelSystem {
+    create_geometry(BOX_DIMS, &[SplitType::Bifurcation], &GeometryConfig::default(), channel_config)
+}
+
+fn small_config(objective: SystemObjective) -> SystemOptimizationConfig {
+    SystemOptimizationConfig {
+        objective,
+        budget: OptimizationBudget::new(120, 20, 1e-9),
+        ..SystemOptimizationConfig::default()
+    }
+}
+
+/// Test that joint optimization keeps clearances and does not lose length
+#[test]
+fn test_total_length_optimization() {
+    let serpentine_config = SerpentineConfig::default();
+    let system = bifurcation_system(&ChannelTypeConfig::AllSerpentine(serpentine_config));
+    let config = small_config(SystemObjective::TotalLength);
+
+    let result = optimize_channel_system(&system, &GeometryConfig::default(), &serpentine_config, &config);
+
+    let serpentine_count = system.channels.iter()
+        .filter(|channel| matches!(channel.channel_type, ChannelType::Serpentine { .. }))
+        .count();
+    assert_eq!(result.channels.len(), serpentine_count);
+    assert!(result.evaluations > 0);
+    assert!(result.is_feasible, "Optimized system violates clearances: {result:?}");
+    assert!(result.min_wall_distance >= GeometryConfig::default().wall_clearance);
+
+    // The optimizer starts from the configured parameters, so it can only improve
+    let initial_total: f64 = result.channels.iter().map(|channel| channel.initial_length).sum();
+    let optimized_total: f64 = result.channels.iter().map(|channel| channel.optimized_length).sum();
+    assert!((result.total_length - optimized_total).abs() < 1e-9);
+    assert!(optimized_total >= initial_total - 1e-6, "{optimized_total} < {initial_total}");
+}
+
+/// Test that the minimum-length objective raises the shortest branch
+#[test]
+fn test_minimum_length_optimization() {
+    let serpentine_config = SerpentineConfig::default();
+    let system = bifurcation_system(&ChannelTypeConfig::AllSerpentine(serpentine_config));
+    let config = small_config(SystemObjective::MinimumLength);
+
+    let result = optimize_channel_system(&system, &GeometryConfig::default(), &serpentine_config, &config);
+
+    let initial_min = result.channels.iter()
+        .map(|channel| channel.initial_length)
+        .fold(f64::INFINITY, f64::min);
+    assert!(result.min_length >= initial_min - 1e-6, "{} < {initial_min}", result.min_length);
+    assert!(result.channels.iter().all(|channel| channel.optimized_length >= result.min_length - 1e-9));
+}
+
+/// Test that optimized paths stay connected to their nodes
+#[test]
+fn test_optimized_paths_connect_nodes() {
+    let serpentine_config = SerpentineConfig::default();
+    let system = bifurcation_system(&ChannelTypeConfig::AllSerpentine(serpentine_config));
+    let config = small_config(SystemObjective::TotalLength);
+
+    let result = optimize_channel_system(&system, &GeometryConfig::default(), &serpentine_config, &config);
+
+    for (channel, optimization) in result.system.channels.iter()
+        .filter(|channel| matches!(channel.channel_type, ChannelType::Serpentine { .. }))
+        .zip(&result.channels)
+    {
+        assert_eq!(channel.id, optimization.channel_id);
+        let ChannelType::Serpentine { path } = &channel.channel_type else { unreachable!() };
+        let from = result.system.nodes[channel.from_node].point;
+        let to = result.system.nodes[channel.to_node].point;
+        let first = path.first().unwrap();
+        let last = path.last().unwrap();
+        assert!((first.0 - from.0).hypot(first.1 - from.1) < 1e-6);
+        assert!((last.0 - to.0).hypot(last.1 - to.1) < 1e-6);
+        assert!((calculate_path_length(path) - optimization.optimized_length).abs() < 1e-9);
+    }
+}
+
+/// Test that a system without serpentine channels is returned unchanged
+#[test]
+fn test_system_without_serpentines() {
+    let system = bifurcation_system(&ChannelTypeConfig::AllStraight);
+    let config = small_config(SystemObjective::TotalLength);
+
+    let result = optimize_channel_system(&system, &GeometryConfig::default(), &SerpentineConfig::default(), &config);
+
+    assert!(result.channels.is_empty());
+    assert_eq!(result.evaluations, 0);
+    assert_eq!(result.system.channels.len(), system.channels.len());
+    assert!(result.min_channel_spacing.is_infinite());
+}
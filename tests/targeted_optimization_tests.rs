@@ -0,0 +1,195 @@
+//! Targeted Optimization Tests
+//!
+//! Tests for inverse-design serpentine optimization, where channels are tuned
+//! to reach a specific length or hydraulic resistance instead of the maximum
+//! possible length.
+
+use scheme::{
+    config::{ChannelTypeConfig, GeometryConfig, OptimizationTarget, SerpentineConfig},
+    geometry::{
+        builders::ChannelExt,
+        generator::create_geometry,
+        metadata::TargetMetadata,
+        optimization::{
+            calculate_hydraulic_resistance, calculate_path_length, optimize_serpentine_for_target,
+            TargetFeasibility,
+        },
+        strategies::ChannelGenerationContext,
+        ChannelType,
+    },
+};
+
+const BOX_DIMS: (f64, f64) = (200.0, 100.0);
+const P1: (f64, f64) = (0.0, 50.0);
+const P2: (f64, f64) = (200.0, 50.0);
+
+/// Test that a reachable length target is met within tolerance
+#[test]
+fn test_length_target_reached() {
+    let geometry_config = GeometryConfig::default();
+    let context = ChannelGenerationContext::new(&geometry_config, BOX_DIMS, 1, None);
+    let target = OptimizationTarget::length(800.0, 0.01);
+
+    let result = optimize_serpentine_for_target(P1, P2, &SerpentineConfig::default(), &context, target);
+
+    assert!(result.is_reached(), "Expected target to be reached, got {:?}", result.feasibility);
+    assert!(result.relative_error <= 0.01);
+    assert!((result.path_length - 800.0).abs() <= 8.0);
+    assert!(result.min_wall_distance >= geometry_config.wall_clearance);
+}
+
+/// Test that a target longer than the clearance constraints allow is reported
+#[test]
+fn test_length_target_above_maximum() {
+    let geometry_config = GeometryConfig::default();
+    let context = ChannelGenerationContext::new(&geometry_config, BOX_DIMS, 1, None);
+    let target = OptimizationTarget::length(50_000.0, 0.01);
+
+    let result = optimize_serpentine_for_target(P1, P2, &SerpentineConfig::default(), &context, target);
+
+    assert!(!result.is_reached());
+    match result.feasibility {
+        TargetFeasibility::AboveMaximum { maximum } => {
+            assert!(maximum < 50_000.0);
+            assert!((maximum - result.achieved_value).abs() < 1e-9);
+        }
+        other => panic!("Expected AboveMaximum, got {other:?}"),
+    }
+    assert!(result.min_wall_distance >= geometry_config.wall_clearance);
+}
+
+/// Test that a target shorter than the straight-line distance is reported
+#[test]
+fn test_length_target_below_minimum() {
+    let geometry_config = GeometryConfig::default();
+    let context = ChannelGenerationContext::new(&geometry_config, BOX_DIMS, 1, None);
+    let target = OptimizationTarget::length(100.0, 0.01);
+
+    let result = optimize_serpentine_for_target(P1, P2, &SerpentineConfig::default(), &context, target);
+
+    match result.feasibility {
+        TargetFeasibility::BelowMinimum { minimum } => assert!(minimum >= 200.0),
+        other => panic!("Expected BelowMinimum, got {other:?}"),
+    }
+}
+
+/// Test targets in a multi-channel context respect neighbor clearance
+#[test]
+fn test_length_target_with_neighbors() {
+    let geometry_config = GeometryConfig::default();
+    let neighbors = [25.0, 50.0, 75.0];
+    let context = ChannelGenerationContext::new(&geometry_config, BOX_DIMS, 3, Some(&neighbors));
+    let target = OptimizationTarget::length(400.0, 0.02);
+
+    let result = optimize_serpentine_for_target((0.0, 25.0), (200.0, 25.0), &SerpentineConfig::default(), &context, target);
+
+    assert!(result.is_reached(), "Expected target to be reached, got {:?}", result.feasibility);
+    assert!(result.min_neighbor_distance >= geometry_config.wall_clearance);
+}
+
+/// Test the rectangular channel hydraulic resistance approximation
+#[test]
+fn test_hydraulic_resistance_calculation() {
+    // 10 mm long, 1 mm x 0.5 mm water channel
+    let resistance = calculate_hydraulic_resistance(10.0, 1.0, 0.5, 1.0e-3);
+    let expected = 12.0 * 1.0e-3 * 0.01 / (1.0e-3 * 0.5e-3_f64.powi(3) * 0.685);
+    assert!((resistance - expected).abs() / expected < 1e-12);
+
+    // Resistance is linear in length and symmetric in width/height
+    let doubled = calculate_hydraulic_resistance(20.0, 1.0, 0.5, 1.0e-3);
+    assert!((doubled / resistance - 2.0).abs() < 1e-12);
+    assert!((calculate_hydraulic_resistance(10.0, 0.5, 1.0, 1.0e-3) - resistance).abs() / resistance < 1e-12);
+}
+
+/// Test that resistance and pressure-drop targets convert to the right length
+#[test]
+fn test_resistance_target_conversion() {
+    let geometry_config = GeometryConfig::default();
+    let resistance = calculate_hydraulic_resistance(600.0, 1.0, 0.5, 1.0e-3);
+
+    let target = OptimizationTarget::hydraulic_resistance(resistance, 1.0e-3, 0.01);
+    assert!((target.required_length(&geometry_config) - 600.0).abs() < 1e-6);
+
+    // Pressure drop of R * Q at 10 µL/min yields the same resistance
+    let flow_rate_si = 10.0 * 1.0e-9 / 60.0;
+    let from_pressure = OptimizationTarget::pressure_drop(resistance * flow_rate_si, 10.0, 1.0e-3, 0.01);
+    assert!((from_pressure.value() - resistance).abs() / resistance < 1e-9);
+}
+
+/// Test that a hydraulic resistance target is reached
+#[test]
+fn test_resistance_target_reached() {
+    let geometry_config = GeometryConfig::default();
+    let context = ChannelGenerationContext::new(&geometry_config, BOX_DIMS, 1, None);
+    let resistance = calculate_hydraulic_resistance(1200.0, 1.0, 0.5, 1.0e-3);
+    let target = OptimizationTarget::hydraulic_resistance(resistance, 1.0e-3, 0.01);
+
+    let result = optimize_serpentine_for_target(P1, P2, &SerpentineConfig::default(), &context, target);
+
+    assert!(result.is_reached(), "Expected target to be reached, got {:?}", result.feasibility);
+    assert!((result.achieved_value - resistance).abs() / resistance <= 0.01);
+}
+
+/// Test validation of optimization targets
+#[test]
+fn test_optimization_target_validation() {
+    assert!(OptimizationTarget::length(100.0, 0.01).validate().is_ok());
+    assert!(OptimizationTarget::length(-5.0, 0.01).validate().is_err());
+    assert!(OptimizationTarget::length(100.0, 0.0).validate().is_err());
+    assert!(OptimizationTarget::length(100.0, 0.9).validate().is_err());
+    assert!(OptimizationTarget::hydraulic_resistance(1.0e12, 0.0, 0.01).validate().is_err());
+
+    let invalid = SerpentineConfig {
+        optimization_target: Some(OptimizationTarget::length(f64::NAN, 0.01)),
+        ..SerpentineConfig::default()
+    };
+    assert!(invalid.validate().is_err());
+}
+
+/// Test that targeted configurations drive geometry generation
+#[test]
+fn test_targeted_config_in_geometry_generation() {
+    let geometry_config = GeometryConfig::default();
+    let serpentine_config = SerpentineConfig::default()
+        .with_optimization_target(OptimizationTarget::length(900.0, 0.01));
+    assert!(serpentine_config.optimization_enabled);
+
+    let system = create_geometry(
+        BOX_DIMS,
+        &[],
+        &geometry_config,
+        &ChannelTypeConfig::AllSerpentine(serpentine_config),
+    );
+
+    let ChannelType::Serpentine { path } = &system.channels[0].channel_type else {
+        panic!("Expected a serpentine channel");
+    };
+    let length = calculate_path_length(path);
+    assert!((length - 900.0).abs() / 900.0 <= 0.01, "Generated length {length} misses target");
+
+    let metadata = system.channels[0].get_metadata::<TargetMetadata>().unwrap();
+    assert!(metadata.is_reached());
+    assert!((metadata.achieved_value - length).abs() < 1e-6);
+}
+
+/// Test that a channel missing its target records the miss instead of failing
+#[test]
+fn test_missed_target_recorded_in_metadata() {
+    let serpentine_config = SerpentineConfig::default()
+        .with_optimization_target(OptimizationTarget::length(100_000.0, 0.01));
+
+    let system = create_geometry(
+        BOX_DIMS,
+        &[],
+        &GeometryConfig::default(),
+        &ChannelTypeConfig::AllSerpentine(serpentine_config),
+    );
+
+    let metadata = system.channels[0].get_metadata::<TargetMetadata>().unwrap();
+    assert!(!metadata.is_reached());
+    assert_eq!(metadata.target, OptimizationTarget::length(100_000.0, 0.01));
+    let TargetFeasibility::AboveMaximum { maximum } = metadata.feasibility else {
+        panic!("Expected the target to exceed the achievable maximum, got {:?}", metadata.feasibility);
+    };
+    assert!(metadata.achieved_value <= maximum + 1e-6);
+}
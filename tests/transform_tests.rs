@@ -0,0 +1,184 @@
+//! Transform Tests
+//!
+//! Tests for translating, rotating, scaling and mirroring whole channel
+//! systems: nodes, every path variant, the box outline and bounds, channel
+//! and frustum widths, and analytic paths kept as metadata.
+
+use scheme::{
+    config::{ArcConfig, ChannelTypeConfig, FrustumConfig, GeometryConfig, SerpentineConfig},
+    geometry::{
+        builders::ChannelExt,
+        generator::{create_geometry, create_geometry_with_metadata, MetadataConfig},
+        metadata::ParametricPathMetadata,
+        optimization::calculate_path_length,
+        transform::{MirrorAxis, Transform},
+        ChannelSystem, ChannelType, Point2D, SplitType,
+    },
+};
+use std::f64::consts::FRAC_PI_2;
+
+const BOX_DIMS: (f64, f64) = (200.0, 100.0);
+const TOLERANCE: f64 = 1e-9;
+
+/// Mixed system with straight, serpentine, arc and frustum channels
+fn mixed_system() -> ChannelSystem {
+    create_geometry(
+        BOX_DIMS,
+        &[SplitType::Bifurcation, SplitType::Trifurcation],
+        &GeometryConfig::default(),
+        &ChannelTypeConfig::Adaptive {
+            serpentine_config: SerpentineConfig::default(),
+            arc_config: ArcConfig::default(),
+            frustum_config: FrustumConfig::default(),
+        },
+    )
+}
+
+/// Every point of every channel, in order, with nodes for straight channels
+fn channel_points(system: &ChannelSystem) -> Vec<Point2D> {
+    system
+        .channels
+        .iter()
+        .flat_map(|channel| match &channel.channel_type {
+            ChannelType::Straight => vec![system.nodes[channel.from_node].point, system.nodes[channel.to_node].point],
+            ChannelType::SmoothStraight { path }
+            | ChannelType::Serpentine { path }
+            | ChannelType::Arc { path }
+            | ChannelType::Frustum { path, .. } => path.clone(),
+        })
+        .collect()
+}
+
+/// Assert that `actual` is `expected` mapped through `transform`
+fn assert_mapped(expected: &[Point2D], actual: &[Point2D], transform: &Transform) {
+    assert_eq!(expected.len(), actual.len());
+    for (&point, &image) in expected.iter().zip(actual) {
+        let mapped = transform.apply(point);
+        assert!((mapped.0 - image.0).hypot(mapped.1 - image.1) < TOLERANCE, "{mapped:?} vs {image:?}");
+    }
+}
+
+/// Test that transforms compose in order and keep their invariants
+#[test]
+fn test_transform_composition() {
+    let rotation = Transform::rotation(FRAC_PI_2, (10.0, 0.0));
+    let scaling = Transform::scaling(2.0, (0.0, 0.0));
+    let combined = rotation.then(&scaling);
+    let point = (3.0, 4.0);
+    let expected = scaling.apply(rotation.apply(point));
+    let actual = combined.apply(point);
+    assert!((expected.0 - actual.0).hypot(expected.1 - actual.1) < TOLERANCE);
+
+    assert!((combined.scale_factor() - 2.0).abs() < TOLERANCE);
+    assert!(!combined.is_reflection());
+    let mirror = Transform::reflection(MirrorAxis::Horizontal { y: 1.0 });
+    assert!(mirror.is_reflection() && !mirror.then(&mirror).is_reflection());
+    assert_eq!(mirror.then(&mirror).apply(point), point);
+    assert_eq!(Transform::default().apply(point), point);
+}
+
+/// Test that translation moves every point and the bounds but not the size
+#[test]
+fn test_translate_system() {
+    let original = mixed_system();
+    let mut system = original.clone();
+    system.translate(30.0, -20.0);
+
+    let transform = Transform::translation(30.0, -20.0);
+    assert_mapped(&channel_points(&original), &channel_points(&system), &transform);
+    let nodes = |system: &ChannelSystem| system.nodes.iter().map(|node| node.point).collect::<Vec<_>>();
+    assert_mapped(&nodes(&original), &nodes(&system), &transform);
+    assert_eq!(system.bounds(), ((30.0, -20.0), (230.0, 80.0)));
+    assert_eq!(system.box_dims, BOX_DIMS);
+}
+
+/// Test that a quarter turn swaps the box dimensions and preserves lengths
+#[test]
+fn test_rotate_system() {
+    let original = mixed_system();
+    let mut system = original.clone();
+    system.rotate(FRAC_PI_2, (0.0, 0.0));
+
+    assert_mapped(&channel_points(&original), &channel_points(&system), &Transform::rotation(FRAC_PI_2, (0.0, 0.0)));
+    assert!((system.box_dims.0 - BOX_DIMS.1).abs() < TOLERANCE);
+    assert!((system.box_dims.1 - BOX_DIMS.0).abs() < TOLERANCE);
+    let ((min_x, min_y), (max_x, max_y)) = system.bounds();
+    assert!((min_x + 100.0).abs() < TOLERANCE && max_x.abs() < TOLERANCE);
+    assert!(min_y.abs() < TOLERANCE && (max_y - 200.0).abs() < TOLERANCE);
+
+    for (before, after) in original.get_path_segments().iter().zip(system.get_path_segments()) {
+        assert!((calculate_path_length(before) - calculate_path_length(&after)).abs() < 1e-6);
+    }
+}
+
+/// Test that scaling scales lengths, channel widths and frustum width profiles
+#[test]
+fn test_scale_system() {
+    let original = create_geometry(
+        BOX_DIMS,
+        &[SplitType::Bifurcation],
+        &GeometryConfig::default(),
+        &ChannelTypeConfig::AllFrustum(FrustumConfig::default()),
+    );
+    let mut system = original.clone();
+    system.scale(2.5, (100.0, 50.0));
+
+    assert_eq!(system.box_dims, (500.0, 250.0));
+    assert_eq!(system.bounds(), ((-150.0, -75.0), (350.0, 175.0)));
+    for (before, after) in original.channels.iter().zip(&system.channels) {
+        assert!(2.5f64.mul_add(-before.width, after.width).abs() < TOLERANCE);
+        assert!((after.height - before.height).abs() < f64::EPSILON);
+        let (
+            ChannelType::Frustum { path: path_before, widths: widths_before, throat_width: throat_before, .. },
+            ChannelType::Frustum { path: path_after, widths: widths_after, throat_width: throat_after, .. },
+        ) = (&before.channel_type, &after.channel_type)
+        else {
+            panic!("Expected frustum channels");
+        };
+        assert!((throat_after - 2.5 * throat_before).abs() < TOLERANCE);
+        for (width_before, width_after) in widths_before.iter().zip(widths_after) {
+            assert!((width_after - 2.5 * width_before).abs() < TOLERANCE);
+        }
+        assert!(2.5f64.mul_add(-calculate_path_length(path_before), calculate_path_length(path_after)).abs() < 1e-6);
+    }
+}
+
+/// Test that mirroring flips the geometry and that mirroring twice restores it
+#[test]
+fn test_mirror_system() {
+    let original = mixed_system();
+    let mut system = original.clone();
+    system.mirror(MirrorAxis::Vertical { x: 0.0 });
+
+    assert_mapped(&channel_points(&original), &channel_points(&system), &Transform::reflection(MirrorAxis::Vertical { x: 0.0 }));
+    assert_eq!(system.bounds(), ((-200.0, 0.0), (0.0, 100.0)));
+
+    system.mirror(MirrorAxis::Vertical { x: 0.0 });
+    assert_mapped(&channel_points(&original), &channel_points(&system), &Transform::identity());
+    assert_eq!(system.box_dims, original.box_dims);
+}
+
+/// Test that analytic paths follow the transform of their channels
+#[test]
+fn test_transform_updates_parametric_paths() {
+    let metadata_config = MetadataConfig { track_parametric_paths: true, ..MetadataConfig::default() };
+    let mut system = create_geometry_with_metadata(
+        BOX_DIMS,
+        &[SplitType::Bifurcation],
+        &GeometryConfig::default(),
+        &ChannelTypeConfig::AllSerpentine(SerpentineConfig::default()),
+        &metadata_config,
+    );
+    system
+        .rotate(0.3, (20.0, 10.0))
+        .mirror(MirrorAxis::Horizontal { y: 5.0 })
+        .scale(1.5, (0.0, 0.0));
+
+    for channel in &system.channels {
+        let ChannelType::Serpentine { path } = &channel.channel_type else {
+            panic!("Expected a serpentine channel");
+        };
+        let curve = &channel.get_metadata::<ParametricPathMetadata>().unwrap().path;
+        assert_mapped(&curve.discretize(path.len()), path, &Transform::identity());
+    }
+}
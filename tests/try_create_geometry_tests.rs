@@ -0,0 +1,110 @@
+//! Fallible Generation Tests
+//!
+//! Tests for `try_create_geometry`: valid inputs generate the same system as
+//! `create_geometry`, while invalid dimensions, invalid configurations, wall
+//! clearances larger than the box and overcrowded split patterns are
+//! reported as errors.
+
+use scheme::{
+    config::{ArcConfig, ChannelTypeConfig, GeometryConfig, SerpentineConfig},
+    error::{ConfigurationError, GeometryError, SchemeError},
+    geometry::{
+        generator::{create_geometry, try_create_geometry},
+        SplitType,
+    },
+};
+
+/// Test that valid inputs generate the same system as `create_geometry`
+#[test]
+fn test_valid_inputs_match_create_geometry() {
+    let config = GeometryConfig::default();
+    let channel_type = ChannelTypeConfig::AllSerpentine(SerpentineConfig::default());
+    let splits = [SplitType::Trifurcation, SplitType::Bifurcation];
+
+    let system = try_create_geometry((200.0, 100.0), &splits, &config, &channel_type).unwrap();
+    let expected = create_geometry((200.0, 100.0), &splits, &config, &channel_type);
+    let points = |system: &scheme::geometry::ChannelSystem| system.nodes.iter().map(|node| node.point).collect::<Vec<_>>();
+    assert_eq!(points(&system), points(&expected));
+    assert_eq!(system.channels.len(), expected.channels.len());
+}
+
+/// Test that non-positive and non-finite box dimensions are rejected
+#[test]
+fn test_invalid_box_dimensions() {
+    for box_dims in [(200.0, -100.0), (0.0, 100.0), (f64::NAN, 100.0), (200.0, f64::INFINITY)] {
+        let result = try_create_geometry(box_dims, &[], &GeometryConfig::default(), &ChannelTypeConfig::AllStraight);
+        assert!(matches!(result, Err(SchemeError::Geometry(GeometryError::InvalidBoxDimensions { .. }))));
+    }
+}
+
+/// Test that geometry and channel type configurations are validated
+#[test]
+fn test_invalid_configurations() {
+    let config = GeometryConfig { channel_width: -1.0, ..GeometryConfig::default() };
+    let result = try_create_geometry((200.0, 100.0), &[], &config, &ChannelTypeConfig::AllStraight);
+    assert!(matches!(result, Err(SchemeError::Configuration(_))));
+
+    let serpentine = SerpentineConfig { fill_factor: 2.0, ..SerpentineConfig::default() };
+    let mixed = ChannelTypeConfig::MixedByPosition {
+        middle_zone_fraction: 1.5,
+        serpentine_config: SerpentineConfig::default(),
+        arc_config: ArcConfig::default(),
+    };
+    for channel_type in [ChannelTypeConfig::AllSerpentine(serpentine), mixed] {
+        let result = try_create_geometry((200.0, 100.0), &[], &GeometryConfig::default(), &channel_type);
+        assert!(matches!(result, Err(SchemeError::Configuration(_))));
+    }
+}
+
+/// Test that non-finite geometry values are reported by field name
+#[test]
+fn test_non_finite_geometry_config() {
+    let defaults = GeometryConfig::default();
+    let configs = [
+        ("wall_clearance", GeometryConfig { wall_clearance: f64::NAN, ..defaults }),
+        ("channel_width", GeometryConfig { channel_width: f64::INFINITY, ..defaults }),
+        ("channel_height", GeometryConfig { channel_height: f64::NAN, ..defaults }),
+    ];
+    for (name, config) in configs {
+        let result = try_create_geometry((200.0, 100.0), &[SplitType::Bifurcation], &config, &ChannelTypeConfig::AllStraight);
+        assert!(
+            matches!(result, Err(SchemeError::Configuration(ConfigurationError::InvalidGeometryConfig { ref field, .. })) if field == name),
+            "{name}: {result:?}"
+        );
+    }
+}
+
+/// Test that a wall clearance leaving no room for a channel is reported
+#[test]
+fn test_wall_clearance_larger_than_box() {
+    let config = GeometryConfig { wall_clearance: 30.0, ..GeometryConfig::default() };
+    let result = try_create_geometry((200.0, 50.0), &[], &config, &ChannelTypeConfig::AllStraight);
+    let Err(SchemeError::Geometry(GeometryError::InsufficientSpace { required, available })) = result else {
+        panic!("Expected insufficient space");
+    };
+    assert!((required - 61.0).abs() < 1e-12);
+    assert!((available - 50.0).abs() < 1e-12);
+}
+
+/// Test that split patterns crowding branches or segments are reported
+#[test]
+fn test_overcrowded_split_patterns() {
+    let config = GeometryConfig::default();
+    // 3^4 = 81 branches in 100 - 2 * 0.5 = 99 leaves 1.22 each; 3^5 = 243 do not fit
+    assert!(try_create_geometry((400.0, 100.0), &[SplitType::Trifurcation; 4], &config, &ChannelTypeConfig::AllStraight).is_ok());
+    let crowded = try_create_geometry((400.0, 100.0), &[SplitType::Trifurcation; 5], &config, &ChannelTypeConfig::AllStraight);
+    assert!(matches!(
+        crowded,
+        Err(SchemeError::Geometry(GeometryError::InvalidSplitPattern { ref reason })) if reason.contains("243 branches")
+    ));
+
+    let short = try_create_geometry((10.0, 100.0), &[SplitType::Bifurcation; 3], &config, &ChannelTypeConfig::AllStraight);
+    assert!(matches!(
+        short,
+        Err(SchemeError::Geometry(GeometryError::InvalidSplitPattern { ref reason })) if reason.contains("segments")
+    ));
+
+    // 3^41 branches overflow the branch count
+    let overflowing = try_create_geometry((200.0, 100.0), &[SplitType::Trifurcation; 41], &config, &ChannelTypeConfig::AllStraight);
+    assert!(matches!(overflowing, Err(SchemeError::Geometry(GeometryError::InvalidSplitPattern { .. }))));
+}
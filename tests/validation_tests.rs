@@ -0,0 +1,135 @@
+//! System Validation Tests
+//!
+//! Tests for structural validation of channel systems: generated and
+//! transformed systems pass, while dangling node references, duplicate IDs,
+//! node IDs out of order, misaligned or short paths, non-finite coordinates
+//! and points outside the outline are reported as geometry errors, including
+//! on load.
+
+use scheme::{
+    config::{ArcConfig, ChannelTypeConfig, FrustumConfig, GeometryConfig, SerpentineConfig},
+    error::GeometryError,
+    geometry::{
+        generator::create_geometry, strategies::SmoothTransitionConfig, transform::Transform, ChannelSystem,
+        ChannelType, SplitType,
+    },
+};
+
+/// Arc bifurcation with explicit channel paths
+fn arcs() -> ChannelSystem {
+    create_geometry((200.0, 100.0), &[SplitType::Bifurcation], &GeometryConfig::default(), &ChannelTypeConfig::AllArcs(ArcConfig::default()))
+}
+
+/// Test that systems from every channel type and transform pass validation
+#[test]
+fn test_generated_systems_are_valid() {
+    let serpentine = SerpentineConfig::default();
+    let channel_types = [
+        ChannelTypeConfig::AllStraight,
+        ChannelTypeConfig::AllSmoothStraight(SmoothTransitionConfig::default()),
+        ChannelTypeConfig::AllSerpentine(serpentine),
+        ChannelTypeConfig::AllArcs(ArcConfig::default()),
+        ChannelTypeConfig::AllFrustum(FrustumConfig::default()),
+        ChannelTypeConfig::default(),
+    ];
+    for channel_type in channel_types {
+        let mut system = create_geometry(
+            (200.0, 100.0),
+            &[SplitType::Trifurcation, SplitType::Bifurcation],
+            &GeometryConfig::default(),
+            &channel_type,
+        );
+        assert!(system.validation_errors().is_empty(), "{:?}", system.validation_errors());
+        system.transform(&Transform::rotation(0.7, (30.0, 20.0)).then(&Transform::scaling(1.5, (0.0, 0.0))));
+        assert!(system.validate().is_ok());
+    }
+}
+
+/// Test that dangling node references and duplicate IDs are reported
+#[test]
+fn test_references_and_ids() {
+    let mut system = arcs();
+    system.channels[1].from_node = system.nodes.len();
+    system.channels[2].id = system.channels[0].id;
+    system.nodes[3].id = system.nodes[2].id;
+
+    let errors = system.validation_errors();
+    assert!(matches!(errors[0], GeometryError::DuplicateId { ref element, id: 2 } if element == "node"));
+    assert!(errors.iter().any(|error| matches!(
+        error,
+        GeometryError::DanglingNodeReference { channel_id: 1, node_id, node_count } if *node_id == *node_count
+    )));
+    assert!(errors.iter().any(|error| matches!(error, GeometryError::DuplicateId { element, id: 0 } if element == "channel")));
+    assert!(matches!(system.validate(), Err(GeometryError::DuplicateId { .. })));
+}
+
+/// Test that node IDs must match their position in the node list
+#[test]
+fn test_node_id_must_match_index() {
+    let mut system = arcs();
+    let last = system.nodes.len() - 1;
+    system.nodes[last].id = last + 100;
+
+    assert!(matches!(
+        system.validate(),
+        Err(GeometryError::NodeIdMismatch { index, id }) if index == last && id == last + 100
+    ));
+
+    // Swapping two IDs keeps them unique but breaks the index lookup of channels
+    let mut system = arcs();
+    system.nodes.swap(0, 1);
+    let errors = system.validation_errors();
+    assert!(matches!(errors[0], GeometryError::NodeIdMismatch { index: 0, id: 1 }));
+    assert!(matches!(errors[1], GeometryError::NodeIdMismatch { index: 1, id: 0 }));
+}
+
+/// Test that misaligned and short paths are reported without panicking
+#[test]
+fn test_path_problems() {
+    let mut system = arcs();
+    if let ChannelType::Arc { path } = &mut system.channels[0].channel_type {
+        path[0].1 += 0.5;
+    }
+    system.channels[1].channel_type = ChannelType::Serpentine { path: Vec::new() };
+
+    let errors = system.validation_errors();
+    assert_eq!(errors.len(), 2);
+    assert!(matches!(
+        errors[0],
+        GeometryError::PathEndpointMismatch { channel_id: 0, node_id: 0, distance } if (distance - 0.5).abs() < 1e-9
+    ));
+    assert!(matches!(errors[1], GeometryError::InvalidChannelPath { ref reason } if reason.contains("channel 1")));
+    assert!(!system.get_lines().is_empty());
+}
+
+/// Test that non-finite coordinates and points outside the outline are reported
+#[test]
+fn test_coordinates_and_containment() {
+    let mut system = arcs();
+    system.nodes[0].point = (f64::NAN, 50.0);
+    system.nodes[1].point.1 = 150.0;
+    if let ChannelType::Arc { path } = &mut system.channels[2].channel_type {
+        path[1] = (f64::INFINITY, 0.0);
+    }
+
+    let errors = system.validation_errors();
+    assert!(matches!(errors[0], GeometryError::InvalidPoint { x, .. } if x.is_nan()));
+    assert!(matches!(errors[1], GeometryError::OutsideOutline { y, .. } if (y - 150.0).abs() < 1e-12));
+    assert!(errors.iter().any(|error| matches!(error, GeometryError::InvalidPoint { x, .. } if x.is_infinite())));
+}
+
+/// Test that validated loading rejects broken files that plain loading accepts
+#[test]
+fn test_validated_loading() {
+    let system = arcs();
+    assert!(ChannelSystem::from_json_validated(&system.to_json().unwrap()).is_ok());
+
+    let mut broken = system;
+    broken.channels[0].to_node = 42;
+    let json = broken.to_json().unwrap();
+    assert!(ChannelSystem::from_json(&json).is_ok());
+    assert!(matches!(
+        ChannelSystem::from_json_validated(&json),
+        Err(GeometryError::DanglingNodeReference { node_id: 42, .. })
+    ));
+}